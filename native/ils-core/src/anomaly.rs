@@ -9,8 +9,9 @@
 
 use napi_derive::napi;
 use rayon::prelude::*;
+use serde::Serialize;
 
-use crate::statistics::{mean, std_dev, quantile, moving_average, linear_regression};
+use crate::statistics::{mean, median, std_dev, quantile, moving_average, linear_regression, t_p_value};
 
 /// Detect anomalies using multiple statistical methods
 ///
@@ -33,7 +34,7 @@ pub fn detect_anomalies(data: Vec<f64>, threshold: f64) -> Vec<AnomalyResult> {
     let upper_bound = q3 + 1.5 * iqr;
 
     // Calculate moving average
-    let window_size = (data.len() / 3).max(3).min(7) as u32;
+    let window_size = (data.len() / 3).clamp(3, 7) as u32;
     let mov_avg = moving_average(data.clone(), window_size);
 
     let results: Vec<Option<AnomalyResult>> = data
@@ -106,7 +107,8 @@ pub fn detect_anomalies(data: Vec<f64>, threshold: f64) -> Vec<AnomalyResult> {
 }
 
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AnomalyResult {
     pub index: u32,
     pub value: f64,
@@ -125,16 +127,53 @@ pub fn detect_anomalies_realtime(
     new_value: f64,
     sensitivity: String,
 ) -> RealTimeAnomalyResult {
-    // Use recent data for adaptive threshold (last 14 data points)
-    let recent_window: Vec<f64> = historical_data
-        .iter()
-        .rev()
-        .take(14)
-        .cloned()
-        .collect();
+    detect_anomalies_realtime_with_options(historical_data, new_value, sensitivity, None)
+}
 
-    let m = mean(recent_window.clone());
-    let sd = std_dev(recent_window);
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct RealtimeOptions {
+    /// "window" (default, mean/sd of the last `window_size` points),
+    /// "ewma" (exponentially weighted mean/variance of the whole history) or
+    /// "quantile" (empirical quantiles of the last `window_size` points)
+    pub baseline: Option<String>,
+    /// Points in the rolling window (default 14, or 100 for the quantile baseline)
+    pub window_size: Option<u32>,
+    /// Quantiles bounding the normal range for the quantile baseline
+    /// (defaults by sensitivity: low 0.005/0.995, medium 0.01/0.99, high 0.025/0.975)
+    pub lower_quantile: Option<f64>,
+    pub upper_quantile: Option<f64>,
+    /// Observations after which an EWMA weight halves (default 7)
+    pub half_life: Option<f64>,
+    /// History needed before the baseline is trusted (default 5)
+    pub warmup: Option<u32>,
+    /// During warm-up: "window" (default) falls back to the rolling window,
+    /// "skip" never flags, "flag_all" flags every value
+    pub warmup_policy: Option<String>,
+}
+
+/// Real-time anomaly detection with a configurable baseline
+///
+/// The EWMA baseline weights each past point by `0.5^(age / half_life)`, so
+/// an old outlier fades out gradually instead of dropping out of a window
+/// and moving the threshold abruptly. The quantile baseline takes the
+/// normal range straight from recent empirical quantiles, which suits skewed,
+/// heavy-tailed metrics such as latencies where mean ± k·σ is too tight on
+/// one side and too loose on the other.
+///
+/// # Arguments
+/// * `historical_data` - Past values, oldest first
+/// * `new_value` - Value to score
+/// * `sensitivity` - "low", "medium" or "high"
+/// * `options` - Baseline and warm-up handling
+#[napi]
+pub fn detect_anomalies_realtime_with_options(
+    historical_data: Vec<f64>,
+    new_value: f64,
+    sensitivity: String,
+    options: Option<RealtimeOptions>,
+) -> RealTimeAnomalyResult {
+    let options = options.unwrap_or_default();
 
     // Adjust threshold based on sensitivity
     let threshold_multiplier = match sensitivity.as_str() {
@@ -144,6 +183,95 @@ pub fn detect_anomalies_realtime(
         _ => 2.0,
     };
 
+    let window_baseline = || {
+        // Use recent data for adaptive threshold (last 14 data points by default)
+        let recent_window: Vec<f64> = historical_data
+            .iter()
+            .rev()
+            .take(options.window_size.unwrap_or(14).max(1) as usize)
+            .cloned()
+            .collect();
+        (mean(recent_window.clone()), std_dev(recent_window))
+    };
+
+    let warming_up = historical_data.len() < options.warmup.unwrap_or(5) as usize;
+    if options.baseline.as_deref() == Some("quantile") && !warming_up {
+        let recent: Vec<f64> = historical_data.iter().rev().take(options.window_size.unwrap_or(100).max(1) as usize).copied().collect();
+        let (default_lower, default_upper) = match sensitivity.as_str() {
+            "low" => (0.005, 0.995),
+            "high" => (0.025, 0.975),
+            _ => (0.01, 0.99),
+        };
+        let lower = quantile(recent.clone(), options.lower_quantile.unwrap_or(default_lower));
+        let upper = quantile(recent.clone(), options.upper_quantile.unwrap_or(default_upper));
+        return score_realtime_range(new_value, lower, median(recent), upper);
+    }
+
+    let (m, sd) = match options.baseline.as_deref() {
+        Some("ewma") if !warming_up => ewma_baseline(&historical_data, options.half_life.unwrap_or(7.0)),
+        _ => window_baseline(),
+    };
+
+    let mut result = score_realtime(new_value, m, sd, threshold_multiplier);
+    if warming_up {
+        match options.warmup_policy.as_deref() {
+            Some("skip") => result.is_anomaly = false,
+            Some("flag_all") => result.is_anomaly = true,
+            _ => {}
+        }
+    }
+    result.warming_up = warming_up;
+    result
+}
+
+/// Exponentially weighted mean and standard deviation of `data`
+fn ewma_baseline(data: &[f64], half_life: f64) -> (f64, f64) {
+    let alpha = 1.0 - 0.5f64.powf(1.0 / half_life.max(1e-9));
+    let mut iter = data.iter();
+    let Some(&first) = iter.next() else {
+        return (0.0, 0.0);
+    };
+    let (mut m, mut var) = (first, 0.0);
+    for &x in iter {
+        let diff = x - m;
+        let increment = alpha * diff;
+        m += increment;
+        var = (1.0 - alpha) * (var + diff * increment);
+    }
+    (m, var.sqrt())
+}
+
+/// Score a value against an explicit normal range `[lower, upper]` around `center`
+fn score_realtime_range(new_value: f64, lower: f64, center: f64, upper: f64) -> RealTimeAnomalyResult {
+    let range = (upper - lower).max(f64::EPSILON);
+    let half_width = if new_value >= center { upper - center } else { center - lower };
+    let confidence = if half_width > 0.0 {
+        ((new_value - center).abs() / half_width * 100.0).min(100.0)
+    } else {
+        100.0
+    };
+    let excess = (lower - new_value).max(new_value - upper).max(0.0) / range;
+
+    let severity = if excess > 0.5 {
+        "high".to_string()
+    } else if excess > 0.1 {
+        "medium".to_string()
+    } else {
+        "low".to_string()
+    };
+
+    RealTimeAnomalyResult {
+        is_anomaly: new_value < lower || new_value > upper,
+        severity,
+        confidence,
+        expected_min: lower,
+        expected_max: upper,
+        actual_value: new_value,
+        warming_up: false,
+    }
+}
+
+fn score_realtime(new_value: f64, m: f64, sd: f64, threshold_multiplier: f64) -> RealTimeAnomalyResult {
     let threshold = sd * threshold_multiplier;
     let deviation = (new_value - m).abs();
     let z_score = if sd != 0.0 { deviation / sd } else { 0.0 };
@@ -166,6 +294,7 @@ pub fn detect_anomalies_realtime(
         expected_min: m - threshold,
         expected_max: m + threshold,
         actual_value: new_value,
+        warming_up: false,
     }
 }
 
@@ -178,6 +307,8 @@ pub struct RealTimeAnomalyResult {
     pub expected_min: f64,
     pub expected_max: f64,
     pub actual_value: f64,
+    /// History was shorter than the warm-up length
+    pub warming_up: bool,
 }
 
 /// Detect seasonal anomalies by comparing with historical same-period values
@@ -235,7 +366,76 @@ pub fn detect_seasonal_anomalies(
 }
 
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+pub struct SeasonalAnomalyOptions {
+    /// Prior cycles compared at each seasonal position (default 4; 0 = all)
+    pub lookback_cycles: Option<u32>,
+    /// Robust z-score (median / scaled MAD) above which a value is anomalous (default 3.5)
+    pub threshold: Option<f64>,
+    /// Prior cycles required before a position is scored (default 2)
+    pub min_cycles: Option<u32>,
+}
+
+/// Detect seasonal anomalies against the same position in prior cycles, using MAD
+///
+/// Each value is compared with the median of the same seasonal position over
+/// up to `lookback_cycles` previous cycles; the spread is the MAD scaled by
+/// 1.4826 so thresholds read like standard deviations. A single past outlier
+/// therefore neither shifts the baseline nor masks later anomalies. When the
+/// MAD is zero (identical history) the mean absolute deviation is used, and
+/// any change counts when that is zero too.
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `seasonal_period` - Length of one cycle (e.g. 7 for daily data with weekly seasonality)
+/// * `options` - Lookback, threshold and warm-up
+#[napi]
+pub fn detect_seasonal_anomalies_with_options(
+    data: Vec<f64>,
+    seasonal_period: u32,
+    options: Option<SeasonalAnomalyOptions>,
+) -> Vec<SeasonalAnomalyResult> {
+    let period = seasonal_period as usize;
+    let options = options.unwrap_or_default();
+    let lookback = match options.lookback_cycles.unwrap_or(4) {
+        0 => usize::MAX,
+        cycles => cycles as usize,
+    };
+    let threshold = options.threshold.unwrap_or(3.5);
+    let min_cycles = options.min_cycles.unwrap_or(2).max(1) as usize;
+
+    if period == 0 || data.len() <= period * min_cycles {
+        return vec![];
+    }
+
+    (period * min_cycles..data.len())
+        .into_par_iter()
+        .filter_map(|i| {
+            let history: Vec<f64> = (1..=lookback.min(i / period)).map(|c| data[i - c * period]).collect();
+            let expected_value = median(history.clone());
+            let deviation = (data[i] - expected_value).abs();
+
+            let mad = median(history.iter().map(|v| (v - expected_value).abs()).collect());
+            let spread = if mad > 0.0 {
+                1.4826 * mad
+            } else {
+                1.2533 * history.iter().map(|v| (v - expected_value).abs()).sum::<f64>() / history.len() as f64
+            };
+            let is_anomaly = if spread > 0.0 { deviation / spread > threshold } else { deviation > 0.0 };
+
+            is_anomaly.then(|| SeasonalAnomalyResult {
+                index: i as u32,
+                value: data[i],
+                expected_value,
+                deviation,
+            })
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SeasonalAnomalyResult {
     pub index: u32,
     pub value: f64,
@@ -243,31 +443,59 @@ pub struct SeasonalAnomalyResult {
     pub deviation: f64,
 }
 
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct TrendChangeOptions {
+    /// "test" (default) compares the two window slopes with a t-test;
+    /// "heuristic" flags relative slope changes above `min_change_percent`
+    pub method: Option<String>,
+    /// Significance level of the slope comparison (default 0.05)
+    pub significance_level: Option<f64>,
+    /// Relative slope change, in percent, required by the heuristic (default 50)
+    pub min_change_percent: Option<f64>,
+}
+
 /// Detect trend changes and shifts in data patterns
 ///
 /// Identifies points where the trend significantly changes direction or magnitude.
 #[napi]
 pub fn detect_trend_changes(data: Vec<f64>, window_size: u32) -> Vec<TrendChangeResult> {
+    detect_trend_changes_with_options(data, window_size, None)
+}
+
+/// Detect trend changes, with a configurable significance rule
+///
+/// At each index the slope of the preceding window is compared with the
+/// slope of the window before it. By default a change is reported when the
+/// difference of the slopes is significant (Welch-style t statistic on the
+/// two slope standard errors, `2 * window - 4` degrees of freedom).
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `window_size` - Points per regression window
+/// * `options` - Test or heuristic rule and its threshold
+#[napi]
+pub fn detect_trend_changes_with_options(
+    data: Vec<f64>,
+    window_size: u32,
+    options: Option<TrendChangeOptions>,
+) -> Vec<TrendChangeResult> {
     let window = window_size as usize;
+    let options = options.unwrap_or_default();
+    let heuristic = options.method.as_deref() == Some("heuristic");
+    let alpha = options.significance_level.unwrap_or(0.05);
+    let min_change_percent = options.min_change_percent.unwrap_or(50.0);
 
-    if data.len() < window * 2 {
+    if window < 3 || data.len() < window * 2 {
         return vec![];
     }
 
     let results: Vec<TrendChangeResult> = (window * 2..data.len())
         .into_par_iter()
         .filter_map(|i| {
-            // Calculate trend for previous window
-            let old_window: Vec<f64> = data[(i - window * 2)..(i - window)].to_vec();
-            let old_x: Vec<f64> = (0..old_window.len()).map(|j| j as f64).collect();
-            let old_trend = linear_regression(old_x, old_window).slope;
-
-            // Calculate trend for current window
-            let new_window: Vec<f64> = data[(i - window)..i].to_vec();
-            let new_x: Vec<f64> = (0..new_window.len()).map(|j| j as f64).collect();
-            let new_trend = linear_regression(new_x, new_window).slope;
+            let (old_trend, old_std_error) = slope_with_error(&data[(i - window * 2)..(i - window)]);
+            let (new_trend, new_std_error) = slope_with_error(&data[(i - window)..i]);
 
-            // Check if trend has significantly changed
             let change_percent = if old_trend != 0.0 {
                 ((new_trend - old_trend).abs() / old_trend.abs()) * 100.0
             } else if new_trend != 0.0 {
@@ -276,18 +504,38 @@ pub fn detect_trend_changes(data: Vec<f64>, window_size: u32) -> Vec<TrendChange
                 0.0
             };
 
-            let significant = change_percent > 50.0;
+            let pooled_error = (old_std_error.powi(2) + new_std_error.powi(2)).sqrt();
+            let p_value = if pooled_error > 0.0 {
+                t_p_value((new_trend - old_trend) / pooled_error, (2 * window - 4) as f64)
+            } else if new_trend != old_trend {
+                0.0
+            } else {
+                1.0
+            };
 
+            let significant = if heuristic { change_percent > min_change_percent } else { p_value < alpha };
             if !significant {
                 return None;
             }
 
+            let direction = if old_trend * new_trend < 0.0 {
+                "reversal"
+            } else if new_trend.abs() > old_trend.abs() {
+                "acceleration"
+            } else {
+                "deceleration"
+            };
+
             Some(TrendChangeResult {
                 index: i as u32,
                 old_trend,
                 new_trend,
                 change_percent,
                 significant,
+                direction: direction.to_string(),
+                old_std_error,
+                new_std_error,
+                p_value,
             })
         })
         .collect();
@@ -295,14 +543,36 @@ pub fn detect_trend_changes(data: Vec<f64>, window_size: u32) -> Vec<TrendChange
     results
 }
 
+/// Least-squares slope of `window` against its index, with its standard error
+fn slope_with_error(window: &[f64]) -> (f64, f64) {
+    let n = window.len() as f64;
+    let x: Vec<f64> = (0..window.len()).map(|j| j as f64).collect();
+    let fit = linear_regression(x.clone(), window.to_vec());
+    let x_mean = (n - 1.0) / 2.0;
+    let sxx: f64 = x.iter().map(|x| (x - x_mean).powi(2)).sum();
+    let rss: f64 = window
+        .iter()
+        .enumerate()
+        .map(|(j, y)| (y - fit.intercept - fit.slope * j as f64).powi(2))
+        .sum();
+    (fit.slope, (rss / (n - 2.0) / sxx).sqrt())
+}
+
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrendChangeResult {
     pub index: u32,
     pub old_trend: f64,
     pub new_trend: f64,
     pub change_percent: f64,
     pub significant: bool,
+    /// "acceleration", "deceleration" or "reversal" (slope changed sign)
+    pub direction: String,
+    pub old_std_error: f64,
+    pub new_std_error: f64,
+    /// Two-sided p-value for equal slopes
+    pub p_value: f64,
 }
 
 /// Comprehensive anomaly analysis
@@ -315,10 +585,67 @@ pub fn analyze_anomalies(
     seasonal_period: u32,
     window_size: u32,
 ) -> AnomalyAnalysisSummary {
-    // Run all detection methods
-    let anomalies = detect_anomalies(data.clone(), threshold);
-    let seasonal_anomalies = detect_seasonal_anomalies(data.clone(), seasonal_period);
-    let trend_changes = detect_trend_changes(data, window_size);
+    let options = AnomalyAnalysisOptions {
+        threshold: Some(threshold),
+        seasonal_period: Some(seasonal_period),
+        window_size: Some(window_size),
+        ..Default::default()
+    };
+    analyze_anomalies_with_options(data, Some(options))
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyAnalysisOptions {
+    /// Run the point detector (z-score / IQR / moving average, default true)
+    pub detect_points: Option<bool>,
+    /// Run the seasonal detector (default true)
+    pub detect_seasonal: Option<bool>,
+    /// Run the trend-change detector (default true)
+    pub detect_trend_changes: Option<bool>,
+    /// Z-score threshold of the point detector (default 2.5)
+    pub threshold: Option<f64>,
+    /// Cycle length of the seasonal detector (default 7)
+    pub seasonal_period: Option<u32>,
+    /// Use the multi-cycle MAD seasonal detector with these settings (default: legacy detector)
+    pub seasonal_options: Option<SeasonalAnomalyOptions>,
+    /// Window of the trend-change detector (default 7)
+    pub window_size: Option<u32>,
+    pub trend_options: Option<TrendChangeOptions>,
+    /// Contribution of each detection to `weighted_score` (defaults 1)
+    pub point_weight: Option<f64>,
+    pub seasonal_weight: Option<f64>,
+    pub trend_weight: Option<f64>,
+}
+
+/// Anomaly analysis with per-detector toggles, parameters and weights
+///
+/// Disabled detectors return empty lists and add nothing to the score.
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `options` - Detector selection, parameters and weights
+#[napi]
+pub fn analyze_anomalies_with_options(data: Vec<f64>, options: Option<AnomalyAnalysisOptions>) -> AnomalyAnalysisSummary {
+    let options = options.unwrap_or_default();
+    let seasonal_period = options.seasonal_period.unwrap_or(7);
+    let window_size = options.window_size.unwrap_or(7);
+
+    let anomalies = if options.detect_points.unwrap_or(true) {
+        detect_anomalies(data.clone(), options.threshold.unwrap_or(2.5))
+    } else {
+        Vec::new()
+    };
+    let seasonal_anomalies = match (options.detect_seasonal.unwrap_or(true), options.seasonal_options) {
+        (false, _) => Vec::new(),
+        (true, Some(seasonal)) => detect_seasonal_anomalies_with_options(data.clone(), seasonal_period, Some(seasonal)),
+        (true, None) => detect_seasonal_anomalies(data.clone(), seasonal_period),
+    };
+    let trend_changes = if options.detect_trend_changes.unwrap_or(true) {
+        detect_trend_changes_with_options(data, window_size, options.trend_options)
+    } else {
+        Vec::new()
+    };
 
     let total_anomalies = anomalies.len() as u32;
     let high_severity_count = anomalies.iter().filter(|a| a.severity == "high").count() as u32;
@@ -329,12 +656,17 @@ pub fn analyze_anomalies(
         0.0
     };
 
+    let weighted_score = options.point_weight.unwrap_or(1.0) * anomalies.len() as f64
+        + options.seasonal_weight.unwrap_or(1.0) * seasonal_anomalies.len() as f64
+        + options.trend_weight.unwrap_or(1.0) * trend_changes.len() as f64;
+
     AnomalyAnalysisSummary {
         total_anomalies,
         high_severity_count,
         average_deviation,
         significant_trend_changes: trend_changes.len() as u32,
         seasonal_anomaly_count: seasonal_anomalies.len() as u32,
+        weighted_score,
         anomalies,
         seasonal_anomalies,
         trend_changes,
@@ -342,13 +674,16 @@ pub fn analyze_anomalies(
 }
 
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AnomalyAnalysisSummary {
     pub total_anomalies: u32,
     pub high_severity_count: u32,
     pub average_deviation: f64,
     pub significant_trend_changes: u32,
     pub seasonal_anomaly_count: u32,
+    /// Detections of all enabled detectors, weighted per detector
+    pub weighted_score: f64,
     pub anomalies: Vec<AnomalyResult>,
     pub seasonal_anomalies: Vec<SeasonalAnomalyResult>,
     pub trend_changes: Vec<TrendChangeResult>,
@@ -382,6 +717,52 @@ mod tests {
         assert!(result.is_anomaly);
     }
 
+    #[test]
+    fn test_realtime_ewma_baseline() {
+        // An old outlier 14 points back: the window baseline jumps when it drops out
+        let mut history: Vec<f64> = (0..40).map(|i| 10.0 + (i % 3) as f64 * 0.5).collect();
+        history[25] = 40.0;
+        let window = |h: &[f64]| detect_anomalies_realtime(h.to_vec(), 11.0, "medium".to_string()).expected_max;
+        let jump = window(&history[..39]) - window(&history[..40]);
+
+        let ewma_options = RealtimeOptions {
+            baseline: Some("ewma".to_string()),
+            ..Default::default()
+        };
+        let ewma = |h: &[f64]| {
+            detect_anomalies_realtime_with_options(h.to_vec(), 11.0, "medium".to_string(), Some(ewma_options.clone())).expected_max
+        };
+        let ewma_jump = ewma(&history[..39]) - ewma(&history[..40]);
+        assert!(ewma_jump.abs() < jump.abs() / 3.0);
+
+        let warmup = RealtimeOptions {
+            warmup_policy: Some("skip".to_string()),
+            ..ewma_options
+        };
+        let early = detect_anomalies_realtime_with_options(vec![10.0, 10.5], 50.0, "high".to_string(), Some(warmup));
+        assert!(early.warming_up);
+        assert!(!early.is_anomaly);
+    }
+
+    #[test]
+    fn test_realtime_quantile_baseline() {
+        // Skewed latencies: mostly ~100ms with a long right tail
+        let history: Vec<f64> = (0..200).map(|i| 100.0 + ((i * 37) % 100) as f64 * if i % 10 == 0 { 5.0 } else { 0.3 }).collect();
+        let options = RealtimeOptions {
+            baseline: Some("quantile".to_string()),
+            ..Default::default()
+        };
+        let score = |value: f64| detect_anomalies_realtime_with_options(history.clone(), value, "medium".to_string(), Some(options.clone()));
+
+        let tail = score(450.0);
+        assert!(!tail.is_anomaly);
+        assert!(tail.expected_min >= 100.0);
+        assert!(score(1000.0).is_anomaly);
+        assert_eq!(score(2000.0).severity, "high");
+        // The sd-based window would flag the ordinary tail value
+        assert!(detect_anomalies_realtime(history, 450.0, "medium".to_string()).is_anomaly);
+    }
+
     #[test]
     fn test_trend_changes() {
         // Data with clear trend change in the middle
@@ -394,6 +775,25 @@ mod tests {
         let changes = detect_trend_changes(data, 5);
         // Should detect the trend reversal
         assert!(!changes.is_empty());
+        assert!(changes.iter().any(|c| c.direction == "reversal" && c.p_value < 0.05));
+    }
+
+    #[test]
+    fn test_trend_change_significance() {
+        // Noisy flat series: slopes wobble by far more than 50% without a real change
+        let noisy: Vec<f64> = (0..60).map(|i| 100.0 + [3.0, -2.0, 4.0, -4.0, 1.0, -3.0, 2.0][i % 7]).collect();
+        let tested = detect_trend_changes(noisy.clone(), 10);
+        let heuristic = detect_trend_changes_with_options(noisy, 10, Some(TrendChangeOptions {
+            method: Some("heuristic".to_string()),
+            ..Default::default()
+        }));
+        assert!(tested.len() < heuristic.len());
+
+        // Growth that speeds up
+        let accelerating: Vec<f64> = (0..40).map(|i| if i < 20 { i as f64 } else { 20.0 + (i - 20) as f64 * 3.0 }).collect();
+        let changes = detect_trend_changes(accelerating, 8);
+        assert!(changes.iter().all(|c| c.direction == "acceleration"));
+        assert!(!changes.is_empty());
     }
 
     #[test]
@@ -415,4 +815,50 @@ mod tests {
         let anomalies = detect_seasonal_anomalies(data, 7);
         assert!(!anomalies.is_empty());
     }
+
+    #[test]
+    fn test_analyze_anomalies_options() {
+        let mut data: Vec<f64> = (0..60).map(|i| 100.0 + (i % 7) as f64 * 3.0).collect();
+        data[40] = 300.0;
+
+        let full = analyze_anomalies(data.clone(), 2.5, 7, 7);
+        let options = AnomalyAnalysisOptions {
+            detect_trend_changes: Some(false),
+            seasonal_weight: Some(0.5),
+            point_weight: Some(2.0),
+            ..Default::default()
+        };
+        let no_trend = analyze_anomalies_with_options(data, Some(options));
+        assert!(no_trend.trend_changes.is_empty());
+        assert_eq!(no_trend.anomalies.len(), full.anomalies.len());
+        assert_eq!(
+            no_trend.weighted_score,
+            2.0 * no_trend.total_anomalies as f64 + 0.5 * no_trend.seasonal_anomaly_count as f64
+        );
+        assert_eq!(
+            full.weighted_score,
+            (full.total_anomalies + full.seasonal_anomaly_count + full.significant_trend_changes) as f64
+        );
+    }
+
+    #[test]
+    fn test_seasonal_anomalies_lookback() {
+        let mut data: Vec<f64> = (0..56).map(|i| 100.0 + (i % 7) as f64 * 5.0 + ((i * 7) % 5) as f64).collect();
+        data[10] += 200.0; // old outlier in week 1
+        data[45] += 30.0; // moderate anomaly in week 6
+
+        let legacy = detect_seasonal_anomalies(data.clone(), 7);
+        assert!(!legacy.iter().any(|a| a.index == 45));
+
+        let robust = detect_seasonal_anomalies_with_options(data.clone(), 7, None);
+        assert!(robust.iter().any(|a| a.index == 45));
+        // Week 2 is not flagged just because week 1 was an outlier
+        assert!(!robust.iter().any(|a| a.index == 17));
+
+        let short = detect_seasonal_anomalies_with_options(data, 7, Some(SeasonalAnomalyOptions {
+            min_cycles: Some(7),
+            ..Default::default()
+        }));
+        assert!(short.iter().all(|a| a.index >= 49));
+    }
 }
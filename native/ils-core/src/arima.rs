@@ -0,0 +1,842 @@
+//! ARIMA Models
+//!
+//! Box-Jenkins models for autoregressive demand series:
+//! - ARIMA(p, d, q) fitting by conditional sum of squares
+//! - Seasonal ARIMA(p, d, q)(P, D, Q)s with multiplicative seasonal terms
+//! - Stepwise automatic order selection by AICc
+//! - Regression on external drivers with (seasonal) ARIMA errors
+//! - Multi-step forecasts with prediction intervals from the psi-weights
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use std::collections::HashSet;
+
+use crate::forecasting::seasonal_strength;
+use crate::linalg::{dot, least_squares};
+use crate::optimize::nelder_mead;
+use crate::statistics::kpss_test;
+
+/// Objective evaluations allowed per estimated parameter
+const EVALUATIONS_PER_PARAM: usize = 400;
+/// Order limits for `auto_arima`: non-seasonal, seasonal and total p + q + P + Q
+const MAX_ORDER: usize = 5;
+const MAX_SEASONAL_ORDER: usize = 2;
+const MAX_TOTAL_ORDER: usize = 5;
+/// Seasonal strength above which `auto_arima` takes a seasonal difference
+const SEASONAL_DIFFERENCE_STRENGTH: f64 = 0.64;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ArimaModel {
+    pub p: u32,
+    pub d: u32,
+    pub q: u32,
+    /// Seasonal orders (0 for a non-seasonal model)
+    pub seasonal_p: u32,
+    pub seasonal_d: u32,
+    pub seasonal_q: u32,
+    /// Seasonal period `s` (1 for a non-seasonal model)
+    pub season_length: u32,
+    /// AR coefficients `phi_1..phi_p` of the differenced series
+    pub ar: Vec<f64>,
+    /// MA coefficients `theta_1..theta_q`
+    pub ma: Vec<f64>,
+    /// Seasonal AR coefficients at lags `s, 2s, ..`
+    pub seasonal_ar: Vec<f64>,
+    /// Seasonal MA coefficients at lags `s, 2s, ..`
+    pub seasonal_ma: Vec<f64>,
+    /// Mean of the differenced series (estimated only without differencing, else 0)
+    pub intercept: f64,
+    /// Effect of each external regressor on the series (empty without regressors)
+    pub regression_coefficients: Vec<f64>,
+    /// Innovation variance
+    pub sigma2: f64,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    /// Small-sample corrected AIC, the usual criterion for choosing orders
+    pub aicc: f64,
+    pub bic: f64,
+    /// One-step-ahead errors aligned with `history` (NaN where the fit conditions on the data)
+    pub residuals: Vec<f64>,
+    /// Series the model was fitted to, used to forecast
+    pub history: Vec<f64>,
+    /// Regressor rows aligned with `history` (empty without regressors)
+    pub regressors: Vec<Vec<f64>>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SarimaOrder {
+    pub p: u32,
+    pub d: u32,
+    pub q: u32,
+    /// Seasonal AR order (default 0)
+    pub seasonal_p: Option<u32>,
+    /// Seasonal differences (default 0)
+    pub seasonal_d: Option<u32>,
+    /// Seasonal MA order (default 0)
+    pub seasonal_q: Option<u32>,
+    /// Seasonal period, e.g. 7 for daily data with a weekly cycle (default 1: no seasonal terms)
+    pub season_length: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ArimaForecast {
+    pub predictions: Vec<f64>,
+    /// 95% prediction interval bounds
+    pub lower_bounds: Vec<f64>,
+    pub upper_bounds: Vec<f64>,
+}
+
+/// Orders of a (seasonal) ARIMA model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Order {
+    p: usize,
+    d: usize,
+    q: usize,
+    sp: usize,
+    sd: usize,
+    sq: usize,
+    s: usize,
+}
+
+impl From<SarimaOrder> for Order {
+    fn from(order: SarimaOrder) -> Self {
+        let s = order.season_length.unwrap_or(1).max(1) as usize;
+        let seasonal = |value: Option<u32>| if s > 1 { value.unwrap_or(0) as usize } else { 0 };
+        Order {
+            p: order.p as usize,
+            d: order.d as usize,
+            q: order.q as usize,
+            sp: seasonal(order.seasonal_p),
+            sd: seasonal(order.seasonal_d),
+            sq: seasonal(order.seasonal_q),
+            s,
+        }
+    }
+}
+
+impl Order {
+    fn non_seasonal(p: usize, d: usize, q: usize) -> Self {
+        Order { p, d, q, sp: 0, sd: 0, sq: 0, s: 1 }
+    }
+
+    /// Observations of the differenced series the CSS fit conditions on
+    fn conditioning(&self) -> usize {
+        self.p + self.s * self.sp
+    }
+
+    fn n_coefficients(&self) -> usize {
+        self.p + self.q + self.sp + self.sq
+    }
+
+    fn include_mean(&self) -> bool {
+        self.d + self.sd == 0
+    }
+}
+
+/// Fit an ARIMA(p, d, q) model
+///
+/// The series is differenced `d` times, then the ARMA coefficients (and the
+/// mean when `d = 0`) minimize the conditional sum of squares, which matches
+/// maximum likelihood closely for all but very short series. Coefficients
+/// are constrained to the stationary and invertible region. Returns a model
+/// with NaN `sigma2` and criteria when the series is too short for the
+/// orders or contains non-finite values.
+///
+/// # Arguments
+/// * `data` - Time series (e.g. daily demand)
+/// * `p` - Autoregressive order
+/// * `d` - Number of differences
+/// * `q` - Moving-average order
+#[napi]
+pub fn arima_fit(data: Vec<f64>, p: u32, d: u32, q: u32) -> ArimaModel {
+    let order = Order::non_seasonal(p as usize, d as usize, q as usize);
+    fit_order(data, Vec::new(), order, order.conditioning())
+}
+
+/// Fit a seasonal ARIMA(p, d, q)(P, D, Q)s model
+///
+/// The seasonal terms multiply the non-seasonal ones, so e.g. an MA(1) with
+/// a seasonal MA(1) at s = 7 also carries the lag-8 interaction. The series
+/// is seasonally differenced `D` times at lag `s` and then differenced `d`
+/// times; estimation is as for `arima_fit`.
+///
+/// # Arguments
+/// * `data` - Time series (e.g. daily demand)
+/// * `order` - Non-seasonal and seasonal orders with the seasonal period
+#[napi]
+pub fn sarima_fit(data: Vec<f64>, order: SarimaOrder) -> ArimaModel {
+    let order = Order::from(order);
+    fit_order(data, Vec::new(), order, order.conditioning())
+}
+
+/// Fit a regression on external drivers with (seasonal) ARIMA errors
+///
+/// `y_t = b x_t + n_t`, where the error `n_t` follows the given ARIMA model,
+/// so promotions, school holidays or weather explain what they can and the
+/// ARIMA part captures the remaining autocorrelation. Series and regressors
+/// are differenced alike, and for any ARMA coefficients the regression
+/// coefficients (and the mean) are solved exactly by least squares on the
+/// filtered data. Returns an unfitted model (NaN `sigma2`) when the rows do
+/// not match the data or the regressors are collinear.
+///
+/// # Arguments
+/// * `data` - Time series (e.g. daily demand)
+/// * `regressors` - One row per observation, one column per driver
+/// * `order` - Non-seasonal and seasonal orders with the seasonal period
+#[napi]
+pub fn arima_fit_with_regressors(data: Vec<f64>, regressors: Vec<Vec<f64>>, order: SarimaOrder) -> ArimaModel {
+    let order = Order::from(order);
+    fit_order(data, regressors, order, order.conditioning())
+}
+
+/// Automatic seasonal ARIMA with stepwise order selection
+///
+/// Follows the Hyndman-Khandakar procedure: a seasonal difference is taken
+/// when the seasonal strength exceeds 0.64, then up to two ordinary
+/// differences while the KPSS test rejects stationarity. Starting from the
+/// best of four simple models, single orders (and p, q or P, Q together)
+/// are moved up or down by one while that lowers the AICc, within p, q <= 5,
+/// P, Q <= 2 and p + q + P + Q <= 5. All candidates are scored on the same
+/// observations so their AICc values are comparable, and the candidates of
+/// each step are fitted in parallel.
+///
+/// # Arguments
+/// * `data` - Time series (e.g. daily demand)
+/// * `season_length` - Seasonal period, e.g. 7 for a weekly cycle in daily data (default: non-seasonal)
+#[napi]
+pub fn auto_arima(data: Vec<f64>, season_length: Option<u32>) -> ArimaModel {
+    auto_arima_with_regressors(data, Vec::new(), season_length)
+}
+
+/// Automatic regression on external drivers with seasonal ARIMA errors
+///
+/// As `auto_arima`, with the differencing chosen from the residuals of an
+/// ordinary regression on the drivers and every candidate fitted as in
+/// `arima_fit_with_regressors`.
+///
+/// # Arguments
+/// * `data` - Time series (e.g. daily demand)
+/// * `regressors` - One row per observation, one column per driver
+/// * `season_length` - Seasonal period (default: non-seasonal)
+#[napi]
+pub fn auto_arima_with_regressors(data: Vec<f64>, regressors: Vec<Vec<f64>>, season_length: Option<u32>) -> ArimaModel {
+    let mut s = season_length.unwrap_or(1).max(1) as usize;
+    if data.len() < 3 * s {
+        s = 1;
+    }
+    let Some(base) = regression_residuals(&data, &regressors) else {
+        return empty_model(data, Order::non_seasonal(0, 0, 0));
+    };
+
+    let sd = usize::from(s > 1 && seasonal_strength(base.clone(), s as u32) > SEASONAL_DIFFERENCE_STRENGTH);
+    let mut w = lag_difference(&base, s, sd);
+    let mut d = 0;
+    while d < 2 && kpss_test(w.clone(), None, None).p_value < 0.05 {
+        w = difference(&w, 1);
+        d += 1;
+    }
+
+    let max_sp = if s > 1 { MAX_SEASONAL_ORDER } else { 0 };
+    let condition = (MAX_ORDER + s * max_sp).min(w.len() / 3);
+    let admissible = |order: &Order| {
+        order.p <= MAX_ORDER
+            && order.q <= MAX_ORDER
+            && order.sp <= max_sp
+            && order.sq <= max_sp
+            && order.n_coefficients() <= MAX_TOTAL_ORDER
+            && order.conditioning() <= condition
+    };
+    let score = |model: &ArimaModel| if model.aicc.is_nan() { f64::INFINITY } else { model.aicc };
+
+    let seasonal = usize::from(s > 1);
+    let order = |p, q, sp, sq| Order { p, d, q, sp, sd, sq, s };
+    let starts = [order(2, 2, seasonal, seasonal), order(0, 0, 0, 0), order(1, 0, seasonal, 0), order(0, 1, 0, seasonal)];
+    let mut visited: HashSet<Order> = HashSet::new();
+    let mut best = empty_model(data.clone(), order(0, 0, 0, 0));
+    let mut candidates: Vec<Order> = starts.into_iter().filter(|o| admissible(o)).collect();
+    while !candidates.is_empty() {
+        visited.extend(candidates.iter().copied());
+        let fitted = candidates
+            .par_iter()
+            .map(|&o| fit_order(data.clone(), regressors.clone(), o, condition))
+            .min_by(|a, b| score(a).total_cmp(&score(b)));
+        match fitted {
+            Some(model) if score(&model) < score(&best) => best = model,
+            _ => break,
+        }
+        let current = order(best.p as usize, best.q as usize, best.seasonal_p as usize, best.seasonal_q as usize);
+        candidates = neighbours(current)
+            .into_iter()
+            .filter(|o| admissible(o) && !visited.contains(o))
+            .collect();
+    }
+    if best.sigma2.is_nan() {
+        // Too short for the common conditioning; fall back to the plain differenced model
+        let fallback = order(0, 0, 0, 0);
+        return fit_order(data, regressors, fallback, fallback.conditioning());
+    }
+    best
+}
+
+/// Orders one step away from `order` in the stepwise search
+fn neighbours(order: Order) -> Vec<Order> {
+    let mut result = Vec::new();
+    let moves: [(isize, isize, isize, isize); 12] = [
+        (1, 0, 0, 0), (-1, 0, 0, 0), (0, 1, 0, 0), (0, -1, 0, 0),
+        (1, 1, 0, 0), (-1, -1, 0, 0), (0, 0, 1, 0), (0, 0, -1, 0),
+        (0, 0, 0, 1), (0, 0, 0, -1), (0, 0, 1, 1), (0, 0, -1, -1),
+    ];
+    for (dp, dq, dsp, dsq) in moves {
+        let shift = |value: usize, delta: isize| value.checked_add_signed(delta);
+        if let (Some(p), Some(q), Some(sp), Some(sq)) =
+            (shift(order.p, dp), shift(order.q, dq), shift(order.sp, dsp), shift(order.sq, dsq))
+        {
+            result.push(Order { p, q, sp, sq, ..order });
+        }
+    }
+    result
+}
+
+/// Residuals of an ordinary regression of `data` on `[1, regressors]`, or
+/// `data` itself without regressors; `None` for invalid or collinear input
+fn regression_residuals(data: &[f64], regressors: &[Vec<f64>]) -> Option<Vec<f64>> {
+    if data.iter().any(|v| !v.is_finite()) || !valid_regressors(data, regressors) {
+        return None;
+    }
+    if regressors.is_empty() {
+        return Some(data.to_vec());
+    }
+    let rows: Vec<Vec<f64>> = regressors.iter().map(|row| std::iter::once(1.0).chain(row.iter().copied()).collect()).collect();
+    let fit = least_squares(&rows, data)?;
+    Some(rows.iter().zip(data).map(|(row, y)| y - dot(row, &fit.coefficients)).collect())
+}
+
+fn valid_regressors(data: &[f64], regressors: &[Vec<f64>]) -> bool {
+    let k = regressors.first().map_or(0, Vec::len);
+    regressors.is_empty()
+        || (regressors.len() == data.len() && k > 0 && regressors.iter().all(|row| row.len() == k && row.iter().all(|v| v.is_finite())))
+}
+
+/// CSS fit of `order` with optional regressor rows, conditioning on the
+/// first `condition` differenced values
+fn fit_order(data: Vec<f64>, regressors: Vec<Vec<f64>>, order: Order, condition: usize) -> ArimaModel {
+    let Order { p, d, q, sp, sd, sq, s } = order;
+    let include_mean = order.include_mean();
+    let k = regressors.first().map_or(0, Vec::len);
+    let n_params = order.n_coefficients() + usize::from(include_mean) + k;
+    let condition = condition.max(order.conditioning());
+    let transform = |series: &[f64]| difference(&lag_difference(series, s, sd), d);
+    let w = transform(&data);
+    if data.iter().any(|v| !v.is_finite()) || !valid_regressors(&data, &regressors) || w.len() <= condition + n_params + 2 {
+        return empty_model(data, order);
+    }
+
+    // Differenced regressor columns, led by a column of ones for the mean.
+    // The CSS filter is linear, so for given ARMA coefficients these
+    // coefficients are the least-squares fit of the filtered series on the
+    // filtered columns.
+    let mut columns: Vec<Vec<f64>> = (0..k).map(|j| transform(&regressors.iter().map(|row| row[j]).collect::<Vec<f64>>())).collect();
+    if include_mean {
+        columns.insert(0, vec![1.0; w.len()]);
+    }
+    let profile = |ar: &[f64], ma: &[f64]| -> Option<(Vec<f64>, f64)> {
+        let filtered = css_residuals(&w, 0.0, ar, ma, condition).0;
+        if columns.is_empty() {
+            return Some((Vec::new(), filtered[condition..].iter().map(|e| e * e).sum()));
+        }
+        let filtered_columns: Vec<Vec<f64>> = columns.iter().map(|c| css_residuals(c, 0.0, ar, ma, condition).0).collect();
+        let rows: Vec<Vec<f64>> = (condition..w.len()).map(|t| filtered_columns.iter().map(|c| c[t]).collect()).collect();
+        least_squares(&rows, &filtered[condition..]).map(|fit| (fit.coefficients, fit.rss))
+    };
+    if profile(&[], &[]).is_none() {
+        return empty_model(data, order);
+    }
+
+    let center = if include_mean { w.iter().sum::<f64>() / w.len() as f64 } else { 0.0 };
+    let mut start: Vec<f64> = initial_ar(&w, center, p).iter().map(|r| r.atanh()).collect();
+    start.extend(std::iter::repeat_n(0.0, q + sp + sq));
+
+    let unpack = |x: &[f64]| {
+        let stationary = |u: &[f64]| pacf_to_ar(&u.iter().map(|v| v.tanh()).collect::<Vec<f64>>());
+        let invertible = |u: &[f64]| stationary(u).iter().map(|c| -c).collect::<Vec<f64>>();
+        let k = p + q + sp;
+        (stationary(&x[..p]), invertible(&x[p..p + q]), stationary(&x[p + q..k]), invertible(&x[k..k + sq]))
+    };
+    let objective = |x: &[f64]| {
+        let (ar, ma, sar, sma) = unpack(x);
+        profile(&expand_ar(&ar, &sar, s), &expand_ma(&ma, &sma, s)).map_or(f64::INFINITY, |(_, sse)| sse)
+    };
+    // One restart from the first optimum guards against a collapsed simplex
+    let budget = EVALUATIONS_PER_PARAM * n_params.max(1);
+    let (first, _) = nelder_mead(objective, &start, 0.1, budget);
+    let (best, _) = nelder_mead(objective, &first, 0.05, budget);
+
+    let (ar, ma, seasonal_ar, seasonal_ma) = unpack(&best);
+    let (full_ar, full_ma) = (expand_ar(&ar, &seasonal_ar, s), expand_ma(&ma, &seasonal_ma, s));
+    let Some((mut beta, _)) = profile(&full_ar, &full_ma) else {
+        return empty_model(data, order);
+    };
+    let intercept = if include_mean { beta.remove(0) } else { 0.0 };
+    let adjusted: Vec<f64> = (0..w.len()).map(|t| w[t] - (0..k).map(|j| beta[j] * columns[j + usize::from(include_mean)][t]).sum::<f64>()).collect();
+    let (residuals, sse, n_eff) = css_residuals(&adjusted, intercept, &full_ar, &full_ma, condition);
+    let sigma2 = sse / n_eff as f64;
+    let criteria = information_criteria(sigma2, n_eff, n_params + 1);
+
+    let mut aligned = vec![f64::NAN; d + s * sd + condition];
+    aligned.extend_from_slice(&residuals[condition..]);
+    ArimaModel {
+        ar,
+        ma,
+        seasonal_ar,
+        seasonal_ma,
+        intercept,
+        regression_coefficients: beta,
+        sigma2,
+        log_likelihood: criteria.0,
+        aic: criteria.1,
+        aicc: criteria.2,
+        bic: criteria.3,
+        residuals: aligned,
+        regressors,
+        ..empty_model(data, order)
+    }
+}
+
+/// Forecast a fitted ARIMA model
+///
+/// Future shocks are set to zero and the differencing is undone, so with
+/// `d = 1` the forecast levels off at the last value plus the ARMA
+/// dynamics, and a seasonal difference repeats the last season. Intervals
+/// widen with the cumulative psi-weights. Returns empty vectors for a model
+/// that could not be fitted, or one with regressors (use
+/// `arima_forecast_with_regressors`).
+///
+/// # Arguments
+/// * `model` - Model returned by `arima_fit`, `sarima_fit` or `auto_arima`
+/// * `steps` - Forecast horizon
+#[napi]
+pub fn arima_forecast(model: ArimaModel, steps: u32) -> ArimaForecast {
+    if !model.regression_coefficients.is_empty() {
+        return empty_forecast();
+    }
+    forecast_model(&model, &vec![Vec::new(); steps as usize])
+}
+
+/// Forecast a model fitted with regressors, given their future values
+///
+/// The known drivers' effect is added to the ARIMA forecast of the
+/// regression errors; intervals come from the ARIMA part alone. Returns
+/// empty vectors when the rows do not match the model's regressors.
+///
+/// # Arguments
+/// * `model` - Model returned by `arima_fit_with_regressors` or `auto_arima_with_regressors`
+/// * `future_regressors` - One row per forecast step, in the columns used for fitting
+#[napi]
+pub fn arima_forecast_with_regressors(model: ArimaModel, future_regressors: Vec<Vec<f64>>) -> ArimaForecast {
+    let k = model.regression_coefficients.len();
+    if future_regressors.iter().any(|row| row.len() != k || row.iter().any(|v| !v.is_finite())) {
+        return empty_forecast();
+    }
+    forecast_model(&model, &future_regressors)
+}
+
+/// Forecast one step per row of `future` (rows empty when there are no regressors)
+fn forecast_model(model: &ArimaModel, future: &[Vec<f64>]) -> ArimaForecast {
+    if !model.sigma2.is_finite() {
+        return empty_forecast();
+    }
+    let effect = |row: &[f64]| if row.is_empty() { 0.0 } else { dot(row, &model.regression_coefficients) };
+    let errors: Vec<f64> = model
+        .history
+        .iter()
+        .enumerate()
+        .map(|(t, y)| y - model.regressors.get(t).map_or(0.0, |row| effect(row)))
+        .collect();
+
+    let s = model.season_length.max(1) as usize;
+    let ar = expand_ar(&model.ar, &model.seasonal_ar, s);
+    let full_ar = integrate_ar(&integrate_ar(&ar, 1, model.d as usize), s, model.seasonal_d as usize);
+    let constant = model.intercept * (1.0 - ar.iter().sum::<f64>());
+    let ma = expand_ma(&model.ma, &model.seasonal_ma, s);
+    let forecast = forecast_with_polynomials(&errors, &model.residuals, &full_ar, &ma, constant, model.sigma2, future.len());
+    let shift = |values: Vec<f64>| values.iter().zip(future).map(|(v, row)| v + effect(row)).collect();
+    ArimaForecast {
+        predictions: shift(forecast.predictions),
+        lower_bounds: shift(forecast.lower_bounds),
+        upper_bounds: shift(forecast.upper_bounds),
+    }
+}
+
+fn empty_forecast() -> ArimaForecast {
+    ArimaForecast {
+        predictions: Vec::new(),
+        lower_bounds: Vec::new(),
+        upper_bounds: Vec::new(),
+    }
+}
+
+/// Recursive forecast from expanded AR (including differencing) and MA polynomials
+pub(crate) fn forecast_with_polynomials(
+    history: &[f64],
+    residuals: &[f64],
+    full_ar: &[f64],
+    ma: &[f64],
+    constant: f64,
+    sigma2: f64,
+    steps: usize,
+) -> ArimaForecast {
+    let n = history.len();
+    let mut values = history.to_vec();
+    let mut errors: Vec<f64> = residuals.iter().map(|e| if e.is_finite() { *e } else { 0.0 }).collect();
+    errors.resize(n, 0.0);
+    for t in n..n + steps {
+        let ar_part: f64 = full_ar.iter().enumerate().filter(|(i, _)| t > *i).map(|(i, phi)| phi * values[t - 1 - i]).sum();
+        let ma_part: f64 = ma.iter().enumerate().filter(|(j, _)| t > *j).map(|(j, theta)| theta * errors[t - 1 - j]).sum();
+        values.push(constant + ar_part + ma_part);
+        errors.push(0.0);
+    }
+
+    // psi_0 = 1, psi_j = theta_j + sum_i phi*_i psi_{j-i}
+    let mut psi = vec![1.0];
+    for j in 1..steps {
+        let theta = ma.get(j - 1).copied().unwrap_or(0.0);
+        let weight = theta + (1..=j.min(full_ar.len())).map(|i| full_ar[i - 1] * psi[j - i]).sum::<f64>();
+        psi.push(weight);
+    }
+    let predictions = values[n..].to_vec();
+    let mut cumulative = 0.0;
+    let half_widths: Vec<f64> = psi
+        .iter()
+        .take(steps)
+        .map(|weight| {
+            cumulative += weight * weight;
+            1.96 * (sigma2 * cumulative).sqrt()
+        })
+        .collect();
+    ArimaForecast {
+        lower_bounds: predictions.iter().zip(&half_widths).map(|(p, h)| p - h).collect(),
+        upper_bounds: predictions.iter().zip(&half_widths).map(|(p, h)| p + h).collect(),
+        predictions,
+    }
+}
+
+/// `d`-th differences of `data`
+pub(crate) fn difference(data: &[f64], d: usize) -> Vec<f64> {
+    lag_difference(data, 1, d)
+}
+
+/// `times`-fold differences of `data` at `lag`, e.g. `y_t - y_{t-7}` for a weekly cycle
+pub(crate) fn lag_difference(data: &[f64], lag: usize, times: usize) -> Vec<f64> {
+    (0..times).fold(data.to_vec(), |series, _| series.iter().skip(lag).zip(&series).map(|(a, b)| a - b).collect())
+}
+
+/// AR coefficients of `phi(B) (1 - B^lag)^times`, i.e. the model for the undifferenced series
+pub(crate) fn integrate_ar(ar: &[f64], lag: usize, times: usize) -> Vec<f64> {
+    // Polynomial coefficients of 1 - sum phi_i B^i
+    let mut polynomial: Vec<f64> = std::iter::once(1.0).chain(ar.iter().map(|phi| -phi)).collect();
+    for _ in 0..times {
+        let mut next = polynomial.clone();
+        next.resize(polynomial.len() + lag, 0.0);
+        for (i, c) in polynomial.iter().enumerate() {
+            next[i + lag] -= c;
+        }
+        polynomial = next;
+    }
+    polynomial[1..].iter().map(|c| -c).collect()
+}
+
+/// Coefficients of `phi(B) Phi(B^s)` in the `1 - sum c_i B^i` convention
+pub(crate) fn expand_ar(ar: &[f64], seasonal_ar: &[f64], s: usize) -> Vec<f64> {
+    multiply_lag_polynomials(ar, seasonal_ar, s, -1.0)
+}
+
+/// Coefficients of `theta(B) Theta(B^s)` in the `1 + sum c_i B^i` convention
+pub(crate) fn expand_ma(ma: &[f64], seasonal_ma: &[f64], s: usize) -> Vec<f64> {
+    multiply_lag_polynomials(ma, seasonal_ma, s, 1.0)
+}
+
+fn multiply_lag_polynomials(short: &[f64], seasonal: &[f64], s: usize, sign: f64) -> Vec<f64> {
+    if seasonal.is_empty() {
+        return short.to_vec();
+    }
+    let mut product = vec![0.0; short.len() + s * seasonal.len() + 1];
+    for (i, a) in std::iter::once(1.0).chain(short.iter().map(|c| sign * c)).enumerate() {
+        for (j, b) in std::iter::once(1.0).chain(seasonal.iter().map(|c| sign * c)).enumerate() {
+            product[i + s * j] += a * b;
+        }
+    }
+    product[1..].iter().map(|c| sign * c).collect()
+}
+
+/// Conditional-sum-of-squares residuals: `(residuals, sse, number of residuals)`
+///
+/// The first `start` observations (at least `ar.len()`) are conditioned on
+/// (residual 0).
+pub(crate) fn css_residuals(w: &[f64], mean: f64, ar: &[f64], ma: &[f64], start: usize) -> (Vec<f64>, f64, usize) {
+    let start = start.max(ar.len());
+    let mut residuals = vec![0.0; w.len()];
+    let mut sse = 0.0;
+    for t in start..w.len() {
+        let ar_part: f64 = ar.iter().enumerate().map(|(i, phi)| phi * (w[t - 1 - i] - mean)).sum();
+        let ma_part: f64 = ma.iter().enumerate().filter(|(j, _)| t > *j).map(|(j, theta)| theta * residuals[t - 1 - j]).sum();
+        let e = w[t] - mean - ar_part - ma_part;
+        residuals[t] = e;
+        sse += e * e;
+    }
+    (residuals, sse, w.len().saturating_sub(start))
+}
+
+/// Gaussian `(log-likelihood, AIC, AICc, BIC)` for `k` parameters including the variance
+pub(crate) fn information_criteria(sigma2: f64, n: usize, k: usize) -> (f64, f64, f64, f64) {
+    let (nf, kf) = (n as f64, k as f64);
+    let log_likelihood = -0.5 * nf * ((2.0 * std::f64::consts::PI * sigma2.max(f64::MIN_POSITIVE)).ln() + 1.0);
+    let aic = -2.0 * log_likelihood + 2.0 * kf;
+    let aicc = if nf > kf + 1.0 { aic + 2.0 * kf * (kf + 1.0) / (nf - kf - 1.0) } else { f64::INFINITY };
+    (log_likelihood, aic, aicc, -2.0 * log_likelihood + kf * nf.ln())
+}
+
+/// Map partial autocorrelations in (-1, 1) to stationary AR coefficients (Durbin-Levinson)
+pub(crate) fn pacf_to_ar(pacf: &[f64]) -> Vec<f64> {
+    let mut phi: Vec<f64> = Vec::with_capacity(pacf.len());
+    for (k, &r) in pacf.iter().enumerate() {
+        let previous = phi.clone();
+        for j in 0..k {
+            phi[j] = previous[j] - r * previous[k - 1 - j];
+        }
+        phi.push(r);
+    }
+    phi
+}
+
+/// Inverse of `pacf_to_ar`; `None` when the coefficients are not stationary
+pub(crate) fn ar_to_pacf(ar: &[f64]) -> Option<Vec<f64>> {
+    let mut phi = ar.to_vec();
+    let mut pacf = vec![0.0; ar.len()];
+    for k in (0..ar.len()).rev() {
+        let r = phi[k];
+        if r.abs() >= 1.0 {
+            return None;
+        }
+        pacf[k] = r;
+        let previous = phi.clone();
+        for j in 0..k {
+            phi[j] = (previous[j] + r * previous[k - 1 - j]) / (1.0 - r * r);
+        }
+        phi.truncate(k);
+    }
+    Some(pacf)
+}
+
+/// Starting partial autocorrelations from a least-squares AR(p) fit, shrunk into the interior
+fn initial_ar(w: &[f64], mean: f64, p: usize) -> Vec<f64> {
+    if p == 0 {
+        return Vec::new();
+    }
+    let rows: Vec<Vec<f64>> = (p..w.len()).map(|t| (1..=p).map(|i| w[t - i] - mean).collect()).collect();
+    let targets: Vec<f64> = (p..w.len()).map(|t| w[t] - mean).collect();
+    least_squares(&rows, &targets)
+        .and_then(|fit| ar_to_pacf(&fit.coefficients))
+        .map_or_else(|| vec![0.0; p], |pacf| pacf.iter().map(|r| r.clamp(-0.95, 0.95)).collect())
+}
+
+fn empty_model(data: Vec<f64>, order: Order) -> ArimaModel {
+    ArimaModel {
+        p: order.p as u32,
+        d: order.d as u32,
+        q: order.q as u32,
+        seasonal_p: order.sp as u32,
+        seasonal_d: order.sd as u32,
+        seasonal_q: order.sq as u32,
+        season_length: order.s as u32,
+        ar: vec![0.0; order.p],
+        ma: vec![0.0; order.q],
+        seasonal_ar: vec![0.0; order.sp],
+        seasonal_ma: vec![0.0; order.sq],
+        intercept: 0.0,
+        regression_coefficients: Vec::new(),
+        sigma2: f64::NAN,
+        log_likelihood: f64::NAN,
+        aic: f64::NAN,
+        aicc: f64::NAN,
+        bic: f64::NAN,
+        residuals: vec![f64::NAN; data.len()],
+        history: data,
+        regressors: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampling::Rng;
+
+    /// Standard normal draws (Box-Muller)
+    fn gaussian_noise(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = Rng::new(seed);
+        (0..n)
+            .map(|_| (-2.0 * (1.0 - rng.next_f64()).ln()).sqrt() * (std::f64::consts::TAU * rng.next_f64()).cos())
+            .collect()
+    }
+
+    #[test]
+    fn test_arima_recovers_arma_coefficients() {
+        // y_t = 10 + 0.6 (y_{t-1} - 10) + e_t + 0.3 e_{t-1}
+        let e = gaussian_noise(2000, 4);
+        let mut y = vec![10.0];
+        for t in 1..e.len() {
+            y.push(10.0 + 0.6 * (y[t - 1] - 10.0) + e[t] + 0.3 * e[t - 1]);
+        }
+        let model = arima_fit(y.clone(), 1, 0, 1);
+        assert!((model.ar[0] - 0.6).abs() < 0.05, "{:?}", model.ar);
+        assert!((model.ma[0] - 0.3).abs() < 0.06, "{:?}", model.ma);
+        assert!((model.intercept - 10.0).abs() < 0.2);
+        assert!((model.sigma2 - 1.0).abs() < 0.1);
+        assert!(model.aicc > model.aic);
+
+        let forecast = arima_forecast(model, 50);
+        // Reverts to the mean, with intervals approaching the marginal spread
+        assert!((forecast.predictions[49] - 10.0).abs() < 0.3);
+        assert!(forecast.upper_bounds[0] - forecast.lower_bounds[0] < forecast.upper_bounds[49] - forecast.lower_bounds[49]);
+        assert!(arima_fit(vec![1.0, 2.0, 3.0], 2, 1, 1).sigma2.is_nan());
+    }
+
+    #[test]
+    fn test_arima_with_differencing() {
+        // Random walk with AR(1) increments
+        let e = gaussian_noise(1500, 9);
+        let mut increments = vec![0.0];
+        for t in 1..e.len() {
+            increments.push(0.5 * increments[t - 1] + e[t]);
+        }
+        let y: Vec<f64> = increments.iter().scan(100.0, |level, dy| {
+            *level += dy;
+            Some(*level)
+        }).collect();
+
+        let model = arima_fit(y.clone(), 1, 1, 0);
+        assert!((model.ar[0] - 0.5).abs() < 0.05);
+        assert_eq!(model.intercept, 0.0);
+        assert_eq!(model.residuals.len(), y.len());
+        assert!(model.residuals[..2].iter().all(|r| r.is_nan()));
+
+        // The forecast converges to last + phi / (1 - phi) * last increment
+        let last = y[y.len() - 1];
+        let step = y[y.len() - 1] - y[y.len() - 2];
+        let forecast = arima_forecast(model.clone(), 30);
+        let limit = last + model.ar[0] / (1.0 - model.ar[0]) * step;
+        assert!((forecast.predictions[29] - limit).abs() < 1e-6);
+        assert_eq!(integrate_ar(&[0.5], 1, 1), vec![1.5, -0.5]);
+    }
+
+    #[test]
+    fn test_seasonal_arima() {
+        // (1 - 0.5B)(1 - B^7) y_t = (1 + 0.4B^7) e_t on top of a weekly profile
+        let profile = [20.0, 22.0, 25.0, 24.0, 30.0, 40.0, 15.0];
+        let e = gaussian_noise(1400, 21);
+        let mut w = vec![0.0; 8];
+        for t in 8..e.len() {
+            w.push(0.5 * w[t - 1] + e[t] + 0.4 * e[t - 7]);
+        }
+        let mut y: Vec<f64> = (0..7).map(|i| profile[i]).collect();
+        for t in 7..w.len() {
+            y.push(y[t - 7] + w[t]);
+        }
+        let order = SarimaOrder {
+            p: 1,
+            d: 0,
+            q: 0,
+            seasonal_d: Some(1),
+            seasonal_q: Some(1),
+            season_length: Some(7),
+            ..Default::default()
+        };
+        let model = sarima_fit(y.clone(), order);
+        assert!((model.ar[0] - 0.5).abs() < 0.06, "{:?}", model.ar);
+        assert!((model.seasonal_ma[0] - 0.4).abs() < 0.06, "{:?}", model.seasonal_ma);
+        assert!(model.residuals[..8].iter().all(|r| r.is_nan()));
+
+        // The seasonal difference carries the weekly shape into the forecast
+        let forecast = arima_forecast(model, 14);
+        let n = y.len();
+        assert!((forecast.predictions[6] - y[n - 1]).abs() < 3.0);
+        for h in 7..14 {
+            assert!((forecast.predictions[h] - forecast.predictions[h - 7]).abs() < 0.5);
+        }
+        assert_eq!(expand_ma(&[0.5], &[0.4], 7), vec![0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.4, 0.2]);
+        assert_eq!(integrate_ar(&[], 7, 1), vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_auto_arima() {
+        // Stationary AR(1) around a weekly profile
+        let profile = [20.0, 22.0, 25.0, 24.0, 30.0, 40.0, 15.0];
+        let e = gaussian_noise(730, 5);
+        let mut noise = vec![0.0];
+        for t in 1..e.len() {
+            noise.push(0.6 * noise[t - 1] + e[t]);
+        }
+        let y: Vec<f64> = noise.iter().enumerate().map(|(t, v)| profile[t % 7] + v).collect();
+
+        let model = auto_arima(y.clone(), Some(7));
+        assert_eq!(model.season_length, 7);
+        assert_eq!(model.seasonal_d, 1);
+        assert_eq!(model.d, 0);
+        assert!(model.p + model.q >= 1);
+        assert!(model.p + model.q + model.seasonal_p + model.seasonal_q <= 5);
+        let forecast = arima_forecast(model, 7);
+        let errors: Vec<f64> = forecast.predictions.iter().enumerate().map(|(h, f)| (f - profile[(y.len() + h) % 7]).abs()).collect();
+        assert!(errors.iter().sum::<f64>() / 7.0 < 1.5, "{:?}", errors);
+
+        // Non-seasonal random walk: one difference, no seasonal terms
+        let walk: Vec<f64> = gaussian_noise(300, 8).iter().scan(50.0, |level, step| {
+            *level += step;
+            Some(*level)
+        }).collect();
+        let model = auto_arima(walk, None);
+        assert_eq!((model.d, model.season_length, model.seasonal_p + model.seasonal_q), (1, 1, 0));
+        assert!(model.aicc.is_finite());
+    }
+
+    #[test]
+    fn test_arima_with_regressors() {
+        // Promotions lift demand by 25 and a temperature effect of 1.5 per degree, with AR(1) errors
+        let e = gaussian_noise(800, 13);
+        let promo = |t: usize| if t.is_multiple_of(9) { 1.0 } else { 0.0 };
+        let temperature = |t: usize| 15.0 + 8.0 * (t as f64 / 30.0).sin();
+        let mut noise = vec![0.0];
+        for t in 1..e.len() {
+            noise.push(0.7 * noise[t - 1] + e[t]);
+        }
+        let regressors: Vec<Vec<f64>> = (0..800).map(|t| vec![promo(t), temperature(t)]).collect();
+        let y: Vec<f64> = (0..800).map(|t| 50.0 + 25.0 * promo(t) + 1.5 * temperature(t) + noise[t]).collect();
+
+        let order = SarimaOrder { p: 1, d: 0, q: 0, ..Default::default() };
+        let model = arima_fit_with_regressors(y.clone(), regressors.clone(), order.clone());
+        assert!((model.regression_coefficients[0] - 25.0).abs() < 0.3, "{:?}", model.regression_coefficients);
+        assert!((model.regression_coefficients[1] - 1.5).abs() < 0.1);
+        assert!((model.ar[0] - 0.7).abs() < 0.05);
+        assert!((model.intercept - 50.0).abs() < 2.0);
+
+        let future: Vec<Vec<f64>> = (800..810).map(|t| vec![promo(t), temperature(t)]).collect();
+        let forecast = arima_forecast_with_regressors(model.clone(), future);
+        // Day 801 is a promotion day
+        assert!(forecast.predictions[1] - forecast.predictions[2] > 20.0);
+        assert!(arima_forecast(model.clone(), 5).predictions.is_empty());
+        assert!(arima_forecast_with_regressors(model, vec![vec![1.0]]).predictions.is_empty());
+
+        // Mismatched or collinear regressors give an unfitted model
+        assert!(arima_fit_with_regressors(y.clone(), regressors[..10].to_vec(), order.clone()).sigma2.is_nan());
+        assert!(arima_fit_with_regressors(y, vec![vec![1.0]; 800], order).sigma2.is_nan());
+    }
+
+    #[test]
+    fn test_pacf_transform_round_trip() {
+        let ar = pacf_to_ar(&[0.5, -0.3, 0.2]);
+        let back = ar_to_pacf(&ar).unwrap();
+        assert!(back.iter().zip([0.5, -0.3, 0.2]).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(ar_to_pacf(&[1.2]).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+//! Gradient Boosting
+//!
+//! Gradient-boosted regression trees (squared-error loss) with:
+//! - Shrinkage and row subsampling (stochastic gradient boosting)
+//! - Early stopping on a held-out validation split
+//! - Split-gain feature importances
+
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::check_shape;
+use crate::sampling::Rng;
+use crate::tree::{normalize_importances, Criterion, Tree, TreeParams};
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct GradientBoostingOptions {
+    /// Maximum number of boosting rounds (default 100)
+    pub n_estimators: Option<u32>,
+    /// Shrinkage applied to each tree (default 0.1)
+    pub learning_rate: Option<f64>,
+    /// Maximum tree depth (default 3)
+    pub max_depth: Option<u32>,
+    /// Minimum rows per leaf (default 5)
+    pub min_samples_leaf: Option<u32>,
+    /// Fraction of training rows sampled per round (default 1.0)
+    pub subsample: Option<f64>,
+    /// Fraction of rows held out for early stopping (default 0.1, 0 disables)
+    pub validation_fraction: Option<f64>,
+    /// Stop after this many rounds without validation improvement (default 10)
+    pub early_stopping_rounds: Option<u32>,
+    pub seed: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GradientBoostingFitResult {
+    pub n_trees: u32,
+    pub best_iteration: u32,
+    pub stopped_early: bool,
+    pub train_loss: Vec<f64>,
+    pub validation_loss: Vec<f64>,
+}
+
+/// Gradient-boosted regression tree model
+#[napi]
+pub struct GradientBoostingRegressor {
+    options: GradientBoostingOptions,
+    base_prediction: f64,
+    trees: Vec<Tree>,
+    importances: Vec<f64>,
+}
+
+#[napi]
+impl GradientBoostingRegressor {
+    #[napi(constructor)]
+    pub fn new(options: Option<GradientBoostingOptions>) -> Self {
+        GradientBoostingRegressor {
+            options: options.unwrap_or_default(),
+            base_prediction: 0.0,
+            trees: Vec::new(),
+            importances: Vec::new(),
+        }
+    }
+
+    /// Fit the model to feature rows `x` and targets `y`
+    ///
+    /// Fails when `x` and `y` differ in length or the rows differ in width.
+    #[napi]
+    pub fn fit(&mut self, x: Vec<Vec<f64>>, y: Vec<f64>) -> napi::Result<GradientBoostingFitResult> {
+        check_shape(&x, y.len())?;
+        let n_features = x.first().map_or(0, |row| row.len());
+        self.trees.clear();
+        self.importances = vec![0.0; n_features];
+        self.base_prediction = 0.0;
+
+        if x.is_empty() {
+            return Ok(GradientBoostingFitResult {
+                n_trees: 0,
+                best_iteration: 0,
+                stopped_early: false,
+                train_loss: vec![],
+                validation_loss: vec![],
+            });
+        }
+
+        let opts = &self.options;
+        let n_estimators = opts.n_estimators.unwrap_or(100) as usize;
+        let learning_rate = opts.learning_rate.unwrap_or(0.1);
+        let subsample = opts.subsample.unwrap_or(1.0).clamp(0.05, 1.0);
+        let validation_fraction = opts.validation_fraction.unwrap_or(0.1).clamp(0.0, 0.5);
+        let patience = opts.early_stopping_rounds.unwrap_or(10) as usize;
+        let params = TreeParams {
+            max_depth: opts.max_depth.unwrap_or(3) as usize,
+            min_samples_leaf: opts.min_samples_leaf.unwrap_or(5) as usize,
+            max_features: None,
+            criterion: Criterion::SquaredError,
+        };
+        let mut rng = Rng::new(opts.seed.unwrap_or(42) as u64);
+
+        let mut rows: Vec<usize> = (0..x.len()).collect();
+        rng.shuffle(&mut rows);
+        let n_validation = ((x.len() as f64 * validation_fraction) as usize).min(x.len() - 1);
+        let (validation_rows, train_rows) = rows.split_at(n_validation);
+        let (validation_rows, train_rows) = (validation_rows.to_vec(), train_rows.to_vec());
+
+        self.base_prediction = train_rows.iter().map(|&i| y[i]).sum::<f64>() / train_rows.len() as f64;
+        let mut predictions = vec![self.base_prediction; x.len()];
+
+        let mut train_loss = Vec::new();
+        let mut validation_loss = Vec::new();
+        let mut best_loss = f64::INFINITY;
+        let mut best_iteration = 0;
+        let mut stopped_early = false;
+
+        for round in 0..n_estimators {
+            // Negative gradient of squared error is the residual
+            let residuals: Vec<f64> = y.iter().zip(&predictions).map(|(t, p)| t - p).collect();
+
+            let mut sample = train_rows.clone();
+            if subsample < 1.0 {
+                rng.shuffle(&mut sample);
+                sample.truncate(((sample.len() as f64 * subsample) as usize).max(1));
+            }
+
+            let tree = Tree::fit(&x, &residuals, &sample, &params, &mut rng, &mut self.importances);
+            predictions
+                .par_iter_mut()
+                .zip(x.par_iter())
+                .for_each(|(p, row)| *p += learning_rate * tree.predict(row));
+            self.trees.push(tree);
+
+            train_loss.push(mse(&train_rows, &y, &predictions));
+
+            if !validation_rows.is_empty() {
+                let loss = mse(&validation_rows, &y, &predictions);
+                validation_loss.push(loss);
+                if loss < best_loss - 1e-12 {
+                    best_loss = loss;
+                    best_iteration = round + 1;
+                } else if round + 1 - best_iteration >= patience.max(1) {
+                    stopped_early = true;
+                    break;
+                }
+            } else {
+                best_iteration = round + 1;
+            }
+        }
+
+        self.trees.truncate(best_iteration);
+
+        Ok(GradientBoostingFitResult {
+            n_trees: self.trees.len() as u32,
+            best_iteration: best_iteration as u32,
+            stopped_early,
+            train_loss,
+            validation_loss,
+        })
+    }
+
+    /// Predict targets for feature rows (parallel)
+    #[napi]
+    pub fn predict(&self, x: Vec<Vec<f64>>) -> Vec<f64> {
+        let learning_rate = self.options.learning_rate.unwrap_or(0.1);
+        x.par_iter()
+            .map(|row| {
+                self.base_prediction + learning_rate * self.trees.iter().map(|t| t.predict(row)).sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// Normalized split-gain importance per feature (sums to 1)
+    #[napi]
+    pub fn feature_importances(&self) -> Vec<f64> {
+        normalize_importances(&self.importances)
+    }
+}
+
+fn mse(rows: &[usize], y: &[f64], predictions: &[f64]) -> f64 {
+    rows.iter().map(|&i| (y[i] - predictions[i]).powi(2)).sum::<f64>() / rows.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let mut rng = Rng::new(3);
+        let x: Vec<Vec<f64>> = (0..300).map(|_| vec![rng.next_f64() * 10.0, rng.next_f64()]).collect();
+        let y: Vec<f64> = x.iter().map(|r| if r[0] > 5.0 { 20.0 } else { 5.0 } + r[0]).collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_gradient_boosting_fit_predict() {
+        let (x, y) = synthetic();
+        let mut model = GradientBoostingRegressor::new(Some(GradientBoostingOptions {
+            n_estimators: Some(200),
+            ..Default::default()
+        }));
+        assert!(model.fit(x[1..].to_vec(), y.clone()).is_err());
+        let mut ragged = x.clone();
+        ragged[5].pop();
+        assert!(model.fit(ragged, y.clone()).is_err());
+        let result = model.fit(x, y).unwrap();
+        assert!(result.n_trees > 0);
+        assert!(result.train_loss.last().unwrap() < &result.train_loss[0]);
+
+        let predictions = model.predict(vec![vec![2.0, 0.5], vec![8.0, 0.5]]);
+        assert!((predictions[0] - 7.0).abs() < 2.0);
+        assert!((predictions[1] - 28.0).abs() < 2.0);
+
+        let importances = model.feature_importances();
+        assert!(importances[0] > 0.9);
+    }
+
+    #[test]
+    fn test_gradient_boosting_early_stopping() {
+        let (x, y) = synthetic();
+        let mut model = GradientBoostingRegressor::new(Some(GradientBoostingOptions {
+            n_estimators: Some(2000),
+            learning_rate: Some(0.5),
+            early_stopping_rounds: Some(5),
+            ..Default::default()
+        }));
+        let result = model.fit(x, y).unwrap();
+        assert!(result.stopped_early);
+        assert_eq!(result.n_trees, result.best_iteration);
+    }
+}
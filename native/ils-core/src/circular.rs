@@ -0,0 +1,161 @@
+//! Circular Statistics
+//!
+//! Summaries for cyclic quantities such as hour of day, weekday or compass
+//! angle, where 23:00 and 01:00 are two hours apart rather than 22:
+//! - Circular mean and circular standard deviation
+//! - Rayleigh test for uniformity around the cycle
+
+use std::f64::consts::TAU;
+
+use napi_derive::napi;
+
+/// Default cycle length: hours in a day
+const DEFAULT_PERIOD: f64 = 24.0;
+
+/// Circular mean of cyclic values
+///
+/// Averages the values as unit vectors around the cycle, so the mean of
+/// 23:00 and 01:00 is midnight, not noon. Returns a value in `[0, period)`,
+/// or NaN when the data is empty or spread so evenly that there is no mean
+/// direction. NaN values are ignored.
+///
+/// # Arguments
+/// * `values` - Cyclic values (e.g. arrival hours, 0-24)
+/// * `period` - Cycle length in the values' units (default 24; 360 for degrees, 2π for radians)
+#[napi]
+pub fn circular_mean(values: Vec<f64>, period: Option<f64>) -> f64 {
+    let period = cycle_length(period);
+    match resultant(&values, period) {
+        Some((_, length, direction)) if length > 1e-12 => direction * period / TAU,
+        _ => f64::NAN,
+    }
+}
+
+/// Circular standard deviation `sqrt(-2 ln R)`, in the values' units
+///
+/// `R` is the mean resultant length: 1 when all values coincide (standard
+/// deviation 0), near 0 when they are spread around the whole cycle
+/// (standard deviation grows without bound). For tightly clustered values
+/// it approaches the ordinary standard deviation. NaN values are ignored;
+/// empty input gives NaN.
+///
+/// # Arguments
+/// * `values` - Cyclic values
+/// * `period` - Cycle length in the values' units (default 24)
+#[napi]
+pub fn circular_std(values: Vec<f64>, period: Option<f64>) -> f64 {
+    let period = cycle_length(period);
+    match resultant(&values, period) {
+        Some((_, length, _)) => (-2.0 * length.min(1.0).ln()).sqrt() * period / TAU,
+        None => f64::NAN,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RayleighTestResult {
+    /// Mean resultant length `R` in [0, 1]; larger means more concentrated
+    pub mean_resultant_length: f64,
+    /// Rayleigh statistic `n R^2`
+    pub z: f64,
+    /// P-value for the null hypothesis of uniformity around the cycle
+    pub p_value: f64,
+    /// Circular mean in the values' units (NaN when there is no mean direction)
+    pub mean: f64,
+    /// Whether uniformity is not rejected at the 5% level (no peak)
+    pub is_uniform: bool,
+    pub n: u32,
+}
+
+/// Rayleigh test for uniformity of cyclic values
+///
+/// Tests whether arrivals cluster around a peak time against the null that
+/// every point of the cycle is equally likely; it is most sensitive to a
+/// single peak. Uses Zar's p-value approximation, accurate for small
+/// samples. NaN values are ignored.
+///
+/// # Arguments
+/// * `values` - Cyclic values
+/// * `period` - Cycle length in the values' units (default 24)
+#[napi]
+pub fn rayleigh_test(values: Vec<f64>, period: Option<f64>) -> RayleighTestResult {
+    let period = cycle_length(period);
+    let Some((n, length, direction)) = resultant(&values, period) else {
+        return RayleighTestResult {
+            mean_resultant_length: 0.0,
+            z: 0.0,
+            p_value: 1.0,
+            mean: f64::NAN,
+            is_uniform: true,
+            n: 0,
+        };
+    };
+
+    let resultant_sum = n * length;
+    let p_value = ((1.0 + 4.0 * n + 4.0 * (n * n - resultant_sum * resultant_sum)).sqrt() - (1.0 + 2.0 * n))
+        .exp()
+        .clamp(0.0, 1.0);
+    RayleighTestResult {
+        mean_resultant_length: length,
+        z: n * length * length,
+        p_value,
+        mean: if length > 1e-12 { direction * period / TAU } else { f64::NAN },
+        is_uniform: p_value >= 0.05,
+        n: n as u32,
+    }
+}
+
+fn cycle_length(period: Option<f64>) -> f64 {
+    period.filter(|p| p.is_finite() && *p > 0.0).unwrap_or(DEFAULT_PERIOD)
+}
+
+/// `(n, mean resultant length, mean direction in [0, 2π))` of the non-NaN values
+fn resultant(values: &[f64], period: f64) -> Option<(f64, f64, f64)> {
+    let (mut sin_sum, mut cos_sum, mut n) = (0.0, 0.0, 0usize);
+    for value in values.iter().filter(|v| !v.is_nan()) {
+        let angle = value * TAU / period;
+        sin_sum += angle.sin();
+        cos_sum += angle.cos();
+        n += 1;
+    }
+    if n == 0 {
+        return None;
+    }
+    let n = n as f64;
+    let length = (sin_sum / n).hypot(cos_sum / n);
+    Some((n, length, sin_sum.atan2(cos_sum).rem_euclid(TAU)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circular_mean_and_std() {
+        let midnight = circular_mean(vec![23.0, 1.0], None);
+        assert!(midnight < 1e-9 || (midnight - 24.0).abs() < 1e-9);
+        assert!((circular_mean(vec![350.0, 20.0, f64::NAN], Some(360.0)) - 5.0).abs() < 1e-9);
+        assert!(circular_mean(vec![0.0, 12.0], None).is_nan());
+
+        assert!(circular_std(vec![9.0; 5], None).abs() < 1e-6);
+        // Tight clusters match the ordinary standard deviation
+        let tight = vec![23.9, 0.0, 0.1];
+        assert!((circular_std(tight, None) - (0.02f64 / 3.0).sqrt()).abs() < 1e-4);
+        assert!(circular_std(vec![0.0, 6.0, 12.0, 18.0], None) > 10.0);
+    }
+
+    #[test]
+    fn test_rayleigh_test() {
+        // Evenly spread over the day: no peak
+        let spread: Vec<f64> = (0..48).map(|i| i as f64 / 2.0).collect();
+        let uniform = rayleigh_test(spread, None);
+        assert!(uniform.is_uniform && uniform.mean_resultant_length < 1e-9);
+
+        // Evening peak around 18:00
+        let peaked: Vec<f64> = (0..30).map(|i| 16.5 + (i % 7) as f64 * 0.5).collect();
+        let result = rayleigh_test(peaked, None);
+        assert!(!result.is_uniform && result.p_value < 1e-6);
+        assert!((result.mean - 18.0).abs() < 0.1);
+        assert_eq!(result.n, 30);
+    }
+}
@@ -0,0 +1,287 @@
+//! Hierarchical Clustering
+//!
+//! Agglomerative clustering of feature vectors under Euclidean distance:
+//! - Ward, average, complete and single linkage (nearest-neighbor chain)
+//! - Cutting the tree into k clusters or at a distance threshold
+//! - Linkage (merge) list and leaf order for dendrogram rendering
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DendrogramMerge {
+    /// Cluster ids joined by this merge: ids below `n_points` are the input
+    /// points, id `n_points + i` is the cluster formed by merge `i`
+    pub left: u32,
+    pub right: u32,
+    /// Linkage distance at which the two clusters were joined
+    pub distance: f64,
+    /// Number of points in the merged cluster
+    pub size: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Dendrogram {
+    pub n_points: u32,
+    pub linkage: String,
+    /// `n_points - 1` merges in order of increasing distance (same layout as
+    /// a SciPy linkage matrix)
+    pub merges: Vec<DendrogramMerge>,
+    /// Input point indices in dendrogram leaf order, left to right
+    pub leaf_order: Vec<u32>,
+}
+
+/// Agglomerative hierarchical clustering
+///
+/// Builds the full merge tree in O(n^2) time and memory. The distance matrix
+/// is computed in parallel; merges use the nearest-neighbor chain algorithm
+/// with Lance-Williams distance updates. Fails when the points differ in
+/// length or have a non-finite coordinate.
+///
+/// # Arguments
+/// * `points` - Feature vectors, one per item (all the same length)
+/// * `linkage` - "ward" (default), "average", "complete" or "single"
+#[napi]
+pub fn hierarchical_clustering(points: Vec<Vec<f64>>, linkage: Option<String>) -> napi::Result<Dendrogram> {
+    let linkage = match linkage.as_deref() {
+        Some(l @ ("average" | "complete" | "single")) => l.to_string(),
+        _ => "ward".to_string(),
+    };
+    let n = points.len();
+    let dims = points.first().map_or(0, |p| p.len());
+    if let Some(i) = points.iter().position(|p| p.len() != dims) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("point {} has {} coordinates; expected {}", i, points[i].len(), dims),
+        ));
+    }
+    if let Some(i) = points.iter().position(|p| p.iter().any(|v| !v.is_finite())) {
+        return Err(Error::new(Status::InvalidArg, format!("point {} has a non-finite coordinate", i)));
+    }
+    if n < 2 {
+        return Ok(Dendrogram {
+            n_points: n as u32,
+            linkage,
+            merges: Vec::new(),
+            leaf_order: (0..n as u32).collect(),
+        });
+    }
+
+    let mut distances: Vec<f64> = (0..n * n)
+        .into_par_iter()
+        .map(|k| {
+            let (i, j) = (k / n, k % n);
+            points[i].iter().zip(&points[j]).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
+        })
+        .collect();
+
+    let raw = nearest_neighbor_chain(&mut distances, n, &linkage);
+    let merges = label_merges(raw, n);
+    let leaf_order = leaf_order(&merges, n);
+    Ok(Dendrogram {
+        n_points: n as u32,
+        linkage,
+        merges,
+        leaf_order,
+    })
+}
+
+/// Flat cluster labels from a dendrogram
+///
+/// With `k`, merges are applied until `k` clusters remain; otherwise every
+/// merge at or below `distance_threshold` is applied. Labels are numbered
+/// 0.. in order of each cluster's first point. Without either argument every
+/// point is its own cluster.
+///
+/// # Arguments
+/// * `dendrogram` - Result of `hierarchical_clustering`
+/// * `k` - Number of clusters
+/// * `distance_threshold` - Largest linkage distance merged (used when `k` is omitted)
+#[napi]
+pub fn cut_tree(dendrogram: Dendrogram, k: Option<u32>, distance_threshold: Option<f64>) -> Vec<u32> {
+    let n = dendrogram.n_points as usize;
+    let applied = match (k, distance_threshold) {
+        (Some(k), _) => n.saturating_sub((k as usize).max(1)).min(dendrogram.merges.len()),
+        (None, Some(t)) => dendrogram.merges.iter().take_while(|m| m.distance <= t).count(),
+        (None, None) => 0,
+    };
+
+    // Cluster id -> representative point, then union the applied merges
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut representative: Vec<usize> = (0..n).collect();
+    for (i, merge) in dendrogram.merges.iter().take(applied).enumerate() {
+        let (Some(&a), Some(&b)) = (representative.get(merge.left as usize), representative.get(merge.right as usize)) else {
+            break;
+        };
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        parent[rb] = ra;
+        if representative.len() == n + i {
+            representative.push(ra);
+        }
+    }
+
+    let mut labels = vec![u32::MAX; n];
+    let mut root_label: Vec<Option<u32>> = vec![None; n];
+    let mut next = 0;
+    for (point, label) in labels.iter_mut().enumerate() {
+        let root = find(&mut parent, point);
+        *label = *root_label[root].get_or_insert_with(|| {
+            next += 1;
+            next - 1
+        });
+    }
+    labels
+}
+
+/// Merge clusters until one remains; returns `(slot_a, slot_b, distance)` in merge order
+///
+/// Slot `s` always holds the cluster containing input point `s`. Valid for
+/// reducible linkages, which all supported ones are.
+fn nearest_neighbor_chain(d: &mut [f64], n: usize, linkage: &str) -> Vec<(usize, usize, f64)> {
+    let mut active = vec![true; n];
+    let mut size = vec![1usize; n];
+    let mut chain: Vec<usize> = Vec::with_capacity(n);
+    let mut merges = Vec::with_capacity(n - 1);
+
+    for _ in 0..n - 1 {
+        if chain.is_empty() {
+            chain.push(active.iter().position(|&a| a).unwrap_or(0));
+        }
+        let (a, b) = loop {
+            let x = chain[chain.len() - 1];
+            let previous = chain.len().checked_sub(2).map(|i| chain[i]);
+            // Prefer the previous chain element on ties so the chain terminates
+            let mut nearest = previous;
+            let mut best = previous.map_or(f64::INFINITY, |p| d[x * n + p]);
+            for z in (0..n).filter(|&z| active[z] && z != x) {
+                if d[x * n + z] < best {
+                    best = d[x * n + z];
+                    nearest = Some(z);
+                }
+            }
+            let z = nearest.unwrap_or(x);
+            if Some(z) == previous {
+                chain.truncate(chain.len() - 2);
+                break (x, z);
+            }
+            chain.push(z);
+        };
+
+        let dab = d[a * n + b];
+        merges.push((a, b, dab));
+        active[a] = false;
+        for k in (0..n).filter(|&k| active[k] && k != b) {
+            let (dak, dbk) = (d[a * n + k], d[b * n + k]);
+            let updated = match linkage {
+                "single" => dak.min(dbk),
+                "complete" => dak.max(dbk),
+                "average" => (size[a] as f64 * dak + size[b] as f64 * dbk) / (size[a] + size[b]) as f64,
+                _ => {
+                    let (na, nb, nk) = (size[a] as f64, size[b] as f64, size[k] as f64);
+                    (((na + nk) * dak * dak + (nb + nk) * dbk * dbk - nk * dab * dab) / (na + nb + nk)).max(0.0).sqrt()
+                }
+            };
+            d[b * n + k] = updated;
+            d[k * n + b] = updated;
+        }
+        size[b] += size[a];
+    }
+    merges
+}
+
+/// Sort raw merges by distance and relabel them with SciPy-style cluster ids
+fn label_merges(mut raw: Vec<(usize, usize, f64)>, n: usize) -> Vec<DendrogramMerge> {
+    raw.sort_by(|x, y| x.2.total_cmp(&y.2));
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut cluster_id: Vec<usize> = (0..n).collect();
+    let mut size = vec![1u32; n];
+    raw.into_iter()
+        .enumerate()
+        .map(|(i, (a, b, distance))| {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            let (ida, idb) = (cluster_id[ra], cluster_id[rb]);
+            parent[rb] = ra;
+            cluster_id[ra] = n + i;
+            size[ra] += size[rb];
+            DendrogramMerge {
+                left: ida.min(idb) as u32,
+                right: ida.max(idb) as u32,
+                distance,
+                size: size[ra],
+            }
+        })
+        .collect()
+}
+
+/// Leaves of the tree left to right (iterative depth-first walk from the root)
+fn leaf_order(merges: &[DendrogramMerge], n: usize) -> Vec<u32> {
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![n + merges.len() - 1];
+    while let Some(id) = stack.pop() {
+        if id < n {
+            order.push(id as u32);
+        } else {
+            let merge = &merges[id - n];
+            stack.push(merge.right as usize);
+            stack.push(merge.left as usize);
+        }
+    }
+    order
+}
+
+/// Union-find root with path halving
+fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linkage_distances() {
+        let points = vec![vec![0.0], vec![1.0], vec![5.0], vec![6.0]];
+        let average = hierarchical_clustering(points.clone(), Some("average".to_string())).unwrap();
+        let rows: Vec<(u32, u32, f64, u32)> = average.merges.iter().map(|m| (m.left, m.right, m.distance, m.size)).collect();
+        assert_eq!(rows, vec![(0, 1, 1.0, 2), (2, 3, 1.0, 2), (4, 5, 5.0, 4)]);
+
+        // Ward distance between {0, 1} and {5, 6} is sqrt(2 * 2 * 2 / 4) * 5
+        let ward = hierarchical_clustering(points.clone(), None).unwrap();
+        assert!((ward.merges[2].distance - 50f64.sqrt()).abs() < 1e-9);
+        let complete = hierarchical_clustering(points, Some("complete".to_string())).unwrap();
+        assert_eq!(complete.merges[2].distance, 6.0);
+        assert_eq!(complete.leaf_order.len(), 4);
+
+        assert!(hierarchical_clustering(vec![vec![0.0], vec![f64::NAN], vec![2.0]], None).is_err());
+        assert!(hierarchical_clustering(vec![vec![0.0], vec![1.0, 2.0]], None).is_err());
+    }
+
+    #[test]
+    fn test_cut_tree() {
+        // Three well separated groups in 2-D
+        let points: Vec<Vec<f64>> = (0..30)
+            .map(|i| {
+                let center = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)][i % 3];
+                vec![center.0 + (i / 3) as f64 * 0.1, center.1 + ((i * 7) % 5) as f64 * 0.1]
+            })
+            .collect();
+        let dendrogram = hierarchical_clustering(points, None).unwrap();
+        assert_eq!(dendrogram.merges.len(), 29);
+        assert!(dendrogram.merges.windows(2).all(|w| w[0].distance <= w[1].distance));
+
+        let labels = cut_tree(dendrogram.clone(), Some(3), None);
+        assert_eq!(&labels[..6], &[0, 1, 2, 0, 1, 2]);
+        assert!(labels.iter().enumerate().all(|(i, &l)| l == (i % 3) as u32));
+
+        assert_eq!(cut_tree(dendrogram.clone(), None, Some(5.0)), labels);
+        assert!(cut_tree(dendrogram.clone(), None, Some(1e9)).iter().all(|&l| l == 0));
+        assert_eq!(cut_tree(dendrogram, None, None).iter().max(), Some(&29));
+    }
+}
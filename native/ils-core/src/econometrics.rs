@@ -0,0 +1,610 @@
+//! Multivariate Time Series
+//!
+//! Models for the joint dynamics of several related series:
+//! - Vector autoregression (VAR) with lag selection by AIC
+//! - Joint multi-step forecasts with prediction intervals
+//! - Engle-Granger cointegration test
+//! - Distributed-lag regression between two series
+
+use napi_derive::napi;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+use crate::linalg::{cholesky, gram, least_squares, solve, xt_y};
+use crate::statistics::t_p_value;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VarModel {
+    pub n_series: u32,
+    /// Selected lag order `p`
+    pub lag_order: u32,
+    /// Intercept of each equation
+    pub intercepts: Vec<f64>,
+    /// `coefficients[lag][i][j]`: effect of series `j` at lag `lag + 1` on series `i`
+    pub coefficients: Vec<Vec<Vec<f64>>>,
+    /// Residual covariance matrix
+    pub residual_covariance: Vec<Vec<f64>>,
+    /// AIC for each candidate lag order 1..=max_lag (same estimation sample)
+    pub aic_by_lag: Vec<f64>,
+    /// Last `p` observations (oldest first, one row per time step), used to forecast
+    pub last_observations: Vec<Vec<f64>>,
+    pub n_observations: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VarForecast {
+    /// `predictions[i][h]`: forecast of series `i` at step `h + 1`
+    pub predictions: Vec<Vec<f64>>,
+    /// 95% prediction interval bounds, same layout as `predictions`
+    pub lower_bounds: Vec<Vec<f64>>,
+    pub upper_bounds: Vec<Vec<f64>>,
+}
+
+/// Fit a vector autoregression to several aligned series
+///
+/// Each equation is estimated by least squares. The lag order is the one
+/// minimizing AIC over `1..=max_lag`, with all candidates fitted on the same
+/// sample so their AIC values are comparable.
+///
+/// # Arguments
+/// * `series` - One inner array per series, all of the same length
+/// * `max_lag` - Largest lag order considered (default 4)
+#[napi]
+pub fn fit_var(series: Vec<Vec<f64>>, max_lag: Option<u32>) -> VarModel {
+    let k = series.len();
+    let n = series.iter().map(|s| s.len()).min().unwrap_or(0);
+    let max_lag = (max_lag.unwrap_or(4).max(1) as usize).min(n.saturating_sub(2) / (k + 1).max(1)).max(1);
+    if k == 0 || n <= max_lag + 1 {
+        return empty_var_model(k);
+    }
+
+    // Row-major observations: rows[t][j] = series j at time t
+    let rows: Vec<Vec<f64>> = (0..n).map(|t| series.iter().map(|s| s[t]).collect()).collect();
+
+    let fits: Vec<Option<VarFit>> = (1..=max_lag).map(|p| fit_var_order(&rows, p, max_lag)).collect();
+    let aic_by_lag: Vec<f64> = fits.iter().map(|f| f.as_ref().map_or(f64::INFINITY, |f| f.aic)).collect();
+
+    // Refit the selected order on all available observations
+    let best = (0..fits.len()).min_by(|&a, &b| aic_by_lag[a].total_cmp(&aic_by_lag[b])).unwrap_or(0) + 1;
+    let fit = match fit_var_order(&rows, best, best) {
+        Some(fit) => fit,
+        None => return empty_var_model(k),
+    };
+
+    VarModel {
+        n_series: k as u32,
+        lag_order: best as u32,
+        intercepts: fit.intercepts,
+        coefficients: fit.coefficients,
+        residual_covariance: fit.covariance,
+        aic_by_lag,
+        last_observations: rows[n - best..].to_vec(),
+        n_observations: n as u32,
+    }
+}
+
+/// Forecast all series of a fitted VAR jointly
+///
+/// Intervals use the forecast-error covariance implied by the model's
+/// moving-average representation.
+///
+/// # Arguments
+/// * `model` - Model returned by `fit_var`
+/// * `steps` - Forecast horizon
+#[napi]
+pub fn var_forecast(model: VarModel, steps: u32) -> VarForecast {
+    let k = model.n_series as usize;
+    let p = model.lag_order as usize;
+    let steps = steps as usize;
+    let mut history = model.last_observations.clone();
+    if k == 0 || p == 0 || history.len() < p {
+        return VarForecast {
+            predictions: vec![Vec::new(); k],
+            lower_bounds: vec![Vec::new(); k],
+            upper_bounds: vec![Vec::new(); k],
+        };
+    }
+
+    let mut predictions = vec![Vec::with_capacity(steps); k];
+    for _ in 0..steps {
+        let next: Vec<f64> = (0..k)
+            .map(|i| {
+                model.intercepts[i]
+                    + (0..p)
+                        .map(|lag| {
+                            let past = &history[history.len() - 1 - lag];
+                            model.coefficients[lag][i].iter().zip(past).map(|(a, x)| a * x).sum::<f64>()
+                        })
+                        .sum::<f64>()
+            })
+            .collect();
+        for (i, value) in next.iter().enumerate() {
+            predictions[i].push(*value);
+        }
+        history.push(next);
+    }
+
+    // MA coefficients: psi_0 = I, psi_h = sum_{l=1..min(h,p)} A_l psi_{h-l}
+    let mut psi: Vec<Vec<Vec<f64>>> = vec![identity(k)];
+    for h in 1..steps {
+        let mut next = vec![vec![0.0; k]; k];
+        for lag in 1..=h.min(p) {
+            let product = matmul(&model.coefficients[lag - 1], &psi[h - lag]);
+            add_assign(&mut next, &product);
+        }
+        psi.push(next);
+    }
+
+    let mut lower_bounds = vec![Vec::with_capacity(steps); k];
+    let mut upper_bounds = vec![Vec::with_capacity(steps); k];
+    let mut mse = vec![vec![0.0; k]; k];
+    for (h, psi_h) in psi.iter().enumerate() {
+        let term = matmul(&matmul(psi_h, &model.residual_covariance), &transpose(psi_h));
+        add_assign(&mut mse, &term);
+        for i in 0..k {
+            let half_width = 1.96 * mse[i][i].max(0.0).sqrt();
+            lower_bounds[i].push(predictions[i][h] - half_width);
+            upper_bounds[i].push(predictions[i][h] + half_width);
+        }
+    }
+
+    VarForecast {
+        predictions,
+        lower_bounds,
+        upper_bounds,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CointegrationResult {
+    /// ADF t-statistic of the cointegrating residuals
+    pub test_statistic: f64,
+    /// Approximate p-value, interpolated from MacKinnon critical values
+    pub p_value: f64,
+    /// Critical values at the 1%, 5% and 10% levels
+    pub critical_values: Vec<f64>,
+    /// Whether the null of no cointegration is rejected at the 5% level
+    pub is_cointegrated: bool,
+    /// Long-run relationship `y = intercept + hedge_ratio * x`
+    pub intercept: f64,
+    pub hedge_ratio: f64,
+    /// Deviations from the long-run relationship (the spread)
+    pub residuals: Vec<f64>,
+    /// Latest spread in standard deviations; large magnitudes flag decoupling
+    pub latest_spread_z: f64,
+    /// Augmentation lags chosen by AIC
+    pub lag_order: u32,
+}
+
+/// Two-step Engle-Granger cointegration test for a pair of series
+///
+/// Step one regresses `y` on `x` to estimate the long-run relationship;
+/// step two runs an augmented Dickey-Fuller test on its residuals. Critical
+/// values follow MacKinnon (2010) for two variables with a constant.
+///
+/// # Arguments
+/// * `y`, `x` - Aligned series (e.g. invoiced revenue and orders)
+/// * `max_lag` - Largest ADF augmentation lag considered (default `12 * (n / 100)^(1/4)`)
+#[napi]
+pub fn engle_granger_test(y: Vec<f64>, x: Vec<f64>, max_lag: Option<u32>) -> CointegrationResult {
+    let n = y.len().min(x.len());
+    let design: Vec<Vec<f64>> = x[..n].iter().map(|&v| vec![1.0, v]).collect();
+    let beta = if n >= 10 {
+        solve(gram(&design, None), xt_y(&design, &y[..n], None))
+    } else {
+        None
+    };
+    let Some(beta) = beta else {
+        return CointegrationResult {
+            test_statistic: 0.0,
+            p_value: 1.0,
+            critical_values: Vec::new(),
+            is_cointegrated: false,
+            intercept: 0.0,
+            hedge_ratio: 0.0,
+            residuals: Vec::new(),
+            latest_spread_z: 0.0,
+            lag_order: 0,
+        };
+    };
+
+    let residuals: Vec<f64> = (0..n).map(|t| y[t] - beta[0] - beta[1] * x[t]).collect();
+    let default_lag = (12.0 * (n as f64 / 100.0).powf(0.25)) as u32;
+    let (test_statistic, lag_order) = adf_statistic(&residuals, max_lag.unwrap_or(default_lag) as usize, AdfTerms::None);
+
+    // MacKinnon (2010) response surfaces, N = 2, constant
+    let t = n as f64;
+    let critical_values = vec![
+        -3.89644 - 10.9519 / t - 22.527 / (t * t),
+        -3.33613 - 6.1101 / t - 6.823 / (t * t),
+        -3.04445 - 4.2412 / t - 2.720 / (t * t),
+    ];
+    let p_value = interpolate_p_value(test_statistic, &critical_values);
+
+    let sd = (residuals.iter().map(|r| r * r).sum::<f64>() / n as f64).sqrt();
+    CointegrationResult {
+        test_statistic,
+        p_value,
+        is_cointegrated: test_statistic < critical_values[1],
+        critical_values,
+        intercept: beta[0],
+        hedge_ratio: beta[1],
+        latest_spread_z: if sd > 0.0 { residuals[n - 1] / sd } else { 0.0 },
+        residuals,
+        lag_order: lag_order as u32,
+    }
+}
+
+/// Deterministic terms included in the ADF regression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AdfTerms {
+    None,
+    Constant,
+    ConstantTrend,
+}
+
+/// Augmented Dickey-Fuller t-statistic with the augmentation lag chosen by AIC
+///
+/// Returns `(statistic, lag)`. All candidate lags are compared on the same
+/// sample; the selected lag is then refitted on all usable observations.
+pub(crate) fn adf_statistic(data: &[f64], max_lag: usize, terms: AdfTerms) -> (f64, usize) {
+    let max_lag = max_lag.min(data.len().saturating_sub(4) / 3);
+    let best = (0..=max_lag)
+        .filter_map(|lag| adf_regression(data, lag, terms, max_lag + 1).map(|(_, aic)| (lag, aic)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(lag, _)| lag);
+    let statistic = adf_regression(data, best, terms, best + 1).map_or(0.0, |(t, _)| t);
+    (statistic, best)
+}
+
+/// Regress `Δy_t` on `y_{t-1}`, `lags` lagged differences and the
+/// deterministic terms for `t >= start`; returns the t-statistic on
+/// `y_{t-1}` and the regression AIC
+fn adf_regression(data: &[f64], lags: usize, terms: AdfTerms, start: usize) -> Option<(f64, f64)> {
+    let diff: Vec<f64> = data.windows(2).map(|w| w[1] - w[0]).collect();
+    // diff[t - 1] = y_t - y_{t-1}
+    let rows: Vec<(Vec<f64>, f64)> = (start..data.len())
+        .map(|t| {
+            let mut row = vec![data[t - 1]];
+            row.extend((1..=lags).map(|i| diff[t - 1 - i]));
+            if terms != AdfTerms::None {
+                row.push(1.0);
+            }
+            if terms == AdfTerms::ConstantTrend {
+                row.push(t as f64);
+            }
+            (row, diff[t - 1])
+        })
+        .collect();
+    let k = lags + 1 + match terms {
+        AdfTerms::None => 0,
+        AdfTerms::Constant => 1,
+        AdfTerms::ConstantTrend => 2,
+    };
+    if rows.len() <= k + 1 {
+        return None;
+    }
+
+    let x: Vec<Vec<f64>> = rows.iter().map(|r| r.0.clone()).collect();
+    let y: Vec<f64> = rows.iter().map(|r| r.1).collect();
+    let xtx = gram(&x, None);
+    let beta = solve(xtx.clone(), xt_y(&x, &y, None))?;
+    let rss: f64 = x
+        .iter()
+        .zip(&y)
+        .map(|(row, target)| (target - row.iter().zip(&beta).map(|(a, b)| a * b).sum::<f64>()).powi(2))
+        .sum();
+
+    let n = rows.len() as f64;
+    let sigma2 = rss / (n - k as f64);
+    let mut unit = vec![0.0; k];
+    unit[0] = 1.0;
+    let variance = solve(xtx, unit)?[0] * sigma2;
+    if variance <= 0.0 {
+        return None;
+    }
+    let aic = n * (rss / n).max(1e-300).ln() + 2.0 * k as f64;
+    Some((beta[0] / variance.sqrt(), aic))
+}
+
+/// Approximate p-value from critical values at the 1%, 5% and 10% levels
+///
+/// Fits a line from test statistic to normal quantile through the three
+/// tabulated points, which extrapolates smoothly outside the table.
+pub(crate) fn interpolate_p_value(statistic: f64, critical_values: &[f64]) -> f64 {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let quantiles: Vec<f64> = [0.01, 0.05, 0.10].iter().map(|&p| normal.inverse_cdf(p)).collect();
+    let mean_c = critical_values.iter().sum::<f64>() / 3.0;
+    let mean_q = quantiles.iter().sum::<f64>() / 3.0;
+    let slope = critical_values
+        .iter()
+        .zip(&quantiles)
+        .map(|(c, q)| (c - mean_c) * (q - mean_q))
+        .sum::<f64>()
+        / critical_values.iter().map(|c| (c - mean_c).powi(2)).sum::<f64>();
+    normal.cdf(mean_q + slope * (statistic - mean_c)).clamp(0.0, 1.0)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LagCoefficient {
+    pub lag: u32,
+    pub coefficient: f64,
+    pub std_error: f64,
+    pub t_statistic: f64,
+    /// Two-sided p-value
+    pub p_value: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DistributedLagResult {
+    pub intercept: f64,
+    /// Effect of `x` at lags 0..=max_lag
+    pub coefficients: Vec<LagCoefficient>,
+    /// Total effect of a one-unit change in `x` summed over all lags
+    pub cumulative_effect: f64,
+    pub cumulative_std_error: f64,
+    pub cumulative_p_value: f64,
+    pub r_squared: f64,
+    pub n_observations: u32,
+}
+
+/// Distributed-lag regression `y_t = a + b_0 x_t + b_1 x_{t-1} + ... + b_L x_{t-L}`
+///
+/// Quantifies how a change in `x` propagates into `y` over the following
+/// periods, with ordinary least-squares standard errors and t-test p-values.
+///
+/// # Arguments
+/// * `y` - Response series (e.g. daily orders)
+/// * `x` - Driver series aligned with `y` (e.g. daily marketing spend)
+/// * `max_lag` - Largest lag of `x` included
+#[napi]
+pub fn distributed_lag_regression(y: Vec<f64>, x: Vec<f64>, max_lag: u32) -> DistributedLagResult {
+    let n = y.len().min(x.len());
+    let max_lag = max_lag as usize;
+    let design: Vec<Vec<f64>> = (max_lag..n)
+        .map(|t| std::iter::once(1.0).chain((0..=max_lag).map(|lag| x[t - lag])).collect())
+        .collect();
+    let target: Vec<f64> = (max_lag..n).map(|t| y[t]).collect();
+
+    let Some(fit) = least_squares(&design, &target) else {
+        return DistributedLagResult {
+            intercept: 0.0,
+            coefficients: Vec::new(),
+            cumulative_effect: 0.0,
+            cumulative_std_error: 0.0,
+            cumulative_p_value: 1.0,
+            r_squared: 0.0,
+            n_observations: 0,
+        };
+    };
+
+    let df = fit.df as f64;
+    let std_errors = fit.std_errors();
+    let coefficients = (0..=max_lag)
+        .map(|lag| {
+            let coefficient = fit.coefficients[lag + 1];
+            let std_error = std_errors[lag + 1];
+            let t_statistic = if std_error > 0.0 { coefficient / std_error } else { 0.0 };
+            LagCoefficient {
+                lag: lag as u32,
+                coefficient,
+                std_error,
+                t_statistic,
+                p_value: t_p_value(t_statistic, df),
+            }
+        })
+        .collect();
+
+    // Var(sum b) = sigma2 * sum of the lag block of (X'X)^-1
+    let cumulative_effect: f64 = fit.coefficients[1..].iter().sum();
+    let block: f64 = fit.xtx_inv[1..].iter().map(|row| row[1..].iter().sum::<f64>()).sum();
+    let cumulative_std_error = (block * fit.sigma2()).max(0.0).sqrt();
+    let cumulative_t = if cumulative_std_error > 0.0 { cumulative_effect / cumulative_std_error } else { 0.0 };
+
+    let target_mean = target.iter().sum::<f64>() / target.len() as f64;
+    let tss: f64 = target.iter().map(|v| (v - target_mean).powi(2)).sum();
+
+    DistributedLagResult {
+        intercept: fit.coefficients[0],
+        coefficients,
+        cumulative_effect,
+        cumulative_std_error,
+        cumulative_p_value: t_p_value(cumulative_t, df),
+        r_squared: if tss > 0.0 { 1.0 - fit.rss / tss } else { 0.0 },
+        n_observations: target.len() as u32,
+    }
+}
+
+struct VarFit {
+    intercepts: Vec<f64>,
+    coefficients: Vec<Vec<Vec<f64>>>,
+    covariance: Vec<Vec<f64>>,
+    aic: f64,
+}
+
+/// Least-squares VAR(p) using observations from `start` onward as targets
+fn fit_var_order(rows: &[Vec<f64>], p: usize, start: usize) -> Option<VarFit> {
+    let k = rows[0].len();
+    let design: Vec<Vec<f64>> = (start..rows.len())
+        .map(|t| {
+            let mut row = Vec::with_capacity(1 + k * p);
+            row.push(1.0);
+            for lag in 1..=p {
+                row.extend_from_slice(&rows[t - lag]);
+            }
+            row
+        })
+        .collect();
+    let t_obs = design.len();
+    if t_obs <= 1 + k * p {
+        return None;
+    }
+
+    let xtx = gram(&design, None);
+    let mut intercepts = Vec::with_capacity(k);
+    let mut coefficients = vec![vec![vec![0.0; k]; k]; p];
+    let mut residuals: Vec<Vec<f64>> = Vec::with_capacity(k);
+    for i in 0..k {
+        let target: Vec<f64> = (start..rows.len()).map(|t| rows[t][i]).collect();
+        let beta = solve(xtx.clone(), xt_y(&design, &target, None))?;
+        intercepts.push(beta[0]);
+        for (lag, block) in coefficients.iter_mut().enumerate() {
+            block[i].copy_from_slice(&beta[1 + lag * k..1 + (lag + 1) * k]);
+        }
+        residuals.push(
+            design
+                .iter()
+                .zip(&target)
+                .map(|(row, y)| y - row.iter().zip(&beta).map(|(a, b)| a * b).sum::<f64>())
+                .collect(),
+        );
+    }
+
+    let covariance: Vec<Vec<f64>> = (0..k)
+        .map(|a| {
+            (0..k)
+                .map(|b| residuals[a].iter().zip(&residuals[b]).map(|(x, y)| x * y).sum::<f64>() / t_obs as f64)
+                .collect()
+        })
+        .collect();
+    let log_det = cholesky(&covariance)
+        .map(|l| 2.0 * (0..k).map(|i| l[i][i].ln()).sum::<f64>())
+        .unwrap_or(f64::NEG_INFINITY);
+    let aic = log_det + 2.0 * (p * k * k) as f64 / t_obs as f64;
+
+    Some(VarFit {
+        intercepts,
+        coefficients,
+        covariance,
+        aic,
+    })
+}
+
+fn empty_var_model(k: usize) -> VarModel {
+    VarModel {
+        n_series: k as u32,
+        lag_order: 0,
+        intercepts: vec![0.0; k],
+        coefficients: Vec::new(),
+        residual_covariance: vec![vec![0.0; k]; k],
+        aic_by_lag: Vec::new(),
+        last_observations: Vec::new(),
+        n_observations: 0,
+    }
+}
+
+fn identity(k: usize) -> Vec<Vec<f64>> {
+    (0..k).map(|i| (0..k).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect()
+}
+
+fn matmul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let cols = b.first().map_or(0, |r| r.len());
+    a.iter()
+        .map(|row| (0..cols).map(|j| row.iter().zip(b).map(|(x, b_row)| x * b_row[j]).sum()).collect())
+        .collect()
+}
+
+fn transpose(a: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let cols = a.first().map_or(0, |r| r.len());
+    (0..cols).map(|j| a.iter().map(|row| row[j]).collect()).collect()
+}
+
+fn add_assign(target: &mut [Vec<f64>], other: &[Vec<f64>]) {
+    for (row, other_row) in target.iter_mut().zip(other) {
+        row.iter_mut().zip(other_row).for_each(|(a, b)| *a += b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampling::Rng;
+
+    /// Orders drive remakes one step later
+    fn simulate(n: usize) -> Vec<Vec<f64>> {
+        let mut rng = Rng::new(4);
+        let mut orders = vec![0.0];
+        let mut remakes = vec![0.0];
+        for t in 1..n {
+            let o = 10.0 + 0.5 * orders[t - 1] + rng.next_f64() - 0.5;
+            let r = 1.0 + 0.2 * remakes[t - 1] + 0.3 * orders[t - 1] + rng.next_f64() - 0.5;
+            orders.push(o);
+            remakes.push(r);
+        }
+        vec![orders, remakes]
+    }
+
+    #[test]
+    fn test_fit_var_recovers_dynamics() {
+        let model = fit_var(simulate(400), Some(3));
+        assert_eq!(model.lag_order, 1);
+        assert_eq!(model.aic_by_lag.len(), 3);
+        assert!((model.coefficients[0][0][0] - 0.5).abs() < 0.1);
+        assert!((model.coefficients[0][1][0] - 0.3).abs() < 0.1);
+        assert!(model.coefficients[0][0][1].abs() < 0.1);
+    }
+
+    #[test]
+    fn test_engle_granger() {
+        let mut rng = Rng::new(8);
+        let mut orders = vec![100.0];
+        for _ in 1..300 {
+            let last = *orders.last().unwrap();
+            orders.push(last + rng.next_f64() * 4.0 - 2.0);
+        }
+        let revenue: Vec<f64> = orders.iter().map(|o| 50.0 + 2.5 * o + rng.next_f64() * 4.0 - 2.0).collect();
+
+        let result = engle_granger_test(revenue, orders.clone(), None);
+        assert!(result.is_cointegrated);
+        assert!(result.p_value < 0.01);
+        assert!((result.hedge_ratio - 2.5).abs() < 0.1);
+
+        // An independent random walk is not cointegrated with orders
+        let mut other = vec![0.0];
+        for _ in 1..300 {
+            let last = *other.last().unwrap();
+            other.push(last + rng.next_f64() * 4.0 - 2.0);
+        }
+        let unrelated = engle_granger_test(other, orders, None);
+        assert!(unrelated.p_value > 0.05);
+        assert!(!unrelated.is_cointegrated);
+    }
+
+    #[test]
+    fn test_distributed_lag_regression() {
+        let mut rng = Rng::new(12);
+        let spend: Vec<f64> = (0..200).map(|_| rng.next_f64() * 10.0).collect();
+        let orders: Vec<f64> = (0..200)
+            .map(|t| {
+                let lagged = |lag: usize| if t >= lag { spend[t - lag] } else { 0.0 };
+                20.0 + 1.0 * lagged(1) + 0.5 * lagged(2) + rng.next_f64() - 0.5
+            })
+            .collect();
+
+        let result = distributed_lag_regression(orders, spend, 3);
+        assert_eq!(result.coefficients.len(), 4);
+        assert_eq!(result.n_observations, 197);
+        assert!(result.coefficients[0].p_value > 0.01);
+        assert!((result.coefficients[1].coefficient - 1.0).abs() < 0.05);
+        assert!(result.coefficients[1].p_value < 1e-6);
+        assert!((result.cumulative_effect - 1.5).abs() < 0.1);
+        assert!(result.r_squared > 0.9);
+    }
+
+    #[test]
+    fn test_var_forecast_converges_to_mean() {
+        let model = fit_var(simulate(400), Some(2));
+        let forecast = var_forecast(model, 30);
+        assert_eq!(forecast.predictions.len(), 2);
+        assert_eq!(forecast.predictions[0].len(), 30);
+        // Unconditional means: orders = 10 / 0.5 = 20, remakes = (1 + 0.3 * 20) / 0.8 = 8.75
+        assert!((forecast.predictions[0][29] - 20.0).abs() < 0.5);
+        assert!((forecast.predictions[1][29] - 8.75).abs() < 0.5);
+        let width = |h: usize| forecast.upper_bounds[0][h] - forecast.lower_bounds[0][h];
+        assert!(width(29) > width(0));
+    }
+}
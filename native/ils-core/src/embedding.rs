@@ -0,0 +1,327 @@
+//! Embedding
+//!
+//! UMAP-style dimensionality reduction for visualizing high-dimensional
+//! feature vectors:
+//! - Exact k-nearest-neighbor graph (parallel KD-tree queries)
+//! - Fuzzy simplicial set with per-point bandwidths
+//! - PCA initialization and negative-sampling SGD layout
+
+use std::collections::HashMap;
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::{check_width, column_scaling, dot};
+use crate::neighbors::KdTree;
+use crate::sampling::Rng;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct UmapOptions {
+    /// Neighborhood size; larger values preserve more global structure (default 15)
+    pub n_neighbors: Option<u32>,
+    /// Minimum distance between embedded points (default 0.1)
+    pub min_dist: Option<f64>,
+    /// Output dimensions (default 2)
+    pub n_components: Option<u32>,
+    /// Optimization epochs (default 200)
+    pub n_epochs: Option<u32>,
+    /// Initial SGD learning rate (default 1.0)
+    pub learning_rate: Option<f64>,
+    /// Negative samples per positive edge (default 5)
+    pub negative_sample_rate: Option<u32>,
+    /// Standardize features before building the neighbor graph (default true)
+    pub standardize: Option<bool>,
+    pub seed: Option<u32>,
+}
+
+/// Project feature rows into a low-dimensional embedding (UMAP)
+///
+/// Returns one coordinate vector (length `n_components`) per input row.
+/// Fewer than three rows have no neighborhood to lay out and get the
+/// placeholder coordinates `[i; n_components]`. Fails for rows of different
+/// lengths or `n_components` of 0.
+///
+/// # Arguments
+/// * `data` - Feature rows, all of the same length
+/// * `options` - Neighborhood, layout and optimization settings
+#[napi]
+pub fn umap_embed(data: Vec<Vec<f64>>, options: Option<UmapOptions>) -> napi::Result<Vec<Vec<f64>>> {
+    let options = options.unwrap_or_default();
+    let n = data.len();
+    check_width(&data)?;
+    let n_components = options.n_components.unwrap_or(2) as usize;
+    if n_components == 0 {
+        return Err(Error::new(Status::InvalidArg, "n_components must be at least 1".to_string()));
+    }
+    if n <= 2 {
+        return Ok((0..n).map(|i| vec![i as f64; n_components]).collect());
+    }
+
+    let points = if options.standardize.unwrap_or(true) {
+        let (means, scales) = column_scaling(&data);
+        data.iter()
+            .map(|row| row.iter().zip(&means).zip(&scales).map(|((v, m), s)| (v - m) / s).collect())
+            .collect()
+    } else {
+        data
+    };
+
+    let k = (options.n_neighbors.unwrap_or(15) as usize).clamp(2, n - 1);
+    let edges = fuzzy_graph(&points, k);
+    let (a, b) = fit_curve(options.min_dist.unwrap_or(0.1).max(0.0));
+    let mut embedding = pca_init(&points, n_components);
+
+    optimize_layout(
+        &mut embedding,
+        &edges,
+        a,
+        b,
+        options.n_epochs.unwrap_or(200).max(1) as usize,
+        options.learning_rate.unwrap_or(1.0),
+        options.negative_sample_rate.unwrap_or(5) as usize,
+        options.seed.unwrap_or(42) as u64,
+    );
+    Ok(embedding)
+}
+
+/// Symmetrized fuzzy k-NN graph as `(i, j, weight)` edges with `i < j`
+fn fuzzy_graph(points: &[Vec<f64>], k: usize) -> Vec<(usize, usize, f64)> {
+    let tree = KdTree::new(points.to_vec());
+    let target = (k as f64).log2();
+
+    let directed: Vec<Vec<(usize, f64)>> = points
+        .par_iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let neighbors: Vec<(usize, f64)> =
+                tree.nearest(p, k + 1).into_iter().filter(|&(j, _)| j != i).take(k).collect();
+            let rho = neighbors.iter().map(|n| n.1).find(|&d| d > 0.0).unwrap_or(0.0);
+
+            // Binary search for the bandwidth giving an effective neighbor count of log2(k)
+            let (mut lo, mut hi, mut sigma) = (0.0, f64::INFINITY, 1.0);
+            for _ in 0..64 {
+                let total: f64 = neighbors.iter().map(|&(_, d)| (-(d - rho).max(0.0) / sigma).exp()).sum();
+                if (total - target).abs() < 1e-5 {
+                    break;
+                }
+                if total > target {
+                    hi = sigma;
+                    sigma = (lo + hi) / 2.0;
+                } else {
+                    lo = sigma;
+                    sigma = if hi.is_finite() { (lo + hi) / 2.0 } else { sigma * 2.0 };
+                }
+            }
+            let sigma = sigma.max(1e-3 * neighbors.iter().map(|n| n.1).sum::<f64>() / k as f64).max(1e-12);
+
+            neighbors.into_iter().map(|(j, d)| (j, (-(d - rho).max(0.0) / sigma).exp())).collect()
+        })
+        .collect();
+
+    // Fuzzy union: w = w_ij + w_ji - w_ij * w_ji
+    let mut combined: HashMap<(usize, usize), (f64, f64)> = HashMap::new();
+    for (i, neighbors) in directed.iter().enumerate() {
+        for &(j, w) in neighbors {
+            let entry = combined.entry((i.min(j), i.max(j))).or_insert((0.0, 0.0));
+            if i < j {
+                entry.0 = w;
+            } else {
+                entry.1 = w;
+            }
+        }
+    }
+    let mut edges: Vec<(usize, usize, f64)> = combined
+        .into_iter()
+        .map(|((i, j), (a, b))| (i, j, a + b - a * b))
+        .filter(|e| e.2 > 0.0)
+        .collect();
+    edges.sort_by_key(|e| (e.0, e.1));
+    edges
+}
+
+/// Fit `1 / (1 + a d^(2b))` to the target membership curve for `min_dist`
+fn fit_curve(min_dist: f64) -> (f64, f64) {
+    let xs: Vec<f64> = (1..300).map(|i| i as f64 * 0.01).collect();
+    let target: Vec<f64> = xs.iter().map(|&d| if d < min_dist { 1.0 } else { (-(d - min_dist)).exp() }).collect();
+
+    let error = |a: f64, b: f64| -> f64 {
+        xs.iter()
+            .zip(&target)
+            .map(|(&d, t)| (1.0 / (1.0 + a * d.powf(2.0 * b)) - t).powi(2))
+            .sum()
+    };
+
+    let mut best = (1.0, 1.0, f64::INFINITY);
+    for ai in 0..60 {
+        let a = 0.1 * 1.08_f64.powi(ai);
+        for bi in 0..40 {
+            let b = 0.3 + bi as f64 * 0.03;
+            let e = error(a, b);
+            if e < best.2 {
+                best = (a, b, e);
+            }
+        }
+    }
+    (best.0, best.1)
+}
+
+/// Project onto the leading principal components, scaled to a [-10, 10] box
+fn pca_init(points: &[Vec<f64>], n_components: usize) -> Vec<Vec<f64>> {
+    let n = points.len();
+    let p = points[0].len();
+    let means: Vec<f64> = (0..p).map(|j| points.iter().map(|r| r[j]).sum::<f64>() / n as f64).collect();
+    let centered: Vec<Vec<f64>> = points
+        .iter()
+        .map(|r| r.iter().zip(&means).map(|(v, m)| v - m).collect())
+        .collect();
+
+    let mut rng = Rng::new(7);
+    let mut components: Vec<Vec<f64>> = Vec::new();
+    for _ in 0..n_components {
+        // Power iteration on X'X, deflated against earlier components
+        let mut v: Vec<f64> = (0..p).map(|_| rng.next_f64() - 0.5).collect();
+        for _ in 0..100 {
+            let scores: Vec<f64> = centered.iter().map(|r| dot(r, &v)).collect();
+            let mut next = vec![0.0; p];
+            for (row, s) in centered.iter().zip(&scores) {
+                next.iter_mut().zip(row).for_each(|(acc, x)| *acc += s * x);
+            }
+            for c in &components {
+                let proj = dot(&next, c);
+                next.iter_mut().zip(c).for_each(|(x, ci)| *x -= proj * ci);
+            }
+            let norm = dot(&next, &next).sqrt();
+            if norm < 1e-12 {
+                break;
+            }
+            v = next.iter().map(|x| x / norm).collect();
+        }
+        components.push(v);
+    }
+
+    let mut embedding: Vec<Vec<f64>> = centered
+        .iter()
+        .map(|r| components.iter().map(|c| dot(r, c)).collect())
+        .collect();
+
+    let max_abs = embedding.iter().flatten().fold(0.0_f64, |m, v| m.max(v.abs()));
+    let mut jitter = Rng::new(11);
+    for row in &mut embedding {
+        for v in row.iter_mut() {
+            *v = if max_abs > 0.0 { *v / max_abs * 10.0 } else { 0.0 } + (jitter.next_f64() - 0.5) * 1e-4;
+        }
+    }
+    embedding
+}
+
+#[allow(clippy::too_many_arguments)]
+fn optimize_layout(
+    embedding: &mut [Vec<f64>],
+    edges: &[(usize, usize, f64)],
+    a: f64,
+    b: f64,
+    n_epochs: usize,
+    learning_rate: f64,
+    negative_rate: usize,
+    seed: u64,
+) {
+    let n = embedding.len();
+    let max_weight = edges.iter().map(|e| e.2).fold(0.0, f64::max);
+    if max_weight <= 0.0 {
+        return;
+    }
+    // Edges are sampled in proportion to their weight
+    let epochs_per_sample: Vec<f64> = edges.iter().map(|e| max_weight / e.2).collect();
+    let mut next_sample = epochs_per_sample.clone();
+    let mut rng = Rng::new(seed);
+
+    for epoch in 0..n_epochs {
+        let alpha = learning_rate * (1.0 - epoch as f64 / n_epochs as f64);
+        for (e, &(i, j, _)) in edges.iter().enumerate() {
+            if next_sample[e] > (epoch + 1) as f64 {
+                continue;
+            }
+            next_sample[e] += epochs_per_sample[e];
+
+            // Attraction along the edge
+            let d2 = squared_distance(&embedding[i], &embedding[j]);
+            let coeff = if d2 > 0.0 {
+                -2.0 * a * b * d2.powf(b - 1.0) / (1.0 + a * d2.powf(b))
+            } else {
+                0.0
+            };
+            for c in 0..embedding[i].len() {
+                let grad = (coeff * (embedding[i][c] - embedding[j][c])).clamp(-4.0, 4.0) * alpha;
+                embedding[i][c] += grad;
+                embedding[j][c] -= grad;
+            }
+
+            // Repulsion from random points
+            for _ in 0..negative_rate {
+                let k = rng.gen_index(n);
+                if k == i {
+                    continue;
+                }
+                let d2 = squared_distance(&embedding[i], &embedding[k]);
+                let coeff = 2.0 * b / ((0.001 + d2) * (1.0 + a * d2.powf(b)));
+                for c in 0..embedding[i].len() {
+                    let grad = if coeff > 0.0 {
+                        (coeff * (embedding[i][c] - embedding[k][c])).clamp(-4.0, 4.0)
+                    } else {
+                        4.0
+                    };
+                    embedding[i][c] += grad * alpha;
+                }
+            }
+        }
+    }
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn centroid(points: &[Vec<f64>]) -> Vec<f64> {
+        let n = points.len() as f64;
+        (0..2).map(|c| points.iter().map(|p| p[c]).sum::<f64>() / n).collect()
+    }
+
+    #[test]
+    fn test_umap_separates_clusters() {
+        let mut rng = Rng::new(3);
+        let data: Vec<Vec<f64>> = (0..120)
+            .map(|i| {
+                let offset = if i < 60 { 0.0 } else { 8.0 };
+                (0..6).map(|_| offset + rng.next_f64()).collect()
+            })
+            .collect();
+
+        let ragged = vec![vec![1.0, 2.0], vec![3.0], vec![4.0, 5.0]];
+        assert!(umap_embed(ragged, None).is_err());
+        assert!(umap_embed(data.clone(), Some(UmapOptions { n_components: Some(0), ..Default::default() })).is_err());
+
+        let embedding = umap_embed(data, Some(UmapOptions { n_epochs: Some(100), ..Default::default() })).unwrap();
+        assert_eq!(embedding.len(), 120);
+        assert!(embedding.iter().all(|p| p.len() == 2 && p.iter().all(|v| v.is_finite())));
+
+        let (first, second) = embedding.split_at(60);
+        let (c1, c2) = (centroid(first), centroid(second));
+        let between = squared_distance(&c1, &c2).sqrt();
+        let spread = first.iter().map(|p| squared_distance(p, &c1).sqrt()).sum::<f64>() / 60.0;
+        assert!(between > 2.0 * spread);
+    }
+
+    #[test]
+    fn test_fit_curve_matches_reference() {
+        // Reference UMAP values for min_dist = 0.1, spread = 1: a ~ 1.58, b ~ 0.90
+        let (a, b) = fit_curve(0.1);
+        assert!((a - 1.58).abs() < 0.2);
+        assert!((b - 0.90).abs() < 0.05);
+    }
+}
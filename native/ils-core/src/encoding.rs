@@ -0,0 +1,233 @@
+//! Categorical Encoding
+//!
+//! Turns categorical attributes into numeric feature columns for the native
+//! regression and classification models:
+//! - One-hot encoding with a fixed category vocabulary
+//! - Smoothed target (mean) encoding
+//! - Out-of-fold target encoding to avoid target leakage during training
+
+use std::collections::HashMap;
+
+use napi::{Error, Status};
+use napi_derive::napi;
+
+use crate::model_selection::k_fold_indices;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct OneHotEncoding {
+    /// Column order of `matrix`
+    pub categories: Vec<String>,
+    /// One row per input value; unknown categories encode as all zeros
+    pub matrix: Vec<Vec<f64>>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TargetEncoder {
+    pub categories: Vec<String>,
+    /// Smoothed target mean per category, aligned with `categories`
+    pub encodings: Vec<f64>,
+    pub counts: Vec<u32>,
+    /// Fallback for categories not seen during fitting
+    pub global_mean: f64,
+    pub smoothing: f64,
+}
+
+/// One-hot encode a categorical column
+///
+/// # Arguments
+/// * `values` - Category of each row
+/// * `categories` - Vocabulary to encode against (default: distinct values in first-seen order)
+#[napi]
+pub fn one_hot_encode(values: Vec<String>, categories: Option<Vec<String>>) -> OneHotEncoding {
+    let categories = categories.unwrap_or_else(|| {
+        let mut seen = Vec::new();
+        for value in &values {
+            if !seen.contains(value) {
+                seen.push(value.clone());
+            }
+        }
+        seen
+    });
+    let lookup: HashMap<&str, usize> = categories.iter().enumerate().map(|(i, c)| (c.as_str(), i)).collect();
+
+    let matrix = values
+        .iter()
+        .map(|value| {
+            let mut row = vec![0.0; categories.len()];
+            if let Some(&i) = lookup.get(value.as_str()) {
+                row[i] = 1.0;
+            }
+            row
+        })
+        .collect();
+
+    OneHotEncoding { categories, matrix }
+}
+
+/// Fit a smoothed target encoder
+///
+/// Each category is encoded as `(n * category_mean + m * global_mean) / (n + m)`,
+/// which shrinks rare categories toward the global mean. Fails when `values`
+/// and `target` differ in length.
+///
+/// # Arguments
+/// * `values` - Category of each row
+/// * `target` - Numeric target (or 0/1 label) of each row
+/// * `smoothing` - Prior weight `m` in rows (default 10)
+#[napi]
+pub fn fit_target_encoder(values: Vec<String>, target: Vec<f64>, smoothing: Option<f64>) -> napi::Result<TargetEncoder> {
+    check_lengths(&values, &target)?;
+    let rows: Vec<usize> = (0..values.len()).collect();
+    Ok(fit_on_rows(&values, &target, &rows, smoothing.unwrap_or(10.0).max(0.0)))
+}
+
+/// Encode a categorical column with a fitted target encoder
+#[napi]
+pub fn target_encode(encoder: TargetEncoder, values: Vec<String>) -> Vec<f64> {
+    let lookup = encoder_lookup(&encoder);
+    values.iter().map(|v| lookup.get(v.as_str()).copied().unwrap_or(encoder.global_mean)).collect()
+}
+
+/// Out-of-fold target encoding for training data
+///
+/// Each row is encoded by an encoder fitted on the other folds only, so a row's
+/// own target never leaks into its feature. Use `fit_target_encoder` on the
+/// full data to encode new rows at prediction time. Fails when `values` and
+/// `target` differ in length.
+///
+/// # Arguments
+/// * `values` - Category of each row
+/// * `target` - Numeric target of each row
+/// * `n_folds` - Number of folds (default 5)
+/// * `smoothing` - Prior weight in rows (default 10)
+/// * `seed` - Fold shuffle seed
+#[napi]
+pub fn target_encode_out_of_fold(
+    values: Vec<String>,
+    target: Vec<f64>,
+    n_folds: Option<u32>,
+    smoothing: Option<f64>,
+    seed: Option<u32>,
+) -> napi::Result<Vec<f64>> {
+    check_lengths(&values, &target)?;
+    let n = values.len();
+    let smoothing = smoothing.unwrap_or(10.0).max(0.0);
+    let mut encoded = vec![0.0; n];
+
+    for fold in k_fold_indices(n as u32, n_folds.unwrap_or(5).max(2), Some(true), seed) {
+        let train: Vec<usize> = fold.train.iter().map(|&i| i as usize).collect();
+        let encoder = fit_on_rows(&values, &target, &train, smoothing);
+        let lookup = encoder_lookup(&encoder);
+        for &i in &fold.test {
+            let i = i as usize;
+            encoded[i] = lookup.get(values[i].as_str()).copied().unwrap_or(encoder.global_mean);
+        }
+    }
+    Ok(encoded)
+}
+
+fn check_lengths(values: &[String], target: &[f64]) -> napi::Result<()> {
+    if values.len() != target.len() {
+        return Err(Error::new(Status::InvalidArg, format!("{} values but {} targets", values.len(), target.len())));
+    }
+    Ok(())
+}
+
+fn fit_on_rows(values: &[String], target: &[f64], rows: &[usize], smoothing: f64) -> TargetEncoder {
+    let global_mean = if rows.is_empty() {
+        0.0
+    } else {
+        rows.iter().map(|&i| target[i]).sum::<f64>() / rows.len() as f64
+    };
+
+    let mut categories: Vec<String> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut sums: Vec<f64> = Vec::new();
+    let mut counts: Vec<u32> = Vec::new();
+    for &i in rows {
+        let slot = *index.entry(values[i].as_str()).or_insert_with(|| {
+            categories.push(values[i].clone());
+            sums.push(0.0);
+            counts.push(0);
+            categories.len() - 1
+        });
+        sums[slot] += target[i];
+        counts[slot] += 1;
+    }
+
+    let encodings = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, &count)| {
+            let n = count as f64;
+            if n + smoothing > 0.0 {
+                (sum + smoothing * global_mean) / (n + smoothing)
+            } else {
+                global_mean
+            }
+        })
+        .collect();
+
+    TargetEncoder {
+        categories,
+        encodings,
+        counts,
+        global_mean,
+        smoothing,
+    }
+}
+
+fn encoder_lookup(encoder: &TargetEncoder) -> HashMap<&str, f64> {
+    encoder
+        .categories
+        .iter()
+        .zip(&encoder.encodings)
+        .map(|(c, &e)| (c.as_str(), e))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_one_hot_encode() {
+        let encoded = one_hot_encode(labels(&["b", "a", "b", "c"]), None);
+        assert_eq!(encoded.categories, labels(&["b", "a", "c"]));
+        assert_eq!(encoded.matrix[1], vec![0.0, 1.0, 0.0]);
+
+        let fixed = one_hot_encode(labels(&["a", "z"]), Some(labels(&["a", "b"])));
+        assert_eq!(fixed.matrix, vec![vec![1.0, 0.0], vec![0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_target_encoder_smoothing() {
+        let values = labels(&["a", "a", "a", "a", "b"]);
+        let target = vec![1.0, 1.0, 1.0, 1.0, 0.0];
+
+        assert!(fit_target_encoder(values.clone(), target[1..].to_vec(), None).is_err());
+        let raw = fit_target_encoder(values.clone(), target.clone(), Some(0.0)).unwrap();
+        assert_eq!(target_encode(raw, labels(&["a", "b"])), vec![1.0, 0.0]);
+
+        let smoothed = fit_target_encoder(values, target, Some(1.0)).unwrap();
+        let encoded = target_encode(smoothed, labels(&["b", "unseen"]));
+        assert!((encoded[0] - 0.4).abs() < 1e-10);
+        assert!((encoded[1] - 0.8).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_out_of_fold_encoding_excludes_own_target() {
+        // Every category is unique, so out-of-fold encodings can only see the global mean
+        let values = labels(&["a", "b", "c", "d", "e", "f"]);
+        let target = vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0];
+        assert!(target_encode_out_of_fold(values.clone(), target[1..].to_vec(), None, None, None).is_err());
+        let encoded = target_encode_out_of_fold(values, target.clone(), Some(3), Some(0.0), Some(7)).unwrap();
+        assert!(encoded.iter().zip(&target).all(|(e, t)| (e - t).abs() > 1e-10));
+    }
+}
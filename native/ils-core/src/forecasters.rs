@@ -0,0 +1,581 @@
+//! Forecasting Method Registry
+//!
+//! Named univariate forecasters with one-step-ahead in-sample fits, shared by
+//! model comparison, automatic selection and ensembling. Every method returns
+//! the same `MethodFit`, so callers can score, rank and combine them without
+//! knowing how each one works.
+
+use napi::{Error, Status};
+
+use crate::arima::{arima_forecast, auto_arima};
+use crate::forecasting::{holt_winters, predict_next_with_options, seasonal_regression, simple_exponential_smoothing};
+use crate::prophet::{prophet_like_fit, prophet_like_forecast, ProphetOptions};
+use crate::statistics::linear_regression;
+
+/// Season lengths checked by `detect_season_length` (weekly, monthly, hourly, yearly cycles)
+const SEASON_CANDIDATES: &[usize] = &[7, 12, 24, 4, 52];
+
+/// Methods tried when the caller does not name any
+pub(crate) const DEFAULT_METHODS: &[&str] = &["naive", "seasonal_naive", "drift", "linear_trend", "ses", "holt", "holt_winters"];
+
+/// Every method `fit_method` knows
+pub(crate) const METHODS: &[&str] = &[
+    "naive",
+    "seasonal_naive",
+    "drift",
+    "linear_trend",
+    "ses",
+    "holt",
+    "damped_holt",
+    "holt_winters",
+    "theta",
+    "croston",
+    "sba",
+    "predict_next",
+    "auto_arima",
+    "prophet_like",
+    "seasonal_regression",
+];
+
+/// Smoothing parameter values searched when fitting the exponential smoothing methods
+const SMOOTHING_GRID: &[f64] = &[0.05, 0.1, 0.2, 0.3, 0.5, 0.7, 0.9];
+
+/// Trend damping factors searched for "damped_holt"
+const DAMPING_GRID: &[f64] = &[0.8, 0.85, 0.9, 0.95, 0.98];
+
+/// Smoothing weights tried for Croston's method; sparse series need low values
+pub(crate) const CROSTON_GRID: &[f64] = &[0.05, 0.1, 0.2, 0.3];
+
+/// In-sample fit and out-of-sample forecast of one method
+pub(crate) struct MethodFit {
+    /// One-step-ahead fitted values aligned with the data; NaN where the
+    /// method has no forecast yet (e.g. the first value for "naive")
+    pub fitted: Vec<f64>,
+    pub forecast: Vec<f64>,
+    /// Named parameter estimates (smoothing weights, slope, ...)
+    pub params: Vec<(String, f64)>,
+    /// Number of estimated parameters and initial states, for information criteria
+    pub n_params: usize,
+}
+
+impl MethodFit {
+    /// `actual - fitted` aligned with the data (NaN where `fitted` is NaN)
+    pub(crate) fn residuals(&self, data: &[f64]) -> Vec<f64> {
+        data.iter().zip(&self.fitted).map(|(y, f)| y - f).collect()
+    }
+
+    /// Sum of squared one-step errors and the number of errors behind it
+    pub(crate) fn sse(&self, data: &[f64]) -> (f64, usize) {
+        self.residuals(data)
+            .iter()
+            .filter(|r| r.is_finite())
+            .fold((0.0, 0), |(sse, n), r| (sse + r * r, n + 1))
+    }
+}
+
+/// Fit `method` to `data` and forecast `steps` values ahead
+///
+/// Returns `None` for unknown methods (see `METHODS`) or when the history is
+/// too short for the method (seasonal methods need more than one,
+/// Holt-Winters two, full seasons).
+pub(crate) fn fit_method(method: &str, data: &[f64], season_len: usize, steps: usize) -> Option<MethodFit> {
+    let n = data.len();
+    if n < 3 {
+        return None;
+    }
+    match method {
+        "naive" => Some(naive(data, steps)),
+        "seasonal_naive" if season_len > 1 && n > season_len => Some(seasonal_naive(data, season_len, steps)),
+        "drift" => Some(drift(data, steps)),
+        "linear_trend" => Some(linear_trend(data, steps)),
+        "ses" => Some(best_of(SMOOTHING_GRID.iter().map(|&alpha| ses(data, alpha, steps)), data)),
+        "croston" => data.iter().any(|&y| y != 0.0).then(|| best_of(
+            CROSTON_GRID.iter().map(|&alpha| croston(data, alpha, false, steps).fit),
+            data,
+        )),
+        "sba" => data.iter().any(|&y| y != 0.0).then(|| best_of(
+            CROSTON_GRID.iter().map(|&alpha| croston(data, alpha, true, steps).fit),
+            data,
+        )),
+        "theta" => Some(best_of(SMOOTHING_GRID.iter().map(|&alpha| theta(data, alpha, season_len, steps)), data)),
+        "holt" => Some(best_of(grid2().map(|(alpha, beta)| holt(data, alpha, beta, 1.0, steps)), data)),
+        "damped_holt" => Some(best_of(
+            grid2().flat_map(|(alpha, beta)| DAMPING_GRID.iter().map(move |&phi| holt(data, alpha, beta, phi, steps))),
+            data,
+        )),
+        "holt_winters" if season_len > 1 && n >= 2 * season_len => {
+            let candidates = grid2()
+                .flat_map(|(alpha, beta)| [0.05, 0.1, 0.3].into_iter().map(move |gamma| (alpha, beta, gamma)))
+                .map(|(alpha, beta, gamma)| holt_winters_additive(data, alpha, beta, gamma, season_len, steps));
+            Some(best_of(candidates, data))
+        }
+        "predict_next" => predict_next_fit(data, season_len, steps),
+        "auto_arima" => finite(auto_arima_fit(data, season_len, steps)),
+        "prophet_like" => finite(prophet_fit(data, season_len, steps)),
+        "seasonal_regression" => {
+            let fit = seasonal_regression(data, steps, season_len as f64, &Default::default())?;
+            finite(MethodFit {
+                fitted: fit.fitted,
+                forecast: fit.forecasts.iter().map(|f| f.predicted_value).collect(),
+                params: Vec::new(),
+                n_params: fit.n_params,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Fails unless every name in `methods` is in `METHODS`
+pub(crate) fn check_methods(methods: &[String]) -> napi::Result<()> {
+    match methods.iter().find(|m| !METHODS.contains(&m.as_str())) {
+        Some(method) => Err(Error::new(
+            Status::InvalidArg,
+            format!("unknown forecasting method \"{}\"; expected one of {}", method, METHODS.join(", ")),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Most pronounced seasonal cycle among common candidates
+///
+/// Returns the candidate lag with the highest autocorrelation of the
+/// linearly detrended series, or `None` when no candidate (with at least two
+/// full cycles of history) reaches `min_autocorrelation`.
+pub(crate) fn detect_season_length(data: &[f64], min_autocorrelation: f64) -> Option<(usize, f64)> {
+    let n = data.len();
+    let fit = linear_regression((0..n).map(|t| t as f64).collect(), data.to_vec());
+    let detrended: Vec<f64> = data.iter().enumerate().map(|(t, y)| y - fit.intercept - fit.slope * t as f64).collect();
+    let variance: f64 = detrended.iter().map(|r| r * r).sum();
+    if variance <= 0.0 {
+        return None;
+    }
+
+    SEASON_CANDIDATES
+        .iter()
+        .filter(|&&lag| n >= 2 * lag)
+        .map(|&lag| {
+            let acf = (lag..n).map(|t| detrended[t] * detrended[t - lag]).sum::<f64>() / variance;
+            (lag, acf)
+        })
+        .filter(|&(_, acf)| acf >= min_autocorrelation)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Syntetos-Boylan demand classification
+///
+/// Uses the average demand interval (ADI, cutoff 1.32) and the squared
+/// coefficient of variation of non-zero demand sizes (CV², cutoff 0.49):
+/// "smooth", "erratic", "intermittent" or "lumpy".
+pub(crate) fn demand_pattern(data: &[f64]) -> (&'static str, f64, f64) {
+    let sizes: Vec<f64> = data.iter().copied().filter(|&y| y != 0.0).collect();
+    if sizes.is_empty() {
+        return ("intermittent", f64::INFINITY, 0.0);
+    }
+    let adi = data.len() as f64 / sizes.len() as f64;
+    let mean = sizes.iter().sum::<f64>() / sizes.len() as f64;
+    let variance = sizes.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / sizes.len() as f64;
+    let cv2 = if mean != 0.0 { variance / (mean * mean) } else { 0.0 };
+    let pattern = match (adi >= 1.32, cv2 >= 0.49) {
+        (false, false) => "smooth",
+        (false, true) => "erratic",
+        (true, false) => "intermittent",
+        (true, true) => "lumpy",
+    };
+    (pattern, adi, cv2)
+}
+
+fn grid2() -> impl Iterator<Item = (f64, f64)> {
+    SMOOTHING_GRID
+        .iter()
+        .flat_map(|&alpha| [0.05, 0.1, 0.2, 0.3].into_iter().filter(move |&b| b <= alpha).map(move |beta| (alpha, beta)))
+}
+
+/// Candidate with the smallest one-step squared error
+fn best_of(candidates: impl Iterator<Item = MethodFit>, data: &[f64]) -> MethodFit {
+    candidates
+        .map(|fit| (fit.sse(data).0, fit))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, fit)| fit)
+        .expect("parameter grid is not empty")
+}
+
+fn naive(data: &[f64], steps: usize) -> MethodFit {
+    let mut fitted = vec![f64::NAN];
+    fitted.extend_from_slice(&data[..data.len() - 1]);
+    MethodFit {
+        fitted,
+        forecast: vec![data[data.len() - 1]; steps],
+        params: Vec::new(),
+        n_params: 0,
+    }
+}
+
+fn seasonal_naive(data: &[f64], season_len: usize, steps: usize) -> MethodFit {
+    let n = data.len();
+    let fitted = (0..n).map(|t| if t >= season_len { data[t - season_len] } else { f64::NAN }).collect();
+    MethodFit {
+        fitted,
+        forecast: (0..steps).map(|h| data[n - season_len + h % season_len]).collect(),
+        params: Vec::new(),
+        n_params: 0,
+    }
+}
+
+fn drift(data: &[f64], steps: usize) -> MethodFit {
+    let n = data.len();
+    let fitted = (0..n)
+        .map(|t| if t >= 2 { data[t - 1] + (data[t - 1] - data[0]) / (t - 1) as f64 } else { f64::NAN })
+        .collect();
+    let slope = (data[n - 1] - data[0]) / (n - 1) as f64;
+    MethodFit {
+        fitted,
+        forecast: (1..=steps).map(|h| data[n - 1] + slope * h as f64).collect(),
+        params: vec![("slope".to_string(), slope)],
+        n_params: 1,
+    }
+}
+
+fn linear_trend(data: &[f64], steps: usize) -> MethodFit {
+    let n = data.len();
+    let fit = linear_regression((0..n).map(|t| t as f64).collect(), data.to_vec());
+    let at = |t: usize| fit.intercept + fit.slope * t as f64;
+    MethodFit {
+        fitted: (0..n).map(at).collect(),
+        forecast: (n..n + steps).map(at).collect(),
+        params: vec![("intercept".to_string(), fit.intercept), ("slope".to_string(), fit.slope)],
+        n_params: 2,
+    }
+}
+
+fn ses(data: &[f64], alpha: f64, steps: usize) -> MethodFit {
+    let mut level = data[0];
+    let mut fitted = vec![f64::NAN];
+    for &y in &data[1..] {
+        fitted.push(level);
+        level += alpha * (y - level);
+    }
+    MethodFit {
+        fitted,
+        forecast: vec![level; steps],
+        params: vec![("alpha".to_string(), alpha)],
+        n_params: 2,
+    }
+}
+
+/// Theta method (Hyndman-Billah form): SES plus half the linear-trend slope
+///
+/// With at least two full seasons the series is first seasonally adjusted
+/// with additive indices from the linearly detrended data, and the indices
+/// are added back to the fitted values and forecasts.
+fn theta(data: &[f64], alpha: f64, season_len: usize, steps: usize) -> MethodFit {
+    let n = data.len();
+    let m = if season_len > 1 && n >= 2 * season_len { season_len } else { 1 };
+    let mut indices = vec![0.0; m];
+    if m > 1 {
+        let fit = linear_regression((0..n).map(|t| t as f64).collect(), data.to_vec());
+        let mut counts = vec![0.0; m];
+        for (t, y) in data.iter().enumerate() {
+            indices[t % m] += y - fit.intercept - fit.slope * t as f64;
+            counts[t % m] += 1.0;
+        }
+        indices.iter_mut().zip(&counts).for_each(|(s, c)| *s /= c);
+        let centre = indices.iter().sum::<f64>() / m as f64;
+        indices.iter_mut().for_each(|s| *s -= centre);
+    }
+    let adjusted: Vec<f64> = data.iter().enumerate().map(|(t, y)| y - indices[t % m]).collect();
+    let slope = linear_regression((0..n).map(|t| t as f64).collect(), adjusted.clone()).slope;
+
+    // Forecast of the value after `t` observations, `h` steps ahead of the level
+    let drift = |t: usize, h: usize| 0.5 * slope * ((h - 1) as f64 + (1.0 - (1.0 - alpha).powi(t as i32)) / alpha);
+    let mut level = adjusted[0];
+    let mut fitted = vec![f64::NAN];
+    for (t, &y) in adjusted.iter().enumerate().skip(1) {
+        fitted.push(level + drift(t, 1) + indices[t % m]);
+        level += alpha * (y - level);
+    }
+    MethodFit {
+        fitted,
+        forecast: (1..=steps).map(|h| level + drift(n, h) + indices[(n + h - 1) % m]).collect(),
+        params: vec![("alpha".to_string(), alpha), ("slope".to_string(), slope)],
+        n_params: 3 + m - 1,
+    }
+}
+
+/// Holt's linear trend method; `phi < 1` damps the trend toward a flat forecast
+fn holt(data: &[f64], alpha: f64, beta: f64, phi: f64, steps: usize) -> MethodFit {
+    let mut level = data[1];
+    let mut trend = data[1] - data[0];
+    let mut fitted = vec![f64::NAN, f64::NAN];
+    for &y in &data[2..] {
+        let predicted = level + phi * trend;
+        fitted.push(predicted);
+        let last_level = level;
+        level = predicted + alpha * (y - predicted);
+        trend = phi * trend + beta * (level - last_level - phi * trend);
+    }
+    let mut params = vec![("alpha".to_string(), alpha), ("beta".to_string(), beta)];
+    if phi < 1.0 {
+        params.push(("phi".to_string(), phi));
+    }
+    MethodFit {
+        fitted,
+        forecast: (1..=steps).map(|h| level + trend * damped_steps(phi, h)).collect(),
+        n_params: 2 + params.len(),
+        params,
+    }
+}
+
+/// `phi + phi^2 + ... + phi^h`: how many trend increments a damped forecast adds by step `h`
+pub(crate) fn damped_steps(phi: f64, h: usize) -> f64 {
+    if phi == 1.0 {
+        h as f64
+    } else {
+        phi * (1.0 - phi.powi(h as i32)) / (1.0 - phi)
+    }
+}
+
+/// Croston fit with the final smoothed demand size and interval
+pub(crate) struct CrostonFit {
+    pub fit: MethodFit,
+    pub demand_size: f64,
+    pub demand_interval: f64,
+}
+
+/// Croston's method (or the Syntetos-Boylan bias-corrected variant)
+///
+/// Smooths non-zero demand sizes and the intervals between them separately;
+/// the per-period forecast is their ratio.
+pub(crate) fn croston(data: &[f64], alpha: f64, bias_corrected: bool, steps: usize) -> CrostonFit {
+    let correction = if bias_corrected { 1.0 - alpha / 2.0 } else { 1.0 };
+    let mut size = f64::NAN;
+    let mut interval = f64::NAN;
+    let mut since_last = 1.0;
+    let mut fitted = Vec::with_capacity(data.len());
+    for &y in data {
+        fitted.push(correction * size / interval);
+        if y != 0.0 {
+            if size.is_nan() {
+                size = y;
+                interval = since_last;
+            } else {
+                size += alpha * (y - size);
+                interval += alpha * (since_last - interval);
+            }
+            since_last = 1.0;
+        } else {
+            since_last += 1.0;
+        }
+    }
+    CrostonFit {
+        fit: MethodFit {
+            fitted,
+            forecast: vec![correction * size / interval; steps],
+            params: vec![("alpha".to_string(), alpha)],
+            n_params: 3,
+        },
+        demand_size: size,
+        demand_interval: interval,
+    }
+}
+
+/// Additive Holt-Winters, initialized from the first two seasons
+fn holt_winters_additive(data: &[f64], alpha: f64, beta: f64, gamma: f64, m: usize, steps: usize) -> MethodFit {
+    let n = data.len();
+    let state = additive_holt_winters(data, alpha, beta, gamma, 1.0, m);
+    MethodFit {
+        forecast: (1..=steps).map(|h| state.level + state.trend * h as f64 + state.seasonal[(n + h - 1) % m]).collect(),
+        fitted: state.fitted,
+        params: vec![("alpha".to_string(), alpha), ("beta".to_string(), beta), ("gamma".to_string(), gamma)],
+        n_params: 5 + m - 1,
+    }
+}
+
+/// Unrounded `predict_next` forecasts
+///
+/// Fitted values are the smoothed values of its fixed-parameter model:
+/// simple exponential smoothing below two full seasons, Holt-Winters from
+/// then on.
+fn predict_next_fit(data: &[f64], season_len: usize, steps: usize) -> Option<MethodFit> {
+    let m = season_len.max(1);
+    let forecast = predict_next_with_options(data.to_vec(), steps as u32, m as u32, None)
+        .ok()?
+        .into_iter()
+        .map(|f| f.predicted_value)
+        .collect();
+    let fit = if data.len() < 2 * m {
+        // `predict_simple` forecasts each value with the previous smoothed level
+        let levels = simple_exponential_smoothing(data.to_vec(), 0.3);
+        MethodFit {
+            fitted: std::iter::once(f64::NAN).chain(levels).take(data.len()).collect(),
+            forecast,
+            params: vec![("alpha".to_string(), 0.3)],
+            n_params: 1,
+        }
+    } else {
+        MethodFit {
+            fitted: holt_winters(data.to_vec(), 0.3, 0.1, 0.1, m as u32),
+            forecast,
+            params: vec![("alpha".to_string(), 0.3), ("beta".to_string(), 0.1), ("gamma".to_string(), 0.1)],
+            n_params: 2 + m,
+        }
+    };
+    finite(fit)
+}
+
+/// Automatically ordered (seasonal) ARIMA; fitted values are `data - residuals`
+fn auto_arima_fit(data: &[f64], season_len: usize, steps: usize) -> MethodFit {
+    let model = auto_arima(data.to_vec(), (season_len > 1).then_some(season_len as u32));
+    let fitted = data.iter().zip(&model.residuals).map(|(y, r)| y - r).collect();
+    let coefficients = [("ar", &model.ar), ("ma", &model.ma), ("sar", &model.seasonal_ar), ("sma", &model.seasonal_ma)];
+    let mut params: Vec<(String, f64)> = coefficients
+        .iter()
+        .flat_map(|(name, values)| values.iter().enumerate().map(move |(i, &v)| (format!("{}{}", name, i + 1), v)))
+        .collect();
+    if model.d + model.seasonal_d == 0 {
+        params.push(("intercept".to_string(), model.intercept));
+    }
+    // Coefficients plus the innovation variance
+    let n_params = params.len() + 1;
+    MethodFit {
+        fitted,
+        forecast: arima_forecast(model, steps as u32).predictions,
+        params,
+        n_params,
+    }
+}
+
+/// Piecewise-linear trend plus Fourier seasonality at `season_len`
+fn prophet_fit(data: &[f64], season_len: usize, steps: usize) -> MethodFit {
+    let options = ProphetOptions {
+        seasonal_periods: Some(if season_len > 1 { vec![season_len as f64] } else { Vec::new() }),
+        ..Default::default()
+    };
+    let model = prophet_like_fit(data.to_vec(), Some(options));
+    let params = vec![("growth_rate".to_string(), model.growth_rate), ("offset".to_string(), model.offset)];
+    let n_params = 2 + model.rate_changes.len() + model.seasonal_coefficients.len();
+    MethodFit {
+        fitted: model.fitted.clone(),
+        forecast: prophet_like_forecast(model, steps as u32).predictions,
+        params,
+        n_params,
+    }
+}
+
+/// `fit` if it forecasts finite values, for methods that can fail numerically
+fn finite(fit: MethodFit) -> Option<MethodFit> {
+    fit.forecast.iter().all(|v| v.is_finite()).then_some(fit)
+}
+
+/// Smoothing pass of additive Holt-Winters
+pub(crate) struct AdditiveHoltWinters {
+    /// One-step-ahead forecasts; NaN over the first season
+    pub fitted: Vec<f64>,
+    /// `level + phi trend + seasonal` after each update; the first season,
+    /// used for initialization, reproduces the data
+    pub smoothed: Vec<f64>,
+    pub level: f64,
+    pub trend: f64,
+    pub seasonal: Vec<f64>,
+}
+
+/// Additive Holt-Winters with trend damping `phi` (1 = undamped)
+///
+/// The level and trend start from the means of the first two seasons and
+/// the seasonal offsets from the first season's deviations; smoothing runs
+/// from the second season on. Requires at least two full seasons.
+pub(crate) fn additive_holt_winters(data: &[f64], alpha: f64, beta: f64, gamma: f64, phi: f64, m: usize) -> AdditiveHoltWinters {
+    let first = data[..m].iter().sum::<f64>() / m as f64;
+    let second = data[m..2 * m].iter().sum::<f64>() / m as f64;
+    let mut level = first;
+    let mut trend = (second - first) / m as f64;
+    let mut seasonal: Vec<f64> = data[..m].iter().map(|y| y - first).collect();
+
+    let mut fitted = vec![f64::NAN; m];
+    let mut smoothed = data[..m].to_vec();
+    for (t, &y) in data.iter().enumerate().skip(m) {
+        let s = seasonal[t % m];
+        let predicted = level + phi * trend;
+        fitted.push(predicted + s);
+        let last_level = level;
+        level = alpha * (y - s) + (1.0 - alpha) * predicted;
+        trend = beta * (level - last_level) + (1.0 - beta) * phi * trend;
+        seasonal[t % m] = gamma * (y - level) + (1.0 - gamma) * s;
+        smoothed.push(level + phi * trend + seasonal[t % m]);
+    }
+    AdditiveHoltWinters {
+        fitted,
+        smoothed,
+        level,
+        trend,
+        seasonal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damped_holt() {
+        // Growth that levels off: the damped trend stops extrapolating it
+        let data: Vec<f64> = (0..60).map(|t| 100.0 * (1.0 - (-(t as f64) / 15.0).exp())).collect();
+        let damped = fit_method("damped_holt", &data, 1, 60).unwrap();
+        let linear = fit_method("holt", &data, 1, 60).unwrap();
+        let phi = damped.params.iter().find(|p| p.0 == "phi").unwrap().1;
+        assert!(phi < 1.0);
+        assert!(damped.forecast[59] < linear.forecast[59]);
+        assert!(damped.forecast[59] < 110.0);
+        assert_eq!(damped.n_params, 5);
+
+        assert_eq!(damped_steps(1.0, 4), 4.0);
+        assert!((damped_steps(0.5, 3) - 0.875).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_methods_track_simple_series() {
+        let trend: Vec<f64> = (0..30).map(|t| 10.0 + 2.0 * t as f64).collect();
+        for method in ["drift", "linear_trend", "holt"] {
+            let fit = fit_method(method, &trend, 1, 3).unwrap();
+            assert!((fit.forecast[2] - 74.0).abs() < 1e-6, "{}", method);
+            assert_eq!(fit.fitted.len(), 30);
+        }
+
+        let seasonal: Vec<f64> = (0..28).map(|t| [5.0, 9.0, 2.0, 7.0][t % 4]).collect();
+        let fit = fit_method("seasonal_naive", &seasonal, 4, 6).unwrap();
+        assert_eq!(fit.forecast, vec![5.0, 9.0, 2.0, 7.0, 5.0, 9.0]);
+        assert_eq!(fit.sse(&seasonal), (0.0, 24));
+        let hw = fit_method("holt_winters", &seasonal, 4, 4).unwrap();
+        assert!(hw.forecast.iter().zip([5.0, 9.0, 2.0, 7.0]).all(|(f, y)| (f - y).abs() < 0.5));
+
+        // Theta extrapolates half the slope and keeps the seasonal shape
+        let fit = fit_method("theta", &trend, 1, 3).unwrap();
+        assert!((fit.forecast[2] - fit.forecast[1] - 1.0).abs() < 1e-9);
+        let fit = fit_method("theta", &seasonal, 4, 4).unwrap();
+        assert!(fit.forecast.iter().zip([5.0, 9.0, 2.0, 7.0]).all(|(f, y)| (f - y).abs() < 0.5));
+    }
+
+    #[test]
+    fn test_season_and_demand_pattern() {
+        let weekly: Vec<f64> = (0..70).map(|t| 50.0 + t as f64 * 0.3 + [9.0, 4.0, 0.0, -2.0, -3.0, -4.0, -4.0][t % 7]).collect();
+        assert_eq!(detect_season_length(&weekly, 0.3).map(|s| s.0), Some(7));
+        let flat: Vec<f64> = (0..70).map(|t| t as f64).collect();
+        assert!(detect_season_length(&flat, 0.3).is_none());
+
+        let sparse: Vec<f64> = (0..60).map(|t| if t % 4 == 3 { 8.0 } else { 0.0 }).collect();
+        assert_eq!(demand_pattern(&sparse).0, "intermittent");
+        let fit = fit_method("croston", &sparse, 1, 2).unwrap();
+        assert!((fit.forecast[0] - 2.0).abs() < 1e-9);
+        assert!(fit_method("sba", &sparse, 1, 2).unwrap().forecast[0] < 2.0);
+        assert_eq!(fit.params.len(), 1);
+        assert_eq!(demand_pattern(&weekly).0, "smooth");
+    }
+
+    #[test]
+    fn test_unavailable_methods() {
+        let short = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(fit_method("holt_winters", &short, 7, 2).is_none());
+        assert!(fit_method("seasonal_naive", &short, 7, 2).is_none());
+        assert!(fit_method("unknown", &short, 1, 2).is_none());
+        assert!(fit_method("ses", &short[..2], 1, 2).is_none());
+    }
+}
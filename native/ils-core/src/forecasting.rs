@@ -1,14 +1,37 @@
 //! Time Series Forecasting
 //!
 //! High-performance implementations of forecasting algorithms including:
-//! - Holt-Winters exponential smoothing (triple exponential smoothing)
+//! - Holt-Winters exponential smoothing (triple exponential smoothing),
+//!   with multiplicative or additive seasonality and optional trend damping
+//! - Multi-seasonal Holt-Winters for several cycles at once (e.g. hourly
+//!   data with daily and weekly patterns)
 //! - Simple exponential smoothing
+//! - Trend-plus-seasonality regression with Fourier or seasonal-index terms
 //! - Trend analysis
-
+//! - Trend and seasonal strength from a classical decomposition
+//! - Demand classification (ADI / CV²) and Croston and Syntetos-Boylan
+//!   forecasts for intermittent demand
+//! - Automatic model selection per series
+//! - Forecast combinations (mean, median or error-weighted) of several methods
+//! - Forecasts driven by known future regressors (promotions, holidays, weather)
+//! - Holiday and closure adjustment, including post-holiday catch-up
+
+use napi::{Error, Status};
 use napi_derive::napi;
 use rayon::prelude::*;
-
-use crate::statistics::{mean, std_dev, linear_regression};
+use serde::Serialize;
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::function::erf::erf;
+use std::collections::HashSet;
+
+use crate::arima::{arima_forecast_with_regressors, auto_arima_with_regressors};
+use crate::forecasters::{additive_holt_winters, check_methods, croston, damped_steps, demand_pattern, detect_season_length, fit_method, MethodFit, CROSTON_GRID};
+use crate::linalg::{dot, least_squares, LeastSquares};
+use crate::model_selection::ParamValue;
+use crate::optimize::nelder_mead;
+use crate::prophet::fourier_terms;
+use crate::regression::theil_sen;
+use crate::statistics::{mean, median, std_dev, linear_regression, weighted_linear_regression};
 
 /// Holt-Winters triple exponential smoothing
 ///
@@ -34,6 +57,125 @@ pub fn holt_winters(
         return simple_exponential_smoothing(data, alpha);
     }
 
+    fit_holt_winters(&data, alpha, beta, gamma, season_len, Seasonality::Multiplicative, 1.0).fitted
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct HoltWintersOptions {
+    /// "multiplicative" (default) scales the level by seasonal factors;
+    /// "additive" adds seasonal offsets, which stays stable when some
+    /// periods are near zero; other values are rejected
+    pub seasonality_type: Option<String>,
+    /// Trend damping factor `phi` in (0, 1] (default 1, undamped). Below 1
+    /// the trend fades with each step, so long-horizon forecasts level off
+    /// instead of growing linearly; 0.8-0.98 is typical. Values outside
+    /// (0, 1] are rejected
+    pub damping: Option<f64>,
+}
+
+/// Holt-Winters triple exponential smoothing with model options
+///
+/// Same as `holt_winters`, with a choice of seasonality type and trend damping.
+/// Fails for a `seasonality_type` other than "additive" or "multiplicative"
+/// and for `damping` outside (0, 1].
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `alpha` - Level smoothing parameter (0-1)
+/// * `beta` - Trend smoothing parameter (0-1)
+/// * `gamma` - Seasonal smoothing parameter (0-1)
+/// * `season_length` - Length of one seasonal cycle (e.g., 7 for weekly)
+/// * `options` - Seasonality type and trend damping
+#[napi]
+pub fn holt_winters_with_options(
+    data: Vec<f64>,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_length: u32,
+    options: Option<HoltWintersOptions>,
+) -> napi::Result<Vec<f64>> {
+    let options = options.unwrap_or_default();
+    let season_len = season_length as usize;
+    let seasonality = match options.seasonality_type.as_deref() {
+        Some("additive") => Seasonality::Additive,
+        None | Some("multiplicative") => Seasonality::Multiplicative,
+        Some(other) => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown seasonality type \"{}\"; expected \"additive\" or \"multiplicative\"", other),
+            ))
+        }
+    };
+    let phi = options.damping.unwrap_or(1.0);
+    if !(phi > 0.0 && phi <= 1.0) {
+        return Err(Error::new(Status::InvalidArg, format!("damping {} is outside (0, 1]", phi)));
+    }
+
+    if data.len() < season_len * 2 {
+        return Ok(simple_exponential_smoothing(data, alpha));
+    }
+
+    Ok(fit_holt_winters(&data, alpha, beta, gamma, season_len, seasonality, phi).fitted)
+}
+
+/// How seasonal components combine with the level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Seasonality {
+    Additive,
+    Multiplicative,
+}
+
+/// Smoothing state at the end of a Holt-Winters pass
+pub(crate) struct HoltWintersState {
+    pub fitted: Vec<f64>,
+    pub level: f64,
+    pub trend: f64,
+    pub seasonal: Vec<f64>,
+    pub seasonality: Seasonality,
+    /// Trend damping factor (1 for an undamped trend)
+    pub phi: f64,
+}
+
+impl HoltWintersState {
+    /// Project `steps` values beyond a history of length `n`
+    pub(crate) fn forecast(&self, n: usize, steps: usize) -> Vec<f64> {
+        let season_len = self.seasonal.len();
+        (1..=steps)
+            .map(|h| {
+                let base = self.level + self.trend * damped_steps(self.phi, h);
+                let seasonal = self.seasonal[(n + h - 1) % season_len];
+                match self.seasonality {
+                    Seasonality::Additive => base + seasonal,
+                    Seasonality::Multiplicative => base * seasonal,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Run Holt-Winters over `data` (requires at least two full seasons)
+pub(crate) fn fit_holt_winters(
+    data: &[f64],
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_len: usize,
+    seasonality: Seasonality,
+    phi: f64,
+) -> HoltWintersState {
+    if seasonality == Seasonality::Additive {
+        let state = additive_holt_winters(data, alpha, beta, gamma, phi, season_len);
+        return HoltWintersState {
+            fitted: state.smoothed,
+            level: state.level,
+            trend: state.trend,
+            seasonal: state.seasonal,
+            seasonality,
+            phi,
+        };
+    }
     let mut forecasts = Vec::with_capacity(data.len());
     let mut level = data[0];
     let mut trend = 0.0;
@@ -56,7 +198,7 @@ pub fn holt_winters(
     }
 
     // Apply Holt-Winters algorithm
-    for i in 0..data.len() {
+    for (i, &value) in data.iter().enumerate() {
         let seasonal_index = i % season_len;
         let last_level = level;
         let last_trend = trend;
@@ -69,21 +211,235 @@ pub fn holt_winters(
         };
 
         // Update level
-        level = alpha * (data[i] / seasonal_factor) + (1.0 - alpha) * (last_level + last_trend);
+        level = alpha * (value / seasonal_factor) + (1.0 - alpha) * (last_level + phi * last_trend);
 
         // Update trend
-        trend = beta * (level - last_level) + (1.0 - beta) * last_trend;
+        trend = beta * (level - last_level) + (1.0 - beta) * phi * last_trend;
 
         // Update seasonal component
         if level != 0.0 {
-            seasonal[seasonal_index] = gamma * (data[i] / level) + (1.0 - gamma) * seasonal[seasonal_index];
+            seasonal[seasonal_index] = gamma * (value / level) + (1.0 - gamma) * seasonal[seasonal_index];
         }
 
         // Calculate forecast
-        forecasts.push((level + trend) * seasonal[seasonal_index]);
+        forecasts.push((level + phi * trend) * seasonal[seasonal_index]);
+    }
+
+    HoltWintersState {
+        fitted: forecasts,
+        level,
+        trend,
+        seasonal,
+        seasonality,
+        phi,
+    }
+}
+
+/// Out-of-sample Holt-Winters forecast
+///
+/// Falls back to a flat simple-exponential-smoothing forecast when the
+/// history is shorter than two seasons.
+pub(crate) fn holt_winters_forecast(
+    data: &[f64],
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_len: usize,
+    steps: usize,
+) -> Vec<f64> {
+    if season_len == 0 || data.len() < season_len * 2 {
+        let last = simple_exponential_smoothing(data.to_vec(), alpha).last().copied().unwrap_or(0.0);
+        return vec![last; steps];
+    }
+    fit_holt_winters(data, alpha, beta, gamma, season_len, Seasonality::Multiplicative, 1.0).forecast(data.len(), steps)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct MultiSeasonalOptions {
+    /// Level smoothing weight in (0, 1) (default: estimated)
+    pub alpha: Option<f64>,
+    /// Trend smoothing weight in [0, alpha] (default: estimated)
+    pub beta: Option<f64>,
+    /// Seasonal smoothing weights, one per season length in ascending order
+    /// of length, each in [0, 1 - alpha] (default: estimated)
+    pub gammas: Option<Vec<f64>>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct MultiSeasonalForecast {
+    pub predictions: Vec<f64>,
+    /// 95% prediction interval bounds
+    pub lower_bounds: Vec<f64>,
+    pub upper_bounds: Vec<f64>,
+    /// One-step-ahead fitted values over the history
+    pub fitted: Vec<f64>,
+    /// Season lengths modelled, ascending (lengths without two full cycles of history are dropped)
+    pub season_lengths: Vec<u32>,
+    pub alpha: f64,
+    pub beta: f64,
+    /// Seasonal smoothing weight for each entry of `season_lengths`
+    pub gammas: Vec<f64>,
+    /// Final seasonal offsets for each entry of `season_lengths`, indexed by `t % length`
+    pub seasonal_components: Vec<Vec<f64>>,
+}
+
+/// Holt-Winters with several seasonal cycles (additive, Taylor-style)
+///
+/// Adds one seasonal offset per period to the level and trend, e.g. the
+/// 24-slot intraday shape and the 168-slot weekly shape of an hourly order
+/// feed, which a single `season_length` cannot capture together. States are
+/// updated in error-correction form so every component shares the one-step
+/// error, and unset smoothing weights minimize the in-sample squared error.
+/// Components are initialized from the first two cycles of the longest
+/// period, shortest period first. Intervals use the additive state-space
+/// variance, which grows with the horizon and jumps at each seasonal lag.
+/// Returns empty vectors for fewer than 3 values or non-finite data.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `season_lengths` - Seasonal periods (e.g. [24, 168])
+/// * `steps` - Number of future periods to predict
+/// * `options` - Fixed smoothing weights
+#[napi]
+pub fn multi_seasonal_forecast(
+    data: Vec<f64>,
+    season_lengths: Vec<u32>,
+    steps: u32,
+    options: Option<MultiSeasonalOptions>,
+) -> MultiSeasonalForecast {
+    let options = options.unwrap_or_default();
+    let mut periods: Vec<usize> = season_lengths
+        .iter()
+        .map(|&m| m as usize)
+        .filter(|&m| m > 1 && data.len() >= 2 * m)
+        .collect();
+    periods.sort_unstable();
+    periods.dedup();
+    if data.len() < 3 || data.iter().any(|v| !v.is_finite()) {
+        return MultiSeasonalForecast {
+            predictions: Vec::new(),
+            lower_bounds: Vec::new(),
+            upper_bounds: Vec::new(),
+            fitted: Vec::new(),
+            season_lengths: periods.iter().map(|&m| m as u32).collect(),
+            alpha: f64::NAN,
+            beta: f64::NAN,
+            gammas: Vec::new(),
+            seasonal_components: Vec::new(),
+        };
+    }
+
+    // Unconstrained parameters map through the logistic function into
+    // 0 < alpha < 1, 0 <= beta <= alpha and 0 <= gamma_i <= 1 - alpha
+    let logistic = |u: f64| 1.0 / (1.0 + (-u).exp());
+    let logit = |p: f64| (p.clamp(1e-6, 1.0 - 1e-6) / (1.0 - p.clamp(1e-6, 1.0 - 1e-6))).ln();
+    let k = periods.len();
+    let unpack = |u: &[f64]| {
+        let alpha = options.alpha.map_or_else(|| logistic(u[0]), |a| a.clamp(1e-6, 1.0 - 1e-6));
+        let beta = options.beta.map_or_else(|| alpha * logistic(u[1]), |b| b.clamp(0.0, alpha));
+        let gammas: Vec<f64> = (0..k)
+            .map(|i| match options.gammas.as_ref().and_then(|g| g.get(i)) {
+                Some(g) => g.clamp(0.0, 1.0 - alpha),
+                None => (1.0 - alpha) * logistic(u[2 + i]),
+            })
+            .collect();
+        (alpha, beta, gammas)
+    };
+    let mut start = vec![logit(0.2), logit(0.1)];
+    start.extend(std::iter::repeat_n(logit(0.1), k));
+    let objective = |u: &[f64]| {
+        let (alpha, beta, gammas) = unpack(u);
+        let state = run_multi_seasonal(&data, &periods, alpha, beta, &gammas);
+        data.iter().zip(&state.fitted).map(|(y, f)| (y - f).powi(2)).sum::<f64>()
+    };
+    let (best, sse) = nelder_mead(objective, &start, 0.5, 400 * start.len());
+    let (alpha, beta, gammas) = unpack(&best);
+    let state = run_multi_seasonal(&data, &periods, alpha, beta, &gammas);
+
+    let n = data.len();
+    let sigma2 = sse / n as f64;
+    let mut cumulative = 1.0;
+    let mut predictions = Vec::with_capacity(steps as usize);
+    let mut half_widths = Vec::with_capacity(steps as usize);
+    for h in 1..=steps as usize {
+        let seasonal: f64 = state.seasonal.iter().map(|s| s[(n + h - 1) % s.len()]).sum();
+        predictions.push(state.level + state.trend * h as f64 + seasonal);
+        half_widths.push(Z_95 * (sigma2 * cumulative).sqrt());
+        // c_h = alpha + beta h + gamma_i for each period that h completes
+        let c = alpha + beta * h as f64 + periods.iter().zip(&gammas).filter(|(m, _)| h % **m == 0).map(|(_, g)| g).sum::<f64>();
+        cumulative += c * c;
+    }
+    MultiSeasonalForecast {
+        lower_bounds: predictions.iter().zip(&half_widths).map(|(p, w)| p - w).collect(),
+        upper_bounds: predictions.iter().zip(&half_widths).map(|(p, w)| p + w).collect(),
+        predictions,
+        fitted: state.fitted,
+        season_lengths: periods.iter().map(|&m| m as u32).collect(),
+        alpha,
+        beta,
+        gammas,
+        seasonal_components: state.seasonal,
+    }
+}
+
+/// Smoothing state at the end of a multi-seasonal Holt-Winters pass
+struct MultiSeasonalState {
+    fitted: Vec<f64>,
+    level: f64,
+    trend: f64,
+    /// Offsets per period, indexed by `t % period`
+    seasonal: Vec<Vec<f64>>,
+}
+
+/// Run additive multi-seasonal Holt-Winters over `data`
+///
+/// `periods` must be ascending with at least two cycles of the longest in
+/// `data`.
+fn run_multi_seasonal(data: &[f64], periods: &[usize], alpha: f64, beta: f64, gammas: &[f64]) -> MultiSeasonalState {
+    // Level and trend from the first two cycles of the longest period
+    let cycle = periods.last().copied().unwrap_or(1).max(1);
+    let window = (2 * cycle).min(data.len());
+    let first = data[..cycle.min(window)].iter().sum::<f64>() / cycle.min(window) as f64;
+    let mut trend = if window >= 2 * cycle {
+        (data[cycle..window].iter().sum::<f64>() / cycle as f64 - first) / cycle as f64
+    } else {
+        0.0
+    };
+    let mut level = first - trend * (cycle as f64 + 1.0) / 2.0;
+
+    // Seasonal offsets from what the trend line leaves, shortest period first
+    let mut remainder: Vec<f64> = (0..window).map(|t| data[t] - level - trend * (t + 1) as f64).collect();
+    let mut seasonal: Vec<Vec<f64>> = Vec::with_capacity(periods.len());
+    for &m in periods {
+        let mut offsets: Vec<f64> = (0..m)
+            .map(|phase| {
+                let values: Vec<f64> = remainder.iter().skip(phase).step_by(m).copied().collect();
+                values.iter().sum::<f64>() / values.len() as f64
+            })
+            .collect();
+        let center = offsets.iter().sum::<f64>() / m as f64;
+        offsets.iter_mut().for_each(|o| *o -= center);
+        for (t, r) in remainder.iter_mut().enumerate() {
+            *r -= offsets[t % m];
+        }
+        seasonal.push(offsets);
     }
 
-    forecasts
+    let mut fitted = Vec::with_capacity(data.len());
+    for (t, &value) in data.iter().enumerate() {
+        let forecast = level + trend + seasonal.iter().map(|s| s[t % s.len()]).sum::<f64>();
+        let error = value - forecast;
+        fitted.push(forecast);
+        level += trend + alpha * error;
+        trend += beta * error;
+        for (s, gamma) in seasonal.iter_mut().zip(gammas) {
+            let phase = t % s.len();
+            s[phase] += gamma * error;
+        }
+    }
+    MultiSeasonalState { fitted, level, trend, seasonal }
 }
 
 /// Simple exponential smoothing
@@ -104,14 +460,1120 @@ pub fn simple_exponential_smoothing(data: Vec<f64>, alpha: f64) -> Vec<f64> {
     result
 }
 
+/// Strength of seasonality in `[0, 1]` (Hyndman's `F_S`)
+///
+/// `max(0, 1 - Var(R) / Var(S + R))` from an additive classical
+/// decomposition with the given period. Values above about 0.6 indicate
+/// seasonality worth modelling with `holt_winters`; values near 0 mean
+/// `simple_exponential_smoothing` (or a trend model) is enough. Returns 0
+/// with fewer than two full periods.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `period` - Length of one seasonal cycle (e.g. 7 for weekly)
+#[napi]
+pub fn seasonal_strength(data: Vec<f64>, period: u32) -> f64 {
+    let period = period as usize;
+    if period < 2 || data.len() < 2 * period || data.iter().any(|v| !v.is_finite()) {
+        return 0.0;
+    }
+    let (trend, seasonal) = classical_decomposition(&data, period);
+    let (seasonal_plus_remainder, remainder): (Vec<f64>, Vec<f64>) = trend
+        .iter()
+        .enumerate()
+        .filter_map(|(t, level)| level.map(|level| (data[t] - level, data[t] - level - seasonal[t % period])))
+        .unzip();
+    strength(&remainder, &seasonal_plus_remainder)
+}
+
+/// Strength of trend in `[0, 1]` (Hyndman's `F_T`)
+///
+/// `max(0, 1 - Var(R) / Var(T + R))`, where the trend `T` is a centered
+/// moving average and, when `period` is given, the seasonal component is
+/// removed first. Values near 1 mean a dominant trend. Returns 0 for series
+/// too short to smooth.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `period` - Seasonal cycle to remove first and smoothing window (default:
+///   non-seasonal, with an odd window of about a tenth of the series, at least 3)
+#[napi]
+pub fn trend_strength(data: Vec<f64>, period: Option<u32>) -> f64 {
+    let n = data.len();
+    let period = period.map_or(0, |p| p as usize);
+    if data.iter().any(|v| !v.is_finite()) {
+        return 0.0;
+    }
+    let (trend, seasonal) = if period >= 2 {
+        if n < 2 * period {
+            return 0.0;
+        }
+        classical_decomposition(&data, period)
+    } else {
+        let window = ((n / 10) | 1).max(3);
+        if n < 2 * window {
+            return 0.0;
+        }
+        (centered_moving_average(&data, window), vec![0.0])
+    };
+    let cycle = seasonal.len();
+    let (trend_plus_remainder, remainder): (Vec<f64>, Vec<f64>) = trend
+        .iter()
+        .enumerate()
+        .filter_map(|(t, level)| level.map(|level| (data[t] - seasonal[t % cycle], data[t] - seasonal[t % cycle] - level)))
+        .unzip();
+    strength(&remainder, &trend_plus_remainder)
+}
+
+/// Additive classical decomposition: centered moving-average trend (`None`
+/// at the edges) and seasonal indices centred on zero
+fn classical_decomposition(data: &[f64], period: usize) -> (Vec<Option<f64>>, Vec<f64>) {
+    let trend = centered_moving_average(data, period);
+    let mut sums = vec![(0.0, 0usize); period];
+    for (t, level) in trend.iter().enumerate() {
+        if let Some(level) = level {
+            sums[t % period].0 += data[t] - level;
+            sums[t % period].1 += 1;
+        }
+    }
+    let indices: Vec<f64> = sums.iter().map(|&(sum, count)| if count > 0 { sum / count as f64 } else { 0.0 }).collect();
+    let offset = indices.iter().sum::<f64>() / period as f64;
+    (trend, indices.iter().map(|s| s - offset).collect())
+}
+
+/// Centered moving average; even windows use the usual 2 x window average
+fn centered_moving_average(data: &[f64], window: usize) -> Vec<Option<f64>> {
+    let n = data.len();
+    let half = window / 2;
+    (0..n)
+        .map(|t| {
+            if t < half || t + half >= n {
+                return None;
+            }
+            let sum: f64 = if window % 2 == 1 {
+                data[t - half..=t + half].iter().sum()
+            } else {
+                data[t - half + 1..t + half].iter().sum::<f64>() + (data[t - half] + data[t + half]) / 2.0
+            };
+            Some(sum / window as f64)
+        })
+        .collect()
+}
+
+/// `max(0, 1 - Var(remainder) / Var(component + remainder))`
+fn strength(remainder: &[f64], combined: &[f64]) -> f64 {
+    let variance = |v: &[f64]| {
+        let m = v.iter().sum::<f64>() / v.len() as f64;
+        v.iter().map(|x| (x - m).powi(2)).sum::<f64>()
+    };
+    let total = variance(combined);
+    if combined.len() < 2 || total <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - variance(remainder) / total).clamp(0.0, 1.0)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct PredictOptions {
+    /// Round predicted values and bounds to whole numbers (default false)
+    pub round_to_integer: Option<bool>,
+    /// Round predicted values and bounds to this many decimal places (default: no rounding)
+    pub decimal_places: Option<u32>,
+    /// Clamp predicted values and lower bounds at zero (default false)
+    pub non_negative: Option<bool>,
+    /// Lowest value reported for predictions and bounds, e.g. 0 for counts
+    /// or -1 for a signed ratio (default: none; the higher of this and zero
+    /// when `non_negative` is set)
+    pub floor: Option<f64>,
+    /// Highest value reported for predictions and bounds, e.g. 1 for a
+    /// utilization ratio or a capacity limit; a cap below the floor is an
+    /// error (default: none)
+    pub cap: Option<f64>,
+    /// Multi-step strategy: "recursive" (default) extrapolates one fitted model;
+    /// "direct" fits a separate regression for every horizon step; anything
+    /// else is an error
+    pub strategy: Option<String>,
+    /// Per-step confidence: "interval" (default) is the probability that the
+    /// actual falls within `confidence_tolerance` of the prediction given the
+    /// step's interval width; "linear" decays by `confidence_decay` per step
+    pub confidence_method: Option<String>,
+    /// Relative error counted as "on target" by the interval method (default 0.1 = ±10%)
+    pub confidence_tolerance: Option<f64>,
+    /// Confidence lost per step by the linear method (default 0.05)
+    pub confidence_decay: Option<f64>,
+    /// Lowest confidence reported by the linear method (default 0.6)
+    pub confidence_floor: Option<f64>,
+    /// Slope estimator for the trend: "ols" (default), "theil_sen", which a
+    /// single spike cannot flip, or "weighted", which favours recent points
+    pub trend_method: Option<String>,
+    /// Half-life in periods of the "weighted" trend's exponentially decaying weights (default 7)
+    pub trend_half_life: Option<f64>,
+    /// Known drivers such as promotion flags, school holidays or temperature:
+    /// one row per history period followed by one row per forecast step, one
+    /// column per driver. When given, the forecast is a regression on the
+    /// drivers with seasonal ARIMA errors and `strategy` is ignored; any
+    /// other row count is an error (default: history only)
+    pub regressors: Option<Vec<Vec<f64>>>,
+    /// Holiday and closure periods as indices into the series; indices from
+    /// the history length on fall in the forecast horizon (history length +
+    /// step - 1). These periods and the catch-up after them are replaced by
+    /// a seasonal baseline before fitting, and their effects, estimated from
+    /// past holidays, are applied to future ones (default: none)
+    pub holidays: Option<Vec<u32>>,
+    /// Periods after each holiday treated as catch-up (default 1)
+    pub post_holiday_periods: Option<u32>,
+    /// Coverage probability of the prediction intervals, e.g. 0.8, 0.9,
+    /// 0.95 or 0.99 (default 0.95; values outside 0..1 fall back to it)
+    pub confidence_level: Option<f64>,
+}
+
 /// Predict future values using exponential smoothing
+///
+/// Values and bounds are rounded to integers and clamped at zero; use
+/// `predict_next_with_options` for fractional or signed metrics.
 #[napi]
 pub fn predict_next(data: Vec<f64>, steps: u32, season_length: u32) -> Vec<ForecastResult> {
+    let options = PredictOptions {
+        round_to_integer: Some(true),
+        non_negative: Some(true),
+        ..Default::default()
+    };
+    predict_next_with_options(data, steps, season_length, Some(options)).expect("rounding options are always valid")
+}
+
+/// Predict future values using exponential smoothing, with output options
+///
+/// Fails for an unknown `strategy`, a `floor` above `cap`, or `regressors`
+/// without one row per history period and forecast step.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `steps` - Number of future periods to predict
+/// * `season_length` - Length of seasonal cycle
+/// * `options` - Output rounding, floor and cap, multi-step strategy, confidence scheme,
+///   interval level, trend estimator, known regressors and holidays (default:
+///   unrounded, signed, recursive, interval-based confidence, 95% intervals,
+///   OLS trend, no regressors or holidays)
+#[napi]
+pub fn predict_next_with_options(
+    data: Vec<f64>,
+    steps: u32,
+    season_length: u32,
+    options: Option<PredictOptions>,
+) -> napi::Result<Vec<ForecastResult>> {
+    let options = options.unwrap_or_default();
+    let decimals = if options.round_to_integer.unwrap_or(false) {
+        Some(0)
+    } else {
+        options.decimal_places
+    };
+    let mut floor = options.floor.unwrap_or(f64::NEG_INFINITY);
+    if options.non_negative.unwrap_or(false) {
+        floor = floor.max(0.0);
+    }
+    let cap = options.cap.unwrap_or(f64::INFINITY);
+    if floor > cap {
+        return Err(Error::new(Status::InvalidArg, format!("floor {} is above cap {}", floor, cap)));
+    }
+    let level = options.confidence_level.filter(|l| *l > 0.0 && *l < 1.0).unwrap_or(0.95);
+    // Every forecaster produces 95% bands; rescale them to the requested level
+    let scale = interval_z(level) / Z_95;
+    let n = data.len();
+    let holidays: HashSet<usize> = options.holidays.iter().flatten().map(|&t| t as usize).collect();
+    // Catch-up longer than the history has no effects to estimate
+    let window = (options.post_holiday_periods.unwrap_or(1) as usize).min(n);
+    let (data, effects) = calendar_adjust(&data, &holidays, window, season_length as usize);
+
+    if let Some(strategy) = options.strategy.as_deref().filter(|s| !matches!(*s, "recursive" | "direct")) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("unknown strategy \"{}\"; expected \"recursive\" or \"direct\"", strategy),
+        ));
+    }
+    let regressors = match &options.regressors {
+        Some(rows) if rows.len() != n + steps as usize => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("regressors have {} rows; expected {} history plus {} forecast rows", rows.len(), n, steps),
+            ))
+        }
+        rows => rows.as_ref(),
+    };
+    let raw = match (regressors, options.strategy.as_deref()) {
+        (Some(rows), _) => forecast_with_regressors(&data, rows, season_length as usize, &options),
+        (None, Some("direct")) => forecast_direct(&data, steps as usize, season_length as usize, &options),
+        (None, _) => forecast_raw(data, steps, season_length, &options),
+    };
+    Ok(raw
+        .into_iter()
+        .enumerate()
+        .map(|(i, f)| {
+            // Holiday effects scale the forecast made from the adjusted history
+            let factor = calendar_offset(n + i, &holidays, window).map_or(1.0, |k| effects[k]);
+            let f = ForecastResult {
+                predicted_value: f.predicted_value * factor,
+                lower_bound: (f.predicted_value - scale * (f.predicted_value - f.lower_bound)) * factor,
+                upper_bound: (f.predicted_value + scale * (f.upper_bound - f.predicted_value)) * factor,
+                confidence_level: level,
+                ..f
+            };
+            ForecastResult {
+                confidence: step_confidence(&options, i, &f),
+                predicted_value: round_to(f.predicted_value.max(floor).min(cap), decimals),
+                lower_bound: round_to(f.lower_bound.max(floor).min(cap), decimals),
+                upper_bound: round_to(f.upper_bound.max(floor).min(cap), decimals),
+                ..f
+            }
+        })
+        .collect())
+}
+
+/// Periods since the latest holiday at or before `t`, if within `window` (0 on a holiday)
+fn calendar_offset(t: usize, holidays: &HashSet<usize>, window: usize) -> Option<usize> {
+    (0..=window.min(t)).find(|k| holidays.contains(&(t - k)))
+}
+
+/// Replace holiday and post-holiday periods with a baseline and estimate their effects
+///
+/// The baseline of an affected period is the mean of unaffected values one
+/// and two seasons (or, without seasonality, one and two periods) either
+/// side. Effect `k` is the ratio of actual to baseline totals over periods
+/// `k` after a holiday (0 = the holiday itself); 1 when the history has no
+/// such periods.
+fn calendar_adjust(data: &[f64], holidays: &HashSet<usize>, window: usize, season_len: usize) -> (Vec<f64>, Vec<f64>) {
+    if holidays.is_empty() {
+        return (data.to_vec(), vec![1.0; window.min(data.len()) + 1]);
+    }
+    let window = window.min(data.len());
+    let mut effects = vec![1.0; window + 1];
+    let offsets: Vec<Option<usize>> = (0..data.len()).map(|t| calendar_offset(t, holidays, window)).collect();
+    let unaffected: Vec<f64> = data.iter().zip(&offsets).filter(|(_, o)| o.is_none()).map(|(y, _)| *y).collect();
+    if unaffected.is_empty() {
+        return (data.to_vec(), effects);
+    }
+    let fallback = mean(unaffected);
+
+    let lag = season_len.max(1);
+    let mut adjusted = data.to_vec();
+    let mut totals = vec![(0.0, 0.0); window + 1];
+    for (t, offset) in offsets.iter().enumerate() {
+        let Some(k) = *offset else {
+            continue;
+        };
+        let neighbours: Vec<f64> = [t.checked_sub(lag), Some(t + lag), t.checked_sub(2 * lag), Some(t + 2 * lag)]
+            .into_iter()
+            .flatten()
+            .filter(|&s| s < data.len() && offsets[s].is_none())
+            .map(|s| data[s])
+            .collect();
+        let baseline = if neighbours.is_empty() { fallback } else { mean(neighbours) };
+        totals[k].0 += data[t];
+        totals[k].1 += baseline;
+        adjusted[t] = baseline;
+    }
+    for (effect, (actual, baseline)) in effects.iter_mut().zip(totals) {
+        if baseline > 0.0 {
+            *effect = actual / baseline;
+        }
+    }
+    (adjusted, effects)
+}
+
+/// Confidence of the forecast `step` steps ahead (0-based) under the configured method
+fn step_confidence(options: &PredictOptions, step: usize, forecast: &ForecastResult) -> f64 {
+    if options.confidence_method.as_deref() == Some("linear") {
+        let decay = options.confidence_decay.unwrap_or(0.05);
+        return (1.0 - step as f64 * decay).max(options.confidence_floor.unwrap_or(0.6)).min(1.0);
+    }
+    // Bands are ±z sd, so their half-width recovers the forecast error sd
+    let sd = (forecast.upper_bound - forecast.lower_bound) / (2.0 * interval_z(forecast.confidence_level));
+    if sd <= 0.0 {
+        return 1.0;
+    }
+    let tolerance = options.confidence_tolerance.unwrap_or(0.1) * forecast.predicted_value.abs();
+    erf(tolerance / (sd * std::f64::consts::SQRT_2))
+}
+
+/// Half-width, in standard errors, of the 95% bands the forecasters build
+pub(crate) const Z_95: f64 = 1.96;
+
+/// Half-width, in standard errors, of a two-sided interval with coverage `level`
+///
+/// The exact normal quantile ratio to 95% applied to `Z_95`, so a 95% level
+/// reproduces the forecasters' own bands. Levels outside (0, 1), including
+/// NaN or percentages such as 95, are treated as 95%.
+pub(crate) fn interval_z(level: f64) -> f64 {
+    if !(level > 0.0 && level < 1.0) {
+        return Z_95;
+    }
+    let normal = Normal::new(0.0, 1.0).expect("standard normal");
+    Z_95 * normal.inverse_cdf(0.5 + level / 2.0) / normal.inverse_cdf(0.975)
+}
+
+fn round_to(value: f64, decimals: Option<u32>) -> f64 {
+    match decimals {
+        Some(d) => {
+            let scale = 10f64.powi(d as i32);
+            (value * scale).round() / scale
+        }
+        None => value,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct AutoForecastOptions {
+    /// Seasonal cycle; skips detection when set (0 = non-seasonal)
+    pub season_length: Option<u32>,
+    /// Minimum detrended autocorrelation at the seasonal lag to treat the series as seasonal (default 0.3)
+    pub min_seasonal_autocorrelation: Option<f64>,
+    /// Restrict the shortlist to these methods (default: chosen from the demand pattern)
+    pub models: Option<Vec<String>>,
+    /// Trailing values held out to score candidates (default: horizon, at most a quarter of the data)
+    pub holdout: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CandidateScore {
+    pub model: String,
+    /// Mean absolute error on the holdout
+    pub holdout_mae: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct AutoForecastResult {
+    pub forecasts: Vec<ForecastResult>,
+    /// Chosen method
+    pub model: String,
+    pub params: Vec<ParamValue>,
+    /// Detected or given seasonal cycle (0 = non-seasonal)
+    pub season_length: u32,
+    /// Detrended autocorrelation at `season_length` (0 when non-seasonal or given)
+    pub seasonal_autocorrelation: f64,
+    /// "smooth", "erratic", "intermittent" or "lumpy" (Syntetos-Boylan)
+    pub demand_pattern: String,
+    /// Average interval between non-zero values
+    pub average_demand_interval: f64,
+    /// Squared coefficient of variation of non-zero values
+    pub cv_squared: f64,
+    /// Every method tried, best first
+    pub candidates: Vec<CandidateScore>,
+    /// In-sample fit of the chosen method on the full history
+    pub fit: FitResult,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FitResult {
+    pub method: String,
+    /// In-sample fitted values aligned with the data; NaN where the method
+    /// has no forecast yet (e.g. the first value for "naive")
+    pub fitted: Vec<f64>,
+    /// `actual - fitted`, NaN where `fitted` is NaN
+    pub residuals: Vec<f64>,
+    /// Parameters used or estimated by the method
+    pub params: Vec<ParamValue>,
+}
+
+/// Fitted values, residuals and parameters of a forecasting method
+///
+/// Lets accuracy metrics, residual diagnostics and anomaly detection on
+/// residuals use exactly what the forecaster saw. Methods report one-step-ahead
+/// fitted values with their estimated parameters, except "predict_next",
+/// "prophet_like", "seasonal_regression" and "holt_winters" with fixed
+/// `params`, which report the smoothed or regression values of the model.
+/// Series too short for the method give empty vectors. Fails for unknown
+/// methods, including "multi_seasonal" and "ensemble", whose fits come from
+/// `multi_seasonal_forecast` and `ensemble_forecast`.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `method` - "naive", "seasonal_naive", "drift", "linear_trend", "ses",
+///   "holt", "damped_holt", "holt_winters", "theta", "croston", "sba",
+///   "predict_next", "auto_arima", "prophet_like" or "seasonal_regression"
+/// * `season_length` - Length of seasonal cycle
+/// * `params` - Fixed smoothing parameters for "holt_winters" (`alpha`, `beta`,
+///   `gamma`); when given, the `holt_winters` function is reproduced instead
+///   of estimating parameters
+#[napi]
+pub fn fit_forecaster(
+    data: Vec<f64>,
+    method: String,
+    season_length: u32,
+    params: Option<Vec<ParamValue>>,
+) -> napi::Result<FitResult> {
+    if matches!(method.as_str(), "multi_seasonal" | "ensemble") {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("\"{}\" has no single fit; use {}_forecast", method, method),
+        ));
+    }
+    check_methods(std::slice::from_ref(&method))?;
+    let season_len = season_length as usize;
+
+    let (fitted, params) = match params {
+        Some(params) if method == "holt_winters" => {
+            let fixed = |name: &str, default: f64| params.iter().find(|p| p.name == name).map_or(default, |p| p.value);
+            let (alpha, beta, gamma) = (fixed("alpha", 0.3), fixed("beta", 0.1), fixed("gamma", 0.1));
+            (
+                holt_winters(data.clone(), alpha, beta, gamma, season_length),
+                vec![("alpha".to_string(), alpha), ("beta".to_string(), beta), ("gamma".to_string(), gamma)],
+            )
+        }
+        _ => match fit_method(&method, &data, season_len, 0) {
+            Some(fit) => (fit.fitted, fit.params),
+            None => (Vec::new(), Vec::new()),
+        },
+    };
+    Ok(fit_result(method, fitted, params, &data))
+}
+
+fn fit_result(method: String, fitted: Vec<f64>, params: Vec<(String, f64)>, data: &[f64]) -> FitResult {
+    FitResult {
+        method,
+        residuals: data.iter().zip(&fitted).map(|(y, f)| y - f).collect(),
+        fitted,
+        params: params.into_iter().map(|(name, value)| ParamValue { name, value }).collect(),
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct CrostonOptions {
+    /// "croston" (default) or "sba", the Syntetos-Boylan approximation, which
+    /// scales the forecast by `1 - alpha / 2` to remove Croston's upward bias
+    pub variant: Option<String>,
+    /// Smoothing weight for demand sizes and intervals in (0, 1] (default:
+    /// the value in 0.05-0.3 with the smallest one-step error)
+    pub alpha: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CrostonResult {
+    /// Expected demand per period, the same for every step
+    pub forecast: Vec<f64>,
+    /// One-step-ahead fitted demand rate (NaN before the first demand)
+    pub fitted: Vec<f64>,
+    /// Smoothed size of non-zero demands
+    pub demand_size: f64,
+    /// Smoothed number of periods between non-zero demands
+    pub demand_interval: f64,
+    pub alpha: f64,
+}
+
+/// Forecast intermittent demand with Croston's method or SBA
+///
+/// Exponential smoothing of a series with many zero days chases every
+/// order and overforecasts right after one. Croston's method instead
+/// smooths the sizes of non-zero demands and the intervals between them,
+/// updating only when demand occurs; the forecast rate is size / interval.
+/// The Syntetos-Boylan approximation ("sba") corrects Croston's known upward
+/// bias and is usually the better choice for stocking decisions. A series
+/// without any demand forecasts zero; one with non-finite values returns
+/// empty vectors.
+///
+/// # Arguments
+/// * `data` - Historical demand per period, mostly zeros
+/// * `steps` - Number of future periods to forecast
+/// * `options` - Variant and smoothing weight
+#[napi]
+pub fn croston_forecast(data: Vec<f64>, steps: u32, options: Option<CrostonOptions>) -> CrostonResult {
+    let options = options.unwrap_or_default();
+    let bias_corrected = options.variant.as_deref() == Some("sba");
+    let alpha = options.alpha.filter(|a| *a > 0.0 && *a <= 1.0);
+    if data.iter().any(|y| !y.is_finite()) {
+        return CrostonResult {
+            forecast: Vec::new(),
+            fitted: Vec::new(),
+            demand_size: f64::NAN,
+            demand_interval: f64::NAN,
+            alpha: f64::NAN,
+        };
+    }
+    if data.iter().all(|&y| y == 0.0) {
+        return CrostonResult {
+            forecast: vec![0.0; steps as usize],
+            fitted: vec![f64::NAN; data.len()],
+            demand_size: 0.0,
+            demand_interval: f64::INFINITY,
+            alpha: alpha.unwrap_or(f64::NAN),
+        };
+    }
+
+    let grid = alpha.map_or(CROSTON_GRID.to_vec(), |alpha| vec![alpha]);
+    let (alpha, best) = grid
+        .into_iter()
+        .map(|a| (a, croston(&data, a, bias_corrected, steps as usize)))
+        .min_by(|a, b| a.1.fit.sse(&data).0.total_cmp(&b.1.fit.sse(&data).0))
+        .expect("smoothing grid is not empty");
+    CrostonResult {
+        demand_size: best.demand_size,
+        demand_interval: best.demand_interval,
+        alpha,
+        forecast: best.fit.forecast,
+        fitted: best.fit.fitted,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DemandClassification {
+    /// "smooth", "erratic", "intermittent" or "lumpy"
+    pub demand_pattern: String,
+    /// Average number of periods per non-zero demand (ADI)
+    pub average_demand_interval: f64,
+    /// Squared coefficient of variation of non-zero demand sizes
+    pub cv_squared: f64,
+    /// Number of periods with non-zero demand
+    pub non_zero_periods: u32,
+    /// Forecasting method suited to the pattern, usable as a `fit_forecaster`
+    /// method or an `auto_forecast` model
+    pub recommended_method: String,
+}
+
+/// Classify a demand series by how often and how evenly demand occurs
+///
+/// Syntetos-Boylan classification: an ADI of 1.32 or more makes demand
+/// intermittent, and a CV² of 0.49 or more makes the sizes erratic, giving
+/// "smooth", "erratic", "intermittent" or "lumpy". The recommended method
+/// is "ses" for smooth and erratic demand, "croston" for intermittent and
+/// "sba" for lumpy demand, where Croston's bias is largest; a series with no
+/// demand at all gets "naive", which forecasts zero.
+///
+/// # Arguments
+/// * `data` - Historical demand per period
+#[napi]
+pub fn classify_demand(data: Vec<f64>) -> DemandClassification {
+    let (pattern, adi, cv2) = demand_pattern(&data);
+    let non_zero_periods = data.iter().filter(|&&y| y != 0.0).count();
+    let recommended_method = match pattern {
+        _ if non_zero_periods == 0 => "naive",
+        "intermittent" => "croston",
+        "lumpy" => "sba",
+        _ => "ses",
+    };
+    DemandClassification {
+        demand_pattern: pattern.to_string(),
+        average_demand_interval: adi,
+        cv_squared: cv2,
+        non_zero_periods: non_zero_periods as u32,
+        recommended_method: recommended_method.to_string(),
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SeasonalRegressionOptions {
+    /// "fourier" (default) models the seasonal shape with `fourier_order`
+    /// sine/cosine pairs; "index" fits one effect per season position
+    pub seasonality: Option<String>,
+    /// Number of harmonics K for "fourier", at most half the season length
+    /// (default: 1-10 chosen by AICc)
+    pub fourier_order: Option<u32>,
+    /// Include a linear trend (default true)
+    pub trend: Option<bool>,
+}
+
+/// Forecast with a regression on trend and seasonality
+///
+/// With Fourier terms a long cycle such as a 365.25-day year costs only
+/// 2K parameters, so each harmonic is estimated from every observation;
+/// per-index effects ("index") need one parameter per day of the cycle and
+/// become noisy with only a few years of history. Intervals are 95%
+/// prediction intervals from the regression. Returns no forecasts when the
+/// history is too short for the terms or contains non-finite values.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `steps` - Number of future periods to predict
+/// * `season_length` - Seasonal period, may be fractional for "fourier" (e.g. 365.25)
+/// * `options` - Seasonal terms and trend
+#[napi]
+pub fn seasonal_regression_forecast(
+    data: Vec<f64>,
+    steps: u32,
+    season_length: f64,
+    options: Option<SeasonalRegressionOptions>,
+) -> Vec<ForecastResult> {
+    seasonal_regression(&data, steps as usize, season_length, &options.unwrap_or_default())
+        .map_or_else(Vec::new, |fit| fit.forecasts)
+}
+
+/// In-sample fit and forecasts of `seasonal_regression_forecast`
+pub(crate) struct SeasonalRegressionFit {
+    /// Regression values over the history
+    pub fitted: Vec<f64>,
+    pub forecasts: Vec<ForecastResult>,
+    /// Number of regression coefficients
+    pub n_params: usize,
+}
+
+/// Fit of `seasonal_regression_forecast`, `None` when it gives no forecasts
+pub(crate) fn seasonal_regression(
+    data: &[f64],
+    steps: usize,
+    season_length: f64,
+    options: &SeasonalRegressionOptions,
+) -> Option<SeasonalRegressionFit> {
+    let n = data.len();
+    if season_length < 2.0 || data.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let with_trend = options.trend.unwrap_or(true);
+    let features = |t: usize, terms: &SeasonalTerms| -> Vec<f64> {
+        let mut row = vec![1.0];
+        if with_trend {
+            row.push(t as f64);
+        }
+        row.extend(terms.row(t));
+        row
+    };
+    let fit = |terms: SeasonalTerms| {
+        let x: Vec<Vec<f64>> = (0..n).map(|t| features(t, &terms)).collect();
+        least_squares(&x, data).map(|fit| (fit, terms))
+    };
+    // Small-sample corrected AIC of a fit, counting the variance
+    let aicc = |fit: &LeastSquares| {
+        let (nf, p) = (n as f64, fit.coefficients.len() as f64 + 1.0);
+        if nf - p - 1.0 <= 0.0 {
+            return f64::INFINITY;
+        }
+        nf * (fit.rss.max(f64::MIN_POSITIVE) / nf).ln() + 2.0 * p + 2.0 * p * (p + 1.0) / (nf - p - 1.0)
+    };
+
+    let chosen = if options.seasonality.as_deref() == Some("index") {
+        fit(SeasonalTerms::Index(season_length.round() as usize))
+    } else {
+        let max_order = ((season_length / 2.0).floor() as usize).max(1);
+        let orders = match options.fourier_order {
+            Some(k) => vec![(k as usize).clamp(1, max_order)],
+            None => (1..=max_order.min(10)).collect(),
+        };
+        orders
+            .into_iter()
+            .filter_map(|k| fit(SeasonalTerms::Fourier(season_length, k)))
+            .min_by(|a, b| aicc(&a.0).total_cmp(&aicc(&b.0)))
+    };
+    let (best, terms) = chosen?;
+
+    let sigma2 = best.sigma2();
+    let slope = if with_trend { best.coefficients[1] } else { 0.0 };
+    let fitted = (0..n).map(|t| dot(&features(t, &terms), &best.coefficients)).collect();
+    let forecasts = (n..n + steps)
+        .enumerate()
+        .map(|(i, t)| {
+            let row = features(t, &terms);
+            let predicted_value = dot(&row, &best.coefficients);
+            let leverage: f64 = best.xtx_inv.iter().zip(&row).map(|(inv, r)| r * dot(inv, &row)).sum();
+            let margin = Z_95 * (sigma2 * (1.0 + leverage)).sqrt();
+            let mut forecast = ForecastResult {
+                predicted_value,
+                confidence: 0.0,
+                lower_bound: predicted_value - margin,
+                upper_bound: predicted_value + margin,
+                confidence_level: 0.95,
+                trend: trend_label(slope),
+            };
+            forecast.confidence = step_confidence(&PredictOptions::default(), i, &forecast);
+            forecast
+        })
+        .collect();
+    Some(SeasonalRegressionFit { fitted, forecasts, n_params: best.coefficients.len() })
+}
+
+/// Seasonal design columns of `seasonal_regression_forecast`
+enum SeasonalTerms {
+    /// One indicator per season position after the first
+    Index(usize),
+    /// Period and number of harmonics
+    Fourier(f64, usize),
+}
+
+impl SeasonalTerms {
+    fn row(&self, t: usize) -> Vec<f64> {
+        match *self {
+            SeasonalTerms::Index(m) => (1..m).map(|position| if t % m == position { 1.0 } else { 0.0 }).collect(),
+            SeasonalTerms::Fourier(period, order) => fourier_terms(t as f64, period, order),
+        }
+    }
+}
+
+/// Forecast a series with an automatically selected method
+///
+/// Detects seasonality, classifies the demand pattern, scores a shortlist
+/// of suitable methods on a trailing holdout and refits the best one on the
+/// full history. Intervals are 95% bands from the chosen method's one-step
+/// in-sample errors, widening with the square root of the horizon. Fails
+/// for unknown methods in `options.models`.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `horizon` - Number of future periods to predict
+/// * `options` - Seasonality, shortlist and holdout overrides
+#[napi]
+pub fn auto_forecast(data: Vec<f64>, horizon: u32, options: Option<AutoForecastOptions>) -> napi::Result<AutoForecastResult> {
+    let options = options.unwrap_or_default();
+    if let Some(models) = &options.models {
+        check_methods(models)?;
+    }
+    let steps = horizon as usize;
+    let n = data.len();
+
+    let (season_len, seasonal_autocorrelation) = match options.season_length {
+        Some(m) => (m as usize, 0.0),
+        None => detect_season_length(&data, options.min_seasonal_autocorrelation.unwrap_or(0.3)).unwrap_or((0, 0.0)),
+    };
+    let (pattern, adi, cv2) = demand_pattern(&data);
+
+    let shortlist: Vec<String> = options.models.clone().unwrap_or_else(|| {
+        let methods: &[&str] = match (pattern, season_len > 1) {
+            ("intermittent" | "lumpy", _) => &["croston", "sba", "ses", "naive"],
+            (_, true) => &["holt_winters", "seasonal_naive", "ses", "holt"],
+            _ => &["ses", "holt", "drift", "naive"],
+        };
+        methods.iter().map(|m| m.to_string()).collect()
+    });
+
+    let holdout = options.holdout.map_or(steps.min(n / 4), |h| h as usize).max(1);
+    let mut candidates: Vec<CandidateScore> = if n > holdout {
+        let (train, test) = data.split_at(n - holdout);
+        shortlist
+            .par_iter()
+            .filter_map(|model| {
+                let forecast = fit_method(model, train, season_len, holdout)?.forecast;
+                let mae = forecast.iter().zip(test).map(|(f, y)| (f - y).abs()).sum::<f64>() / holdout as f64;
+                Some(CandidateScore { model: model.clone(), holdout_mae: mae })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    candidates.sort_by(|a, b| a.holdout_mae.total_cmp(&b.holdout_mae));
+
+    // Refit on the full history; "naive" is the last resort for very short series
+    let chosen = candidates
+        .iter()
+        .map(|c| c.model.as_str())
+        .chain(shortlist.iter().map(String::as_str))
+        .chain(["naive"])
+        .find_map(|model| fit_method(model, &data, season_len, steps).map(|fit| (model.to_string(), fit)));
+
+    let (model, fit) = match chosen {
+        Some(chosen) => chosen,
+        None => {
+            return Ok(AutoForecastResult {
+                forecasts: Vec::new(),
+                model: String::new(),
+                params: Vec::new(),
+                season_length: season_len as u32,
+                seasonal_autocorrelation,
+                demand_pattern: pattern.to_string(),
+                average_demand_interval: adi,
+                cv_squared: cv2,
+                candidates,
+                fit: fit_result(String::new(), Vec::new(), Vec::new(), &data),
+            })
+        }
+    };
+
+    let forecasts = method_forecasts(&fit, &data);
+    let fit = fit_result(model.clone(), fit.fitted, fit.params, &data);
+    Ok(AutoForecastResult {
+        forecasts,
+        model,
+        params: fit.params.clone(),
+        season_length: season_len as u32,
+        seasonal_autocorrelation,
+        demand_pattern: pattern.to_string(),
+        average_demand_interval: adi,
+        cv_squared: cv2,
+        candidates,
+        fit,
+    })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct EnsembleOptions {
+    /// "mean" (default), "median" or "inverse_error" (weights proportional
+    /// to 1 / holdout MAE); ignored when `weights` is given
+    pub combination: Option<String>,
+    /// Fixed weights, one per model; normalized to sum to 1 (any other
+    /// length is an error)
+    pub weights: Option<Vec<f64>>,
+    /// Seasonal cycle; skips detection when set (0 = non-seasonal)
+    pub season_length: Option<u32>,
+    /// Trailing values held out to score members (default: steps, at most a quarter of the data)
+    pub holdout: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EnsembleMember {
+    pub model: String,
+    /// Share of the combined forecast (0 for "median")
+    pub weight: f64,
+    /// Mean absolute error on the holdout (NaN when the series is too short)
+    pub holdout_mae: f64,
+    pub predictions: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EnsembleForecast {
+    pub forecasts: Vec<ForecastResult>,
+    /// Combination actually used: "mean", "median", "inverse_error" or "weights"
+    pub combination: String,
+    /// Detected or given seasonal cycle (0 = non-seasonal)
+    pub season_length: u32,
+    /// Methods that could be fitted, in the order requested
+    pub members: Vec<EnsembleMember>,
+}
+
+/// Combine the forecasts of several methods
+///
+/// Fits each method on the full history and averages the point forecasts,
+/// by plain mean, median, weights inversely proportional to trailing-holdout
+/// MAE, or fixed weights. Methods that cannot be fitted (e.g. "holt_winters"
+/// without two full seasons) are left out and the remaining weights
+/// renormalized. Intervals are 95% bands from the one-step in-sample errors
+/// of the combined fit. Fails for unknown methods or `weights` of a
+/// different length than `models`.
+///
+/// # Arguments
+/// * `data` - Historical time series data
+/// * `steps` - Number of future periods to predict
+/// * `models` - `fit_forecaster` methods to combine (default "ses",
+///   "holt_winters", "theta", "naive")
+/// * `options` - Combination rule, fixed weights, seasonality and holdout
+#[napi]
+pub fn ensemble_forecast(
+    data: Vec<f64>,
+    steps: u32,
+    models: Option<Vec<String>>,
+    options: Option<EnsembleOptions>,
+) -> napi::Result<EnsembleForecast> {
+    let options = options.unwrap_or_default();
+    let steps = steps as usize;
+    let n = data.len();
+    let models = models.unwrap_or_else(|| ["ses", "holt_winters", "theta", "naive"].iter().map(|m| m.to_string()).collect());
+    check_methods(&models)?;
+    if let Some(weights) = options.weights.as_ref().filter(|w| w.len() != models.len()) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("{} weights given for {} models", weights.len(), models.len()),
+        ));
+    }
+    let season_len = match options.season_length {
+        Some(m) => m as usize,
+        None => detect_season_length(&data, 0.3).map_or(0, |s| s.0),
+    };
+    let combination = match (&options.weights, options.combination.as_deref()) {
+        (Some(_), _) => "weights",
+        (None, Some("median")) => "median",
+        (None, Some("inverse_error")) => "inverse_error",
+        _ => "mean",
+    };
+
+    let holdout = options.holdout.map_or(steps.min(n / 4), |h| h as usize).max(1);
+    let fits: Vec<(usize, MethodFit, f64)> = models
+        .par_iter()
+        .enumerate()
+        .filter_map(|(i, model)| {
+            let fit = fit_method(model, &data, season_len, steps)?;
+            let holdout_mae = if n > holdout {
+                let (train, test) = data.split_at(n - holdout);
+                fit_method(model, train, season_len, holdout).map_or(f64::NAN, |f| {
+                    f.forecast.iter().zip(test).map(|(f, y)| (f - y).abs()).sum::<f64>() / holdout as f64
+                })
+            } else {
+                f64::NAN
+            };
+            Some((i, fit, holdout_mae))
+        })
+        .collect();
+
+    let raw: Vec<f64> = fits
+        .iter()
+        .map(|(i, _, mae)| match combination {
+            "weights" => options.weights.as_ref().map_or(0.0, |w| w[*i]).max(0.0),
+            "inverse_error" if *mae == 0.0 => f64::INFINITY,
+            "inverse_error" if mae.is_finite() => 1.0 / mae,
+            "inverse_error" => 0.0,
+            _ => 1.0,
+        })
+        .collect();
+    // Perfect holdout scores share all the weight; no usable weights fall back to equal ones
+    let raw: Vec<f64> = if raw.iter().any(|w| w.is_infinite()) {
+        raw.iter().map(|w| if w.is_infinite() { 1.0 } else { 0.0 }).collect()
+    } else if raw.iter().sum::<f64>() > 0.0 {
+        raw
+    } else {
+        vec![1.0; raw.len()]
+    };
+    let total: f64 = raw.iter().sum();
+    let weights: Vec<f64> = raw.iter().map(|w| if combination == "median" { 0.0 } else { w / total }).collect();
+
+    let combine = |values: Vec<f64>| -> f64 {
+        if values.iter().any(|v| !v.is_finite()) {
+            return f64::NAN;
+        }
+        if combination == "median" {
+            median(values)
+        } else {
+            values.iter().zip(&weights).map(|(v, w)| v * w).sum()
+        }
+    };
+    let combined = MethodFit {
+        fitted: (0..n).map(|t| combine(fits.iter().map(|(_, fit, _)| fit.fitted[t]).collect())).collect(),
+        forecast: (0..steps).map(|h| combine(fits.iter().map(|(_, fit, _)| fit.forecast[h]).collect())).collect(),
+        params: Vec::new(),
+        n_params: 0,
+    };
+
+    Ok(EnsembleForecast {
+        forecasts: if fits.is_empty() { Vec::new() } else { method_forecasts(&combined, &data) },
+        combination: combination.to_string(),
+        season_length: season_len as u32,
+        members: fits
+            .into_iter()
+            .zip(weights)
+            .map(|((i, fit, holdout_mae), weight)| EnsembleMember {
+                model: models[i].clone(),
+                weight,
+                holdout_mae,
+                predictions: fit.forecast,
+            })
+            .collect(),
+    })
+}
+
+/// Wrap a registry forecast in `ForecastResult`s with residual-based 95% bands
+fn method_forecasts(fit: &MethodFit, data: &[f64]) -> Vec<ForecastResult> {
+    let (sse, count) = fit.sse(data);
+    let sigma = if count > 1 { (sse / (count - 1) as f64).sqrt() } else { 0.0 };
+    let trend = calculate_trend(&fit.forecast, &PredictOptions::default());
+    fit.forecast
+        .iter()
+        .enumerate()
+        .map(|(i, &predicted_value)| {
+            let margin = 1.96 * sigma * ((i + 1) as f64).sqrt();
+            let mut forecast = ForecastResult {
+                predicted_value,
+                confidence: 0.0,
+                lower_bound: predicted_value - margin,
+                upper_bound: predicted_value + margin,
+                confidence_level: 0.95,
+                trend: trend_label(trend),
+            };
+            forecast.confidence = step_confidence(&PredictOptions::default(), i, &forecast);
+            forecast
+        })
+        .collect()
+}
+
+/// Direct multi-step forecasts: one least-squares model per horizon step
+///
+/// The model for step `h` regresses `y[t + h]` on the last few observations at
+/// `t` and, when seasonal, the most recent value in the same season phase as
+/// `t + h`. Its interval comes from that model's own residual spread, so
+/// uncertainty grows with the horizon as the data dictates. Steps without
+/// enough training rows fall back to the recursive forecast.
+fn forecast_direct(data: &[f64], steps: usize, season_len: usize, options: &PredictOptions) -> Vec<ForecastResult> {
+    let recursive = forecast_raw(data.to_vec(), steps as u32, season_len as u32, options);
+    let n = data.len();
+    let lags = if season_len > 1 { season_len.min(14) } else { 3 };
+    let trend = calculate_trend(&data[n.saturating_sub(7)..], options);
+
+    let features = |t: usize, h: usize| -> Option<Vec<f64>> {
+        if t + 1 < lags {
+            return None;
+        }
+        let mut row = vec![1.0];
+        row.extend((0..lags).map(|k| data[t - k]));
+        // Latest observed value in the same season phase as t + h, unless already a lag
+        let back = season_len * h.div_ceil(season_len.max(1));
+        if season_len > 1 && back - h >= lags {
+            row.push(*data.get((t + h).checked_sub(back)?)?);
+        }
+        Some(row)
+    };
+
+    recursive
+        .into_iter()
+        .enumerate()
+        .map(|(i, fallback)| {
+            let h = i + 1;
+            let (x, y): (Vec<Vec<f64>>, Vec<f64>) = (0..n.saturating_sub(h))
+                .filter_map(|t| features(t, h).map(|row| (row, data[t + h])))
+                .unzip();
+            let (Some(fit), Some(last)) = (least_squares(&x, &y), features(n - 1, h)) else {
+                return fallback;
+            };
+            let predicted_value = dot(&last, &fit.coefficients);
+            let margin = 1.96 * fit.sigma2().sqrt();
+            ForecastResult {
+                predicted_value,
+                confidence: (1.0 - (i as f64 * 0.05)).max(0.6),
+                lower_bound: predicted_value - margin,
+                upper_bound: predicted_value + margin,
+                confidence_level: 0.95,
+                trend: trend_label(trend),
+            }
+        })
+        .collect()
+}
+
+/// Regression on known drivers with automatically chosen seasonal ARIMA errors
+///
+/// `regressors` holds the history rows followed by one row per step. Falls
+/// back to the recursive forecast when the model cannot be fitted (e.g.
+/// collinear drivers or too little history).
+fn forecast_with_regressors(data: &[f64], regressors: &[Vec<f64>], season_len: usize, options: &PredictOptions) -> Vec<ForecastResult> {
+    let (history, future) = regressors.split_at(data.len());
+    let model = auto_arima_with_regressors(data.to_vec(), history.to_vec(), Some(season_len as u32));
+    let forecast = arima_forecast_with_regressors(model, future.to_vec());
+    if forecast.predictions.len() != future.len() {
+        return forecast_raw(data.to_vec(), future.len() as u32, season_len as u32, options);
+    }
+    let trend = calculate_trend(&data[data.len().saturating_sub(7)..], options);
+    forecast
+        .predictions
+        .iter()
+        .zip(forecast.lower_bounds.iter().zip(&forecast.upper_bounds))
+        .enumerate()
+        .map(|(i, (&predicted_value, (&lower_bound, &upper_bound)))| ForecastResult {
+            predicted_value,
+            confidence: (1.0 - (i as f64 * 0.05)).max(0.6),
+            lower_bound,
+            upper_bound,
+            confidence_level: 0.95,
+            trend: trend_label(trend),
+        })
+        .collect()
+}
+
+fn trend_label(trend: f64) -> String {
+    if trend > 0.1 {
+        "increasing".to_string()
+    } else if trend < -0.1 {
+        "decreasing".to_string()
+    } else {
+        "stable".to_string()
+    }
+}
+
+/// Unrounded, unclamped forecasts behind `predict_next`
+fn forecast_raw(data: Vec<f64>, steps: u32, season_length: u32, options: &PredictOptions) -> Vec<ForecastResult> {
     let steps = steps as usize;
     let season_len = season_length as usize;
 
     if data.len() < season_len * 2 {
-        return predict_simple(data, steps);
+        return predict_simple(data, steps, options);
     }
 
     // Get Holt-Winters forecasts
@@ -120,7 +1582,7 @@ pub fn predict_next(data: Vec<f64>, steps: u32, season_length: u32) -> Vec<Forec
 
     // Calculate trend from recent data
     let recent_data: Vec<f64> = data.iter().rev().take(7).cloned().collect();
-    let trend = calculate_trend(&recent_data);
+    let trend = calculate_trend(&recent_data, options);
 
     // Calculate standard deviation for confidence intervals
     let errors: Vec<f64> = data.iter().zip(forecasts.iter())
@@ -145,20 +1607,13 @@ pub fn predict_next(data: Vec<f64>, steps: u32, season_length: u32) -> Vec<Forec
         // Confidence decreases over time
         let confidence = (1.0 - (i as f64 * 0.05)).max(0.6);
 
-        let trend_direction = if trend > 0.1 {
-            "increasing".to_string()
-        } else if trend < -0.1 {
-            "decreasing".to_string()
-        } else {
-            "stable".to_string()
-        };
-
         results.push(ForecastResult {
-            predicted_value: predicted_value.max(0.0).round(),
+            predicted_value,
             confidence,
-            lower_bound: (predicted_value - confidence_margin).max(0.0).round(),
-            upper_bound: (predicted_value + confidence_margin).round(),
-            trend: trend_direction,
+            lower_bound: predicted_value - confidence_margin,
+            upper_bound: predicted_value + confidence_margin,
+            confidence_level: 0.95,
+            trend: trend_label(trend),
         });
     }
 
@@ -166,7 +1621,7 @@ pub fn predict_next(data: Vec<f64>, steps: u32, season_length: u32) -> Vec<Forec
 }
 
 /// Predict using simple exponential smoothing (fallback for insufficient data)
-fn predict_simple(data: Vec<f64>, steps: usize) -> Vec<ForecastResult> {
+fn predict_simple(data: Vec<f64>, steps: usize, options: &PredictOptions) -> Vec<ForecastResult> {
     if data.is_empty() {
         return vec![];
     }
@@ -175,7 +1630,7 @@ fn predict_simple(data: Vec<f64>, steps: usize) -> Vec<ForecastResult> {
     let smoothed = simple_exponential_smoothing(data.clone(), alpha);
     let last_smoothed = *smoothed.last().unwrap_or(&0.0);
     let sd = std_dev(data.clone());
-    let trend = calculate_trend(&data);
+    let trend = calculate_trend(&data, options);
 
     let mut results = Vec::with_capacity(steps);
 
@@ -184,35 +1639,37 @@ fn predict_simple(data: Vec<f64>, steps: usize) -> Vec<ForecastResult> {
         let confidence_margin = 1.96 * sd * ((i + 1) as f64).sqrt();
         let confidence = (1.0 - (i as f64 * 0.08)).max(0.5);
 
-        let trend_direction = if trend > 0.1 {
-            "increasing".to_string()
-        } else if trend < -0.1 {
-            "decreasing".to_string()
-        } else {
-            "stable".to_string()
-        };
-
         results.push(ForecastResult {
-            predicted_value: predicted_value.max(0.0).round(),
+            predicted_value,
             confidence,
-            lower_bound: (predicted_value - confidence_margin).max(0.0).round(),
-            upper_bound: (predicted_value + confidence_margin).round(),
-            trend: trend_direction,
+            lower_bound: predicted_value - confidence_margin,
+            upper_bound: predicted_value + confidence_margin,
+            confidence_level: 0.95,
+            trend: trend_label(trend),
         });
     }
 
     results
 }
 
-/// Calculate trend using linear regression
-fn calculate_trend(data: &[f64]) -> f64 {
+/// Calculate trend using linear regression (or the configured estimator)
+fn calculate_trend(data: &[f64], options: &PredictOptions) -> f64 {
     if data.len() < 2 {
         return 0.0;
     }
 
     let x: Vec<f64> = (0..data.len()).map(|i| i as f64).collect();
     let y: Vec<f64> = data.to_vec();
-    let result = linear_regression(x, y);
+    let result = match options.trend_method.as_deref() {
+        Some("theil_sen") => theil_sen(x, y),
+        Some("weighted") => {
+            let half_life = options.trend_half_life.filter(|h| *h > 0.0).unwrap_or(7.0);
+            let last = (data.len() - 1) as f64;
+            let weights = x.iter().map(|t| 0.5f64.powf((last - t) / half_life)).collect();
+            weighted_linear_regression(x, y, weights)
+        }
+        _ => linear_regression(x, y),
+    };
     result.slope
 }
 
@@ -225,15 +1682,10 @@ fn extract_seasonal_pattern(data: &[f64], season_length: usize) -> Vec<f64> {
     }
 
     // Calculate average for each position in the season
-    for i in 0..season_length {
-        let mut values = Vec::new();
-        let mut j = i;
-        while j < data.len() {
-            values.push(data[j]);
-            j += season_length;
-        }
+    for (i, factor) in seasonal.iter_mut().enumerate() {
+        let values: Vec<f64> = data.iter().skip(i).step_by(season_length).cloned().collect();
         if !values.is_empty() {
-            seasonal[i] = mean(values);
+            *factor = mean(values);
         }
     }
 
@@ -247,15 +1699,21 @@ fn extract_seasonal_pattern(data: &[f64], season_length: usize) -> Vec<f64> {
 }
 
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ForecastResult {
     pub predicted_value: f64,
     pub confidence: f64,
     pub lower_bound: f64,
     pub upper_bound: f64,
+    /// Coverage probability of `lower_bound..upper_bound` (e.g. 0.95)
+    pub confidence_level: f64,
     pub trend: String,
 }
 
+/// Orders one lab tech handles per regular shift at 100% efficiency
+pub(crate) const ORDERS_PER_TECH_DAY: f64 = 15.0;
+
 /// Calculate staffing requirements based on predicted order volume
 #[napi]
 pub fn calculate_staffing_needs(
@@ -263,8 +1721,7 @@ pub fn calculate_staffing_needs(
     complexity_score: f64,
     historical_efficiency: f64,
 ) -> StaffingResult {
-    // Base calculation: 1 lab tech can handle ~15 orders/day
-    let base_lab_techs = (order_volume / (15.0 * historical_efficiency)).ceil();
+    let base_lab_techs = (order_volume / (ORDERS_PER_TECH_DAY * historical_efficiency)).ceil();
     let complex_orders = order_volume * complexity_score;
     let engineers = (complex_orders / 25.0).ceil();
 
@@ -350,61 +1807,126 @@ pub struct AccuracyMetrics {
     pub accuracy: f64,
 }
 
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SurgeOptions {
+    /// Ratio to the mean above which a period is a surge (default 1.2)
+    pub surge_threshold: Option<f64>,
+    /// Also report dips (default false)
+    pub detect_dips: Option<bool>,
+    /// Ratio to the mean below which a period is a dip (default 0.8)
+    pub dip_threshold: Option<f64>,
+    /// Surge ratios above which severity is "medium" / "high" (defaults 1.35 / 1.5)
+    pub surge_medium_ratio: Option<f64>,
+    pub surge_high_ratio: Option<f64>,
+    /// Dip ratios below which severity is "medium" / "high" (defaults 0.65 / 0.5)
+    pub dip_medium_ratio: Option<f64>,
+    pub dip_high_ratio: Option<f64>,
+}
+
 /// Identify surge periods in forecast data
 #[napi]
 pub fn identify_surges(
     predicted_values: Vec<f64>,
     dates: Vec<String>,
     threshold: f64,
+) -> Vec<SurgePeriod> {
+    let options = SurgeOptions {
+        surge_threshold: Some(threshold),
+        ..Default::default()
+    };
+    identify_surges_with_options(predicted_values, dates, Some(options))
+}
+
+/// Identify surge and dip periods in forecast data
+///
+/// Consecutive periods beyond the same threshold are merged into one
+/// period; `peak_value` is the highest value of a surge and the lowest value
+/// of a dip, and severity follows the most extreme ratio in the period.
+///
+/// # Arguments
+/// * `predicted_values` - Forecast values
+/// * `dates` - Labels aligned with `predicted_values`
+/// * `options` - Thresholds, dip detection and severity cutoffs
+#[napi]
+pub fn identify_surges_with_options(
+    predicted_values: Vec<f64>,
+    dates: Vec<String>,
+    options: Option<SurgeOptions>,
 ) -> Vec<SurgePeriod> {
     if predicted_values.is_empty() || predicted_values.len() != dates.len() {
         return vec![];
     }
 
+    let options = options.unwrap_or_default();
+    let surge_threshold = options.surge_threshold.unwrap_or(1.2);
+    let dip_threshold = if options.detect_dips.unwrap_or(false) {
+        options.dip_threshold.unwrap_or(0.8)
+    } else {
+        f64::NEG_INFINITY
+    };
+
     let avg_volume = mean(predicted_values.clone());
-    let mut surges = Vec::new();
-    let mut current_surge: Option<SurgePeriod> = None;
+    let mut periods = Vec::new();
+    let mut current: Option<SurgePeriod> = None;
 
     for (i, &value) in predicted_values.iter().enumerate() {
         let ratio = if avg_volume != 0.0 { value / avg_volume } else { 1.0 };
-        let is_high_volume = ratio > threshold;
+        let direction = if ratio > surge_threshold {
+            Some("surge")
+        } else if ratio < dip_threshold {
+            Some("dip")
+        } else {
+            None
+        };
 
-        match (&mut current_surge, is_high_volume) {
-            (None, true) => {
-                current_surge = Some(SurgePeriod {
+        match (&mut current, direction) {
+            (Some(period), Some(d)) if period.direction == d => {
+                period.end_date = dates[i].clone();
+                let more_extreme = if d == "surge" { value > period.peak_value } else { value < period.peak_value };
+                if more_extreme {
+                    period.peak_value = value;
+                    period.severity = determine_severity(ratio, d, &options);
+                }
+            }
+            (_, direction) => {
+                if let Some(period) = current.take() {
+                    periods.push(period);
+                }
+                current = direction.map(|d| SurgePeriod {
                     start_date: dates[i].clone(),
                     end_date: dates[i].clone(),
                     peak_value: value,
-                    severity: determine_severity(ratio),
+                    severity: determine_severity(ratio, d, &options),
+                    direction: d.to_string(),
                 });
             }
-            (Some(ref mut surge), true) => {
-                surge.end_date = dates[i].clone();
-                if value > surge.peak_value {
-                    surge.peak_value = value;
-                    surge.severity = determine_severity(ratio);
-                }
-            }
-            (Some(surge), false) => {
-                surges.push(surge.clone());
-                current_surge = None;
-            }
-            (None, false) => {}
         }
     }
 
-    // Close any open surge
-    if let Some(surge) = current_surge {
-        surges.push(surge);
+    // Close any open period
+    if let Some(period) = current {
+        periods.push(period);
     }
 
-    surges
+    periods
 }
 
-fn determine_severity(ratio: f64) -> String {
-    if ratio > 1.5 {
+fn determine_severity(ratio: f64, direction: &str, options: &SurgeOptions) -> String {
+    let (high, medium) = if direction == "dip" {
+        (
+            ratio < options.dip_high_ratio.unwrap_or(0.5),
+            ratio < options.dip_medium_ratio.unwrap_or(0.65),
+        )
+    } else {
+        (
+            ratio > options.surge_high_ratio.unwrap_or(1.5),
+            ratio > options.surge_medium_ratio.unwrap_or(1.35),
+        )
+    };
+    if high {
         "high".to_string()
-    } else if ratio > 1.35 {
+    } else if medium {
         "medium".to_string()
     } else {
         "low".to_string()
@@ -416,8 +1938,11 @@ fn determine_severity(ratio: f64) -> String {
 pub struct SurgePeriod {
     pub start_date: String,
     pub end_date: String,
+    /// Highest value of a surge, lowest value of a dip
     pub peak_value: f64,
     pub severity: String,
+    /// "surge" or "dip"
+    pub direction: String,
 }
 
 #[cfg(test)]
@@ -440,6 +1965,566 @@ mod tests {
         assert!(predictions.iter().all(|p| p.predicted_value >= 0.0));
     }
 
+    #[test]
+    fn test_predict_next_allows_negative_values() {
+        // Net change drifting below zero
+        let data: Vec<f64> = (0..20).map(|i| 5.0 - i as f64).collect();
+        let signed = predict_next_with_options(data.clone(), 3, 7, None).unwrap();
+        assert!(signed.iter().all(|p| p.predicted_value < 0.0));
+        assert!(signed.iter().all(|p| p.lower_bound < p.predicted_value));
+
+        let options = PredictOptions {
+            non_negative: Some(true),
+            ..Default::default()
+        };
+        let clamped = predict_next_with_options(data.clone(), 3, 7, Some(options)).unwrap();
+        assert!(clamped.iter().all(|p| p.predicted_value == 0.0 && p.lower_bound == 0.0));
+        assert!(predict_next(data, 3, 7).iter().all(|p| p.predicted_value == 0.0));
+    }
+
+    #[test]
+    fn test_predict_next_direct_strategy() {
+        let mut rng = crate::sampling::Rng::new(3);
+        let data: Vec<f64> = (0..120)
+            .map(|i| 100.0 + 20.0 * ((i % 7) as f64 - 3.0).abs() + i as f64 * 0.2 + rng.next_f64() - 0.5)
+            .collect();
+        let options = PredictOptions {
+            strategy: Some("direct".to_string()),
+            ..Default::default()
+        };
+        let direct = predict_next_with_options(data.clone(), 21, 7, Some(options)).unwrap();
+        assert_eq!(direct.len(), 21);
+        for (h, forecast) in direct.iter().enumerate() {
+            let t = 120 + h;
+            let actual = 100.0 + 20.0 * ((t % 7) as f64 - 3.0).abs() + t as f64 * 0.2;
+            assert!((forecast.predicted_value - actual).abs() < 3.0);
+            assert!(forecast.lower_bound <= forecast.predicted_value);
+        }
+
+        // Too little history for the per-step regressions falls back to recursive
+        let short = predict_next_with_options(
+            data[..10].to_vec(),
+            3,
+            7,
+            Some(PredictOptions {
+                strategy: Some("direct".to_string()),
+                ..Default::default()
+            }),
+        ).unwrap();
+        assert_eq!(short.len(), 3);
+
+        let typo = PredictOptions {
+            strategy: Some("dirct".to_string()),
+            ..Default::default()
+        };
+        assert!(predict_next_with_options(data, 3, 7, Some(typo)).is_err());
+    }
+
+    #[test]
+    fn test_confidence_level() {
+        let data: Vec<f64> = (0..42).map(|t| 50.0 + [9.0, 4.0, 0.0, -2.0, -3.0, -4.0, -4.0][t % 7] + (t % 5) as f64).collect();
+        let at = |level: Option<f64>| {
+            predict_next_with_options(data.clone(), 7, 7, Some(PredictOptions {
+                confidence_level: level,
+                ..Default::default()
+            })).unwrap()
+        };
+        let default = at(None);
+        let (narrow, wide) = (at(Some(0.8)), at(Some(0.99)));
+        assert!(default.iter().all(|f| f.confidence_level == 0.95));
+        assert!(narrow.iter().all(|f| f.confidence_level == 0.8));
+        assert_eq!(at(Some(95.0))[0].confidence_level, 0.95);
+        assert_eq!(at(Some(f64::NAN))[0].upper_bound, default[0].upper_bound);
+        assert_eq!((interval_z(0.95), interval_z(1.5), interval_z(f64::NAN)), (Z_95, Z_95, Z_95));
+        for ((d, n), w) in default.iter().zip(&narrow).zip(&wide) {
+            let half = |f: &ForecastResult| f.upper_bound - f.predicted_value;
+            assert_eq!(d.predicted_value, n.predicted_value);
+            assert!((half(n) / half(d) - 1.2816 / 1.96).abs() < 1e-3);
+            assert!((half(w) / half(d) - 2.5758 / 1.96).abs() < 1e-3);
+            // The same forecast error distribution, so the same confidence
+            assert!((d.confidence - w.confidence).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_confidence_methods() {
+        let data: Vec<f64> = (0..42).map(|i| 100.0 + (i % 7) as f64 * 5.0 + (i % 3) as f64 * 4.0).collect();
+        let interval = predict_next_with_options(data.clone(), 6, 7, None).unwrap();
+        assert!(interval.windows(2).all(|w| w[1].confidence <= w[0].confidence + 1e-12));
+        assert!(interval.iter().all(|f| f.confidence > 0.0 && f.confidence <= 1.0));
+
+        let strict = predict_next_with_options(data.clone(), 6, 7, Some(PredictOptions {
+            confidence_tolerance: Some(0.01),
+            ..Default::default()
+        })).unwrap();
+        assert!(strict[0].confidence < interval[0].confidence);
+
+        let linear = predict_next_with_options(data, 6, 7, Some(PredictOptions {
+            confidence_method: Some("linear".to_string()),
+            confidence_decay: Some(0.1),
+            confidence_floor: Some(0.65),
+            ..Default::default()
+        })).unwrap();
+        let expected = [1.0, 0.9, 0.8, 0.7, 0.65, 0.65];
+        assert!(linear.iter().zip(expected).all(|(f, c)| (f.confidence - c).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_additive_holt_winters() {
+        // Weekly pattern with closed (zero) days on a slowly rising base
+        let offsets = [-40.0, 10.0, 15.0, 10.0, 5.0, 0.0, 0.0];
+        let data: Vec<f64> = (0..70).map(|t| (40.0 + 0.2 * t as f64 + offsets[t % 7]).max(0.0)).collect();
+
+        let default = holt_winters_with_options(data.clone(), 0.3, 0.1, 0.3, 7, None).unwrap();
+        assert_eq!(default, holt_winters(data.clone(), 0.3, 0.1, 0.3, 7));
+
+        let additive = holt_winters_with_options(data.clone(), 0.3, 0.1, 0.3, 7, Some(HoltWintersOptions {
+            seasonality_type: Some("additive".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let unknown = HoltWintersOptions { seasonality_type: Some("Additive".to_string()), ..Default::default() };
+        assert!(holt_winters_with_options(data.clone(), 0.3, 0.1, 0.3, 7, Some(unknown)).is_err());
+        let mae = |fitted: &[f64]| fitted[42..].iter().zip(&data[42..]).map(|(f, y)| (f - y).abs()).sum::<f64>() / 28.0;
+        assert!(mae(&additive) < 1.5);
+        assert!(mae(&additive) < mae(&default));
+
+        let state = fit_holt_winters(&data, 0.3, 0.1, 0.3, 7, Seasonality::Additive, 1.0);
+        let forecast = state.forecast(data.len(), 7);
+        assert!(forecast.iter().enumerate().all(|(h, f)| (f - (40.0 + 0.2 * (70 + h) as f64 + offsets[h])).abs() < 4.0));
+
+        // Same method, same forecasts as the registry's "holt_winters"
+        let registry = fit_method("holt_winters", &data, 7, 7).unwrap();
+        let param = |name: &str| registry.params.iter().find(|p| p.0 == name).unwrap().1;
+        let state = fit_holt_winters(&data, param("alpha"), param("beta"), param("gamma"), 7, Seasonality::Additive, 1.0);
+        assert_eq!(state.forecast(data.len(), 7), registry.forecast);
+    }
+
+    #[test]
+    fn test_damped_holt_winters() {
+        let data: Vec<f64> = (0..56).map(|t| 100.0 + 2.0 * t as f64 + [5.0, -5.0, 0.0, 3.0, -3.0, 2.0, -2.0][t % 7]).collect();
+        let undamped = fit_holt_winters(&data, 0.3, 0.2, 0.1, 7, Seasonality::Additive, 1.0);
+        let damped = fit_holt_winters(&data, 0.3, 0.2, 0.1, 7, Seasonality::Additive, 0.9);
+        let (far, near) = (undamped.forecast(56, 365), damped.forecast(56, 365));
+        // The damped trend adds at most trend * phi / (1 - phi) however far out
+        assert!(near[364] < far[364] - 500.0);
+        assert!(near[364] - near[357] < 1.0);
+
+        for phi in [0.0, 1.5, f64::NAN] {
+            let options = HoltWintersOptions { damping: Some(phi), ..Default::default() };
+            assert!(holt_winters_with_options(data.clone(), 0.3, 0.1, 0.1, 7, Some(options)).is_err());
+        }
+        let options = HoltWintersOptions { damping: Some(1.0), ..Default::default() };
+        assert_eq!(holt_winters_with_options(data.clone(), 0.3, 0.1, 0.1, 7, Some(options)).unwrap(), holt_winters(data, 0.3, 0.1, 0.1, 7));
+    }
+
+    #[test]
+    fn test_robust_trend_method() {
+        // Rising series with one early spike
+        let mut data: Vec<f64> = (0..10).map(|i| 10.0 + i as f64).collect();
+        data[2] = 200.0;
+        assert_eq!(predict_next_with_options(data.clone(), 2, 7, None).unwrap()[0].trend, "decreasing");
+
+        let robust = predict_next_with_options(data, 2, 7, Some(PredictOptions {
+            trend_method: Some("theil_sen".to_string()),
+            ..Default::default()
+        })).unwrap();
+        assert_eq!(robust[0].trend, "increasing");
+    }
+
+    #[test]
+    fn test_trend_and_seasonal_strength() {
+        let noise = |t: usize| ((t * 37) % 11) as f64 / 11.0 - 0.5;
+        let seasonal: Vec<f64> = (0..84).map(|t| 100.0 + 20.0 * (t as f64 * std::f64::consts::TAU / 7.0).sin() + noise(t)).collect();
+        let trended: Vec<f64> = (0..84).map(|t| 100.0 + 2.0 * t as f64 + 3.0 * noise(t)).collect();
+
+        assert!(seasonal_strength(seasonal.clone(), 7) > 0.95);
+        assert!(seasonal_strength(trended.clone(), 7) < 0.3);
+        assert!(trend_strength(trended.clone(), None) > 0.95);
+        assert!(trend_strength(seasonal.clone(), Some(7)) < 0.5);
+        assert!(trend_strength(trended, Some(7)) > 0.95);
+        assert_eq!(seasonal_strength(seasonal, 50), 0.0);
+
+        // Even periods use the 2 x m moving average, which cancels the cycle exactly
+        let ma = centered_moving_average(&[1.0, 3.0, 1.0, 3.0, 1.0, 3.0], 2);
+        assert_eq!(ma, vec![None, Some(2.0), Some(2.0), Some(2.0), Some(2.0), None]);
+    }
+
+    #[test]
+    fn test_weighted_trend_method() {
+        // Long decline followed by a recent recovery
+        let data: Vec<f64> = (0..12).map(|i| if i < 9 { 100.0 - 5.0 * i as f64 } else { 60.0 + 10.0 * (i - 8) as f64 }).collect();
+        assert_eq!(predict_next_with_options(data.clone(), 1, 7, None).unwrap()[0].trend, "decreasing");
+
+        let weighted = predict_next_with_options(data, 1, 7, Some(PredictOptions {
+            trend_method: Some("weighted".to_string()),
+            trend_half_life: Some(1.0),
+            ..Default::default()
+        })).unwrap();
+        assert_eq!(weighted[0].trend, "increasing");
+    }
+
+    #[test]
+    fn test_predict_with_regressors() {
+        // Weekly demand with promotions every 10th day adding 30 units
+        let promo = |t: usize| if t % 10 == 3 { 1.0 } else { 0.0 };
+        let data: Vec<f64> = (0..140).map(|t| 100.0 + [5.0, 0.0, -3.0, 2.0, 8.0, -6.0, -6.0][t % 7] + 30.0 * promo(t) + ((t * 7919) % 5) as f64 * 0.5).collect();
+        let regressors: Vec<Vec<f64>> = (0..147).map(|t| vec![promo(t)]).collect();
+        let options = PredictOptions {
+            regressors: Some(regressors),
+            ..Default::default()
+        };
+        let forecast = predict_next_with_options(data.clone(), 7, 7, Some(options)).unwrap();
+        assert_eq!(forecast.len(), 7);
+        // Day 143 is a promotion day
+        let lift = forecast[3].predicted_value - (forecast[2].predicted_value + forecast[4].predicted_value) / 2.0;
+        assert!(lift > 20.0, "{:?}", forecast);
+
+        // Rows that do not cover the history and horizon are an error
+        let short = PredictOptions {
+            regressors: Some(vec![vec![0.0]; 10]),
+            ..Default::default()
+        };
+        assert!(predict_next_with_options(data, 7, 7, Some(short)).is_err());
+    }
+
+    #[test]
+    fn test_predict_with_holidays() {
+        // Closed every 20th day, with double demand the day after
+        let weekly = [100.0, 110.0, 105.0, 95.0, 120.0, 80.0, 90.0];
+        let data: Vec<f64> = (0..140)
+            .map(|t| match t % 20 {
+                5 => 0.0,
+                6 => 2.0 * weekly[t % 7],
+                _ => weekly[t % 7],
+            })
+            .collect();
+        let holidays: Vec<u32> = (0..160).filter(|t| t % 20 == 5).collect();
+        let options = PredictOptions {
+            holidays: Some(holidays),
+            ..Default::default()
+        };
+        let forecast = predict_next_with_options(data.clone(), 10, 7, Some(options)).unwrap();
+        // The adjusted history is the clean weekly series, so ordinary steps match its forecast
+        let clean = predict_next_with_options((0..140).map(|t| weekly[t % 7]).collect(), 10, 7, None).unwrap();
+        // Step 6 (period 145) is a closure and step 7 the catch-up
+        assert_eq!(forecast[5].predicted_value, 0.0);
+        assert!((forecast[6].predicted_value - 2.0 * clean[6].predicted_value).abs() < 1e-9);
+        for i in [0, 1, 2, 3, 4, 7, 8, 9] {
+            assert!((forecast[i].predicted_value - clean[i].predicted_value).abs() < 1e-9);
+        }
+
+        let (adjusted, effects) = calendar_adjust(&data, &[5, 25].into_iter().collect(), 1, 7);
+        assert_eq!(adjusted[5], weekly[5]);
+        assert_eq!(adjusted[26], weekly[26 % 7]);
+        assert_eq!(effects, vec![0.0, 2.0]);
+
+        // An oversized catch-up window is bounded by the history
+        let (_, effects) = calendar_adjust(&data, &HashSet::new(), u32::MAX as usize, 7);
+        assert_eq!(effects.len(), data.len() + 1);
+        let long = PredictOptions { holidays: Some(vec![145]), post_holiday_periods: Some(u32::MAX), ..Default::default() };
+        assert_eq!(predict_next_with_options(data, 10, 7, Some(long)).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_multi_seasonal_forecast() {
+        // Hourly orders: a daily shape, busier weekdays and a slow upward drift
+        let mut rng = crate::sampling::Rng::new(11);
+        let daily = |hour: usize| 20.0 * (std::f64::consts::TAU * hour as f64 / 24.0).sin().max(0.0);
+        let weekday = [10.0, 12.0, 12.0, 11.0, 9.0, -25.0, -29.0];
+        let hourly = |t: usize| 50.0 + 0.01 * t as f64 + daily(t % 24) + weekday[(t / 24) % 7] * (1.0 + daily(t % 24) / 20.0);
+        let data: Vec<f64> = (0..24 * 7 * 6).map(|t| hourly(t) + rng.next_f64() - 0.5).collect();
+        let n = data.len();
+
+        let result = multi_seasonal_forecast(data.clone(), vec![168, 24], 48, None);
+        assert_eq!(result.season_lengths, vec![24, 168]);
+        assert_eq!(result.seasonal_components[1].len(), 168);
+        let mae = |predictions: &[f64]| predictions.iter().enumerate().map(|(h, p)| (p - hourly(n + h)).abs()).sum::<f64>() / predictions.len() as f64;
+        assert!(mae(&result.predictions) < 2.0, "{}", mae(&result.predictions));
+        assert!(result.upper_bounds[47] - result.lower_bounds[47] > result.upper_bounds[0] - result.lower_bounds[0]);
+
+        // A single daily cycle misses the weekend drop
+        let daily_only = multi_seasonal_forecast(data.clone(), vec![24], 48, None);
+        assert!(mae(&daily_only.predictions) > 2.0 * mae(&result.predictions));
+
+        // Fixed weights, and periods without two cycles of history are dropped
+        let fixed = MultiSeasonalOptions {
+            alpha: Some(0.1),
+            beta: Some(0.0),
+            gammas: Some(vec![0.05]),
+        };
+        let short = multi_seasonal_forecast(data[..300].to_vec(), vec![24, 168], 5, Some(fixed));
+        assert_eq!((short.season_lengths.clone(), short.alpha, short.gammas.clone()), (vec![24], 0.1, vec![0.05]));
+        assert!(multi_seasonal_forecast(vec![1.0, 2.0], vec![24], 3, None).predictions.is_empty());
+    }
+
+    #[test]
+    fn test_ensemble_forecast() {
+        let mut rng = crate::sampling::Rng::new(11);
+        let data: Vec<f64> = (0..84)
+            .map(|t| 100.0 + 0.5 * t as f64 + [12.0, 5.0, 0.0, -3.0, -4.0, -5.0, -5.0][t % 7] + rng.next_f64() * 4.0)
+            .collect();
+
+        let mean = ensemble_forecast(data.clone(), 7, None, None).unwrap();
+        assert_eq!(mean.season_length, 7);
+        assert_eq!(mean.combination, "mean");
+        let names: Vec<&str> = mean.members.iter().map(|m| m.model.as_str()).collect();
+        assert_eq!(names, vec!["ses", "holt_winters", "theta", "naive"]);
+        assert!(mean.members.iter().all(|m| (m.weight - 0.25).abs() < 1e-12));
+        let average = mean.members.iter().map(|m| m.predictions[3]).sum::<f64>() / 4.0;
+        assert!((mean.forecasts[3].predicted_value - average).abs() < 1e-9);
+        assert!(mean.forecasts.iter().all(|f| f.lower_bound < f.predicted_value && f.predicted_value < f.upper_bound));
+
+        // Error weighting favours the seasonal methods over the flat ones
+        let weighted = ensemble_forecast(data.clone(), 7, None, Some(EnsembleOptions {
+            combination: Some("inverse_error".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let weight = |model: &str| weighted.members.iter().find(|m| m.model == model).unwrap().weight;
+        assert!(weight("holt_winters") > weight("naive"));
+        assert!((weighted.members.iter().map(|m| m.weight).sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let models = vec!["naive".to_string(), "drift".to_string(), "linear_trend".to_string()];
+        let median = ensemble_forecast(data.clone(), 2, Some(models.clone()), Some(EnsembleOptions {
+            combination: Some("median".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let mut first: Vec<f64> = median.members.iter().map(|m| m.predictions[0]).collect();
+        first.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(median.forecasts[0].predicted_value, first[1]);
+
+        // Fixed weights; methods that cannot be fitted drop out
+        let fixed = ensemble_forecast(data[..10].to_vec(), 2, Some(vec!["holt_winters".to_string(), "naive".to_string(), "drift".to_string()]), Some(EnsembleOptions {
+            weights: Some(vec![5.0, 3.0, 1.0]),
+            season_length: Some(7),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(fixed.combination, "weights");
+        assert_eq!(fixed.members.iter().map(|m| m.weight).collect::<Vec<f64>>(), vec![0.75, 0.25]);
+        assert!(ensemble_forecast(vec![1.0], 2, None, None).unwrap().forecasts.is_empty());
+
+        // Forecasters outside the smoothing family combine too
+        let arima = ensemble_forecast(data.clone(), 7, Some(vec!["auto_arima".to_string(), "prophet_like".to_string()]), None).unwrap();
+        assert_eq!(arima.members.len(), 2);
+
+        assert!(ensemble_forecast(data.clone(), 7, Some(vec!["ses".to_string(), "sess".to_string()]), None).is_err());
+        let short_weights = EnsembleOptions { weights: Some(vec![1.0]), ..Default::default() };
+        assert!(ensemble_forecast(data, 7, Some(models), Some(short_weights)).is_err());
+    }
+
+    #[test]
+    fn test_seasonal_regression_forecast() {
+        // Three years of daily volume with a smooth yearly cycle
+        let mut rng = crate::sampling::Rng::new(17);
+        let yearly = |t: usize| 200.0 + 0.05 * t as f64 + 40.0 * (std::f64::consts::TAU * t as f64 / 365.25).sin();
+        let data: Vec<f64> = (0..1096).map(|t| yearly(t) + (rng.next_f64() - 0.5) * 60.0).collect();
+        let mae = |forecast: &[ForecastResult]| forecast.iter().enumerate().map(|(h, f)| (f.predicted_value - yearly(1096 + h)).abs()).sum::<f64>() / forecast.len() as f64;
+
+        let fourier = seasonal_regression_forecast(data.clone(), 90, 365.25, None);
+        assert_eq!(fourier.len(), 90);
+        assert!(mae(&fourier) < 4.0, "{}", mae(&fourier));
+        assert_eq!(fourier[0].trend, "stable");
+        assert!(fourier.iter().all(|f| f.lower_bound < f.predicted_value && f.predicted_value < f.upper_bound));
+        // Confidence follows the band width, like every other forecaster
+        assert_eq!(fourier[0].confidence, step_confidence(&PredictOptions::default(), 0, &fourier[0]));
+
+        let index_options = SeasonalRegressionOptions {
+            seasonality: Some("index".to_string()),
+            ..Default::default()
+        };
+        let index = seasonal_regression_forecast(data.clone(), 90, 365.25, Some(index_options));
+        assert!(mae(&index) > 2.0 * mae(&fourier));
+        // Wider intervals from the many index effects
+        assert!(index[0].upper_bound - index[0].lower_bound > fourier[0].upper_bound - fourier[0].lower_bound);
+
+        let fixed = SeasonalRegressionOptions {
+            fourier_order: Some(2),
+            trend: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(seasonal_regression_forecast(data, 5, 365.25, Some(fixed)).len(), 5);
+        assert!(seasonal_regression_forecast(vec![1.0, f64::NAN], 5, 7.0, None).is_empty());
+    }
+
+    #[test]
+    fn test_predict_next_rounding_options() {
+        let data: Vec<f64> = (0..30).map(|i| 2.0 + (i % 7) as f64 * 0.13).collect();
+        let legacy = predict_next(data.clone(), 3, 7);
+        assert!(legacy.iter().all(|p| p.predicted_value.fract() == 0.0));
+
+        let fractional = predict_next_with_options(data.clone(), 3, 7, None).unwrap();
+        assert!(fractional.iter().any(|p| p.predicted_value.fract() != 0.0));
+
+        let options = PredictOptions {
+            decimal_places: Some(2),
+            ..Default::default()
+        };
+        let two_places = predict_next_with_options(data, 3, 7, Some(options)).unwrap();
+        assert!(two_places.iter().all(|p| ((p.predicted_value * 100.0).round() - p.predicted_value * 100.0).abs() < 1e-9));
+        assert!((two_places[0].predicted_value - fractional[0].predicted_value).abs() <= 0.005 + 1e-12);
+    }
+
+    #[test]
+    fn test_predict_next_floor_and_cap() {
+        // A utilization ratio creeping toward 100%, and a signed margin below zero
+        let utilization: Vec<f64> = (0..28).map(|t| 0.9 + 0.003 * t as f64 + 0.02 * (t % 7) as f64).collect();
+        let capped = predict_next_with_options(utilization.clone(), 7, 7, Some(PredictOptions {
+            cap: Some(1.0),
+            ..Default::default()
+        })).unwrap();
+        let free = predict_next_with_options(utilization, 7, 7, None).unwrap();
+        assert!(free.iter().any(|f| f.upper_bound > 1.0));
+        assert!(capped.iter().all(|f| f.predicted_value <= 1.0 && f.upper_bound <= 1.0));
+        assert!(capped.iter().any(|f| f.predicted_value.fract() != 0.0));
+
+        let margin: Vec<f64> = (0..28).map(|t| -0.4 - 0.01 * t as f64 + 0.05 * (t % 7) as f64).collect();
+        let signed = predict_next_with_options(margin.clone(), 3, 7, Some(PredictOptions {
+            floor: Some(-1.0),
+            ..Default::default()
+        })).unwrap();
+        assert!(signed.iter().all(|f| f.predicted_value < 0.0 && f.lower_bound >= -1.0));
+        let clamped = predict_next_with_options(margin, 3, 7, Some(PredictOptions {
+            floor: Some(-1.0),
+            non_negative: Some(true),
+            ..Default::default()
+        })).unwrap();
+        assert!(clamped.iter().all(|f| f.predicted_value == 0.0 && f.lower_bound == 0.0));
+
+        // No value satisfies both, including a negative cap under `non_negative`
+        for (floor, non_negative) in [(Some(2.0), None), (None, Some(true))] {
+            let crossed = PredictOptions {
+                floor,
+                cap: Some(-0.5),
+                non_negative,
+                ..Default::default()
+            };
+            assert!(predict_next_with_options(vec![1.0; 20], 3, 7, Some(crossed)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_auto_forecast() {
+        let weekly: Vec<f64> = (0..84).map(|t| 200.0 + [40.0, 10.0, 0.0, -5.0, -10.0, -15.0, -20.0][t % 7] + (t % 3) as f64).collect();
+        let bogus = AutoForecastOptions { models: Some(vec!["ses".to_string(), "bogus".to_string()]), ..Default::default() };
+        assert!(auto_forecast(weekly.clone(), 14, Some(bogus)).is_err());
+        let result = auto_forecast(weekly, 14, None).unwrap();
+        assert_eq!(result.season_length, 7);
+        assert_eq!(result.demand_pattern, "smooth");
+        assert!(result.model == "holt_winters" || result.model == "seasonal_naive");
+        assert_eq!(result.forecasts.len(), 14);
+        assert!((result.forecasts[7].predicted_value - result.forecasts[0].predicted_value).abs() < 5.0);
+
+        let sparse: Vec<f64> = (0..60).map(|t| if t % 5 == 0 { 10.0 + (t % 3) as f64 } else { 0.0 }).collect();
+        let intermittent = auto_forecast(sparse, 4, None).unwrap();
+        assert_eq!(intermittent.demand_pattern, "intermittent");
+        assert_eq!(intermittent.candidates.len(), 4);
+        assert!(intermittent.forecasts.iter().all(|f| f.predicted_value > 0.0));
+        assert_eq!(intermittent.fit.fitted.len(), 60);
+    }
+
+    #[test]
+    fn test_croston_forecast() {
+        // An order of about 6 lenses every 4 days
+        let data: Vec<f64> = (0..120).map(|t| if t % 4 == 1 { 5.0 + (t % 3) as f64 } else { 0.0 }).collect();
+        let croston = croston_forecast(data.clone(), 5, None);
+        assert_eq!(croston.forecast.len(), 5);
+        assert!((croston.demand_interval - 4.0).abs() < 0.1);
+        assert!((croston.demand_size - 6.0).abs() < 0.6);
+        assert!((croston.forecast[0] - 1.5).abs() < 0.2);
+        assert_eq!(croston.fitted.len(), 120);
+        assert!(croston.fitted[0].is_nan());
+
+        let options = CrostonOptions {
+            variant: Some("sba".to_string()),
+            alpha: Some(0.2),
+        };
+        let sba = croston_forecast(data, 5, Some(options));
+        assert_eq!(sba.alpha, 0.2);
+        assert!((sba.forecast[0] - 0.9 * sba.demand_size / sba.demand_interval).abs() < 1e-12);
+
+        assert_eq!(croston_forecast(vec![0.0; 10], 3, None).forecast, vec![0.0; 3]);
+        assert!(croston_forecast(vec![1.0, f64::NAN], 3, None).forecast.is_empty());
+    }
+
+    #[test]
+    fn test_classify_demand() {
+        let smooth = classify_demand((0..30).map(|t| 20.0 + (t % 4) as f64).collect());
+        assert_eq!((smooth.demand_pattern.as_str(), smooth.recommended_method.as_str()), ("smooth", "ses"));
+        assert_eq!(smooth.average_demand_interval, 1.0);
+
+        let sparse: Vec<f64> = (0..40).map(|t| if t % 4 == 0 { 6.0 + (t % 3) as f64 } else { 0.0 }).collect();
+        let intermittent = classify_demand(sparse);
+        assert_eq!((intermittent.demand_pattern.as_str(), intermittent.recommended_method.as_str()), ("intermittent", "croston"));
+        assert_eq!(intermittent.non_zero_periods, 10);
+        assert_eq!(intermittent.average_demand_interval, 4.0);
+
+        let spiky: Vec<f64> = (0..40).map(|t| if t % 5 == 0 { if t % 10 == 0 { 50.0 } else { 2.0 } } else { 0.0 }).collect();
+        let lumpy = classify_demand(spiky);
+        assert_eq!((lumpy.demand_pattern.as_str(), lumpy.recommended_method.as_str()), ("lumpy", "sba"));
+        assert!(lumpy.cv_squared >= 0.49);
+
+        let erratic = classify_demand((0..20).map(|t| if t % 2 == 0 { 1.0 } else { 40.0 }).collect());
+        assert_eq!(erratic.demand_pattern, "erratic");
+        assert_eq!(classify_demand(vec![0.0; 5]).recommended_method, "naive");
+    }
+
+    #[test]
+    fn test_fit_forecaster() {
+        let data: Vec<f64> = (0..28).map(|i| 50.0 + (i % 7) as f64 * 3.0).collect();
+
+        let naive = fit_forecaster(data.clone(), "naive".to_string(), 7, None).unwrap();
+        assert!(naive.fitted[0].is_nan());
+        assert_eq!(naive.residuals[1], 3.0);
+
+        let seasonal = fit_forecaster(data.clone(), "seasonal_naive".to_string(), 7, None).unwrap();
+        assert!(seasonal.residuals[7..].iter().all(|&r| r == 0.0));
+
+        let params = vec![ParamValue { name: "alpha".to_string(), value: 0.5 }];
+        let hw = fit_forecaster(data.clone(), "holt_winters".to_string(), 7, Some(params)).unwrap();
+        assert_eq!(hw.fitted, holt_winters(data.clone(), 0.5, 0.1, 0.1, 7));
+        assert_eq!(hw.params.len(), 3);
+
+        let legacy = fit_forecaster(data[..10].to_vec(), "predict_next".to_string(), 7, None).unwrap();
+        assert_eq!(legacy.fitted.len(), 10);
+
+        // The forecasters outside the smoothing family are fitted too
+        for method in ["auto_arima", "prophet_like", "seasonal_regression"] {
+            let fit = fit_forecaster(data.clone(), method.to_string(), 7, None).unwrap();
+            assert_eq!(fit.fitted.len(), data.len(), "{}", method);
+        }
+
+        assert!(fit_forecaster(data.clone(), "bogus".to_string(), 7, None).is_err());
+        assert!(fit_forecaster(data.clone(), "multi_seasonal".to_string(), 7, None).is_err());
+        assert!(fit_forecaster(data, "ensemble".to_string(), 7, None).is_err());
+    }
+
+    #[test]
+    fn test_identify_surges_and_dips() {
+        let values = vec![100.0, 100.0, 160.0, 150.0, 100.0, 40.0, 70.0, 100.0];
+        let dates: Vec<String> = (1..=8).map(|d| format!("d{}", d)).collect();
+
+        let surges = identify_surges(values.clone(), dates.clone(), 1.2);
+        assert_eq!(surges.len(), 1);
+        assert_eq!((surges[0].start_date.as_str(), surges[0].end_date.as_str()), ("d3", "d4"));
+        assert_eq!(surges[0].direction, "surge");
+
+        let options = SurgeOptions {
+            detect_dips: Some(true),
+            surge_high_ratio: Some(1.8),
+            ..Default::default()
+        };
+        let periods = identify_surges_with_options(values, dates, Some(options));
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0].severity, "medium");
+        assert_eq!(periods[1].direction, "dip");
+        assert_eq!(periods[1].peak_value, 40.0);
+        assert_eq!(periods[1].severity, "high");
+        assert_eq!(periods[1].end_date, "d7");
+    }
+
     #[test]
     fn test_calculate_accuracy() {
         let predictions = vec![100.0, 105.0, 98.0, 102.0, 99.0];
@@ -0,0 +1,322 @@
+//! Random Forests
+//!
+//! Bagged ensembles of decorrelated CART trees, built in parallel:
+//! - Regression (mean of tree predictions)
+//! - Classification (mean of tree class probabilities)
+//! - Out-of-bag error estimates and feature importances
+
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::check_shape;
+use crate::sampling::Rng;
+use crate::tree::{normalize_importances, Criterion, Tree, TreeParams};
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct RandomForestOptions {
+    /// Number of trees (default 100)
+    pub n_trees: Option<u32>,
+    /// Maximum tree depth (default 16)
+    pub max_depth: Option<u32>,
+    /// Minimum rows per leaf (default 1)
+    pub min_samples_leaf: Option<u32>,
+    /// Features considered per split (default sqrt(p) for classification, p/3 for regression)
+    pub max_features: Option<u32>,
+    pub seed: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RandomForestFitResult {
+    pub n_trees: u32,
+    /// Out-of-bag mean squared error (regression) or misclassification rate (classification)
+    pub oob_error: f64,
+    /// Out-of-bag R² (regression) or accuracy (classification)
+    pub oob_score: f64,
+    /// Rows that were out-of-bag for at least one tree
+    pub oob_coverage: f64,
+}
+
+/// Shared forest state behind the regressor and classifier classes
+struct Forest {
+    options: RandomForestOptions,
+    trees: Vec<Tree>,
+    importances: Vec<f64>,
+}
+
+impl Forest {
+    fn new(options: Option<RandomForestOptions>) -> Self {
+        Forest {
+            options: options.unwrap_or_default(),
+            trees: Vec::new(),
+            importances: Vec::new(),
+        }
+    }
+
+    /// Fit all trees in parallel and return, per row, the leaf values of the
+    /// trees for which that row was out-of-bag
+    fn fit(&mut self, x: &[Vec<f64>], y: &[f64], criterion: Criterion) -> Vec<Vec<Vec<f64>>> {
+        let n = x.len();
+        let n_features = x.first().map_or(0, |row| row.len());
+        let default_features = match criterion {
+            Criterion::Gini(_) => (n_features as f64).sqrt().ceil() as usize,
+            Criterion::SquaredError => (n_features / 3).max(1),
+        };
+        let params = TreeParams {
+            max_depth: self.options.max_depth.unwrap_or(16) as usize,
+            min_samples_leaf: self.options.min_samples_leaf.unwrap_or(1) as usize,
+            max_features: Some(self.options.max_features.map_or(default_features, |k| k as usize)),
+            criterion,
+        };
+        let n_trees = self.options.n_trees.unwrap_or(100) as usize;
+        let seed = self.options.seed.unwrap_or(42) as u64;
+
+        let fitted: Vec<(Tree, Vec<f64>, Vec<bool>)> = (0..n_trees)
+            .into_par_iter()
+            .map(|t| {
+                let mut rng = Rng::new(seed.wrapping_add(t as u64).wrapping_mul(0x9E37_79B9));
+                let mut in_bag = vec![false; n];
+                let sample: Vec<usize> = (0..n)
+                    .map(|_| {
+                        let i = rng.gen_index(n);
+                        in_bag[i] = true;
+                        i
+                    })
+                    .collect();
+                let mut importances = vec![0.0; n_features];
+                let tree = Tree::fit(x, y, &sample, &params, &mut rng, &mut importances);
+                (tree, importances, in_bag)
+            })
+            .collect();
+
+        self.importances = vec![0.0; n_features];
+        let mut oob: Vec<Vec<Vec<f64>>> = vec![Vec::new(); n];
+        self.trees = fitted
+            .into_iter()
+            .map(|(tree, importances, in_bag)| {
+                self.importances.iter_mut().zip(&importances).for_each(|(a, b)| *a += b);
+                for (i, row) in x.iter().enumerate().filter(|(i, _)| !in_bag[*i]) {
+                    oob[i].push(tree.predict_leaf(row).to_vec());
+                }
+                tree
+            })
+            .collect();
+        oob
+    }
+
+    /// Average leaf values across trees for one row
+    fn average(&self, row: &[f64]) -> Vec<f64> {
+        average_leaves(self.trees.iter().map(|t| t.predict_leaf(row).to_vec()))
+    }
+}
+
+fn average_leaves(leaves: impl Iterator<Item = Vec<f64>>) -> Vec<f64> {
+    let mut total: Vec<f64> = Vec::new();
+    let mut count: f64 = 0.0;
+    for leaf in leaves {
+        if total.is_empty() {
+            total = vec![0.0; leaf.len()];
+        }
+        total.iter_mut().zip(&leaf).for_each(|(a, b)| *a += b);
+        count += 1.0;
+    }
+    total.iter().map(|v| v / count.max(1.0)).collect()
+}
+
+fn argmax(values: &[f64]) -> u32 {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(0, |(i, _)| i as u32)
+}
+
+/// Reject `x`/`y` length mismatches and feature rows of different widths
+fn empty_fit_result() -> RandomForestFitResult {
+    RandomForestFitResult {
+        n_trees: 0,
+        oob_error: 0.0,
+        oob_score: 0.0,
+        oob_coverage: 0.0,
+    }
+}
+
+/// Random forest regression model
+#[napi]
+pub struct RandomForestRegressor {
+    forest: Forest,
+}
+
+#[napi]
+impl RandomForestRegressor {
+    #[napi(constructor)]
+    pub fn new(options: Option<RandomForestOptions>) -> Self {
+        RandomForestRegressor { forest: Forest::new(options) }
+    }
+
+    /// Fit the forest to feature rows `x` and targets `y`
+    ///
+    /// Fails when `x` and `y` differ in length or the rows differ in width.
+    #[napi]
+    pub fn fit(&mut self, x: Vec<Vec<f64>>, y: Vec<f64>) -> napi::Result<RandomForestFitResult> {
+        check_shape(&x, y.len())?;
+        if x.is_empty() {
+            return Ok(empty_fit_result());
+        }
+
+        let oob = self.forest.fit(&x, &y, Criterion::SquaredError);
+        let scored: Vec<(f64, f64)> = oob
+            .into_iter()
+            .zip(&y)
+            .filter(|(leaves, _)| !leaves.is_empty())
+            .map(|(leaves, &target)| (average_leaves(leaves.into_iter())[0], target))
+            .collect();
+
+        let n_scored = scored.len().max(1) as f64;
+        let oob_error = scored.iter().map(|(p, t)| (p - t).powi(2)).sum::<f64>() / n_scored;
+        let target_mean = scored.iter().map(|(_, t)| t).sum::<f64>() / n_scored;
+        let target_var = scored.iter().map(|(_, t)| (t - target_mean).powi(2)).sum::<f64>() / n_scored;
+
+        Ok(RandomForestFitResult {
+            n_trees: self.forest.trees.len() as u32,
+            oob_error,
+            oob_score: if target_var > 0.0 { 1.0 - oob_error / target_var } else { 0.0 },
+            oob_coverage: scored.len() as f64 / y.len() as f64,
+        })
+    }
+
+    /// Predict targets for feature rows (parallel)
+    #[napi]
+    pub fn predict(&self, x: Vec<Vec<f64>>) -> Vec<f64> {
+        x.par_iter().map(|row| self.forest.average(row).first().copied().unwrap_or(0.0)).collect()
+    }
+
+    /// Normalized impurity-decrease importance per feature (sums to 1)
+    #[napi]
+    pub fn feature_importances(&self) -> Vec<f64> {
+        normalize_importances(&self.forest.importances)
+    }
+}
+
+/// Random forest classification model
+///
+/// Class labels are integers `0..n_classes`.
+#[napi]
+pub struct RandomForestClassifier {
+    forest: Forest,
+    n_classes: u32,
+}
+
+#[napi]
+impl RandomForestClassifier {
+    #[napi(constructor)]
+    pub fn new(options: Option<RandomForestOptions>) -> Self {
+        RandomForestClassifier {
+            forest: Forest::new(options),
+            n_classes: 0,
+        }
+    }
+
+    /// Fit the forest to feature rows `x` and class labels `y`
+    ///
+    /// Fails when `x` and `y` differ in length or the rows differ in width.
+    #[napi]
+    pub fn fit(&mut self, x: Vec<Vec<f64>>, y: Vec<u32>) -> napi::Result<RandomForestFitResult> {
+        check_shape(&x, y.len())?;
+        if x.is_empty() {
+            return Ok(empty_fit_result());
+        }
+
+        self.n_classes = y.iter().copied().max().unwrap_or(0) + 1;
+        let labels: Vec<f64> = y.iter().map(|&c| c as f64).collect();
+        let oob = self.forest.fit(&x, &labels, Criterion::Gini(self.n_classes as usize));
+
+        let scored: Vec<bool> = oob
+            .into_iter()
+            .zip(&y)
+            .filter(|(leaves, _)| !leaves.is_empty())
+            .map(|(leaves, &label)| argmax(&average_leaves(leaves.into_iter())) == label)
+            .collect();
+
+        let accuracy = scored.iter().filter(|&&correct| correct).count() as f64 / scored.len().max(1) as f64;
+
+        Ok(RandomForestFitResult {
+            n_trees: self.forest.trees.len() as u32,
+            oob_error: 1.0 - accuracy,
+            oob_score: accuracy,
+            oob_coverage: scored.len() as f64 / y.len() as f64,
+        })
+    }
+
+    /// Predict the most likely class for each row (parallel)
+    #[napi]
+    pub fn predict(&self, x: Vec<Vec<f64>>) -> Vec<u32> {
+        x.par_iter().map(|row| argmax(&self.forest.average(row))).collect()
+    }
+
+    /// Predict class probabilities for each row (parallel)
+    #[napi]
+    pub fn predict_proba(&self, x: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        x.par_iter().map(|row| self.forest.average(row)).collect()
+    }
+
+    /// Normalized Gini-decrease importance per feature (sums to 1)
+    #[napi]
+    pub fn feature_importances(&self) -> Vec<f64> {
+        normalize_importances(&self.forest.importances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(n: usize) -> Vec<Vec<f64>> {
+        let mut rng = Rng::new(11);
+        (0..n).map(|_| vec![rng.next_f64() * 10.0, rng.next_f64() * 10.0, rng.next_f64()]).collect()
+    }
+
+    #[test]
+    fn test_random_forest_regressor() {
+        let x = features(300);
+        let y: Vec<f64> = x.iter().map(|r| 3.0 * r[0] + if r[1] > 5.0 { 10.0 } else { 0.0 }).collect();
+
+        let mut model = RandomForestRegressor::new(Some(RandomForestOptions {
+            n_trees: Some(50),
+            max_features: Some(2),
+            ..Default::default()
+        }));
+        let mut ragged = x.clone();
+        ragged[7].pop();
+        assert!(model.fit(ragged, y.clone()).is_err());
+        assert!(model.fit(x.clone(), y[1..].to_vec()).is_err());
+        let result = model.fit(x, y).unwrap();
+        assert_eq!(result.n_trees, 50);
+        assert!(result.oob_score > 0.8);
+        assert!(result.oob_coverage > 0.95);
+
+        let predictions = model.predict(vec![vec![2.0, 8.0, 0.5]]);
+        assert!((predictions[0] - 16.0).abs() < 4.0);
+        assert!(model.feature_importances()[2] < 0.1);
+    }
+
+    #[test]
+    fn test_random_forest_classifier() {
+        let x = features(300);
+        let y: Vec<u32> = x.iter().map(|r| if r[0] + r[1] > 10.0 { 1 } else { 0 }).collect();
+
+        let mut model = RandomForestClassifier::new(Some(RandomForestOptions {
+            n_trees: Some(50),
+            ..Default::default()
+        }));
+        assert!(model.fit(x.clone(), y[1..].to_vec()).is_err());
+        let result = model.fit(x, y).unwrap();
+        assert!(result.oob_score > 0.85);
+
+        let predictions = model.predict(vec![vec![9.0, 9.0, 0.5], vec![1.0, 1.0, 0.5]]);
+        assert_eq!(predictions, vec![1, 0]);
+        let proba = model.predict_proba(vec![vec![9.0, 9.0, 0.5]]);
+        assert!((proba[0].iter().sum::<f64>() - 1.0).abs() < 1e-10);
+    }
+}
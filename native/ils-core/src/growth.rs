@@ -0,0 +1,142 @@
+//! Growth & Change
+//!
+//! Growth rates and period comparisons for business reporting:
+//! - Percent change over arbitrary periods
+//! - Compound annual growth rate (CAGR)
+//! - Period-over-period and seasonally aligned (year-over-year) comparisons
+
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PeriodChange {
+    /// Index of the current value (end of the current window)
+    pub index: u32,
+    pub current: f64,
+    pub previous: f64,
+    pub absolute_change: f64,
+    /// Relative change; NaN when `previous` is zero
+    pub percent_change: f64,
+}
+
+/// Percent change between each value and the value `periods` steps earlier
+///
+/// The result is aligned with `data`: the first `periods` entries, and any
+/// entry whose earlier value is zero, are NaN.
+///
+/// # Arguments
+/// * `data` - Series
+/// * `periods` - Lag in steps (default 1)
+#[napi]
+pub fn pct_change(data: Vec<f64>, periods: Option<u32>) -> Vec<f64> {
+    let lag = periods.unwrap_or(1).max(1) as usize;
+    (0..data.len())
+        .map(|i| if i >= lag { relative_change(data[i], data[i - lag]) } else { f64::NAN })
+        .collect()
+}
+
+/// Compound growth rate per period between two values
+///
+/// NaN when `start_value` is not positive, `end_value` is negative or
+/// `periods` is not positive.
+///
+/// # Arguments
+/// * `start_value` - Value at the beginning (must be positive)
+/// * `end_value` - Value at the end
+/// * `periods` - Number of periods between them (e.g. years, may be fractional)
+#[napi]
+pub fn cagr(start_value: f64, end_value: f64, periods: f64) -> f64 {
+    if start_value <= 0.0 || end_value < 0.0 || periods <= 0.0 {
+        return f64::NAN;
+    }
+    (end_value / start_value).powf(1.0 / periods) - 1.0
+}
+
+/// Compare each value with the value `periods` steps earlier
+///
+/// # Arguments
+/// * `data` - Series
+/// * `periods` - Lag in steps (default 1)
+#[napi]
+pub fn period_over_period(data: Vec<f64>, periods: Option<u32>) -> Vec<PeriodChange> {
+    compare_windows(&data, periods.unwrap_or(1).max(1) as usize, 1)
+}
+
+/// Seasonally aligned comparison, e.g. this year vs the same period last year
+///
+/// Sums of `window` consecutive values are compared with the same window one
+/// season earlier, so "last 7 days vs the same 7 days last year" is
+/// `year_over_year(daily, 364, 7)`.
+///
+/// # Arguments
+/// * `data` - Series
+/// * `season_length` - Steps per season (e.g. 12 for monthly data, 52 for weekly)
+/// * `window` - Values summed on each side of the comparison (default 1)
+#[napi]
+pub fn year_over_year(data: Vec<f64>, season_length: u32, window: Option<u32>) -> Vec<PeriodChange> {
+    compare_windows(&data, season_length.max(1) as usize, window.unwrap_or(1).max(1) as usize)
+}
+
+fn compare_windows(data: &[f64], lag: usize, window: usize) -> Vec<PeriodChange> {
+    let first = lag + window - 1;
+    if data.len() <= first {
+        return Vec::new();
+    }
+
+    (first..data.len())
+        .map(|i| {
+            let current: f64 = data[i + 1 - window..=i].iter().sum();
+            let previous: f64 = data[i + 1 - window - lag..=i - lag].iter().sum();
+            PeriodChange {
+                index: i as u32,
+                current,
+                previous,
+                absolute_change: current - previous,
+                percent_change: relative_change(current, previous),
+            }
+        })
+        .collect()
+}
+
+fn relative_change(current: f64, previous: f64) -> f64 {
+    if previous == 0.0 {
+        f64::NAN
+    } else {
+        (current - previous) / previous.abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pct_change_and_cagr() {
+        let changes = pct_change(vec![100.0, 110.0, 99.0, 0.0, 5.0], None);
+        assert!(changes[0].is_nan());
+        assert!((changes[1] - 0.1).abs() < 1e-10);
+        assert!((changes[2] + 0.1).abs() < 1e-10);
+        assert!(changes[4].is_nan());
+
+        assert!((cagr(100.0, 121.0, 2.0) - 0.1).abs() < 1e-10);
+        assert!(cagr(0.0, 10.0, 1.0).is_nan());
+        assert!(cagr(100.0, 121.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_year_over_year_windows() {
+        // Two "years" of 4 quarters, second year up 50%
+        let data = vec![10.0, 20.0, 30.0, 40.0, 15.0, 30.0, 45.0, 60.0];
+        let pointwise = year_over_year(data.clone(), 4, None);
+        assert_eq!(pointwise.len(), 4);
+        assert!(pointwise.iter().all(|c| (c.percent_change - 0.5).abs() < 1e-10));
+
+        let half_year = year_over_year(data.clone(), 4, Some(2));
+        assert_eq!(half_year[0].index, 5);
+        assert_eq!(half_year[0].current, 45.0);
+        assert_eq!(half_year[0].previous, 30.0);
+
+        let sequential = period_over_period(data, None);
+        assert_eq!(sequential[0].absolute_change, 10.0);
+    }
+}
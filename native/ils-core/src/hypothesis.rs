@@ -0,0 +1,785 @@
+//! Hypothesis Tests
+//!
+//! Classical significance tests for comparing samples:
+//! - Two-sample t-tests (Welch and pooled-variance)
+//! - Chi-square goodness-of-fit and independence tests
+//! - Fisher's exact test for 2x2 tables
+//! - Shapiro-Wilk normality test
+//! - Two-sample permutation test (parallel)
+//! - Wald-Wolfowitz runs test for randomness of a sequence
+//! - Power analysis and sample size for two-sample t-tests
+//! - Benford's law first-digit analysis
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal, StudentsT};
+use statrs::function::factorial::ln_factorial;
+
+use crate::sampling::Rng;
+use crate::statistics::{mean, median, variance};
+
+/// Permutations drawn per parallel task; fixed so results do not depend on the thread count
+const PERMUTATION_BLOCK: usize = 1000;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct TTestOptions {
+    /// Assume equal variances (Student's pooled test) instead of Welch's test (default false)
+    pub equal_variance: Option<bool>,
+    /// "two-sided" (default), "less" (mean of a < mean of b) or "greater"
+    pub alternative: Option<String>,
+    /// Confidence level of the interval for the mean difference (default 0.95)
+    pub confidence_level: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TTestResult {
+    /// "welch" or "student"
+    pub method: String,
+    pub t_statistic: f64,
+    /// Welch-Satterthwaite degrees of freedom for Welch's test, n_a + n_b - 2 otherwise
+    pub degrees_of_freedom: f64,
+    pub p_value: f64,
+    /// mean(a) - mean(b)
+    pub mean_difference: f64,
+    pub standard_error: f64,
+    /// Confidence interval for the mean difference (one-sided alternatives give an infinite bound)
+    pub confidence_lower: f64,
+    pub confidence_upper: f64,
+}
+
+/// Two-sample t-test for a difference in means
+///
+/// Welch's test by default, which stays valid when the two samples have
+/// different variances (e.g. before and after a process change). Samples with
+/// fewer than two values give a NaN statistic and a p-value of 1.
+///
+/// # Arguments
+/// * `sample_a` - First sample
+/// * `sample_b` - Second sample
+/// * `options` - Variance assumption, alternative hypothesis and confidence level
+#[napi]
+pub fn t_test(sample_a: Vec<f64>, sample_b: Vec<f64>, options: Option<TTestOptions>) -> TTestResult {
+    let options = options.unwrap_or_default();
+    let equal_variance = options.equal_variance.unwrap_or(false);
+    let (na, nb) = (sample_a.len() as f64, sample_b.len() as f64);
+    let mean_difference = mean(sample_a.clone()) - mean(sample_b.clone());
+    let (va, vb) = (variance(sample_a), variance(sample_b));
+
+    let (standard_error, df) = if equal_variance {
+        let pooled = ((na - 1.0) * va + (nb - 1.0) * vb) / (na + nb - 2.0);
+        ((pooled * (1.0 / na + 1.0 / nb)).sqrt(), na + nb - 2.0)
+    } else {
+        let (sa, sb) = (va / na, vb / nb);
+        let df = (sa + sb).powi(2) / (sa * sa / (na - 1.0) + sb * sb / (nb - 1.0));
+        ((sa + sb).sqrt(), df)
+    };
+
+    let method = if equal_variance { "student" } else { "welch" }.to_string();
+    let distribution = (na >= 2.0 && nb >= 2.0 && df.is_finite() && df > 0.0)
+        .then(|| StudentsT::new(0.0, 1.0, df).ok())
+        .flatten();
+    let Some(distribution) = distribution.filter(|_| standard_error > 0.0) else {
+        return TTestResult {
+            method,
+            t_statistic: f64::NAN,
+            degrees_of_freedom: df,
+            p_value: 1.0,
+            mean_difference,
+            standard_error,
+            confidence_lower: f64::NAN,
+            confidence_upper: f64::NAN,
+        };
+    };
+
+    let t = mean_difference / standard_error;
+    let level = options.confidence_level.unwrap_or(0.95).clamp(0.0, 1.0);
+    let (p_value, confidence_lower, confidence_upper) = match options.alternative.as_deref() {
+        Some("less") => {
+            let critical = distribution.inverse_cdf(level);
+            (distribution.cdf(t), f64::NEG_INFINITY, mean_difference + critical * standard_error)
+        }
+        Some("greater") => {
+            let critical = distribution.inverse_cdf(level);
+            (1.0 - distribution.cdf(t), mean_difference - critical * standard_error, f64::INFINITY)
+        }
+        _ => {
+            let critical = distribution.inverse_cdf(0.5 + level / 2.0);
+            let margin = critical * standard_error;
+            (2.0 * (1.0 - distribution.cdf(t.abs())), mean_difference - margin, mean_difference + margin)
+        }
+    };
+
+    TTestResult {
+        method,
+        t_statistic: t,
+        degrees_of_freedom: df,
+        p_value: p_value.clamp(0.0, 1.0),
+        mean_difference,
+        standard_error,
+        confidence_lower,
+        confidence_upper,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ChiSquareResult {
+    pub statistic: f64,
+    pub degrees_of_freedom: u32,
+    pub p_value: f64,
+    /// Expected counts used, in the same layout as the observed counts (tables row by row)
+    pub expected: Vec<f64>,
+}
+
+/// Chi-square goodness-of-fit test
+///
+/// Expected counts are rescaled to the observed total, so proportions or
+/// weights can be passed directly. Without `expected` all categories are
+/// equally likely. Fails when `expected` has a different length than
+/// `observed`.
+///
+/// # Arguments
+/// * `observed` - Observed counts per category
+/// * `expected` - Expected counts or proportions per category
+#[napi]
+pub fn chi_square_test(observed: Vec<f64>, expected: Option<Vec<f64>>) -> napi::Result<ChiSquareResult> {
+    let k = observed.len();
+    let total: f64 = observed.iter().sum();
+    let expected = match expected {
+        Some(e) if e.len() != k => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("{} expected counts for {} observed categories", e.len(), k),
+            ))
+        }
+        Some(e) => e,
+        None => vec![1.0; k],
+    };
+    let expected_total: f64 = expected.iter().sum();
+    let expected: Vec<f64> = expected
+        .iter()
+        .map(|e| if expected_total > 0.0 { e * total / expected_total } else { 0.0 })
+        .collect();
+    Ok(chi_square_result(&observed, expected, k.saturating_sub(1)))
+}
+
+/// Chi-square test of independence for a contingency table
+///
+/// E.g. rows = machines, columns = defect / no defect. Expected counts come
+/// from the row and column totals. Fails when the rows differ in length.
+///
+/// # Arguments
+/// * `table` - Observed counts, one row per category of the first variable
+#[napi]
+pub fn chi_square_independence(table: Vec<Vec<f64>>) -> napi::Result<ChiSquareResult> {
+    let cols = table.first().map_or(0, |r| r.len());
+    if let Some(i) = table.iter().position(|r| r.len() != cols) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("table row {} has {} cells, expected {}", i, table[i].len(), cols),
+        ));
+    }
+    let row_totals: Vec<f64> = table.iter().map(|r| r.iter().sum()).collect();
+    let col_totals: Vec<f64> = (0..cols).map(|c| table.iter().map(|r| r[c]).sum()).collect();
+    let total: f64 = row_totals.iter().sum();
+
+    let observed: Vec<f64> = table.iter().flatten().copied().collect();
+    let expected: Vec<f64> = row_totals
+        .iter()
+        .flat_map(|rt| col_totals.iter().map(move |ct| if total > 0.0 { rt * ct / total } else { 0.0 }))
+        .collect();
+    let df = table.len().saturating_sub(1) * cols.saturating_sub(1);
+    Ok(chi_square_result(&observed, expected, df))
+}
+
+fn chi_square_result(observed: &[f64], expected: Vec<f64>, df: usize) -> ChiSquareResult {
+    // Cells with no expected count carry no information
+    let statistic: f64 = observed
+        .iter()
+        .zip(&expected)
+        .filter(|(_, &e)| e > 0.0)
+        .map(|(o, e)| (o - e).powi(2) / e)
+        .sum();
+    let p_value = match ChiSquared::new(df as f64) {
+        Ok(dist) if df > 0 => (1.0 - dist.cdf(statistic)).clamp(0.0, 1.0),
+        _ => 1.0,
+    };
+    ChiSquareResult {
+        statistic,
+        degrees_of_freedom: df as u32,
+        p_value,
+        expected,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BenfordResult {
+    /// Values with a usable first digit (non-zero and finite)
+    pub n: u32,
+    /// Observed share of first digits 1..9
+    pub observed_frequencies: Vec<f64>,
+    /// Benford share `log10(1 + 1/d)` of first digits 1..9
+    pub expected_frequencies: Vec<f64>,
+    /// Chi-square statistic of the digit counts against Benford (8 degrees of freedom)
+    pub chi_square: f64,
+    pub p_value: f64,
+    /// Mean absolute deviation between observed and expected shares
+    pub mad: f64,
+    /// Nigrini's MAD rating: "close", "acceptable", "marginal" or "nonconformity"
+    pub conformity: String,
+}
+
+/// First-digit analysis against Benford's law
+///
+/// Naturally occurring amounts (invoices, claim values, measurements
+/// spanning several orders of magnitude) start with 1 about 30% of the time
+/// and with 9 under 5%; fabricated or mistyped entries tend to break that
+/// pattern. With large samples the chi-square test rejects on trivial
+/// deviations, so the MAD rating is the better screening signal. Signs are
+/// ignored; zero and non-finite values are skipped.
+///
+/// # Arguments
+/// * `data` - Amounts to screen
+#[napi]
+pub fn benford_analysis(data: Vec<f64>) -> BenfordResult {
+    let mut counts = vec![0.0; 9];
+    for value in data.iter().filter(|v| v.is_finite() && **v != 0.0) {
+        // Scientific notation puts the first significant digit first
+        let digit = format!("{:e}", value.abs()).as_bytes()[0] - b'0';
+        counts[digit as usize - 1] += 1.0;
+    }
+    let n: f64 = counts.iter().sum();
+    let expected_frequencies: Vec<f64> = (1..=9).map(|d| (1.0 + 1.0 / d as f64).log10()).collect();
+    let observed_frequencies: Vec<f64> = counts.iter().map(|c| if n > 0.0 { c / n } else { 0.0 }).collect();
+
+    let test = if n > 0.0 {
+        chi_square_result(&counts, expected_frequencies.iter().map(|e| e * n).collect(), 8)
+    } else {
+        chi_square_result(&[], Vec::new(), 0)
+    };
+    let mad = observed_frequencies.iter().zip(&expected_frequencies).map(|(o, e)| (o - e).abs()).sum::<f64>() / 9.0;
+    let conformity = match mad {
+        _ if n == 0.0 => "nonconformity",
+        m if m <= 0.006 => "close",
+        m if m <= 0.012 => "acceptable",
+        m if m <= 0.015 => "marginal",
+        _ => "nonconformity",
+    };
+
+    BenfordResult {
+        n: n as u32,
+        observed_frequencies,
+        expected_frequencies,
+        chi_square: test.statistic,
+        p_value: test.p_value,
+        mad,
+        conformity: conformity.to_string(),
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FisherExactResult {
+    /// Sample odds ratio `(a * d) / (b * c)` (infinite when `b * c` is zero)
+    pub odds_ratio: f64,
+    pub p_value: f64,
+}
+
+/// Fisher's exact test for a 2x2 table `[[a, b], [c, d]]`
+///
+/// Exact hypergeometric p-value, suited to small counts where the chi-square
+/// approximation breaks down. The two-sided p-value sums all tables with the
+/// same margins that are no more likely than the observed one.
+///
+/// # Arguments
+/// * `a`, `b` - First row counts
+/// * `c`, `d` - Second row counts
+/// * `alternative` - "two-sided" (default), "less" or "greater" (odds ratio vs 1)
+#[napi]
+pub fn fisher_exact(a: u32, b: u32, c: u32, d: u32, alternative: Option<String>) -> FisherExactResult {
+    let (a, b, c, d) = (a as u64, b as u64, c as u64, d as u64);
+    let odds_ratio = if b * c == 0 {
+        if a * d == 0 { f64::NAN } else { f64::INFINITY }
+    } else {
+        (a * d) as f64 / (b * c) as f64
+    };
+
+    // With fixed margins the table is determined by its top-left cell
+    let (row1, col1, n) = (a + b, a + c, a + b + c + d);
+    let low = col1.saturating_sub(c + d);
+    let high = row1.min(col1);
+    let ln_denominator = ln_factorial(n) - ln_factorial(row1) - ln_factorial(n - row1) - ln_factorial(col1) - ln_factorial(n - col1);
+    let probability = |x: u64| {
+        (-(ln_factorial(x) + ln_factorial(row1 - x) + ln_factorial(col1 - x) + ln_factorial(n + x - row1 - col1))
+            - ln_denominator)
+            .exp()
+    };
+
+    let p_value = match alternative.as_deref() {
+        Some("less") => (low..=a).map(probability).sum::<f64>(),
+        Some("greater") => (a..=high).map(probability).sum::<f64>(),
+        _ => {
+            let observed = probability(a);
+            (low..=high)
+                .map(probability)
+                .filter(|&p| p <= observed * (1.0 + 1e-7))
+                .sum::<f64>()
+        }
+    };
+
+    FisherExactResult {
+        odds_ratio,
+        p_value: p_value.min(1.0),
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ShapiroWilkResult {
+    /// W statistic in (0, 1]; values near 1 indicate normality
+    pub w: f64,
+    pub p_value: f64,
+    /// Normality is not rejected at the 5% level
+    pub is_normal: bool,
+}
+
+/// Shapiro-Wilk test for normality
+///
+/// Royston's (1995) approximation of the coefficients and p-value, valid for
+/// 3 to 5000 observations (larger samples use their first 5000 values). A
+/// small p-value means z-score thresholds such as those in
+/// `detect_anomalies` will misjudge how rare extreme values are.
+///
+/// # Arguments
+/// * `data` - Sample values
+#[napi]
+pub fn shapiro_wilk(data: Vec<f64>) -> ShapiroWilkResult {
+    let mut x: Vec<f64> = data.into_iter().filter(|v| v.is_finite()).take(5000).collect();
+    x.sort_by(|a, b| a.total_cmp(b));
+    let n = x.len();
+    let mean = x.iter().sum::<f64>() / n.max(1) as f64;
+    let ss: f64 = x.iter().map(|v| (v - mean).powi(2)).sum();
+    if n < 3 || ss <= 0.0 {
+        return ShapiroWilkResult { w: 1.0, p_value: 1.0, is_normal: true };
+    }
+
+    let standard = Normal::new(0.0, 1.0).expect("standard normal");
+    let a = shapiro_wilk_coefficients(n, &standard);
+    let w = (a.iter().zip(&x).map(|(a, x)| a * x).sum::<f64>().powi(2) / ss).min(1.0);
+
+    let nf = n as f64;
+    let p_value = if n == 3 {
+        let p = 6.0 / std::f64::consts::PI * (w.sqrt().asin() - 0.75f64.sqrt().asin());
+        p.max(0.0)
+    } else {
+        let z = if n <= 11 {
+            let gamma = 0.459 * nf - 2.273;
+            let mu = 0.5440 - 0.39978 * nf + 0.025054 * nf.powi(2) - 0.0006714 * nf.powi(3);
+            let sigma = (1.3822 - 0.77857 * nf + 0.062767 * nf.powi(2) - 0.0020322 * nf.powi(3)).exp();
+            (-(gamma - (1.0 - w).ln()).ln() - mu) / sigma
+        } else {
+            let l = nf.ln();
+            let mu = 0.0038915 * l.powi(3) - 0.083751 * l.powi(2) - 0.31082 * l - 1.5861;
+            let sigma = (0.0030302 * l.powi(2) - 0.082676 * l - 0.4803).exp();
+            ((1.0 - w).ln() - mu) / sigma
+        };
+        1.0 - standard.cdf(z)
+    };
+    let p_value = if p_value.is_nan() { 0.0 } else { p_value.clamp(0.0, 1.0) };
+
+    ShapiroWilkResult {
+        w,
+        p_value,
+        is_normal: p_value >= 0.05,
+    }
+}
+
+/// Royston's approximation of the Shapiro-Wilk coefficients for `n` sorted values
+fn shapiro_wilk_coefficients(n: usize, standard: &Normal) -> Vec<f64> {
+    if n == 3 {
+        let a = 0.5f64.sqrt();
+        return vec![-a, 0.0, a];
+    }
+    let nf = n as f64;
+    let m: Vec<f64> = (1..=n).map(|i| standard.inverse_cdf((i as f64 - 0.375) / (nf + 0.25))).collect();
+    let mm: f64 = m.iter().map(|v| v * v).sum();
+    let u = 1.0 / nf.sqrt();
+    let poly = |c: f64, coefs: [f64; 5]| c + coefs.iter().enumerate().map(|(k, q)| q * u.powi(k as i32 + 1)).sum::<f64>();
+
+    let an = poly(m[n - 1] / mm.sqrt(), [0.221157, -0.147981, -2.071190, 4.434685, -2.706056]);
+    let mut a = vec![0.0; n];
+    let (phi, tails) = if n > 5 {
+        let an1 = poly(m[n - 2] / mm.sqrt(), [0.042981, -0.293762, -1.752461, 5.682633, -3.582633]);
+        a[n - 2] = an1;
+        a[1] = -an1;
+        ((mm - 2.0 * m[n - 1].powi(2) - 2.0 * m[n - 2].powi(2)) / (1.0 - 2.0 * an.powi(2) - 2.0 * an1.powi(2)), 2)
+    } else {
+        ((mm - 2.0 * m[n - 1].powi(2)) / (1.0 - 2.0 * an.powi(2)), 1)
+    };
+    a[n - 1] = an;
+    a[0] = -an;
+    for i in tails..n - tails {
+        a[i] = m[i] / phi.sqrt();
+    }
+    a
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PermutationTestResult {
+    /// mean(a) - mean(b) for the observed samples
+    pub observed_difference: f64,
+    /// Two-sided empirical p-value, (hits + 1) / (iterations + 1)
+    pub p_value: f64,
+    pub iterations: u32,
+}
+
+/// Two-sample permutation test for a difference in means
+///
+/// Makes no normality assumption, so it complements `t_test` for small or
+/// skewed samples. Group labels are reshuffled `iterations` times and the
+/// p-value is the share of shuffles whose absolute mean difference is at
+/// least the observed one. Reproducible for a given seed.
+///
+/// # Arguments
+/// * `sample_a` - First sample
+/// * `sample_b` - Second sample
+/// * `iterations` - Number of random permutations (default 10000)
+/// * `seed` - Random seed (default 42)
+#[napi]
+pub fn permutation_test(sample_a: Vec<f64>, sample_b: Vec<f64>, iterations: Option<u32>, seed: Option<u32>) -> PermutationTestResult {
+    let iterations = iterations.unwrap_or(10_000);
+    if sample_a.is_empty() || sample_b.is_empty() || iterations == 0 {
+        return PermutationTestResult { observed_difference: f64::NAN, p_value: 1.0, iterations: 0 };
+    }
+
+    let na = sample_a.len();
+    let observed_difference = mean(sample_a.clone()) - mean(sample_b.clone());
+    let pooled: Vec<f64> = sample_a.into_iter().chain(sample_b).collect();
+    let total: f64 = pooled.iter().sum();
+    let nb = pooled.len() - na;
+    // Small tolerance so shuffles that tie the observed difference count as hits
+    let threshold = observed_difference.abs() * (1.0 - 1e-12);
+
+    let seed = seed.unwrap_or(42) as u64;
+    let n_iter = iterations as usize;
+    let hits: usize = (0..n_iter.div_ceil(PERMUTATION_BLOCK))
+        .into_par_iter()
+        .map(|block| {
+            let mut rng = Rng::new(seed.wrapping_add(block as u64).wrapping_mul(0x9E37_79B9));
+            let mut values = pooled.clone();
+            let count = PERMUTATION_BLOCK.min(n_iter - block * PERMUTATION_BLOCK);
+            (0..count)
+                .filter(|_| {
+                    rng.shuffle(&mut values);
+                    let sum_a: f64 = values[..na].iter().sum();
+                    (sum_a / na as f64 - (total - sum_a) / nb as f64).abs() >= threshold
+                })
+                .count()
+        })
+        .sum();
+
+    PermutationTestResult {
+        observed_difference,
+        p_value: (hits + 1) as f64 / (n_iter + 1) as f64,
+        iterations,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RunsTestResult {
+    /// Observed number of runs of consecutive values on the same side of the cutoff
+    pub runs: u32,
+    /// Runs expected for a random ordering
+    pub expected_runs: f64,
+    pub z_score: f64,
+    /// Two-sided p-value (normal approximation)
+    pub p_value: f64,
+    /// Whether randomness is not rejected at the 5% level
+    pub is_random: bool,
+    pub n_above: u32,
+    pub n_below: u32,
+    /// Cutoff the values were split at
+    pub cutoff: f64,
+}
+
+/// Wald-Wolfowitz runs test for randomness of a sequence
+///
+/// Splits the values (in order) into above/below a cutoff and counts runs.
+/// Too few runs mean errors persist (e.g. a forecast that stays too low for
+/// weeks, a missed trend or level shift); too many mean they alternate
+/// (over-correction). Values equal to the cutoff and NaN are skipped. Needs
+/// values on both sides; otherwise the p-value is 1.
+///
+/// # Arguments
+/// * `data` - Sequence in time order (e.g. forecast residuals)
+/// * `cutoff` - Split point (default: the median; use 0 for residual signs)
+#[napi]
+pub fn runs_test(data: Vec<f64>, cutoff: Option<f64>) -> RunsTestResult {
+    let values: Vec<f64> = data.into_iter().filter(|v| !v.is_nan()).collect();
+    let cutoff = cutoff.unwrap_or_else(|| median(values.clone()));
+    let signs: Vec<bool> = values.iter().filter(|&&v| v != cutoff).map(|&v| v > cutoff).collect();
+    let runs = if signs.is_empty() { 0 } else { 1 + signs.windows(2).filter(|w| w[0] != w[1]).count() };
+    let n_above = signs.iter().filter(|&&above| above).count();
+    let n_below = signs.len() - n_above;
+
+    let (n1, n2, n) = (n_above as f64, n_below as f64, signs.len() as f64);
+    let expected_runs = if n > 0.0 { 2.0 * n1 * n2 / n + 1.0 } else { 0.0 };
+    let variance = if n > 1.0 { 2.0 * n1 * n2 * (2.0 * n1 * n2 - n) / (n * n * (n - 1.0)) } else { 0.0 };
+    let z_score = if variance > 0.0 { (runs as f64 - expected_runs) / variance.sqrt() } else { 0.0 };
+    let p_value = if variance > 0.0 {
+        let standard = Normal::new(0.0, 1.0).unwrap();
+        (2.0 * (1.0 - standard.cdf(z_score.abs()))).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    RunsTestResult {
+        runs: runs as u32,
+        expected_runs,
+        z_score,
+        p_value,
+        is_random: p_value >= 0.05,
+        n_above: n_above as u32,
+        n_below: n_below as u32,
+        cutoff,
+    }
+}
+
+/// Observations needed per group for a two-sample t-test to detect an effect
+///
+/// Smallest `n` per group at which a two-sided, equal-variance t-test reaches
+/// the requested power. Power uses a shifted-t approximation to the
+/// noncentral t distribution, within one observation of exact tables.
+/// Returns 0 for a zero or non-finite effect size, or alpha/power outside (0, 1).
+///
+/// # Arguments
+/// * `effect_size` - Cohen's d: difference in means over the pooled standard deviation
+///   (0.2 small, 0.5 medium, 0.8 large)
+/// * `alpha` - Significance level (default 0.05)
+/// * `power` - Desired probability of detecting the effect (default 0.8)
+#[napi]
+pub fn required_sample_size(effect_size: f64, alpha: Option<f64>, power: Option<f64>) -> u32 {
+    let alpha = alpha.unwrap_or(0.05);
+    let power = power.unwrap_or(0.8);
+    let d = effect_size.abs();
+    let valid = |p: f64| p > 0.0 && p < 1.0;
+    if !(d > 0.0 && d.is_finite() && valid(alpha) && valid(power)) {
+        return 0;
+    }
+
+    // Start from the normal approximation and correct with the t-based power
+    let standard = Normal::new(0.0, 1.0).unwrap();
+    let z = standard.inverse_cdf(1.0 - alpha / 2.0) + standard.inverse_cdf(power);
+    let mut n = (2.0 * (z / d).powi(2)).ceil().clamp(2.0, u32::MAX as f64);
+    while n < u32::MAX as f64 && two_sample_power(n, d, alpha) < power {
+        n += 1.0;
+    }
+    while n > 2.0 && two_sample_power(n - 1.0, d, alpha) >= power {
+        n -= 1.0;
+    }
+    n as u32
+}
+
+/// Power of a two-sided, two-sample t-test with `n` observations per group
+///
+/// The probability that a real effect of the given size is detected at
+/// level `alpha`; e.g. whether a pilot that ran for `n` weeks could have
+/// shown the improvement at all. Returns NaN for `n < 2` or invalid inputs.
+///
+/// # Arguments
+/// * `n` - Observations per group
+/// * `effect_size` - Cohen's d
+/// * `alpha` - Significance level (default 0.05)
+#[napi]
+pub fn achieved_power(n: u32, effect_size: f64, alpha: Option<f64>) -> f64 {
+    let alpha = alpha.unwrap_or(0.05);
+    if n < 2 || !effect_size.is_finite() || !(alpha > 0.0 && alpha < 1.0) {
+        return f64::NAN;
+    }
+    two_sample_power(n as f64, effect_size.abs(), alpha)
+}
+
+/// Shifted-t approximation to the power of the two-sample t-test
+fn two_sample_power(n: f64, d: f64, alpha: f64) -> f64 {
+    let Ok(t) = StudentsT::new(0.0, 1.0, 2.0 * n - 2.0) else {
+        return f64::NAN;
+    };
+    let critical = t.inverse_cdf(1.0 - alpha / 2.0);
+    let shift = d * (n / 2.0).sqrt();
+    (1.0 - t.cdf(critical - shift) + t.cdf(-critical - shift)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_test() {
+        let mut rng = Rng::new(3);
+        let noise: Vec<f64> = (0..200).map(|_| rng.next_f64() - 0.5).collect();
+        assert!(runs_test(noise, Some(0.0)).is_random);
+
+        // A forecast that under-predicts for weeks, then over-predicts
+        let persistent: Vec<f64> = (0..40).map(|i| if i < 20 { 1.0 + i as f64 } else { -1.0 - i as f64 }).collect();
+        let result = runs_test(persistent, Some(0.0));
+        assert_eq!((result.runs, result.n_above, result.n_below), (2, 20, 20));
+        assert_eq!(result.expected_runs, 21.0);
+        assert!(!result.is_random && result.z_score < 0.0);
+
+        // Strict alternation has too many runs
+        let alternating: Vec<f64> = (0..40).map(|i| (i % 2) as f64).collect();
+        let result = runs_test(alternating, None);
+        assert!(result.runs == 40 && result.z_score > 0.0 && !result.is_random);
+        assert_eq!(runs_test(vec![1.0; 5], None).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_benford_analysis() {
+        // Geometric growth over whole orders of magnitude follows Benford closely
+        let natural: Vec<f64> = (0..5000).map(|i| 10f64.powf(i as f64 / 1000.0) * if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        let result = benford_analysis(natural);
+        assert_eq!(result.n, 5000);
+        assert_eq!(result.conformity, "close");
+        assert!((result.expected_frequencies[0] - std::f64::consts::LOG10_2).abs() < 1e-12);
+        assert!(result.p_value > 0.05);
+
+        // Amounts made up in a narrow 400-999 band
+        let invented: Vec<f64> = (0..900).map(|i| 400.0 + (i * 7 % 600) as f64).chain([0.0, f64::NAN]).collect();
+        let flagged = benford_analysis(invented);
+        assert_eq!((flagged.n, flagged.conformity.as_str()), (900, "nonconformity"));
+        assert_eq!(flagged.observed_frequencies[0], 0.0);
+        assert!(flagged.p_value < 1e-10);
+        assert_eq!(benford_analysis(vec![0.0]).observed_frequencies, vec![0.0; 9]);
+    }
+
+    #[test]
+    fn test_power_analysis() {
+        // Cohen's tables: 64, 26 and 393 per group at alpha 0.05, power 0.8
+        assert!((63..=65).contains(&required_sample_size(0.5, None, None)));
+        assert!((25..=27).contains(&required_sample_size(0.8, None, None)));
+        assert!((392..=394).contains(&required_sample_size(-0.2, None, None)));
+        assert!(required_sample_size(0.5, Some(0.01), Some(0.9)) > required_sample_size(0.5, None, None));
+        assert_eq!(required_sample_size(0.0, None, None), 0);
+
+        let n = required_sample_size(0.5, None, None);
+        assert!(achieved_power(n, 0.5, None) >= 0.8 && achieved_power(n - 1, 0.5, None) < 0.8);
+        // With no effect the test only fires at the false-positive rate
+        assert!((achieved_power(50, 0.0, None) - 0.05).abs() < 1e-9);
+        assert!(achieved_power(1, 0.5, None).is_nan());
+    }
+
+    #[test]
+    fn test_welch_t_test() {
+        // Reference values from scipy.stats.ttest_ind(a, b, equal_var=False)
+        let a = vec![27.5, 21.0, 19.0, 23.6, 17.0, 17.9, 16.9, 20.1, 21.9, 22.6, 23.1, 19.6, 19.0, 21.7, 21.4];
+        let b = vec![27.1, 22.0, 20.8, 23.4, 23.4, 23.5, 25.8, 22.0, 24.8, 20.2, 21.9, 22.1, 22.9, 20.5, 24.4];
+        let result = t_test(a.clone(), b.clone(), None);
+        assert_eq!(result.method, "welch");
+        assert!((result.t_statistic + 2.46).abs() < 0.01);
+        assert!((result.degrees_of_freedom - 24.99).abs() < 0.05);
+        assert!((result.p_value - 0.021).abs() < 0.001);
+        assert!(result.confidence_upper < 0.0);
+
+        let less = t_test(a, b, Some(TTestOptions {
+            alternative: Some("less".to_string()),
+            ..Default::default()
+        }));
+        assert!((less.p_value - result.p_value / 2.0).abs() < 1e-9);
+        assert_eq!(less.confidence_lower, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_chi_square() {
+        // Fair die: 60 rolls
+        let fair = chi_square_test(vec![8.0, 12.0, 9.0, 11.0, 10.0, 10.0], None).unwrap();
+        assert_eq!(fair.degrees_of_freedom, 5);
+        assert!((fair.statistic - 1.0).abs() < 1e-12);
+        assert!(fair.p_value > 0.9);
+
+        let proportions = chi_square_test(vec![50.0, 30.0, 20.0], Some(vec![0.25, 0.25, 0.5])).unwrap();
+        assert_eq!(proportions.expected, vec![25.0, 25.0, 50.0]);
+        assert!(proportions.p_value < 1e-6);
+        assert!(chi_square_test(vec![1.0, 2.0, 3.0], Some(vec![1.0])).is_err());
+
+        // Defects by machine: machine C is clearly worse
+        let table = vec![vec![5.0, 95.0], vec![6.0, 94.0], vec![20.0, 80.0]];
+        let independence = chi_square_independence(table).unwrap();
+        assert_eq!(independence.degrees_of_freedom, 2);
+        assert!(independence.p_value < 0.001);
+        assert!(chi_square_independence(vec![vec![5.0, 95.0], vec![6.0]]).is_err());
+    }
+
+    #[test]
+    fn test_fisher_exact() {
+        // Lady tasting tea: scipy.stats.fisher_exact([[3, 1], [1, 3]])
+        let tea = fisher_exact(3, 1, 1, 3, None);
+        assert!((tea.odds_ratio - 9.0).abs() < 1e-12);
+        assert!((tea.p_value - 0.485714).abs() < 1e-5);
+        let greater = fisher_exact(3, 1, 1, 3, Some("greater".to_string()));
+        assert!((greater.p_value - 0.242857).abs() < 1e-5);
+
+        // scipy.stats.fisher_exact([[8, 2], [1, 5]]) -> p = 0.034965
+        assert!((fisher_exact(8, 2, 1, 5, None).p_value - 0.034965).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_permutation_test() {
+        let a = vec![12.0, 15.0, 14.0, 16.0, 13.0, 15.0];
+        let b = vec![18.0, 21.0, 19.0, 22.0, 20.0, 19.0];
+        let result = permutation_test(a.clone(), b.clone(), Some(5000), Some(7));
+        assert!((result.observed_difference - -5.6667).abs() < 1e-3);
+        assert!(result.p_value < 0.01);
+        assert_eq!(result.iterations, 5000);
+        // Same seed, same answer
+        assert_eq!(permutation_test(a.clone(), b, Some(5000), Some(7)).p_value, result.p_value);
+
+        let result = permutation_test(a.clone(), a.iter().rev().copied().collect(), Some(2000), None);
+        assert!(result.p_value > 0.9);
+        assert_eq!(permutation_test(vec![], vec![1.0], None, None).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_shapiro_wilk() {
+        let standard = Normal::new(0.0, 1.0).unwrap();
+        let normal: Vec<f64> = (1..=100).map(|i| standard.inverse_cdf(i as f64 / 101.0)).collect();
+        let result = shapiro_wilk(normal);
+        assert!(result.w > 0.99);
+        assert!(result.is_normal);
+
+        // Strongly right-skewed (exponential quantiles)
+        let skewed: Vec<f64> = (1..=100).map(|i| -(1.0 - i as f64 / 101.0).ln()).collect();
+        let result = shapiro_wilk(skewed);
+        assert!(result.p_value < 0.001);
+        assert!(!result.is_normal);
+
+        // scipy.stats.shapiro([148, 154, 158, 160, 161, 162, 166, 170, 182, 195, 236])
+        let heights = vec![148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+        let result = shapiro_wilk(heights);
+        assert!((result.w - 0.7888).abs() < 0.001);
+        assert!((result.p_value - 0.0067).abs() < 0.001);
+
+        assert_eq!(shapiro_wilk(vec![1.0, 2.0]).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_student_t_test() {
+        let a = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = vec![3.0, 4.0, 5.0, 6.0, 7.0];
+        let options = TTestOptions {
+            equal_variance: Some(true),
+            ..Default::default()
+        };
+        let result = t_test(a, b, Some(options));
+        assert_eq!(result.degrees_of_freedom, 8.0);
+        assert!((result.t_statistic + 2.0).abs() < 1e-12);
+        assert!((result.p_value - 0.0805).abs() < 0.001);
+
+        assert_eq!(t_test(vec![1.0], vec![2.0, 3.0], None).p_value, 1.0);
+    }
+}
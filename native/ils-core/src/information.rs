@@ -0,0 +1,136 @@
+//! Information Measures
+//!
+//! Entropy and divergence of discrete distributions, e.g. to quantify drift
+//! in an order mix from one period to the next:
+//! - Shannon entropy
+//! - Kullback-Leibler divergence between probability (or count) vectors
+//! - Histogram-based KL divergence between raw samples
+
+use napi_derive::napi;
+
+/// Shannon entropy `-sum p log p` of a discrete distribution
+///
+/// Inputs are normalized to sum to 1, so raw counts work too; zero entries
+/// contribute nothing. Returns NaN for negative entries or an all-zero input.
+///
+/// # Arguments
+/// * `probabilities` - Probability (or count) of each category
+/// * `base` - Logarithm base, e.g. 2 for bits (default e, nats)
+#[napi]
+pub fn shannon_entropy(probabilities: Vec<f64>, base: Option<f64>) -> f64 {
+    let Some(p) = normalize(&probabilities) else {
+        return f64::NAN;
+    };
+    let entropy: f64 = p.iter().filter(|&&v| v > 0.0).map(|v| -v * v.ln()).sum();
+    entropy / log_base(base)
+}
+
+/// Kullback-Leibler divergence `D(p || q) = sum p log(p / q)`
+///
+/// How much information is lost when `q` (e.g. last month's mix) is used to
+/// describe `p` (this month's). Not symmetric; zero only when the
+/// distributions match. Both inputs are normalized; categories where `p > 0`
+/// but `q = 0` make the divergence infinite. Returns NaN for mismatched
+/// lengths, negative entries or all-zero inputs.
+///
+/// # Arguments
+/// * `p` - Probability (or count) of each category in the distribution of interest
+/// * `q` - Reference distribution over the same categories
+/// * `base` - Logarithm base (default e)
+#[napi]
+pub fn kl_divergence(p: Vec<f64>, q: Vec<f64>, base: Option<f64>) -> f64 {
+    let (Some(p), Some(q)) = (normalize(&p), normalize(&q)) else {
+        return f64::NAN;
+    };
+    if p.len() != q.len() {
+        return f64::NAN;
+    }
+    let divergence: f64 = p
+        .iter()
+        .zip(&q)
+        .filter(|(pi, _)| **pi > 0.0)
+        .map(|(pi, qi)| if *qi > 0.0 { pi * (pi / qi).ln() } else { f64::INFINITY })
+        .sum();
+    divergence / log_base(base)
+}
+
+/// KL divergence between two raw samples via shared histograms
+///
+/// Both samples are binned on equal-width bins spanning their combined
+/// range, and every bin gets a pseudo-count so empty bins in `reference` do
+/// not make the result infinite. NaN values are ignored.
+///
+/// # Arguments
+/// * `sample` - Sample of interest (e.g. this month's turnaround times)
+/// * `reference` - Reference sample (e.g. last month's)
+/// * `bins` - Number of bins (default 20)
+/// * `pseudo_count` - Count added to every bin of both histograms (default 0.5)
+#[napi]
+pub fn kl_divergence_samples(sample: Vec<f64>, reference: Vec<f64>, bins: Option<u32>, pseudo_count: Option<f64>) -> f64 {
+    let bins = bins.unwrap_or(20).max(1) as usize;
+    let pseudo_count = pseudo_count.unwrap_or(0.5).max(0.0);
+    let finite = |data: &[f64]| -> Vec<f64> { data.iter().copied().filter(|v| v.is_finite()).collect() };
+    let (sample, reference) = (finite(&sample), finite(&reference));
+    if sample.is_empty() || reference.is_empty() {
+        return f64::NAN;
+    }
+
+    let (min, max) = sample
+        .iter()
+        .chain(&reference)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    let width = (max - min) / bins as f64;
+    let histogram = |data: &[f64]| -> Vec<f64> {
+        let mut counts = vec![pseudo_count; bins];
+        for &v in data {
+            let bin = if width > 0.0 { ((v - min) / width) as usize } else { 0 };
+            counts[bin.min(bins - 1)] += 1.0;
+        }
+        counts
+    };
+    kl_divergence(histogram(&sample), histogram(&reference), None)
+}
+
+/// Scale non-negative values to sum to 1; `None` for negative, NaN or all-zero input
+fn normalize(values: &[f64]) -> Option<Vec<f64>> {
+    if values.iter().any(|v| v.is_nan() || *v < 0.0) {
+        return None;
+    }
+    let total: f64 = values.iter().sum();
+    if !(total > 0.0 && total.is_finite()) {
+        return None;
+    }
+    Some(values.iter().map(|v| v / total).collect())
+}
+
+fn log_base(base: Option<f64>) -> f64 {
+    base.filter(|b| *b > 0.0 && *b != 1.0).map_or(1.0, f64::ln)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_and_kl_divergence() {
+        assert!((shannon_entropy(vec![0.5, 0.5], Some(2.0)) - 1.0).abs() < 1e-12);
+        assert!((shannon_entropy(vec![1.0, 1.0, 1.0, 1.0, 0.0], None) - 4f64.ln()).abs() < 1e-12);
+        assert!(shannon_entropy(vec![0.5, -0.5], None).is_nan());
+
+        assert_eq!(kl_divergence(vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0], None), 0.0);
+        let d = kl_divergence(vec![0.5, 0.5], vec![0.9, 0.1], None);
+        assert!((d - (0.5 * (0.5f64 / 0.9).ln() + 0.5 * (0.5f64 / 0.1).ln())).abs() < 1e-12);
+        assert_eq!(kl_divergence(vec![0.5, 0.5], vec![1.0, 0.0], None), f64::INFINITY);
+        assert!(kl_divergence(vec![1.0], vec![0.5, 0.5], None).is_nan());
+    }
+
+    #[test]
+    fn test_kl_divergence_samples() {
+        let same: Vec<f64> = (0..1000).map(|i| (i % 100) as f64).collect();
+        let shifted: Vec<f64> = same.iter().map(|v| v + 50.0).collect();
+        assert!(kl_divergence_samples(same.clone(), same.clone(), None, None).abs() < 1e-12);
+        let drift = kl_divergence_samples(shifted, same.clone(), None, None);
+        assert!(drift > 1.0 && drift.is_finite());
+        assert!(kl_divergence_samples(vec![], same, None, None).is_nan());
+    }
+}
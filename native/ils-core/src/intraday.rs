@@ -0,0 +1,149 @@
+//! Intraday Forecasting
+//!
+//! Hour-level forecasts for shift planning, built by combining:
+//! - A daily-total forecast (`predict_next` over daily sums)
+//! - Learned intraday arrival profiles, one per weekday
+
+use napi_derive::napi;
+
+use crate::forecasting::predict_next;
+
+const HOURS_PER_DAY: usize = 24;
+const DAYS_PER_WEEK: usize = 7;
+
+/// Learn the intraday arrival profile for each weekday
+///
+/// Returns a 7 x 24 matrix where each row holds the share of the daily
+/// total arriving in each hour (rows sum to 1). Weekdays without history
+/// fall back to the profile pooled across all days.
+///
+/// # Arguments
+/// * `hourly_data` - Hourly history, starting at hour 0 of the first day
+/// * `start_weekday` - Day-of-week index (0-6) of the first day in the history
+#[napi]
+pub fn intraday_profile(hourly_data: Vec<f64>, start_weekday: u32) -> Vec<Vec<f64>> {
+    let start = start_weekday as usize % DAYS_PER_WEEK;
+    let mut weekday_totals = vec![vec![0.0; HOURS_PER_DAY]; DAYS_PER_WEEK];
+    let mut pooled_totals = vec![0.0; HOURS_PER_DAY];
+
+    for (day, hours) in hourly_data.chunks_exact(HOURS_PER_DAY).enumerate() {
+        let weekday = (start + day) % DAYS_PER_WEEK;
+        for (hour, &value) in hours.iter().enumerate() {
+            let value = value.max(0.0);
+            weekday_totals[weekday][hour] += value;
+            pooled_totals[hour] += value;
+        }
+    }
+
+    let pooled = normalize_profile(&pooled_totals)
+        .unwrap_or_else(|| vec![1.0 / HOURS_PER_DAY as f64; HOURS_PER_DAY]);
+
+    weekday_totals
+        .iter()
+        .map(|totals| normalize_profile(totals).unwrap_or_else(|| pooled.clone()))
+        .collect()
+}
+
+/// Forecast hourly volume for the coming days
+///
+/// Daily totals are forecast with `predict_next` (weekly seasonality), then
+/// spread across hours using the learned weekday profile. Only complete days
+/// of history are used.
+///
+/// # Arguments
+/// * `hourly_data` - Hourly history, starting at hour 0 of the first day
+/// * `start_weekday` - Day-of-week index (0-6) of the first day in the history
+/// * `days` - Number of future days to forecast
+#[napi]
+pub fn predict_hourly(hourly_data: Vec<f64>, start_weekday: u32, days: u32) -> Vec<HourlyForecastResult> {
+    let daily_totals: Vec<f64> = hourly_data
+        .chunks_exact(HOURS_PER_DAY)
+        .map(|hours| hours.iter().sum())
+        .collect();
+
+    if daily_totals.is_empty() {
+        return vec![];
+    }
+
+    let profiles = intraday_profile(hourly_data, start_weekday);
+    let daily_forecasts = predict_next(daily_totals.clone(), days, DAYS_PER_WEEK as u32);
+    let first_weekday = (start_weekday as usize + daily_totals.len()) % DAYS_PER_WEEK;
+
+    let mut results = Vec::with_capacity(daily_forecasts.len() * HOURS_PER_DAY);
+
+    for (day, forecast) in daily_forecasts.iter().enumerate() {
+        let weekday = (first_weekday + day) % DAYS_PER_WEEK;
+        for (hour, &share) in profiles[weekday].iter().enumerate() {
+            results.push(HourlyForecastResult {
+                day: day as u32,
+                hour: hour as u32,
+                weekday: weekday as u32,
+                predicted_value: forecast.predicted_value * share,
+                lower_bound: forecast.lower_bound * share,
+                upper_bound: forecast.upper_bound * share,
+                share,
+            });
+        }
+    }
+
+    results
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HourlyForecastResult {
+    pub day: u32,
+    pub hour: u32,
+    pub weekday: u32,
+    pub predicted_value: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub share: f64,
+}
+
+/// Scale hourly totals into shares, or `None` when there is no volume
+fn normalize_profile(totals: &[f64]) -> Option<Vec<f64>> {
+    let sum: f64 = totals.iter().sum();
+    if sum <= 0.0 {
+        return None;
+    }
+    Some(totals.iter().map(|t| t / sum).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_history(weeks: usize) -> Vec<f64> {
+        let mut data = Vec::new();
+        for day in 0..weeks * 7 {
+            let weekday = day % 7;
+            for hour in 0..24 {
+                let base = if (9..17).contains(&hour) { 10.0 } else { 1.0 };
+                let weekend_factor = if weekday >= 5 { 0.5 } else { 1.0 };
+                data.push(base * weekend_factor);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_intraday_profile() {
+        let profiles = intraday_profile(synthetic_history(3), 0);
+        assert_eq!(profiles.len(), 7);
+        for profile in &profiles {
+            assert_eq!(profile.len(), 24);
+            assert!((profile.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+            assert!(profile[12] > profile[3]);
+        }
+    }
+
+    #[test]
+    fn test_predict_hourly() {
+        let forecasts = predict_hourly(synthetic_history(4), 0, 2);
+        assert_eq!(forecasts.len(), 48);
+        assert_eq!(forecasts[24].day, 1);
+        assert!(forecasts.iter().all(|f| f.lower_bound <= f.upper_bound));
+        assert!(forecasts[12].predicted_value > forecasts[3].predicted_value);
+    }
+}
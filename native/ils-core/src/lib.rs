@@ -8,10 +8,70 @@
 mod statistics;
 mod forecasting;
 mod anomaly;
+mod intraday;
+mod temporal;
+mod risk;
+mod staffing;
+mod boosting;
+mod forest;
+mod neighbors;
+mod model_selection;
+mod gaussian_process;
+mod encoding;
+mod embedding;
+mod similarity;
+mod signal;
+mod econometrics;
+mod growth;
+mod report;
+mod monitoring;
+mod ranking;
+mod hypothesis;
+mod regression;
+mod clustering;
+mod sampling;
+mod information;
+mod sketch;
+mod transform;
+mod circular;
+mod arima;
+mod prophet;
+mod forecasters;
+mod linalg;
+mod tree;
+mod optimize;
 
 pub use statistics::*;
 pub use forecasting::*;
 pub use anomaly::*;
+pub use intraday::*;
+pub use temporal::*;
+pub use risk::*;
+pub use staffing::*;
+pub use boosting::*;
+pub use forest::*;
+pub use neighbors::*;
+pub use model_selection::*;
+pub use gaussian_process::*;
+pub use encoding::*;
+pub use embedding::*;
+pub use similarity::*;
+pub use signal::*;
+pub use econometrics::*;
+pub use growth::*;
+pub use report::*;
+pub use monitoring::*;
+pub use ranking::*;
+pub use hypothesis::*;
+pub use regression::*;
+pub use clustering::*;
+pub use sampling::*;
+pub use information::*;
+pub use sketch::*;
+pub use transform::*;
+pub use circular::*;
+pub use arima::*;
+pub use prophet::*;
 
 use napi_derive::napi;
 
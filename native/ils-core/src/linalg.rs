@@ -0,0 +1,293 @@
+//! Dense Linear Algebra Helpers
+//!
+//! Small row-major matrix routines shared by the regression and model
+//! fitting code. Matrices here are tiny (features x features), so plain
+//! `Vec<Vec<f64>>` with partial pivoting is both simple and fast enough.
+
+use napi::{Error, Status};
+
+/// Solve `a * x = b` by Gaussian elimination with partial pivoting
+///
+/// Returns `None` when the system is singular.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        for row in (col + 1)..n {
+            let factor = a[row][col] / a[col][col];
+            if factor == 0.0 {
+                continue;
+            }
+            let (upper, lower) = a.split_at_mut(row);
+            for (target, source) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *target -= factor * source;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = a[row][(row + 1)..].iter().zip(&x[(row + 1)..]).map(|(c, v)| c * v).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Invert a square matrix, or `None` when it is singular
+///
+/// Gauss-Jordan elimination of `[a | I]` with partial pivoting, so the cost
+/// stays cubic for the larger designs of seasonal-index regressions.
+pub(crate) fn invert(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    if a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    let mut m: Vec<Vec<f64>> = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut augmented = row.clone();
+            augmented.resize(2 * n, 0.0);
+            augmented[n + i] = 1.0;
+            augmented
+        })
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))?;
+        if m[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        let scale = m[col][col];
+        m[col].iter_mut().for_each(|v| *v /= scale);
+
+        let pivot_row = m[col].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == col || factor == 0.0 {
+                continue;
+            }
+            for (target, source) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *target -= factor * source;
+            }
+        }
+    }
+    Some(m.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+/// Ordinary (or weighted) least-squares fit
+pub(crate) struct LeastSquares {
+    pub coefficients: Vec<f64>,
+    /// `(X' W X)^-1`; multiply by `sigma2` for the coefficient covariance
+    pub xtx_inv: Vec<Vec<f64>>,
+    /// Residual sum of squares (weighted when weights are given)
+    pub rss: f64,
+    /// Residual degrees of freedom `n - p`
+    pub df: usize,
+}
+
+impl LeastSquares {
+    /// Unbiased residual variance estimate
+    pub(crate) fn sigma2(&self) -> f64 {
+        self.rss / self.df.max(1) as f64
+    }
+
+    /// Standard error of each coefficient
+    pub(crate) fn std_errors(&self) -> Vec<f64> {
+        let sigma2 = self.sigma2();
+        (0..self.coefficients.len()).map(|i| (self.xtx_inv[i][i] * sigma2).max(0.0).sqrt()).collect()
+    }
+}
+
+/// Least-squares fit of `y` on the columns of `x` (include a column of ones for an intercept)
+///
+/// Returns `None` when there are no residual degrees of freedom or `X'X` is singular.
+pub(crate) fn least_squares(x: &[Vec<f64>], y: &[f64]) -> Option<LeastSquares> {
+    let p = x.first().map_or(0, |row| row.len());
+    if x.len() != y.len() || x.len() <= p || p == 0 {
+        return None;
+    }
+    let xtx_inv = invert(&gram(x, None))?;
+    let xty = xt_y(x, y, None);
+    let coefficients: Vec<f64> = xtx_inv.iter().map(|row| dot(row, &xty)).collect();
+    let rss = x.iter().zip(y).map(|(row, target)| (target - dot(row, &coefficients)).powi(2)).sum();
+    Some(LeastSquares {
+        coefficients,
+        xtx_inv,
+        rss,
+        df: x.len() - p,
+    })
+}
+
+/// Cholesky factor `L` (lower triangular, `a = L L'`) of a symmetric
+/// positive-definite matrix, or `None` when `a` is not positive definite
+pub(crate) fn cholesky(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = l[i][..j].iter().zip(&l[j][..j]).map(|(x, y)| x * y).sum();
+            if i == j {
+                let diag = a[i][i] - sum;
+                if diag <= 0.0 {
+                    return None;
+                }
+                l[i][j] = diag.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solve `L z = b` for lower-triangular `L`
+pub(crate) fn forward_substitute(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let mut z = vec![0.0; b.len()];
+    for i in 0..b.len() {
+        let sum: f64 = l[i][..i].iter().zip(&z[..i]).map(|(x, y)| x * y).sum();
+        z[i] = (b[i] - sum) / l[i][i];
+    }
+    z
+}
+
+/// Solve `L' x = z` for lower-triangular `L`
+pub(crate) fn back_substitute_transposed(l: &[Vec<f64>], z: &[f64]) -> Vec<f64> {
+    let n = z.len();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = ((i + 1)..n).map(|k| l[k][i] * x[k]).sum();
+        x[i] = (z[i] - sum) / l[i][i];
+    }
+    x
+}
+
+/// Compute `X' W X` for row-major `x` and optional weights, one per row of `x`
+pub(crate) fn gram(x: &[Vec<f64>], weights: Option<&[f64]>) -> Vec<Vec<f64>> {
+    let p = x.first().map_or(0, |row| row.len());
+    let mut result = vec![vec![0.0; p]; p];
+    for (i, row) in x.iter().enumerate() {
+        let w = weights.map_or(1.0, |w| w[i]);
+        for a in 0..p {
+            let scaled = w * row[a];
+            for b in a..p {
+                result[a][b] += scaled * row[b];
+            }
+        }
+    }
+    symmetrize_upper(&mut result);
+    result
+}
+
+/// Mirror the upper triangle of a square matrix into its lower triangle
+pub(crate) fn symmetrize_upper(m: &mut [Vec<f64>]) {
+    for a in 1..m.len() {
+        let (upper, lower) = m.split_at_mut(a);
+        for (b, row) in upper.iter().enumerate() {
+            lower[0][b] = row[a];
+        }
+    }
+}
+
+/// Compute `X' W y` for row-major `x` and optional weights, one per row of `x`
+pub(crate) fn xt_y(x: &[Vec<f64>], y: &[f64], weights: Option<&[f64]>) -> Vec<f64> {
+    let p = x.first().map_or(0, |row| row.len());
+    let mut result = vec![0.0; p];
+    for (i, row) in x.iter().enumerate() {
+        let w = weights.map_or(1.0, |w| w[i]);
+        for (acc, value) in result.iter_mut().zip(row) {
+            *acc += w * value * y[i];
+        }
+    }
+    result
+}
+
+/// Dot product of two equal-length slices
+pub(crate) fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Fails unless `x` has `n_targets` rows, all of the same width
+pub(crate) fn check_shape(x: &[Vec<f64>], n_targets: usize) -> napi::Result<()> {
+    if x.len() != n_targets {
+        return Err(Error::new(Status::InvalidArg, format!("{} feature rows but {} targets", x.len(), n_targets)));
+    }
+    check_width(x).map(|_| ())
+}
+
+/// Common width of the rows of `x` (0 without rows); fails for ragged rows
+pub(crate) fn check_width(x: &[Vec<f64>]) -> napi::Result<usize> {
+    let width = x.first().map_or(0, |row| row.len());
+    match x.iter().position(|row| row.len() != width) {
+        Some(i) => Err(Error::new(
+            Status::InvalidArg,
+            format!("feature row {} has {} values, expected {}", i, x[i].len(), width),
+        )),
+        None => Ok(width),
+    }
+}
+
+/// Column means and standard deviations (zero-variance columns scale by 1)
+pub(crate) fn column_scaling(x: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+    let n = x.len() as f64;
+    let p = x.first().map_or(0, |row| row.len());
+    let means: Vec<f64> = (0..p).map(|j| x.iter().map(|row| row[j]).sum::<f64>() / n).collect();
+    let scales: Vec<f64> = (0..p)
+        .map(|j| {
+            let var = x.iter().map(|row| (row[j] - means[j]).powi(2)).sum::<f64>() / n;
+            if var > 0.0 { var.sqrt() } else { 1.0 }
+        })
+        .collect();
+    (means, scales)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let a = vec![vec![2.0, 1.0], vec![1.0, 3.0]];
+        let x = solve(a, vec![3.0, 5.0]).unwrap();
+        assert!((x[0] - 0.8).abs() < 1e-10);
+        assert!((x[1] - 1.4).abs() < 1e-10);
+        assert!(solve(vec![vec![1.0, 2.0], vec![2.0, 4.0]], vec![1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_least_squares() {
+        let x: Vec<Vec<f64>> = (0..10).map(|i| vec![1.0, i as f64]).collect();
+        let y: Vec<f64> = (0..10).map(|i| 3.0 + 2.0 * i as f64 + if i % 2 == 0 { 0.5 } else { -0.5 }).collect();
+        let fit = least_squares(&x, &y).unwrap();
+        assert!((fit.coefficients[1] - 2.0).abs() < 0.1);
+        assert_eq!(fit.df, 8);
+        assert!(fit.std_errors()[1] > 0.0);
+        assert!(least_squares(&x[..2], &y[..2]).is_none());
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = vec![vec![4.0, 2.0], vec![2.0, 3.0]];
+        let l = cholesky(&a).unwrap();
+        assert!((l[0][0] - 2.0).abs() < 1e-10);
+        assert!((l[1][0] - 1.0).abs() < 1e-10);
+        assert!((l[1][1] - 2.0_f64.sqrt()).abs() < 1e-10);
+
+        let x = back_substitute_transposed(&l, &forward_substitute(&l, &[2.0, 5.0]));
+        assert!((4.0 * x[0] + 2.0 * x[1] - 2.0).abs() < 1e-10);
+        assert!((2.0 * x[0] + 3.0 * x[1] - 5.0).abs() < 1e-10);
+        assert!(cholesky(&[vec![1.0, 2.0], vec![2.0, 1.0]]).is_none());
+    }
+}
@@ -0,0 +1,818 @@
+//! Model Selection
+//!
+//! Index-based data splitting for validating the native models:
+//! - Seeded train/test split
+//! - K-fold cross-validation folds
+//! - Time-series splits (training always precedes testing, no leakage)
+//! - Parallel grid / random hyperparameter search
+//! - Forecasting model comparison (AIC/BIC and rolling-origin CV errors)
+//! - Rolling-origin backtests of one forecaster with per-fold accuracy
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::boosting::{GradientBoostingOptions, GradientBoostingRegressor};
+use crate::forecasters::{check_methods, fit_method, MethodFit, DEFAULT_METHODS};
+use crate::forecasting::holt_winters_forecast;
+use crate::forest::{RandomForestOptions, RandomForestRegressor};
+use crate::linalg::{check_shape, check_width};
+use crate::neighbors::{KnnOptions, KnnRegressor};
+use crate::sampling::Rng;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SplitIndices {
+    pub train: Vec<u32>,
+    pub test: Vec<u32>,
+}
+
+/// Split `n_samples` row indices into train and test sets
+///
+/// # Arguments
+/// * `n_samples` - Number of rows
+/// * `test_fraction` - Fraction of rows assigned to the test set (0-1)
+/// * `shuffle` - Shuffle before splitting (default true); false keeps row order
+/// * `seed` - Shuffle seed
+#[napi]
+pub fn train_test_split(n_samples: u32, test_fraction: f64, shuffle: Option<bool>, seed: Option<u32>) -> SplitIndices {
+    let n = n_samples as usize;
+    let mut rows: Vec<u32> = (0..n_samples).collect();
+    if shuffle.unwrap_or(true) {
+        Rng::new(seed.unwrap_or(42) as u64).shuffle(&mut rows);
+    }
+
+    let n_test = ((n as f64 * test_fraction.clamp(0.0, 1.0)).round() as usize).min(n);
+    let test = rows.split_off(n - n_test);
+    SplitIndices { train: rows, test }
+}
+
+/// K-fold cross-validation indices
+///
+/// Each row appears in exactly one test fold; fold sizes differ by at most one.
+#[napi]
+pub fn k_fold_indices(n_samples: u32, k: u32, shuffle: Option<bool>, seed: Option<u32>) -> Vec<SplitIndices> {
+    let n = n_samples as usize;
+    let k = (k as usize).clamp(1, n.max(1));
+    let mut rows: Vec<u32> = (0..n_samples).collect();
+    if shuffle.unwrap_or(true) {
+        Rng::new(seed.unwrap_or(42) as u64).shuffle(&mut rows);
+    }
+
+    let mut folds = Vec::with_capacity(k);
+    let mut start = 0;
+    for fold in 0..k {
+        let size = n / k + usize::from(fold < n % k);
+        let test = rows[start..start + size].to_vec();
+        let train = rows[..start].iter().chain(&rows[start + size..]).copied().collect();
+        folds.push(SplitIndices { train, test });
+        start += size;
+    }
+    folds
+}
+
+/// Expanding-window time-series splits
+///
+/// Test blocks are consecutive and move forward through time; each training
+/// set contains only rows before its test block, minus an optional `gap`
+/// that guards against leakage from autocorrelated neighbors.
+///
+/// # Arguments
+/// * `n_samples` - Number of rows, in time order
+/// * `n_splits` - Number of train/test splits
+/// * `test_size` - Rows per test block (default `n_samples / (n_splits + 1)`)
+/// * `gap` - Rows dropped between the end of training and the test block
+/// * `max_train_size` - Cap on training rows (sliding window when set)
+#[napi]
+pub fn time_series_split(
+    n_samples: u32,
+    n_splits: u32,
+    test_size: Option<u32>,
+    gap: Option<u32>,
+    max_train_size: Option<u32>,
+) -> Vec<SplitIndices> {
+    let n = n_samples as usize;
+    let splits = n_splits.max(1) as usize;
+    let test_size = test_size.map_or(n / (splits + 1), |t| t as usize);
+    let gap = gap.unwrap_or(0) as usize;
+
+    if test_size == 0 || test_size * splits + gap >= n {
+        return vec![];
+    }
+
+    (0..splits)
+        .map(|s| {
+            let test_start = n - (splits - s) * test_size;
+            let train_end = test_start - gap;
+            let train_start = max_train_size.map_or(0, |m| train_end.saturating_sub(m as usize));
+            SplitIndices {
+                train: (train_start as u32..train_end as u32).collect(),
+                test: (test_start as u32..(test_start + test_size) as u32).collect(),
+            }
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ParamGridEntry {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ParamValue {
+    pub name: String,
+    pub value: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SearchData {
+    /// Feature rows (ML models); omit for forecasting models
+    pub x: Option<Vec<Vec<f64>>>,
+    /// Targets (ML models) or the time series (forecasting models)
+    pub y: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct CvConfig {
+    /// "kfold" (default for ML models) or "time_series" (always used for forecasting models)
+    pub method: Option<String>,
+    /// Number of folds / splits (default 5)
+    pub n_splits: Option<u32>,
+    /// "grid" (default) or "random"
+    pub search: Option<String>,
+    /// Parameter combinations sampled in random search (default 10)
+    pub n_iter: Option<u32>,
+    /// "mse" (default) or "mae"
+    pub metric: Option<String>,
+    /// Seasonal cycle for forecasting models (default 7)
+    pub season_length: Option<u32>,
+    /// Forecast horizon per time-series fold (default n / (n_splits + 1))
+    pub horizon: Option<u32>,
+    pub seed: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SearchCandidate {
+    pub params: Vec<ParamValue>,
+    pub mean_score: f64,
+    pub std_score: f64,
+    pub fold_scores: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GridSearchResult {
+    pub best_params: Vec<ParamValue>,
+    /// Cross-validated error of the best parameters (lower is better)
+    pub best_score: f64,
+    pub candidates: Vec<SearchCandidate>,
+}
+
+/// Models `grid_search` can tune
+const SEARCH_MODELS: &[&str] = &["gradient_boosting", "random_forest", "knn", "holt_winters", "simple_exponential_smoothing"];
+
+/// Evaluate hyperparameter combinations with cross-validation, in parallel
+///
+/// Fails for an unknown `model_spec`, feature rows of different widths, or
+/// when an ML model is given no feature rows or a different number of rows
+/// than targets.
+///
+/// # Arguments
+/// * `model_spec` - "gradient_boosting", "random_forest", "knn",
+///   "holt_winters" or "simple_exponential_smoothing"
+/// * `param_grid` - Candidate values per parameter; names match the model's
+///   options (e.g. `learning_rate`, `max_depth`, `k`, `alpha`, `gamma`)
+/// * `data` - Training data
+/// * `cv_config` - Split, search and scoring configuration
+#[napi]
+pub fn grid_search(
+    model_spec: String,
+    param_grid: Vec<ParamGridEntry>,
+    data: SearchData,
+    cv_config: Option<CvConfig>,
+) -> napi::Result<GridSearchResult> {
+    if !SEARCH_MODELS.contains(&model_spec.as_str()) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("unknown model \"{}\"; expected one of {}", model_spec, SEARCH_MODELS.join(", ")),
+        ));
+    }
+    let config = cv_config.unwrap_or_default();
+    let n_splits = config.n_splits.unwrap_or(5).max(1);
+    let is_forecaster = matches!(model_spec.as_str(), "holt_winters" | "simple_exponential_smoothing");
+    match &data.x {
+        None if !is_forecaster => {
+            return Err(Error::new(Status::InvalidArg, format!("\"{}\" needs feature rows in `x`", model_spec)))
+        }
+        Some(x) if is_forecaster => {
+            check_width(x)?;
+        }
+        Some(x) => check_shape(x, data.y.len())?,
+        None => {}
+    }
+
+    let mut combinations = expand_grid(&param_grid);
+    if config.search.as_deref() == Some("random") {
+        let mut rng = Rng::new(config.seed.unwrap_or(42) as u64);
+        rng.shuffle(&mut combinations);
+        combinations.truncate(config.n_iter.unwrap_or(10) as usize);
+    }
+
+    let n = data.y.len() as u32;
+    let folds = if is_forecaster || config.method.as_deref() == Some("time_series") {
+        time_series_split(n, n_splits, config.horizon, None, None)
+    } else {
+        k_fold_indices(n, n_splits, Some(true), config.seed)
+    };
+
+    let jobs: Vec<(usize, usize)> = (0..combinations.len())
+        .flat_map(|c| (0..folds.len()).map(move |f| (c, f)))
+        .collect();
+
+    let scores: Vec<Option<f64>> = jobs
+        .par_iter()
+        .map(|&(c, f)| evaluate_fold(&model_spec, &combinations[c], &data, &folds[f], &config))
+        .collect();
+
+    let mut candidates: Vec<SearchCandidate> = combinations
+        .iter()
+        .enumerate()
+        .filter_map(|(c, params)| {
+            let fold_scores: Vec<f64> = scores[c * folds.len()..(c + 1) * folds.len()].iter().flatten().copied().collect();
+            if fold_scores.is_empty() {
+                return None;
+            }
+            let k = fold_scores.len() as f64;
+            let mean_score = fold_scores.iter().sum::<f64>() / k;
+            let std_score = (fold_scores.iter().map(|s| (s - mean_score).powi(2)).sum::<f64>() / k).sqrt();
+            Some(SearchCandidate {
+                params: params.clone(),
+                mean_score,
+                std_score,
+                fold_scores,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.mean_score.total_cmp(&b.mean_score));
+
+    Ok(GridSearchResult {
+        best_params: candidates.first().map_or_else(Vec::new, |c| c.params.clone()),
+        best_score: candidates.first().map_or(f64::NAN, |c| c.mean_score),
+        candidates,
+    })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ModelComparisonOptions {
+    /// Methods to compare (default: naive, seasonal_naive, drift, linear_trend,
+    /// ses, holt, holt_winters)
+    pub models: Option<Vec<String>>,
+    /// Seasonal cycle for the seasonal methods (default 7)
+    pub season_length: Option<u32>,
+    /// Number of rolling-origin folds (default 3)
+    pub n_splits: Option<u32>,
+    /// Forecast horizon per fold (default n / (n_splits + 1), at most 2 seasons)
+    pub horizon: Option<u32>,
+    /// Ranking criterion: "aic", "bic", "cv_mae" (default) or "cv_rmse"
+    pub criterion: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ModelScore {
+    pub model: String,
+    /// Estimated parameters and initial states counted by the criteria
+    pub n_params: u32,
+    pub params: Vec<ParamValue>,
+    /// In-sample one-step-ahead RMSE
+    pub in_sample_rmse: f64,
+    pub aic: f64,
+    pub bic: f64,
+    /// Mean over folds of the out-of-sample MAE / RMSE
+    pub cv_mae: f64,
+    pub cv_rmse: f64,
+    /// Folds the model could be fitted on
+    pub cv_folds: u32,
+    /// 1-based rank by the chosen criterion
+    pub rank: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ModelComparison {
+    pub best_model: String,
+    pub criterion: String,
+    /// One row per model that could be fitted, best first
+    pub models: Vec<ModelScore>,
+}
+
+/// Fit candidate forecasting models and compare them side by side
+///
+/// Information criteria use the in-sample one-step-ahead errors
+/// (`n ln(SSE / n) + 2k` and `n ln(SSE / n) + k ln(n)`), counted only after
+/// the longest warm-up among the fitted models (e.g. the first season for
+/// Holt-Winters) so every model is scored on the same observations and the
+/// criteria are comparable. CV errors come from
+/// rolling-origin folds where each model is refitted on the history before
+/// the fold. Models with too little history to fit are left out of the
+/// table. Fails for unknown models.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `options` - Models, seasonality, folds and ranking criterion
+#[napi]
+pub fn compare_models(data: Vec<f64>, options: Option<ModelComparisonOptions>) -> napi::Result<ModelComparison> {
+    let options = options.unwrap_or_default();
+    let season_len = options.season_length.unwrap_or(7) as usize;
+    let n_splits = options.n_splits.unwrap_or(3).max(1);
+    let criterion = match options.criterion.as_deref() {
+        Some("aic") => "aic",
+        Some("bic") => "bic",
+        Some("cv_rmse") => "cv_rmse",
+        _ => "cv_mae",
+    };
+    let models: Vec<String> = options
+        .models
+        .unwrap_or_else(|| DEFAULT_METHODS.iter().map(|m| m.to_string()).collect());
+    check_methods(&models)?;
+
+    let n = data.len();
+    let horizon = options.horizon.unwrap_or_else(|| (n / (n_splits as usize + 1)).min(2 * season_len.max(1)) as u32);
+    let folds = time_series_split(n as u32, n_splits, Some(horizon), None, None);
+
+    let fits: Vec<(&String, MethodFit)> = models
+        .par_iter()
+        .filter_map(|model| Some((model, fit_method(model, &data, season_len, 0)?)))
+        .collect();
+    let warm_up = fits
+        .iter()
+        .map(|(_, fit)| fit.fitted.iter().position(|f| f.is_finite()).unwrap_or(n))
+        .max()
+        .unwrap_or(0);
+    let mut scores: Vec<ModelScore> = fits
+        .into_par_iter()
+        .filter_map(|(model, fit)| score_model(model, fit, &data, warm_up, season_len, &folds))
+        .collect();
+    let key = |s: &ModelScore| match criterion {
+        "aic" => s.aic,
+        "bic" => s.bic,
+        "cv_rmse" => s.cv_rmse,
+        _ => s.cv_mae,
+    };
+    scores.sort_by(|a, b| key(a).total_cmp(&key(b)));
+    for (i, score) in scores.iter_mut().enumerate() {
+        score.rank = i as u32 + 1;
+    }
+
+    Ok(ModelComparison {
+        best_model: scores.first().map_or_else(String::new, |s| s.model.clone()),
+        criterion: criterion.to_string(),
+        models: scores,
+    })
+}
+
+/// Scores of one fitted model; in-sample errors count from `warm_up` on
+fn score_model(model: &str, fit: MethodFit, data: &[f64], warm_up: usize, season_len: usize, folds: &[SplitIndices]) -> Option<ModelScore> {
+    let (sse, n) = fit
+        .residuals(data)
+        .iter()
+        .skip(warm_up)
+        .filter(|r| r.is_finite())
+        .fold((0.0, 0), |(sse, n), r| (sse + r * r, n + 1));
+    if n == 0 {
+        return None;
+    }
+    let k = fit.n_params as f64;
+    let log_likelihood_term = n as f64 * (sse / n as f64).max(f64::MIN_POSITIVE).ln();
+
+    let fold_errors: Vec<(f64, f64)> = folds
+        .iter()
+        .filter_map(|fold| {
+            let train = &data[..fold.train.len()];
+            let test: Vec<f64> = fold.test.iter().map(|&i| data[i as usize]).collect();
+            let forecast = fit_method(model, train, season_len, test.len())?.forecast;
+            Some((score(&forecast, &test, Some("mae")), score(&forecast, &test, Some("mse")).sqrt()))
+        })
+        .collect();
+    let folds_used = fold_errors.len();
+    let average = |values: Vec<f64>| if values.is_empty() { f64::NAN } else { values.iter().sum::<f64>() / values.len() as f64 };
+
+    Some(ModelScore {
+        model: model.to_string(),
+        n_params: fit.n_params as u32,
+        params: fit.params.into_iter().map(|(name, value)| ParamValue { name, value }).collect(),
+        in_sample_rmse: (sse / n as f64).sqrt(),
+        aic: log_likelihood_term + 2.0 * k,
+        bic: log_likelihood_term + k * (n as f64).ln(),
+        cv_mae: average(fold_errors.iter().map(|e| e.0).collect()),
+        cv_rmse: average(fold_errors.iter().map(|e| e.1).collect()),
+        cv_folds: folds_used as u32,
+        rank: 0,
+    })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct BacktestOptions {
+    /// Forecaster, any `fit_forecaster` method (default "predict_next")
+    pub model: Option<String>,
+    /// Seasonal cycle passed to the forecaster (default 7)
+    pub season_length: Option<u32>,
+    /// Periods forecast from each origin (default 7)
+    pub horizon: Option<u32>,
+    /// Number of origins, one horizon apart and ending at the last full
+    /// horizon (default: as many as fit in the second half of the series)
+    pub n_splits: Option<u32>,
+    /// Train on at most this many periods before each origin, a sliding
+    /// window (default: all history, an expanding window)
+    pub max_train_size: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BacktestFold {
+    /// Index of the first forecast period
+    pub origin: u32,
+    pub train_start: u32,
+    pub train_size: u32,
+    pub predictions: Vec<f64>,
+    pub actuals: Vec<f64>,
+    pub mae: f64,
+    pub rmse: f64,
+    /// Mean absolute percentage error over non-zero actuals (NaN when all are zero)
+    pub mape: f64,
+    /// Mean of prediction minus actual (positive = overforecast)
+    pub bias: f64,
+    /// MAE relative to the in-sample seasonal-naive MAE of the training window
+    pub mase: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    pub model: String,
+    /// Folds the forecaster could be fitted on, in time order
+    pub folds: Vec<BacktestFold>,
+    /// Accuracy over every forecast point of every fold
+    pub mae: f64,
+    pub rmse: f64,
+    pub mape: f64,
+    pub bias: f64,
+    /// Mean of the fold MASE values
+    pub mase: f64,
+    /// MAE at each step ahead (1, 2, ..) across folds
+    pub mae_by_horizon: Vec<f64>,
+}
+
+/// Rolling-origin backtest of one forecaster
+///
+/// Takes its origins from `time_series_split` with one horizon per test
+/// block; at each origin the forecaster is refitted on the training window
+/// (everything before the origin, or at most `max_train_size` periods) and
+/// scored on the next `horizon` periods. Folds run in parallel. Folds the
+/// forecaster cannot fit are skipped; with no folds left the metrics are
+/// NaN. Fails for an unknown model.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `options` - Forecaster, horizon, number of origins and window length
+#[napi]
+pub fn backtest(data: Vec<f64>, options: Option<BacktestOptions>) -> napi::Result<BacktestResult> {
+    let options = options.unwrap_or_default();
+    let model = options.model.unwrap_or_else(|| "predict_next".to_string());
+    check_methods(std::slice::from_ref(&model))?;
+    let season_len = options.season_length.unwrap_or(7) as usize;
+    let horizon = options.horizon.unwrap_or(7).max(1) as usize;
+    let n = data.len();
+    let n_splits = options.n_splits.map_or((n - n / 2) / horizon, |s| s as usize).max(1);
+
+    let splits = time_series_split(n as u32, n_splits as u32, Some(horizon as u32), None, options.max_train_size);
+    let folds: Vec<BacktestFold> = splits
+        .par_iter()
+        .filter_map(|split| {
+            let (train_start, origin) = (*split.train.first()? as usize, split.test[0] as usize);
+            let train = &data[train_start..origin];
+            let actuals = data[origin..origin + horizon].to_vec();
+            let predictions = fit_method(&model, train, season_len, horizon)?.forecast;
+            let accuracy = accuracy(&predictions, &actuals);
+            let lag = if season_len > 1 && train.len() > season_len { season_len } else { 1 };
+            let scale = train.iter().skip(lag).zip(train).map(|(a, b)| (a - b).abs()).sum::<f64>() / train.len().saturating_sub(lag).max(1) as f64;
+            Some(BacktestFold {
+                origin: origin as u32,
+                train_start: train_start as u32,
+                train_size: train.len() as u32,
+                mae: accuracy.0,
+                rmse: accuracy.1,
+                mape: accuracy.2,
+                bias: accuracy.3,
+                mase: if scale > 0.0 { accuracy.0 / scale } else { f64::NAN },
+                predictions,
+                actuals,
+            })
+        })
+        .collect();
+
+    let predictions: Vec<f64> = folds.iter().flat_map(|f| f.predictions.iter().copied()).collect();
+    let actuals: Vec<f64> = folds.iter().flat_map(|f| f.actuals.iter().copied()).collect();
+    let (mae, rmse, mape, bias) = accuracy(&predictions, &actuals);
+    let mae_by_horizon = if folds.is_empty() {
+        Vec::new()
+    } else {
+        (0..horizon)
+            .map(|h| folds.iter().map(|f| (f.predictions[h] - f.actuals[h]).abs()).sum::<f64>() / folds.len() as f64)
+            .collect()
+    };
+    let mase = folds.iter().map(|f| f.mase).sum::<f64>() / folds.len() as f64;
+    Ok(BacktestResult {
+        model,
+        folds,
+        mae,
+        rmse,
+        mape,
+        bias,
+        mase,
+        mae_by_horizon,
+    })
+}
+
+/// `(MAE, RMSE, MAPE over non-zero actuals, bias)`, NaN for empty input
+fn accuracy(predictions: &[f64], actuals: &[f64]) -> (f64, f64, f64, f64) {
+    if predictions.is_empty() {
+        return (f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+    }
+    let percentages: Vec<f64> = predictions
+        .iter()
+        .zip(actuals)
+        .filter(|(_, a)| **a != 0.0)
+        .map(|(p, a)| ((p - a) / a).abs() * 100.0)
+        .collect();
+    let mape = if percentages.is_empty() { f64::NAN } else { percentages.iter().sum::<f64>() / percentages.len() as f64 };
+    let bias = predictions.iter().zip(actuals).map(|(p, a)| p - a).sum::<f64>() / predictions.len() as f64;
+    (score(predictions, actuals, Some("mae")), score(predictions, actuals, None).sqrt(), mape, bias)
+}
+
+/// Cartesian product of all parameter values
+fn expand_grid(grid: &[ParamGridEntry]) -> Vec<Vec<ParamValue>> {
+    grid.iter().fold(vec![vec![]], |acc, entry| {
+        acc.iter()
+            .flat_map(|partial| {
+                entry.values.iter().map(move |&value| {
+                    let mut next = partial.clone();
+                    next.push(ParamValue { name: entry.name.clone(), value });
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+fn param(params: &[ParamValue], name: &str) -> Option<f64> {
+    params.iter().find(|p| p.name == name).map(|p| p.value)
+}
+
+fn param_u32(params: &[ParamValue], name: &str) -> Option<u32> {
+    param(params, name).map(|v| v.max(0.0).round() as u32)
+}
+
+fn score(predictions: &[f64], actuals: &[f64], metric: Option<&str>) -> f64 {
+    let n = predictions.len().max(1) as f64;
+    let errors = predictions.iter().zip(actuals).map(|(p, a)| p - a);
+    match metric {
+        Some("mae") => errors.map(f64::abs).sum::<f64>() / n,
+        _ => errors.map(|e| e * e).sum::<f64>() / n,
+    }
+}
+
+fn evaluate_fold(
+    model_spec: &str,
+    params: &[ParamValue],
+    data: &SearchData,
+    fold: &SplitIndices,
+    config: &CvConfig,
+) -> Option<f64> {
+    let select_y = |rows: &[u32]| -> Vec<f64> { rows.iter().map(|&i| data.y[i as usize]).collect() };
+    let train_y = select_y(&fold.train);
+    let test_y = select_y(&fold.test);
+    let metric = config.metric.as_deref();
+
+    if matches!(model_spec, "holt_winters" | "simple_exponential_smoothing") {
+        let alpha = param(params, "alpha").unwrap_or(0.3);
+        let (beta, gamma, season_len) = if model_spec == "holt_winters" {
+            (
+                param(params, "beta").unwrap_or(0.1),
+                param(params, "gamma").unwrap_or(0.1),
+                param_u32(params, "season_length").unwrap_or(config.season_length.unwrap_or(7)) as usize,
+            )
+        } else {
+            (0.0, 0.0, 0)
+        };
+        let forecast = holt_winters_forecast(&train_y, alpha, beta, gamma, season_len, test_y.len());
+        return Some(score(&forecast, &test_y, metric));
+    }
+
+    let x = data.x.as_ref()?;
+    let select_x = |rows: &[u32]| -> Vec<Vec<f64>> { rows.iter().map(|&i| x[i as usize].clone()).collect() };
+    let (train_x, test_x) = (select_x(&fold.train), select_x(&fold.test));
+
+    let predictions = match model_spec {
+        "gradient_boosting" => {
+            let mut model = GradientBoostingRegressor::new(Some(GradientBoostingOptions {
+                n_estimators: param_u32(params, "n_estimators"),
+                learning_rate: param(params, "learning_rate"),
+                max_depth: param_u32(params, "max_depth"),
+                min_samples_leaf: param_u32(params, "min_samples_leaf"),
+                subsample: param(params, "subsample"),
+                validation_fraction: param(params, "validation_fraction"),
+                early_stopping_rounds: param_u32(params, "early_stopping_rounds"),
+                seed: config.seed,
+            }));
+            model.fit(train_x, train_y).ok()?;
+            model.predict(test_x)
+        }
+        "random_forest" => {
+            let mut model = RandomForestRegressor::new(Some(RandomForestOptions {
+                n_trees: param_u32(params, "n_trees"),
+                max_depth: param_u32(params, "max_depth"),
+                min_samples_leaf: param_u32(params, "min_samples_leaf"),
+                max_features: param_u32(params, "max_features"),
+                seed: config.seed,
+            }));
+            model.fit(train_x, train_y).ok()?;
+            model.predict(test_x)
+        }
+        "knn" => {
+            let mut model = KnnRegressor::new(Some(KnnOptions {
+                k: param_u32(params, "k"),
+                weights: None,
+                standardize: None,
+            }));
+            model.fit(train_x, train_y).ok()?;
+            model.predict(test_x).ok()?
+        }
+        _ => return None,
+    };
+
+    Some(score(&predictions, &test_y, metric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_train_test_split() {
+        let split = train_test_split(100, 0.2, None, Some(7));
+        assert_eq!(split.train.len(), 80);
+        assert_eq!(split.test.len(), 20);
+
+        let mut all: Vec<u32> = split.train.iter().chain(&split.test).copied().collect();
+        all.sort();
+        assert_eq!(all, (0..100).collect::<Vec<u32>>());
+
+        let again = train_test_split(100, 0.2, None, Some(7));
+        assert_eq!(split.test, again.test);
+
+        let ordered = train_test_split(10, 0.3, Some(false), None);
+        assert_eq!(ordered.test, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn test_k_fold_indices() {
+        let folds = k_fold_indices(10, 3, Some(false), None);
+        assert_eq!(folds.len(), 3);
+        assert_eq!(folds[0].test, vec![0, 1, 2, 3]);
+        assert_eq!(folds[2].test, vec![7, 8, 9]);
+        assert_eq!(folds[1].train.len(), 7);
+    }
+
+    #[test]
+    fn test_time_series_split() {
+        let splits = time_series_split(20, 3, Some(4), Some(1), None);
+        assert_eq!(splits.len(), 3);
+        for split in &splits {
+            assert!(split.train.iter().max().unwrap() + 1 < split.test[0]);
+        }
+        assert_eq!(splits[2].test, vec![16, 17, 18, 19]);
+        assert_eq!(splits[0].train.len(), 7);
+    }
+
+    #[test]
+    fn test_grid_search_knn() {
+        let x: Vec<Vec<f64>> = (0..80).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = (0..80).map(|i| (i as f64 / 10.0).sin() * 10.0).collect();
+        let grid = vec![ParamGridEntry { name: "k".to_string(), values: vec![1.0, 3.0, 40.0] }];
+
+        let result = grid_search("knn".to_string(), grid.clone(), SearchData { x: Some(x.clone()), y: y.clone() }, None).unwrap();
+        assert_eq!(result.candidates.len(), 3);
+        assert_eq!(result.candidates[0].fold_scores.len(), 5);
+        assert!(param(&result.best_params, "k").unwrap() < 40.0);
+
+        assert!(grid_search("knn".to_string(), grid.clone(), SearchData { x: Some(x[1..].to_vec()), y: y.clone() }, None).is_err());
+        assert!(grid_search("knn".to_string(), grid.clone(), SearchData { x: None, y: y.clone() }, None).is_err());
+        let mut ragged = x.clone();
+        ragged[3].push(1.0);
+        assert!(grid_search("knn".to_string(), grid.clone(), SearchData { x: Some(ragged), y: y.clone() }, None).is_err());
+        assert!(grid_search("svm".to_string(), grid, SearchData { x: Some(x), y }, None).is_err());
+    }
+
+    #[test]
+    fn test_compare_models() {
+        let mut rng = Rng::new(5);
+        let y: Vec<f64> = (0..84)
+            .map(|t| 100.0 + 0.5 * t as f64 + [0.0, 12.0, 8.0, 4.0, -6.0, -10.0, -8.0][t % 7] + rng.next_f64())
+            .collect();
+
+        let comparison = compare_models(y.clone(), None).unwrap();
+        assert_eq!(comparison.models.len(), 7);
+        assert_eq!(comparison.criterion, "cv_mae");
+        assert!(comparison.best_model == "holt_winters" || comparison.best_model == "seasonal_naive");
+        assert!(comparison.models.iter().all(|m| m.cv_folds == 3));
+        assert_eq!(comparison.models[0].rank, 1);
+
+        let bogus = compare_models(y.clone(), Some(ModelComparisonOptions {
+            models: Some(vec!["naive".to_string(), "bogus".to_string()]),
+            ..Default::default()
+        }));
+        assert!(bogus.is_err());
+
+        let by_aic = compare_models(y.clone(), Some(ModelComparisonOptions {
+            models: Some(vec!["naive".to_string(), "holt_winters".to_string()]),
+            criterion: Some("aic".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(by_aic.models.len(), 2);
+        assert_eq!(by_aic.best_model, "holt_winters");
+        assert!(by_aic.models[0].aic < by_aic.models[1].aic);
+        // Holt-Winters has no one-step forecasts for the first season, so
+        // naive is scored from there on too
+        let naive = &by_aic.models[1];
+        let sse: f64 = (7..84).map(|t| (y[t] - y[t - 1]).powi(2)).sum();
+        assert!((naive.in_sample_rmse - (sse / 77.0).sqrt()).abs() < 1e-9);
+        assert!((naive.aic - 77.0 * (sse / 77.0).ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_backtest() {
+        // Naive forecasts of a unit-slope line miss by 1, 2, 3 periods ahead
+        let data: Vec<f64> = (0..30).map(|t| 10.0 + t as f64).collect();
+        let options = BacktestOptions {
+            model: Some("naive".to_string()),
+            horizon: Some(3),
+            n_splits: Some(6),
+            ..Default::default()
+        };
+        let result = backtest(data.clone(), Some(options.clone())).unwrap();
+        assert_eq!(result.folds.iter().map(|f| f.origin).collect::<Vec<u32>>(), vec![12, 15, 18, 21, 24, 27]);
+        assert_eq!(result.mae_by_horizon, vec![1.0, 2.0, 3.0]);
+        assert_eq!((result.mae, result.bias), (2.0, -2.0));
+        // Seasonal-naive scale on a unit slope is 7
+        assert!((result.mase - 2.0 / 7.0).abs() < 1e-12);
+        assert!(result.folds.iter().all(|f| f.train_start == 0));
+
+        let sliding = backtest(data.clone(), Some(BacktestOptions {
+            n_splits: Some(4),
+            max_train_size: Some(12),
+            ..options
+        }))
+        .unwrap();
+        assert!(sliding.folds.iter().all(|f| f.train_size == 12 && f.train_start + 12 == f.origin));
+        assert_eq!(sliding.folds.len(), 4);
+
+        let default = backtest(data.clone(), None).unwrap();
+        assert_eq!(default.model, "predict_next");
+        assert!(!default.folds.is_empty() && default.mae.is_finite());
+
+        let arima = backtest(data.clone(), Some(BacktestOptions { model: Some("auto_arima".to_string()), ..Default::default() })).unwrap();
+        assert!(!arima.folds.is_empty());
+
+        assert!(backtest(data, Some(BacktestOptions { model: Some("magic".to_string()), ..Default::default() })).is_err());
+    }
+
+    #[test]
+    fn test_random_search_holt_winters() {
+        let y: Vec<f64> = (0..70).map(|i| 100.0 + ((i % 7) as f64 * 10.0)).collect();
+        let grid = vec![
+            ParamGridEntry { name: "alpha".to_string(), values: vec![0.1, 0.3, 0.5, 0.7] },
+            ParamGridEntry { name: "gamma".to_string(), values: vec![0.1, 0.3, 0.5] },
+        ];
+        let config = CvConfig {
+            search: Some("random".to_string()),
+            n_iter: Some(5),
+            n_splits: Some(3),
+            ..Default::default()
+        };
+
+        let result = grid_search("holt_winters".to_string(), grid, SearchData { x: None, y }, Some(config)).unwrap();
+        assert_eq!(result.candidates.len(), 5);
+        assert_eq!(result.best_params.len(), 2);
+        assert!(result.best_score < 50.0);
+    }
+}
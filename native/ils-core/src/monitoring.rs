@@ -0,0 +1,403 @@
+//! Forecast Monitoring
+//!
+//! Tracks how deployed forecasts perform against actuals:
+//! - Systematic bias estimation (overall and per weekday)
+//! - Bias correction of new forecasts
+//! - Drift detection (CUSUM, Page-Hinkley) and refresh recommendations
+
+use napi_derive::napi;
+
+use crate::linalg::least_squares;
+use crate::statistics::t_p_value;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct BiasOptions {
+    /// "additive" (default, bias in units) or "multiplicative" (bias as a ratio)
+    pub method: Option<String>,
+    /// Smoothing factor of the exponentially weighted bias history (default 0.1)
+    pub smoothing: Option<f64>,
+    /// Weekday estimates are shrunk toward the overall bias with this prior weight (default 4)
+    pub weekday_prior_samples: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BiasCorrection {
+    pub method: String,
+    /// Overall bias: mean of `actual - forecast` (additive) or `sum(actual) / sum(forecast)` (multiplicative)
+    pub overall_bias: f64,
+    /// Shrunken bias per weekday 0-6 (same units as `overall_bias`); empty without weekdays
+    pub weekday_bias: Vec<f64>,
+    pub weekday_counts: Vec<u32>,
+    /// Exponentially weighted bias after each observation, for monitoring
+    pub bias_history: Vec<f64>,
+    /// Mean of `(actual - forecast) / actual` over non-zero actuals
+    pub mean_percent_error: f64,
+    /// Cumulative error divided by mean absolute error
+    pub tracking_signal: f64,
+    /// Whether the tracking signal is outside ±4 (persistent bias)
+    pub is_biased: bool,
+    pub n_samples: u32,
+}
+
+/// Estimate systematic forecast bias from past forecasts and actuals
+///
+/// Positive additive bias (or a ratio above 1) means forecasts have been too low.
+///
+/// # Arguments
+/// * `forecasts` - Past forecast values
+/// * `actuals` - Observed values aligned with `forecasts`
+/// * `weekdays` - Optional weekday (0-6) of each observation for per-weekday bias
+/// * `options` - Bias method and smoothing
+#[napi]
+pub fn estimate_forecast_bias(
+    forecasts: Vec<f64>,
+    actuals: Vec<f64>,
+    weekdays: Option<Vec<u32>>,
+    options: Option<BiasOptions>,
+) -> BiasCorrection {
+    let options = options.unwrap_or_default();
+    let multiplicative = options.method.as_deref() == Some("multiplicative");
+    let alpha = options.smoothing.unwrap_or(0.1).clamp(0.0, 1.0);
+    let prior = options.weekday_prior_samples.unwrap_or(4.0).max(0.0);
+    let n = forecasts.len().min(actuals.len());
+    let neutral = if multiplicative { 1.0 } else { 0.0 };
+
+    let errors: Vec<f64> = (0..n).map(|i| actuals[i] - forecasts[i]).collect();
+    let bias_of = |rows: &[usize]| -> f64 {
+        if rows.is_empty() {
+            return neutral;
+        }
+        if multiplicative {
+            let forecast_total: f64 = rows.iter().map(|&i| forecasts[i]).sum();
+            let actual_total: f64 = rows.iter().map(|&i| actuals[i]).sum();
+            if forecast_total != 0.0 { actual_total / forecast_total } else { 1.0 }
+        } else {
+            rows.iter().map(|&i| errors[i]).sum::<f64>() / rows.len() as f64
+        }
+    };
+
+    let all_rows: Vec<usize> = (0..n).collect();
+    let overall_bias = bias_of(&all_rows);
+
+    let (weekday_bias, weekday_counts) = match &weekdays {
+        Some(days) if days.len() >= n => {
+            let mut bias = Vec::with_capacity(7);
+            let mut counts = Vec::with_capacity(7);
+            for day in 0..7u32 {
+                let rows: Vec<usize> = (0..n).filter(|&i| days[i] % 7 == day).collect();
+                let count = rows.len() as f64;
+                let shrink = if count + prior > 0.0 { count / (count + prior) } else { 0.0 };
+                bias.push(overall_bias + shrink * (bias_of(&rows) - overall_bias));
+                counts.push(rows.len() as u32);
+            }
+            (bias, counts)
+        }
+        _ => (Vec::new(), Vec::new()),
+    };
+
+    let mut bias_history = Vec::with_capacity(n);
+    let mut smoothed = neutral;
+    for i in 0..n {
+        let observation = if multiplicative {
+            if forecasts[i] != 0.0 { actuals[i] / forecasts[i] } else { 1.0 }
+        } else {
+            errors[i]
+        };
+        smoothed = if i == 0 { observation } else { alpha * observation + (1.0 - alpha) * smoothed };
+        bias_history.push(smoothed);
+    }
+
+    let percent_errors: Vec<f64> = (0..n).filter(|&i| actuals[i] != 0.0).map(|i| errors[i] / actuals[i]).collect();
+    let mean_percent_error = if percent_errors.is_empty() {
+        0.0
+    } else {
+        percent_errors.iter().sum::<f64>() / percent_errors.len() as f64
+    };
+
+    let mad = errors.iter().map(|e| e.abs()).sum::<f64>() / n.max(1) as f64;
+    let tracking_signal = if mad > 0.0 { errors.iter().sum::<f64>() / mad } else { 0.0 };
+
+    BiasCorrection {
+        method: if multiplicative { "multiplicative" } else { "additive" }.to_string(),
+        overall_bias,
+        weekday_bias,
+        weekday_counts,
+        bias_history,
+        mean_percent_error,
+        tracking_signal,
+        is_biased: tracking_signal.abs() > 4.0,
+        n_samples: n as u32,
+    }
+}
+
+/// Apply an estimated bias correction to new forecasts
+///
+/// Uses the weekday bias when `weekdays` are given and the correction has
+/// weekday estimates, otherwise the overall bias.
+///
+/// # Arguments
+/// * `correction` - Result of `estimate_forecast_bias`
+/// * `forecasts` - New forecast values
+/// * `weekdays` - Optional weekday (0-6) of each forecast
+#[napi]
+pub fn apply_bias_correction(correction: BiasCorrection, forecasts: Vec<f64>, weekdays: Option<Vec<u32>>) -> Vec<f64> {
+    let multiplicative = correction.method == "multiplicative";
+    forecasts
+        .iter()
+        .enumerate()
+        .map(|(i, &forecast)| {
+            let bias = weekdays
+                .as_ref()
+                .and_then(|days| days.get(i))
+                .and_then(|&day| correction.weekday_bias.get((day % 7) as usize))
+                .copied()
+                .unwrap_or(correction.overall_bias);
+            if multiplicative {
+                forecast * bias
+            } else {
+                forecast + bias
+            }
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ModelHealthConfig {
+    /// Leading residuals treated as the healthy reference period (default: first half, at most 60)
+    pub reference_size: Option<u32>,
+    /// CUSUM decision threshold in reference standard deviations (default 5)
+    pub cusum_threshold: Option<f64>,
+    /// CUSUM allowance (slack) in reference standard deviations (default 0.5)
+    pub cusum_drift: Option<f64>,
+    /// Page-Hinkley threshold on standardized absolute error (default 10)
+    pub page_hinkley_threshold: Option<f64>,
+    /// Page-Hinkley tolerance per observation (default 0.1)
+    pub page_hinkley_delta: Option<f64>,
+    /// Relative error increase that triggers "monitor" (default 0.2)
+    pub monitor_error_increase: Option<f64>,
+    /// Relative error increase that triggers "retrain" (default 0.5)
+    pub retrain_error_increase: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ModelHealthAssessment {
+    /// "ok", "monitor" or "retrain"
+    pub recommendation: String,
+    /// Human-readable evidence behind the recommendation
+    pub reasons: Vec<String>,
+    /// Two-sided CUSUM on standardized residuals (bias shift)
+    pub cusum_drift: bool,
+    pub cusum_index: Option<u32>,
+    /// Page-Hinkley on standardized absolute residuals (error growth)
+    pub page_hinkley_drift: bool,
+    pub page_hinkley_index: Option<u32>,
+    /// Mean residual after the reference period, in reference standard deviations
+    pub mean_shift: f64,
+    /// Mean absolute residual after the reference period divided by the reference one
+    pub error_ratio: f64,
+    /// Slope of the absolute residual per observation after the reference period
+    pub error_trend_slope: f64,
+    pub error_trend_p_value: f64,
+}
+
+/// Assess whether a deployed model needs attention from its residual stream
+///
+/// The leading `reference_size` residuals define healthy behavior. The rest
+/// is checked for a bias shift (two-sided CUSUM), error growth
+/// (Page-Hinkley), a higher error level and a rising error trend. Drift or a
+/// moderate error increase yields "monitor"; a large error increase, or drift
+/// together with a moderate increase, yields "retrain".
+///
+/// # Arguments
+/// * `residual_stream` - Residuals (actual - forecast) in time order
+/// * `config` - Detector thresholds and decision levels
+#[napi]
+pub fn assess_model_health(residual_stream: Vec<f64>, config: Option<ModelHealthConfig>) -> ModelHealthAssessment {
+    let config = config.unwrap_or_default();
+    let n = residual_stream.len();
+    let reference_size = config.reference_size.map_or((n / 2).min(60), |r| r as usize).min(n);
+    if reference_size < 5 || n - reference_size < 5 {
+        return ModelHealthAssessment {
+            recommendation: "ok".to_string(),
+            reasons: vec!["Not enough residuals to assess model health".to_string()],
+            cusum_drift: false,
+            cusum_index: None,
+            page_hinkley_drift: false,
+            page_hinkley_index: None,
+            mean_shift: 0.0,
+            error_ratio: 1.0,
+            error_trend_slope: 0.0,
+            error_trend_p_value: 1.0,
+        };
+    }
+
+    let (reference, recent) = residual_stream.split_at(reference_size);
+    let ref_mean = reference.iter().sum::<f64>() / reference_size as f64;
+    let ref_sd = (reference.iter().map(|r| (r - ref_mean).powi(2)).sum::<f64>() / (reference_size - 1) as f64).sqrt();
+    let ref_sd = if ref_sd > 0.0 { ref_sd } else { 1.0 };
+    let standardized: Vec<f64> = recent.iter().map(|r| (r - ref_mean) / ref_sd).collect();
+
+    let cusum_index = cusum(
+        &standardized,
+        config.cusum_drift.unwrap_or(0.5),
+        config.cusum_threshold.unwrap_or(5.0),
+    );
+    // Reference mean of |z| for a normal distribution is sqrt(2 / pi)
+    let abs_errors: Vec<f64> = standardized.iter().map(|z| z.abs() - (2.0 / std::f64::consts::PI).sqrt()).collect();
+    let page_hinkley_index = page_hinkley(
+        &abs_errors,
+        config.page_hinkley_delta.unwrap_or(0.1),
+        config.page_hinkley_threshold.unwrap_or(10.0),
+    );
+
+    let mean_abs = |values: &[f64]| values.iter().map(|v| v.abs()).sum::<f64>() / values.len() as f64;
+    let ref_mae = mean_abs(reference);
+    let error_ratio = if ref_mae > 0.0 { mean_abs(recent) / ref_mae } else { 1.0 };
+    let mean_shift = standardized.iter().sum::<f64>() / standardized.len() as f64;
+
+    let design: Vec<Vec<f64>> = (0..recent.len()).map(|t| vec![1.0, t as f64]).collect();
+    let magnitudes: Vec<f64> = recent.iter().map(|r| r.abs()).collect();
+    let (error_trend_slope, error_trend_p_value) = match least_squares(&design, &magnitudes) {
+        Some(fit) => {
+            let se = fit.std_errors()[1];
+            let t = if se > 0.0 { fit.coefficients[1] / se } else { 0.0 };
+            (fit.coefficients[1], t_p_value(t, fit.df as f64))
+        }
+        None => (0.0, 1.0),
+    };
+
+    let monitor_level = 1.0 + config.monitor_error_increase.unwrap_or(0.2);
+    let retrain_level = 1.0 + config.retrain_error_increase.unwrap_or(0.5);
+    // Indices are reported relative to the full stream
+    let cusum_index = cusum_index.map(|i| (i + reference_size) as u32);
+    let page_hinkley_index = page_hinkley_index.map(|i| (i + reference_size) as u32);
+
+    let mut reasons = Vec::new();
+    if let Some(i) = cusum_index {
+        reasons.push(format!("Bias shift detected at index {} (mean shift {:.2} sd)", i, mean_shift));
+    }
+    if let Some(i) = page_hinkley_index {
+        reasons.push(format!("Error growth detected at index {}", i));
+    }
+    if error_ratio >= monitor_level {
+        reasons.push(format!("Recent mean absolute error is {:.0}% above the reference period", (error_ratio - 1.0) * 100.0));
+    }
+    let rising_errors = error_trend_slope > 0.0 && error_trend_p_value < 0.05;
+    if rising_errors {
+        reasons.push(format!("Absolute errors are trending upward (p = {:.3})", error_trend_p_value));
+    }
+
+    let drift = cusum_index.is_some() || page_hinkley_index.is_some();
+    let recommendation = if error_ratio >= retrain_level || (drift && error_ratio >= monitor_level) {
+        "retrain"
+    } else if drift || rising_errors || error_ratio >= monitor_level {
+        "monitor"
+    } else {
+        reasons.push("Residuals are consistent with the reference period".to_string());
+        "ok"
+    };
+
+    ModelHealthAssessment {
+        recommendation: recommendation.to_string(),
+        reasons,
+        cusum_drift: cusum_index.is_some(),
+        cusum_index,
+        page_hinkley_drift: page_hinkley_index.is_some(),
+        page_hinkley_index,
+        mean_shift,
+        error_ratio,
+        error_trend_slope,
+        error_trend_p_value,
+    }
+}
+
+/// Two-sided tabular CUSUM on standardized values; index of the first alarm
+fn cusum(z: &[f64], drift: f64, threshold: f64) -> Option<usize> {
+    let (mut upper, mut lower) = (0.0_f64, 0.0_f64);
+    z.iter().position(|&v| {
+        upper = (upper + v - drift).max(0.0);
+        lower = (lower - v - drift).max(0.0);
+        upper > threshold || lower > threshold
+    })
+}
+
+/// Page-Hinkley test for an increase in the mean; index of the first alarm
+fn page_hinkley(values: &[f64], delta: f64, threshold: f64) -> Option<usize> {
+    let (mut cumulative, mut minimum) = (0.0_f64, 0.0_f64);
+    values.iter().position(|&v| {
+        cumulative += v - delta;
+        minimum = minimum.min(cumulative);
+        cumulative - minimum > threshold
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_additive_bias_with_weekdays() {
+        // Forecasts run 5 low on Mondays (weekday 1) and are unbiased otherwise
+        let weekdays: Vec<u32> = (0..70).map(|i| i % 7).collect();
+        let actuals: Vec<f64> = (0..70).map(|i| 100.0 + (i % 3) as f64).collect();
+        let forecasts: Vec<f64> = actuals
+            .iter()
+            .zip(&weekdays)
+            .map(|(a, &d)| if d == 1 { a - 5.0 } else { *a })
+            .collect();
+
+        let correction = estimate_forecast_bias(forecasts, actuals, Some(weekdays), None);
+        assert!((correction.overall_bias - 5.0 / 7.0).abs() < 1e-10);
+        assert!(correction.weekday_bias[1] > 3.5);
+        assert!(correction.weekday_bias[3] < 1.0);
+        assert!(correction.is_biased);
+        assert_eq!(correction.bias_history.len(), 70);
+
+        let corrected = apply_bias_correction(correction, vec![100.0, 100.0], Some(vec![1, 3]));
+        assert!(corrected[0] > 103.5);
+        assert!(corrected[1] < 101.0);
+    }
+
+    fn noise(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = crate::sampling::Rng::new(seed);
+        (0..n).map(|_| rng.next_f64() * 2.0 - 1.0).collect()
+    }
+
+    #[test]
+    fn test_assess_model_health() {
+        let healthy = assess_model_health(noise(120, 1), None);
+        assert_eq!(healthy.recommendation, "ok");
+        assert!(!healthy.cusum_drift);
+
+        // Residuals pick up a bias of +1.5 after index 80
+        let mut shifted = noise(120, 2);
+        shifted[80..].iter_mut().for_each(|r| *r += 1.5);
+        let drifted = assess_model_health(shifted, Some(ModelHealthConfig {
+            reference_size: Some(60),
+            ..Default::default()
+        }));
+        assert_eq!(drifted.recommendation, "retrain");
+        assert!(drifted.cusum_drift);
+        assert!(drifted.cusum_index.unwrap() >= 80);
+        assert!(drifted.error_ratio > 1.5);
+        assert!(!drifted.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_multiplicative_bias() {
+        let forecasts = vec![100.0, 200.0, 50.0, 80.0];
+        let actuals: Vec<f64> = forecasts.iter().map(|f| f * 1.1).collect();
+        let options = BiasOptions {
+            method: Some("multiplicative".to_string()),
+            ..Default::default()
+        };
+        let correction = estimate_forecast_bias(forecasts, actuals, None, Some(options));
+        assert!((correction.overall_bias - 1.1).abs() < 1e-10);
+        assert!(correction.weekday_bias.is_empty());
+        assert!((apply_bias_correction(correction, vec![10.0], None)[0] - 11.0).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,437 @@
+//! Nearest Neighbors
+//!
+//! K-nearest-neighbor models backed by a KD-tree for fast lookup:
+//! - Regression (mean or distance-weighted mean of neighbor targets)
+//! - Classification (majority or distance-weighted vote)
+//! - Raw neighbor queries ("find similar past orders")
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::{check_shape, check_width, column_scaling};
+
+/// Points per KD-tree leaf; below this a linear scan is faster than splitting
+const LEAF_SIZE: usize = 16;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct KnnOptions {
+    /// Number of neighbors (default 5)
+    pub k: Option<u32>,
+    /// "uniform" (default) or "distance" (inverse-distance weighting)
+    pub weights: Option<String>,
+    /// Standardize features before measuring distance (default true)
+    pub standardize: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Neighbor {
+    pub index: u32,
+    pub distance: f64,
+}
+
+enum KdNode {
+    Leaf {
+        points: Vec<usize>,
+    },
+    Split {
+        axis: usize,
+        value: f64,
+        left: Box<KdNode>,
+        right: Box<KdNode>,
+    },
+}
+
+/// KD-tree over a fixed set of points (Euclidean distance)
+pub(crate) struct KdTree {
+    points: Vec<Vec<f64>>,
+    root: KdNode,
+}
+
+/// Max-heap entry ordered by distance, so the farthest candidate pops first
+struct Candidate {
+    distance_sq: f64,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance_sq == other.distance_sq
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_sq.total_cmp(&other.distance_sq)
+    }
+}
+
+impl KdTree {
+    pub(crate) fn new(points: Vec<Vec<f64>>) -> Self {
+        let indices: Vec<usize> = (0..points.len()).collect();
+        let root = Self::build(&points, indices);
+        KdTree { points, root }
+    }
+
+    fn build(points: &[Vec<f64>], mut indices: Vec<usize>) -> KdNode {
+        let dims = points.first().map_or(0, |p| p.len());
+        if indices.len() <= LEAF_SIZE || dims == 0 {
+            return KdNode::Leaf { points: indices };
+        }
+
+        // Split on the axis with the widest spread
+        let axis = (0..dims)
+            .max_by(|&a, &b| spread(points, &indices, a).total_cmp(&spread(points, &indices, b)))
+            .unwrap_or(0);
+        if spread(points, &indices, axis) == 0.0 {
+            return KdNode::Leaf { points: indices };
+        }
+
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+        let value = points[indices[mid]][axis];
+        let right = indices.split_off(mid);
+
+        KdNode::Split {
+            axis,
+            value,
+            left: Box::new(Self::build(points, indices)),
+            right: Box::new(Self::build(points, right)),
+        }
+    }
+
+    /// The `k` nearest points to `query`, closest first
+    pub(crate) fn nearest(&self, query: &[f64], k: usize) -> Vec<(usize, f64)> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(&self.root, query, k, &mut heap);
+        }
+        let mut result: Vec<(usize, f64)> = heap.into_iter().map(|c| (c.index, c.distance_sq.sqrt())).collect();
+        result.sort_by(|a, b| a.1.total_cmp(&b.1));
+        result
+    }
+
+    fn search(&self, node: &KdNode, query: &[f64], k: usize, heap: &mut BinaryHeap<Candidate>) {
+        match node {
+            KdNode::Leaf { points } => {
+                for &index in points {
+                    let distance_sq = squared_distance(&self.points[index], query);
+                    if heap.len() < k {
+                        heap.push(Candidate { distance_sq, index });
+                    } else if heap.peek().is_some_and(|worst| distance_sq < worst.distance_sq) {
+                        heap.pop();
+                        heap.push(Candidate { distance_sq, index });
+                    }
+                }
+            }
+            KdNode::Split { axis, value, left, right } => {
+                let diff = query.get(*axis).copied().unwrap_or(0.0) - value;
+                let (near, far) = if diff < 0.0 { (left, right) } else { (right, left) };
+                self.search(near, query, k, heap);
+                if heap.len() < k || heap.peek().is_some_and(|worst| diff * diff < worst.distance_sq) {
+                    self.search(far, query, k, heap);
+                }
+            }
+        }
+    }
+}
+
+fn spread(points: &[Vec<f64>], indices: &[usize], axis: usize) -> f64 {
+    let (lo, hi) = indices.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &i| {
+        (lo.min(points[i][axis]), hi.max(points[i][axis]))
+    });
+    hi - lo
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Shared state behind the KNN regressor and classifier classes
+struct KnnIndex {
+    options: KnnOptions,
+    tree: Option<KdTree>,
+    /// Features per training row
+    width: usize,
+    means: Vec<f64>,
+    scales: Vec<f64>,
+}
+
+impl KnnIndex {
+    fn new(options: Option<KnnOptions>) -> Self {
+        KnnIndex {
+            options: options.unwrap_or_default(),
+            tree: None,
+            width: 0,
+            means: Vec::new(),
+            scales: Vec::new(),
+        }
+    }
+
+    fn fit(&mut self, x: Vec<Vec<f64>>) {
+        let p = x.first().map_or(0, |row| row.len());
+        self.width = p;
+        let (means, scales) = if self.options.standardize.unwrap_or(true) && !x.is_empty() {
+            column_scaling(&x)
+        } else {
+            (vec![0.0; p], vec![1.0; p])
+        };
+        self.means = means;
+        self.scales = scales;
+        let scaled = x.iter().map(|row| self.scale(row)).collect();
+        self.tree = Some(KdTree::new(scaled));
+    }
+
+    /// Fails unless every query row has the training width
+    fn check_queries(&self, x: &[Vec<f64>]) -> napi::Result<()> {
+        let width = check_width(x)?;
+        if self.tree.is_some() && !x.is_empty() && width != self.width {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("query rows have {} values; the model was fitted on {}", width, self.width),
+            ));
+        }
+        Ok(())
+    }
+
+    fn scale(&self, row: &[f64]) -> Vec<f64> {
+        row.iter()
+            .zip(self.means.iter().zip(&self.scales))
+            .map(|(v, (m, s))| (v - m) / s)
+            .collect()
+    }
+
+    fn k(&self) -> usize {
+        self.options.k.unwrap_or(5).max(1) as usize
+    }
+
+    fn neighbors(&self, row: &[f64], k: usize) -> Vec<(usize, f64)> {
+        self.tree.as_ref().map_or_else(Vec::new, |tree| tree.nearest(&self.scale(row), k))
+    }
+
+    /// Neighbor weights according to the weighting scheme
+    fn weights(&self, neighbors: &[(usize, f64)]) -> Vec<f64> {
+        let by_distance = self.options.weights.as_deref() == Some("distance");
+        if by_distance && neighbors.iter().any(|n| n.1 == 0.0) {
+            // Exact matches take all the weight
+            return neighbors.iter().map(|n| if n.1 == 0.0 { 1.0 } else { 0.0 }).collect();
+        }
+        neighbors.iter().map(|n| if by_distance { 1.0 / n.1 } else { 1.0 }).collect()
+    }
+
+    fn query(&self, x: Vec<Vec<f64>>, k: Option<u32>) -> napi::Result<Vec<Vec<Neighbor>>> {
+        self.check_queries(&x)?;
+        let k = k.map_or(self.k(), |k| k.max(1) as usize);
+        Ok(x.par_iter()
+            .map(|row| {
+                self.neighbors(row, k)
+                    .into_iter()
+                    .map(|(index, distance)| Neighbor { index: index as u32, distance })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// K-nearest-neighbors regression model
+#[napi]
+pub struct KnnRegressor {
+    index: KnnIndex,
+    targets: Vec<f64>,
+}
+
+#[napi]
+impl KnnRegressor {
+    #[napi(constructor)]
+    pub fn new(options: Option<KnnOptions>) -> Self {
+        KnnRegressor {
+            index: KnnIndex::new(options),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Index training rows `x` with their targets `y`
+    ///
+    /// Fails when `x` and `y` differ in length or the rows differ in width;
+    /// the previous fit is kept.
+    #[napi]
+    pub fn fit(&mut self, x: Vec<Vec<f64>>, y: Vec<f64>) -> napi::Result<()> {
+        check_shape(&x, y.len())?;
+        self.index.fit(x);
+        self.targets = y;
+        Ok(())
+    }
+
+    /// Predict targets as the (weighted) mean of neighbor targets (parallel)
+    ///
+    /// Fails for rows of a different width than the training rows.
+    #[napi]
+    pub fn predict(&self, x: Vec<Vec<f64>>) -> napi::Result<Vec<f64>> {
+        self.index.check_queries(&x)?;
+        Ok(x.par_iter()
+            .map(|row| {
+                let neighbors = self.index.neighbors(row, self.index.k());
+                let weights = self.index.weights(&neighbors);
+                let total: f64 = weights.iter().sum();
+                if total == 0.0 {
+                    return 0.0;
+                }
+                neighbors.iter().zip(&weights).map(|(n, w)| w * self.targets[n.0]).sum::<f64>() / total
+            })
+            .collect())
+    }
+
+    /// Nearest training rows for each query row, closest first
+    #[napi]
+    pub fn kneighbors(&self, x: Vec<Vec<f64>>, k: Option<u32>) -> napi::Result<Vec<Vec<Neighbor>>> {
+        self.index.query(x, k)
+    }
+}
+
+/// K-nearest-neighbors classification model
+///
+/// Class labels are integers `0..n_classes`.
+#[napi]
+pub struct KnnClassifier {
+    index: KnnIndex,
+    labels: Vec<u32>,
+    n_classes: usize,
+}
+
+#[napi]
+impl KnnClassifier {
+    #[napi(constructor)]
+    pub fn new(options: Option<KnnOptions>) -> Self {
+        KnnClassifier {
+            index: KnnIndex::new(options),
+            labels: Vec::new(),
+            n_classes: 0,
+        }
+    }
+
+    /// Index training rows `x` with their class labels `y`
+    ///
+    /// Fails when `x` and `y` differ in length or the rows differ in width;
+    /// the previous fit is kept.
+    #[napi]
+    pub fn fit(&mut self, x: Vec<Vec<f64>>, y: Vec<u32>) -> napi::Result<()> {
+        check_shape(&x, y.len())?;
+        self.index.fit(x);
+        self.n_classes = y.iter().copied().max().map_or(0, |m| m as usize + 1);
+        self.labels = y;
+        Ok(())
+    }
+
+    /// Class probabilities from (weighted) neighbor votes (parallel)
+    ///
+    /// Fails for rows of a different width than the training rows.
+    #[napi]
+    pub fn predict_proba(&self, x: Vec<Vec<f64>>) -> napi::Result<Vec<Vec<f64>>> {
+        self.index.check_queries(&x)?;
+        Ok(x.par_iter()
+            .map(|row| {
+                let neighbors = self.index.neighbors(row, self.index.k());
+                let weights = self.index.weights(&neighbors);
+                let mut votes = vec![0.0; self.n_classes];
+                for (n, w) in neighbors.iter().zip(&weights) {
+                    votes[self.labels[n.0] as usize] += w;
+                }
+                let total: f64 = votes.iter().sum();
+                if total > 0.0 {
+                    votes.iter_mut().for_each(|v| *v /= total);
+                }
+                votes
+            })
+            .collect())
+    }
+
+    /// Most likely class for each row
+    #[napi]
+    pub fn predict(&self, x: Vec<Vec<f64>>) -> napi::Result<Vec<u32>> {
+        Ok(self
+            .predict_proba(x)?
+            .iter()
+            .map(|p| p.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).map_or(0, |(i, _)| i as u32))
+            .collect())
+    }
+
+    /// Nearest training rows for each query row, closest first
+    #[napi]
+    pub fn kneighbors(&self, x: Vec<Vec<f64>>, k: Option<u32>) -> napi::Result<Vec<Vec<Neighbor>>> {
+        self.index.query(x, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampling::Rng;
+
+    #[test]
+    fn test_kd_tree_matches_brute_force() {
+        let mut rng = Rng::new(5);
+        let points: Vec<Vec<f64>> = (0..500).map(|_| vec![rng.next_f64(), rng.next_f64(), rng.next_f64()]).collect();
+        let tree = KdTree::new(points.clone());
+
+        for _ in 0..20 {
+            let query = vec![rng.next_f64(), rng.next_f64(), rng.next_f64()];
+            let mut brute: Vec<(usize, f64)> =
+                points.iter().enumerate().map(|(i, p)| (i, squared_distance(p, &query).sqrt())).collect();
+            brute.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            let found = tree.nearest(&query, 7);
+            let expected: Vec<usize> = brute.iter().take(7).map(|b| b.0).collect();
+            assert_eq!(found.iter().map(|f| f.0).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_knn_regressor() {
+        let x: Vec<Vec<f64>> = (0..100).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = (0..100).map(|i| i as f64 * 2.0).collect();
+        let mut model = KnnRegressor::new(Some(KnnOptions { k: Some(3), ..Default::default() }));
+        model.fit(x, y).unwrap();
+
+        let predictions = model.predict(vec![vec![50.0]]).unwrap();
+        assert!((predictions[0] - 100.0).abs() < 1e-10);
+
+        let neighbors = model.kneighbors(vec![vec![10.2]], Some(2)).unwrap();
+        assert_eq!(neighbors[0][0].index, 10);
+        assert_eq!(neighbors[0].len(), 2);
+
+        // Bad shapes fail and leave the fitted model in place
+        assert!(model.fit(vec![vec![1.0]], vec![1.0, 2.0]).is_err());
+        assert!(model.fit(vec![vec![1.0], vec![1.0, 2.0]], vec![1.0, 2.0]).is_err());
+        assert!(model.predict(vec![vec![50.0, 1.0]]).is_err());
+        assert!((model.predict(vec![vec![50.0]]).unwrap()[0] - 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_knn_classifier() {
+        let x: Vec<Vec<f64>> = (0..60).map(|i| vec![(i % 30) as f64, if i < 30 { 0.0 } else { 10.0 }]).collect();
+        let y: Vec<u32> = (0..60).map(|i| if i < 30 { 0 } else { 1 }).collect();
+        let mut model = KnnClassifier::new(Some(KnnOptions {
+            k: Some(5),
+            weights: Some("distance".to_string()),
+            standardize: Some(false),
+        }));
+        model.fit(x, y).unwrap();
+
+        assert_eq!(model.predict(vec![vec![5.0, 1.0], vec![5.0, 9.0]]).unwrap(), vec![0, 1]);
+        assert!(model.predict(vec![vec![5.0]]).is_err());
+        assert!(model.fit(vec![vec![1.0, 2.0]], vec![0, 1]).is_err());
+    }
+}
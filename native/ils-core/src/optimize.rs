@@ -0,0 +1,90 @@
+//! Derivative-Free Optimization
+//!
+//! Small unconstrained minimizers for model fitting where the objective has
+//! no convenient gradient (e.g. ARMA conditional sum of squares). Parameters
+//! with bounds are expected to be reparametrized onto the real line first.
+
+/// Minimize `f` with the Nelder-Mead simplex method
+///
+/// Starts from a simplex around `start` with edges of length `step` and
+/// stops when the objective values across the simplex agree to a relative
+/// `1e-10`, or after `max_evaluations` objective calls. Returns the best
+/// point found and its objective value.
+pub(crate) fn nelder_mead<F: Fn(&[f64]) -> f64>(f: F, start: &[f64], step: f64, max_evaluations: usize) -> (Vec<f64>, f64) {
+    let dims = start.len();
+    let value = |x: &[f64]| {
+        let v = f(x);
+        if v.is_nan() { f64::INFINITY } else { v }
+    };
+    if dims == 0 {
+        return (Vec::new(), value(start));
+    }
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=dims)
+        .map(|i| {
+            let mut point = start.to_vec();
+            if i > 0 {
+                point[i - 1] += step;
+            }
+            let v = value(&point);
+            (point, v)
+        })
+        .collect();
+    let mut evaluations = dims + 1;
+
+    while evaluations < max_evaluations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[dims].1);
+        if (worst - best).abs() <= 1e-10 * (best.abs() + 1e-10) {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..dims).map(|j| simplex[..dims].iter().map(|p| p.0[j]).sum::<f64>() / dims as f64).collect();
+        let toward = |coefficient: f64| -> Vec<f64> {
+            centroid.iter().zip(&simplex[dims].0).map(|(c, w)| c + coefficient * (w - c)).collect()
+        };
+
+        let reflected = toward(-1.0);
+        let reflected_value = value(&reflected);
+        evaluations += 1;
+        if reflected_value < best {
+            let expanded = toward(-2.0);
+            let expanded_value = value(&expanded);
+            evaluations += 1;
+            simplex[dims] = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < simplex[dims - 1].1 {
+            simplex[dims] = (reflected, reflected_value);
+        } else {
+            let contracted = if reflected_value < worst { toward(-0.5) } else { toward(0.5) };
+            let contracted_value = value(&contracted);
+            evaluations += 1;
+            if contracted_value < reflected_value.min(worst) {
+                simplex[dims] = (contracted, contracted_value);
+            } else {
+                // Shrink everything toward the best point
+                let anchor = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    vertex.0 = anchor.iter().zip(&vertex.0).map(|(a, x)| a + 0.5 * (x - a)).collect();
+                    vertex.1 = value(&vertex.0);
+                }
+                evaluations += dims;
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_rosenbrock() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let (x, value) = nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 5000);
+        assert!((x[0] - 1.0).abs() < 1e-3 && (x[1] - 1.0).abs() < 1e-3);
+        assert!(value < 1e-6);
+    }
+}
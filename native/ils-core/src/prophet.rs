@@ -0,0 +1,332 @@
+//! Piecewise Trend Forecasting
+//!
+//! Prophet-style additive models for series with level shifts and changes
+//! in growth:
+//! - Piecewise-linear trend with automatically selected changepoints
+//! - Fourier seasonality for one or more periods
+//! - Forecasts whose intervals allow for future trend changes
+
+use napi_derive::napi;
+
+use crate::linalg::{dot, least_squares, xt_y, LeastSquares};
+use crate::regression::lasso_coordinate_descent;
+
+/// Penalties tried on the lasso path when choosing the changepoint penalty by BIC
+const PENALTY_PATH_LENGTH: usize = 30;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ProphetOptions {
+    /// Candidate changepoints, evenly spaced (default 25)
+    pub n_changepoints: Option<u32>,
+    /// Leading share of the history where changepoints may fall (default 0.8),
+    /// so the latest slope is estimated from enough data
+    pub changepoint_range: Option<f64>,
+    /// L1 penalty on slope changes, relative to the series' spread; larger
+    /// values keep fewer changepoints (default: chosen by BIC)
+    pub changepoint_penalty: Option<f64>,
+    /// Seasonal periods in steps, e.g. [7, 365.25] for daily data (default [7]).
+    /// Periods longer than the history are ignored
+    pub seasonal_periods: Option<Vec<f64>>,
+    /// Fourier harmonics for each period, in the same order (default 3, or 10
+    /// for periods of 100 steps or more)
+    pub fourier_orders: Option<Vec<u32>>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ProphetModel {
+    /// Trend slope per step before the first changepoint
+    pub growth_rate: f64,
+    /// Trend value at step 0
+    pub offset: f64,
+    /// Steps where the slope changes (only the changepoints the fit kept)
+    pub changepoints: Vec<f64>,
+    /// Slope change at each changepoint
+    pub rate_changes: Vec<f64>,
+    pub seasonal_periods: Vec<f64>,
+    pub fourier_orders: Vec<u32>,
+    /// Sine and cosine coefficients, period by period and harmonic by harmonic
+    pub seasonal_coefficients: Vec<f64>,
+    /// Penalty used: the given one or the BIC choice
+    pub changepoint_penalty: f64,
+    /// Trend component over the history
+    pub trend: Vec<f64>,
+    /// Trend plus seasonality over the history
+    pub fitted: Vec<f64>,
+    /// Standard deviation of the residuals
+    pub residual_std: f64,
+    /// Variance per step of future slope changes, from the rate and size of
+    /// the historical ones; widens forecast intervals
+    pub trend_volatility: f64,
+    /// Length of the fitted history
+    pub n_observations: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ProphetForecast {
+    pub predictions: Vec<f64>,
+    /// 95% prediction interval bounds
+    pub lower_bounds: Vec<f64>,
+    pub upper_bounds: Vec<f64>,
+    /// Trend component of each prediction
+    pub trend: Vec<f64>,
+}
+
+/// Fit a piecewise-linear trend with Fourier seasonality (Prophet-like)
+///
+/// The trend's slope may change at evenly spaced candidate changepoints in
+/// the first 80% of the history. As in Prophet, the slope changes carry an
+/// L1 (Laplace prior) penalty, so most are exactly zero and the remaining
+/// ones mark real shifts such as a new client going live; the penalty is
+/// chosen by BIC along the lasso path unless given. Seasonality is a sum of
+/// sine/cosine pairs per period. A level shift shows up as two nearby
+/// changepoints, which adapt far faster than Holt-Winters' smoothed level.
+/// Non-finite values are treated as missing and skipped. Returns a model
+/// with NaN `residual_std` and empty components when fewer observations
+/// than parameters remain.
+///
+/// # Arguments
+/// * `data` - Time series at regular steps (e.g. daily volume)
+/// * `options` - Changepoint and seasonality settings
+#[napi]
+pub fn prophet_like_fit(data: Vec<f64>, options: Option<ProphetOptions>) -> ProphetModel {
+    let options = options.unwrap_or_default();
+    let n = data.len();
+    let periods: Vec<f64> = options
+        .seasonal_periods
+        .clone()
+        .unwrap_or_else(|| vec![7.0])
+        .into_iter()
+        .filter(|p| p.is_finite() && *p >= 2.0 && *p <= n as f64)
+        .collect();
+    let orders: Vec<u32> = periods
+        .iter()
+        .enumerate()
+        .map(|(i, &period)| {
+            let default = if period >= 100.0 { 10 } else { 3 };
+            let order = options.fourier_orders.as_ref().and_then(|o| o.get(i).copied()).unwrap_or(default);
+            order.clamp(1, (period / 2.0).floor() as u32)
+        })
+        .collect();
+
+    let observed: Vec<usize> = (0..n).filter(|&t| data[t].is_finite()).collect();
+    let rows: Vec<Vec<f64>> = observed.iter().map(|&t| base_features(t as f64, &periods, &orders)).collect();
+    let y: Vec<f64> = observed.iter().map(|&t| data[t]).collect();
+    let n_base = 2 + 2 * orders.iter().sum::<u32>() as usize;
+    let Some(base) = least_squares(&rows, &y).filter(|_| observed.len() > n_base + 2) else {
+        return empty_model(periods, orders, n);
+    };
+
+    // Project the trend line and seasonality out of the target and the ramps
+    // (t - s)+, so only the slope changes need the penalized fit
+    let range = options.changepoint_range.filter(|r| *r > 0.0 && *r <= 1.0).unwrap_or(0.8);
+    let n_candidates = (options.n_changepoints.unwrap_or(25) as usize).min(observed.len() / 2);
+    let candidates: Vec<f64> = (1..=n_candidates).map(|j| j as f64 * range * (n - 1) as f64 / n_candidates as f64).collect();
+    let ramps: Vec<Vec<f64>> = candidates.iter().map(|&s| observed.iter().map(|&t| (t as f64 - s).max(0.0)).collect()).collect();
+    let m = observed.len() as f64;
+    let target = project_out(&base, &rows, &y);
+    // A target left at rounding-error level has no trend changes to find
+    let magnitude = y.iter().fold(0.0f64, |a, v| a.max(v.abs()));
+    let y_scale = Some((dot(&target, &target) / m).sqrt()).filter(|s| *s > 1e-9 * magnitude).unwrap_or(0.0);
+    let columns: Vec<Vec<f64>> = ramps.iter().map(|ramp| project_out(&base, &rows, ramp)).collect();
+    let scales: Vec<f64> = columns.iter().map(|c| (dot(c, c) / m).sqrt()).collect();
+    let standardized: Vec<Vec<f64>> = columns
+        .iter()
+        .zip(&scales)
+        .map(|(c, &s)| if s > 0.0 { c.iter().map(|v| v / s).collect() } else { vec![0.0; c.len()] })
+        .collect();
+    let scaled_target: Vec<f64> = target.iter().map(|v| if y_scale > 0.0 { v / y_scale } else { 0.0 }).collect();
+
+    // Lasso path from the penalty that keeps no changepoint down to 1e-4 of it
+    let max_penalty = standardized.iter().map(|c| (dot(c, &scaled_target) / m).abs()).fold(0.0, f64::max);
+    let penalties: Vec<f64> = match options.changepoint_penalty {
+        Some(penalty) => vec![penalty.max(0.0)],
+        None if max_penalty > 0.0 => (0..PENALTY_PATH_LENGTH)
+            .map(|k| max_penalty * 1e-4f64.powf(k as f64 / (PENALTY_PATH_LENGTH - 1) as f64))
+            .collect(),
+        None => vec![0.0],
+    };
+    let mut beta = vec![0.0; standardized.len()];
+    let mut residual = scaled_target.clone();
+    let mut best: Option<(f64, f64, Vec<f64>)> = None;
+    for &penalty in &penalties {
+        lasso_coordinate_descent(&standardized, &mut residual, &mut beta, penalty);
+        let rss = dot(&residual, &residual).max(f64::MIN_POSITIVE);
+        let df = n_base + beta.iter().filter(|b| **b != 0.0).count();
+        let bic = m * (rss / m).ln() + df as f64 * m.ln();
+        if best.as_ref().is_none_or(|(score, _, _)| bic < *score) {
+            best = Some((bic, penalty, beta.clone()));
+        }
+    }
+    let (_, penalty, beta) = best.expect("penalty path is not empty");
+    let deltas: Vec<f64> = beta
+        .iter()
+        .zip(&scales)
+        .map(|(b, &s)| if s > 0.0 { b * y_scale / s } else { 0.0 })
+        .collect();
+
+    // Trend line and seasonality given the slope changes
+    let adjusted: Vec<f64> = (0..observed.len())
+        .map(|i| y[i] - deltas.iter().zip(&ramps).map(|(d, ramp)| d * ramp[i]).sum::<f64>())
+        .collect();
+    let coefficients = solve_base(&base, &rows, &adjusted);
+    let (changepoints, rate_changes): (Vec<f64>, Vec<f64>) =
+        candidates.iter().zip(&deltas).filter(|(_, d)| **d != 0.0).map(|(s, d)| (*s, *d)).unzip();
+    let mean_change = deltas.iter().map(|d| d.abs()).sum::<f64>() / deltas.len().max(1) as f64;
+
+    let mut model = ProphetModel {
+        growth_rate: coefficients[1],
+        offset: coefficients[0],
+        changepoints,
+        rate_changes,
+        seasonal_periods: periods,
+        fourier_orders: orders,
+        seasonal_coefficients: coefficients[2..].to_vec(),
+        changepoint_penalty: penalty,
+        trend: Vec::new(),
+        fitted: Vec::new(),
+        residual_std: 0.0,
+        trend_volatility: n_candidates as f64 / n as f64 * 2.0 * mean_change * mean_change,
+        n_observations: n as u32,
+    };
+    model.trend = (0..n).map(|t| trend_at(&model, t as f64)).collect();
+    model.fitted = (0..n).map(|t| model.trend[t] + seasonal_at(&model, t as f64)).collect();
+    let rss: f64 = observed.iter().map(|&t| (data[t] - model.fitted[t]).powi(2)).sum();
+    let df = n_base + model.changepoints.len();
+    model.residual_std = (rss / (observed.len() - df).max(1) as f64).sqrt();
+    model
+}
+
+/// Forecast a model from `prophet_like_fit`
+///
+/// Extends the last trend segment and repeats the seasonality. Interval
+/// variance is the residual variance plus that of future slope changes
+/// occurring at the historical rate and size, which grows with the cube of
+/// the horizon. Returns empty vectors for a model that could not be fitted.
+///
+/// # Arguments
+/// * `model` - Model returned by `prophet_like_fit`
+/// * `steps` - Number of steps after the history to forecast
+#[napi]
+pub fn prophet_like_forecast(model: ProphetModel, steps: u32) -> ProphetForecast {
+    if model.residual_std.is_nan() {
+        return ProphetForecast {
+            predictions: Vec::new(),
+            lower_bounds: Vec::new(),
+            upper_bounds: Vec::new(),
+            trend: Vec::new(),
+        };
+    }
+    let last = model.n_observations as f64 - 1.0;
+    let trend: Vec<f64> = (1..=steps).map(|h| trend_at(&model, last + h as f64)).collect();
+    let predictions: Vec<f64> = trend.iter().enumerate().map(|(i, level)| level + seasonal_at(&model, last + (i + 1) as f64)).collect();
+    let half_widths: Vec<f64> = (1..=steps)
+        .map(|h| 1.96 * (model.residual_std.powi(2) + model.trend_volatility * (h as f64).powi(3) / 3.0).sqrt())
+        .collect();
+    ProphetForecast {
+        lower_bounds: predictions.iter().zip(&half_widths).map(|(p, w)| p - w).collect(),
+        upper_bounds: predictions.iter().zip(&half_widths).map(|(p, w)| p + w).collect(),
+        predictions,
+        trend,
+    }
+}
+
+/// Sine and cosine of each harmonic `1..=order` of `period` at step `t`
+pub(crate) fn fourier_terms(t: f64, period: f64, order: usize) -> Vec<f64> {
+    (1..=order)
+        .flat_map(|k| {
+            let angle = std::f64::consts::TAU * k as f64 * t / period;
+            [angle.sin(), angle.cos()]
+        })
+        .collect()
+}
+
+/// Unpenalized design row: intercept, linear trend and Fourier terms
+fn base_features(t: f64, periods: &[f64], orders: &[u32]) -> Vec<f64> {
+    let mut row = vec![1.0, t];
+    for (&period, &order) in periods.iter().zip(orders) {
+        row.extend(fourier_terms(t, period, order as usize));
+    }
+    row
+}
+
+/// Least-squares coefficients of `v` on `rows`, reusing the factorized base fit
+fn solve_base(base: &LeastSquares, rows: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    let xty = xt_y(rows, v, None);
+    base.xtx_inv.iter().map(|row| dot(row, &xty)).collect()
+}
+
+/// Residual of `v` after regressing it on `rows`
+fn project_out(base: &LeastSquares, rows: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    let coefficients = solve_base(base, rows, v);
+    rows.iter().zip(v).map(|(row, value)| value - dot(row, &coefficients)).collect()
+}
+
+fn trend_at(model: &ProphetModel, t: f64) -> f64 {
+    model.offset
+        + model.growth_rate * t
+        + model.changepoints.iter().zip(&model.rate_changes).map(|(s, d)| d * (t - s).max(0.0)).sum::<f64>()
+}
+
+fn seasonal_at(model: &ProphetModel, t: f64) -> f64 {
+    let row = base_features(t, &model.seasonal_periods, &model.fourier_orders);
+    dot(&row[2..], &model.seasonal_coefficients)
+}
+
+fn empty_model(periods: Vec<f64>, orders: Vec<u32>, n: usize) -> ProphetModel {
+    ProphetModel {
+        growth_rate: f64::NAN,
+        offset: f64::NAN,
+        changepoints: Vec::new(),
+        rate_changes: Vec::new(),
+        seasonal_periods: periods,
+        fourier_orders: orders,
+        seasonal_coefficients: Vec::new(),
+        changepoint_penalty: f64::NAN,
+        trend: Vec::new(),
+        fitted: Vec::new(),
+        residual_std: f64::NAN,
+        trend_volatility: f64::NAN,
+        n_observations: n as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampling::Rng;
+
+    #[test]
+    fn test_prophet_like_slope_change() {
+        // Slope 0.2 until step 120, then 1.0 as new clients come on board, with a weekly cycle
+        let mut rng = Rng::new(3);
+        let weekly = [4.0, 2.0, 0.0, -1.0, -2.0, -6.0, 3.0];
+        let mut data: Vec<f64> = (0..200)
+            .map(|t| {
+                let trend = 50.0 + 0.2 * t as f64 + 0.8 * (t as f64 - 120.0).max(0.0);
+                trend + weekly[t % 7] + (rng.next_f64() - 0.5) * 2.0
+            })
+            .collect();
+        data[30] = f64::NAN;
+
+        let model = prophet_like_fit(data.clone(), None);
+        assert!(!model.changepoints.is_empty() && model.changepoints.len() <= 6, "{:?}", model.changepoints);
+        let total_change: f64 = model.rate_changes.iter().sum();
+        assert!((model.growth_rate + total_change - 1.0).abs() < 0.05);
+        assert!(model.changepoints.iter().zip(&model.rate_changes).all(|(s, d)| d.abs() < 0.1 || (s - 120.0).abs() < 15.0));
+        assert!(model.fitted[30].is_finite());
+        assert!(model.residual_std < 1.0);
+
+        let forecast = prophet_like_forecast(model, 14);
+        let expected = |t: usize| 50.0 + 0.2 * t as f64 + 0.8 * (t as f64 - 120.0) + weekly[t % 7];
+        assert!(forecast.predictions.iter().enumerate().all(|(h, p)| (p - expected(200 + h)).abs() < 2.0));
+        assert!(forecast.upper_bounds[13] - forecast.lower_bounds[13] > forecast.upper_bounds[0] - forecast.lower_bounds[0]);
+
+        let flat = prophet_like_fit((0..100).map(|t| 10.0 + weekly[t % 7]).collect(), None);
+        assert!(flat.changepoints.is_empty());
+        assert!(prophet_like_fit(vec![1.0, 2.0, 3.0], None).residual_std.is_nan());
+        assert!(prophet_like_forecast(prophet_like_fit(vec![1.0, 2.0], None), 3).predictions.is_empty());
+    }
+}
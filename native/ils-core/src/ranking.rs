@@ -0,0 +1,197 @@
+//! Ranking & Value Location
+//!
+//! Finds extreme values together with their positions (without sorting the
+//! whole array) and ranks values:
+//! - Top-k / bottom-k selection (parallel over chunks)
+//! - Argmax / argmin
+//! - Index of a quantile (nearest-rank order statistic)
+//! - Rank transform with configurable tie handling
+//!
+//! NaN values are ignored throughout.
+
+use std::cmp::Ordering;
+
+use napi_derive::napi;
+use rayon::prelude::*;
+
+/// Elements per parallel chunk; smaller inputs are handled on one thread
+const CHUNK_SIZE: usize = 1 << 16;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct IndexedValue {
+    pub index: u32,
+    pub value: f64,
+}
+
+/// The `k` largest values with their indices, largest first
+///
+/// Ties are broken by the lower index.
+///
+/// # Arguments
+/// * `data` - Values to search
+/// * `k` - Number of values to return
+#[napi]
+pub fn top_k(data: Vec<f64>, k: u32) -> Vec<IndexedValue> {
+    select_k(&data, k as usize, |a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)))
+}
+
+/// The `k` smallest values with their indices, smallest first
+///
+/// Ties are broken by the lower index.
+///
+/// # Arguments
+/// * `data` - Values to search
+/// * `k` - Number of values to return
+#[napi]
+pub fn bottom_k(data: Vec<f64>, k: u32) -> Vec<IndexedValue> {
+    select_k(&data, k as usize, |a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+}
+
+/// Index of the largest value (first occurrence), or `None` for empty/all-NaN input
+#[napi]
+pub fn argmax(data: Vec<f64>) -> Option<u32> {
+    top_k(data, 1).first().map(|v| v.index)
+}
+
+/// Index of the smallest value (first occurrence), or `None` for empty/all-NaN input
+#[napi]
+pub fn argmin(data: Vec<f64>) -> Option<u32> {
+    bottom_k(data, 1).first().map(|v| v.index)
+}
+
+/// The element at quantile `q` and its index
+///
+/// Uses the nearest-rank definition (no interpolation), so the result is an
+/// actual element of `data`: the `ceil(q * n)`-th smallest value (the
+/// smallest for `q = 0`).
+///
+/// # Arguments
+/// * `data` - Values to search
+/// * `q` - Quantile in [0, 1]
+#[napi]
+pub fn arg_quantile(data: Vec<f64>, q: f64) -> Option<IndexedValue> {
+    let mut values: Vec<(usize, f64)> = data.iter().copied().enumerate().filter(|v| !v.1.is_nan()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    let rank = ((q.clamp(0.0, 1.0) * values.len() as f64).ceil() as usize).clamp(1, values.len());
+    let (_, &mut (index, value), _) = values.select_nth_unstable_by(rank - 1, |a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    Some(IndexedValue { index: index as u32, value })
+}
+
+/// Rank of each value (1 = smallest), aligned with `data`
+///
+/// Building block for Spearman correlation and rank-based scoring. NaN values
+/// get a NaN rank and are not counted.
+///
+/// # Arguments
+/// * `data` - Values to rank
+/// * `method` - Tie handling: "average" (default, mean of the tied ranks),
+///   "min", "max", "dense" (no gaps after ties) or "ordinal" (ties broken by position)
+#[napi]
+pub fn rank(data: Vec<f64>, method: Option<String>) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..data.len()).filter(|&i| !data[i].is_nan()).collect();
+    order.par_sort_by(|&a, &b| data[a].total_cmp(&data[b]).then(a.cmp(&b)));
+
+    let mut ranks = vec![f64::NAN; data.len()];
+    let mut start = 0;
+    let mut dense = 0.0;
+    while start < order.len() {
+        let end = start + order[start..].iter().take_while(|&&i| data[i] == data[order[start]]).count();
+        dense += 1.0;
+        for (offset, &i) in order[start..end].iter().enumerate() {
+            ranks[i] = match method.as_deref() {
+                Some("min") => (start + 1) as f64,
+                Some("max") => end as f64,
+                Some("dense") => dense,
+                Some("ordinal") => (start + offset + 1) as f64,
+                _ => (start + 1 + end) as f64 / 2.0,
+            };
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Select the first `k` elements under `order`, in order
+fn select_k<F>(data: &[f64], k: usize, order: F) -> Vec<IndexedValue>
+where
+    F: Fn(&(usize, f64), &(usize, f64)) -> Ordering + Sync,
+{
+    if k == 0 || data.is_empty() {
+        return Vec::new();
+    }
+    let best_of = |mut candidates: Vec<(usize, f64)>| {
+        if candidates.len() > k {
+            candidates.select_nth_unstable_by(k - 1, &order);
+            candidates.truncate(k);
+        }
+        candidates
+    };
+
+    // Each chunk keeps only its own best k, so the final selection sees at most k per chunk
+    let candidates: Vec<(usize, f64)> = data
+        .par_chunks(CHUNK_SIZE)
+        .enumerate()
+        .flat_map_iter(|(c, chunk)| {
+            let offset = c * CHUNK_SIZE;
+            best_of(chunk.iter().enumerate().filter(|v| !v.1.is_nan()).map(|(i, &v)| (offset + i, v)).collect())
+        })
+        .collect();
+
+    let mut selected = best_of(candidates);
+    selected.sort_by(&order);
+    selected
+        .into_iter()
+        .map(|(index, value)| IndexedValue { index: index as u32, value })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_and_bottom_k() {
+        let data = vec![3.0, 9.0, f64::NAN, 1.0, 9.0, 7.0, 0.5];
+        let top: Vec<(u32, f64)> = top_k(data.clone(), 3).iter().map(|v| (v.index, v.value)).collect();
+        assert_eq!(top, vec![(1, 9.0), (4, 9.0), (5, 7.0)]);
+        let bottom: Vec<u32> = bottom_k(data.clone(), 2).iter().map(|v| v.index).collect();
+        assert_eq!(bottom, vec![6, 3]);
+        assert_eq!(top_k(data.clone(), 10).len(), 6);
+
+        assert_eq!(argmax(data.clone()), Some(1));
+        assert_eq!(argmin(data), Some(6));
+        assert_eq!(argmax(vec![f64::NAN]), None);
+    }
+
+    #[test]
+    fn test_rank() {
+        let data = vec![10.0, 20.0, 10.0, f64::NAN, 30.0, 20.0, 20.0];
+        let ranks = |method: &str| rank(data.clone(), Some(method.to_string()));
+        let average = rank(data.clone(), None);
+        assert_eq!(&average[..3], &[1.5, 4.0, 1.5]);
+        assert!(average[3].is_nan());
+        assert_eq!(average[4], 6.0);
+        assert_eq!(ranks("min")[1], 3.0);
+        assert_eq!(ranks("max")[1], 5.0);
+        assert_eq!(ranks("dense")[4], 3.0);
+        assert_eq!(ranks("ordinal")[6], 5.0);
+    }
+
+    #[test]
+    fn test_large_input_and_quantile() {
+        // Spans several chunks; values are a permutation of 0..n
+        let n = 200_003;
+        let data: Vec<f64> = (0..n).map(|i| ((i * 7919) % n) as f64).collect();
+        let top = top_k(data.clone(), 5);
+        assert_eq!(top.iter().map(|v| v.value).collect::<Vec<_>>(), vec![200_002.0, 200_001.0, 200_000.0, 199_999.0, 199_998.0]);
+        assert!(top.iter().all(|v| data[v.index as usize] == v.value));
+
+        let median = arg_quantile(data.clone(), 0.5).unwrap();
+        assert_eq!(median.value, 100_001.0);
+        assert_eq!(data[median.index as usize], median.value);
+        assert_eq!(arg_quantile(data, 0.0).unwrap().value, 0.0);
+    }
+}
@@ -0,0 +1,905 @@
+//! Regression Models
+//!
+//! Linear models beyond the single-variable least-squares
+//! `linear_regression` in the statistics module:
+//! - Simple linear regression with coefficient inference and prediction intervals
+//! - Multiple linear regression (OLS) with coefficient inference
+//! - Adjusted R², AIC and BIC for comparing fits of different complexity
+//! - Durbin-Watson test for autocorrelated residuals
+//! - Theil-Sen robust line fit
+//! - Ridge and lasso regression with cross-validated penalty selection
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+use crate::linalg::{column_scaling, dot, gram, least_squares, solve, xt_y};
+use crate::model_selection::k_fold_indices;
+use crate::statistics::{mean, median, stable_sum, t_p_value, LinearRegressionResult};
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct MultipleRegressionResult {
+    pub intercept: f64,
+    pub intercept_std_error: f64,
+    /// One coefficient per predictor column, in input order
+    pub coefficients: Vec<f64>,
+    pub std_errors: Vec<f64>,
+    pub t_statistics: Vec<f64>,
+    /// Two-sided p-values for each coefficient being zero
+    pub p_values: Vec<f64>,
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    /// `n ln(RSS / n) + 2k` with `k = p + 1` coefficients
+    pub aic: f64,
+    /// `n ln(RSS / n) + k ln(n)`
+    pub bic: f64,
+    /// Residual standard deviation `sqrt(RSS / (n - p - 1))`
+    pub residual_std_error: f64,
+    pub n_observations: u32,
+}
+
+/// Multiple linear regression `y = b0 + b1 x1 + ... + bp xp` by ordinary least squares
+///
+/// Returns empty coefficients when the rows have inconsistent lengths, there
+/// are no more observations than parameters, or the predictors are perfectly
+/// collinear.
+///
+/// # Arguments
+/// * `x` - Predictor matrix, one row per observation (e.g. `[promotion, weekday, temperature]`)
+/// * `y` - Response value for each row
+#[napi]
+pub fn multiple_regression(x: Vec<Vec<f64>>, y: Vec<f64>) -> MultipleRegressionResult {
+    let p = x.first().map_or(0, |row| row.len());
+    let design: Vec<Vec<f64>> = x.iter().map(|row| std::iter::once(1.0).chain(row.iter().copied()).collect()).collect();
+    let fit = if x.iter().all(|row| row.len() == p) { least_squares(&design, &y) } else { None };
+    let Some(fit) = fit else {
+        return MultipleRegressionResult {
+            intercept: 0.0,
+            intercept_std_error: 0.0,
+            coefficients: Vec::new(),
+            std_errors: Vec::new(),
+            t_statistics: Vec::new(),
+            p_values: Vec::new(),
+            r_squared: 0.0,
+            adjusted_r_squared: 0.0,
+            aic: f64::NAN,
+            bic: f64::NAN,
+            residual_std_error: 0.0,
+            n_observations: 0,
+        };
+    };
+
+    let df = fit.df as f64;
+    let std_errors = fit.std_errors();
+    let t_statistics: Vec<f64> = fit
+        .coefficients
+        .iter()
+        .zip(&std_errors)
+        .map(|(b, se)| if *se > 0.0 { b / se } else { 0.0 })
+        .collect();
+
+    let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+    let tss: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    let metrics = fit_metrics(fit.rss, tss, y.len(), p + 1);
+
+    MultipleRegressionResult {
+        intercept: fit.coefficients[0],
+        intercept_std_error: std_errors[0],
+        coefficients: fit.coefficients[1..].to_vec(),
+        std_errors: std_errors[1..].to_vec(),
+        p_values: t_statistics[1..].iter().map(|t| t_p_value(*t, df)).collect(),
+        t_statistics: t_statistics[1..].to_vec(),
+        r_squared: metrics.r_squared,
+        adjusted_r_squared: metrics.adjusted_r_squared,
+        aic: metrics.aic,
+        bic: metrics.bic,
+        residual_std_error: fit.sigma2().sqrt(),
+        n_observations: y.len() as u32,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LinearRegressionFit {
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    /// `n ln(RSS / n) + 2k` with `k = 2` coefficients
+    pub aic: f64,
+    /// `n ln(RSS / n) + k ln(n)`
+    pub bic: f64,
+    pub slope_std_error: f64,
+    pub intercept_std_error: f64,
+    pub slope_t_statistic: f64,
+    pub intercept_t_statistic: f64,
+    /// Two-sided p-value for the slope being zero (is the trend significant?)
+    pub slope_p_value: f64,
+    pub intercept_p_value: f64,
+    /// Residual standard deviation `sqrt(RSS / (n - 2))`
+    pub residual_std_error: f64,
+    pub n_observations: u32,
+    /// Mean of `x`, kept for `predict_with_interval`
+    pub x_mean: f64,
+    /// `sum (x - x_mean)^2`, kept for `predict_with_interval`
+    pub x_sum_squares: f64,
+}
+
+/// Least-squares line fit with coefficient inference
+///
+/// Same fit as `linear_regression`, plus standard errors, t-statistics and
+/// p-values, so a trend can be tested for significance rather than read off
+/// the slope's sign. Needs at least three points and some spread in `x`;
+/// otherwise returns a zero fit with p-values of 1.
+///
+/// # Arguments
+/// * `x` - Independent variable
+/// * `y` - Dependent variable
+#[napi]
+pub fn linear_regression_full(x: Vec<f64>, y: Vec<f64>) -> LinearRegressionFit {
+    let n = x.len();
+    let nf = n as f64;
+    let x_mean = mean(x.clone());
+    let x_sum_squares = stable_sum(x.iter().map(|v| (v - x_mean).powi(2)));
+    if n != y.len() || n < 3 || x_sum_squares == 0.0 {
+        return LinearRegressionFit {
+            slope: 0.0,
+            intercept: 0.0,
+            r_squared: 0.0,
+            adjusted_r_squared: 0.0,
+            aic: f64::NAN,
+            bic: f64::NAN,
+            slope_std_error: 0.0,
+            intercept_std_error: 0.0,
+            slope_t_statistic: 0.0,
+            intercept_t_statistic: 0.0,
+            slope_p_value: 1.0,
+            intercept_p_value: 1.0,
+            residual_std_error: 0.0,
+            n_observations: n as u32,
+            x_mean,
+            x_sum_squares,
+        };
+    }
+
+    let y_mean = mean(y.clone());
+    let sxy = stable_sum(x.iter().zip(&y).map(|(a, b)| (a - x_mean) * (b - y_mean)));
+    let slope = sxy / x_sum_squares;
+    let intercept = y_mean - slope * x_mean;
+    let rss = stable_sum(x.iter().zip(&y).map(|(a, b)| (b - intercept - slope * a).powi(2)));
+    let tss = stable_sum(y.iter().map(|b| (b - y_mean).powi(2)));
+
+    let df = nf - 2.0;
+    let sigma = (rss / df).sqrt();
+    let slope_std_error = sigma / x_sum_squares.sqrt();
+    let intercept_std_error = sigma * (1.0 / nf + x_mean * x_mean / x_sum_squares).sqrt();
+    let t = |b: f64, se: f64| if se > 0.0 { b / se } else if b != 0.0 { f64::INFINITY.copysign(b) } else { 0.0 };
+    let (slope_t_statistic, intercept_t_statistic) = (t(slope, slope_std_error), t(intercept, intercept_std_error));
+    let metrics = fit_metrics(rss, tss, n, 2);
+
+    LinearRegressionFit {
+        slope,
+        intercept,
+        r_squared: metrics.r_squared,
+        adjusted_r_squared: metrics.adjusted_r_squared,
+        aic: metrics.aic,
+        bic: metrics.bic,
+        slope_std_error,
+        intercept_std_error,
+        slope_t_statistic,
+        intercept_t_statistic,
+        slope_p_value: t_p_value(slope_t_statistic, df),
+        intercept_p_value: t_p_value(intercept_t_statistic, df),
+        residual_std_error: sigma,
+        n_observations: n as u32,
+        x_mean,
+        x_sum_squares,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RegressionPrediction {
+    pub x: f64,
+    pub prediction: f64,
+    /// Interval for a single new observation at `x`
+    pub lower: f64,
+    pub upper: f64,
+    /// Narrower interval for the fitted mean at `x`
+    pub mean_lower: f64,
+    pub mean_upper: f64,
+}
+
+/// Predict from a `linear_regression_full` fit with t-based intervals
+///
+/// Intervals widen as `x` moves away from the data used in the fit. Returns
+/// no predictions for a fit on fewer than three points.
+///
+/// # Arguments
+/// * `fit` - Result of `linear_regression_full`
+/// * `x_new` - Points to predict at
+/// * `confidence` - Interval coverage in (0, 1) (default 0.95)
+#[napi]
+pub fn predict_with_interval(fit: LinearRegressionFit, x_new: Vec<f64>, confidence: Option<f64>) -> Vec<RegressionPrediction> {
+    let confidence = confidence.filter(|c| *c > 0.0 && *c < 1.0).unwrap_or(0.95);
+    let n = fit.n_observations as f64;
+    let Ok(distribution) = StudentsT::new(0.0, 1.0, n - 2.0) else {
+        return Vec::new();
+    };
+    if fit.x_sum_squares <= 0.0 {
+        return Vec::new();
+    }
+    let critical = distribution.inverse_cdf(0.5 + confidence / 2.0);
+
+    x_new
+        .into_iter()
+        .map(|x| {
+            let prediction = fit.intercept + fit.slope * x;
+            let leverage = 1.0 / n + (x - fit.x_mean).powi(2) / fit.x_sum_squares;
+            let mean_margin = critical * fit.residual_std_error * leverage.sqrt();
+            let margin = critical * fit.residual_std_error * (1.0 + leverage).sqrt();
+            RegressionPrediction {
+                x,
+                prediction,
+                lower: prediction - margin,
+                upper: prediction + margin,
+                mean_lower: prediction - mean_margin,
+                mean_upper: prediction + mean_margin,
+            }
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FitMetrics {
+    pub r_squared: f64,
+    /// R² penalized for the number of coefficients: `1 - (1 - R²)(n - 1) / (n - k)`
+    pub adjusted_r_squared: f64,
+    /// `n ln(RSS / n) + 2k`; lower is better
+    pub aic: f64,
+    /// `n ln(RSS / n) + k ln(n)`; penalizes extra coefficients more than AIC
+    pub bic: f64,
+    pub n_observations: u32,
+}
+
+/// Adjusted R², AIC and BIC of any least-squares fit
+///
+/// For comparing fits made elsewhere (e.g. a polynomial or seasonal-dummy
+/// regression) with the Gaussian criteria `multiple_regression` reports;
+/// only differences between models fitted to the same data are meaningful.
+/// Pairs with a NaN on either side are skipped.
+///
+/// # Arguments
+/// * `actual` - Observed values
+/// * `fitted` - Fitted values of the model
+/// * `n_params` - Number of estimated coefficients, including the intercept
+#[napi]
+pub fn regression_fit_metrics(actual: Vec<f64>, fitted: Vec<f64>, n_params: u32) -> FitMetrics {
+    let pairs: Vec<(f64, f64)> = actual
+        .iter()
+        .zip(&fitted)
+        .filter(|(a, f)| !a.is_nan() && !f.is_nan())
+        .map(|(a, f)| (*a, *f))
+        .collect();
+    if actual.len() != fitted.len() || pairs.is_empty() {
+        return fit_metrics(0.0, 0.0, 0, n_params as usize);
+    }
+    let mean = pairs.iter().map(|p| p.0).sum::<f64>() / pairs.len() as f64;
+    let rss: f64 = pairs.iter().map(|(a, f)| (a - f).powi(2)).sum();
+    let tss: f64 = pairs.iter().map(|(a, _)| (a - mean).powi(2)).sum();
+    fit_metrics(rss, tss, pairs.len(), n_params as usize)
+}
+
+/// Goodness-of-fit and information criteria from residual and total sums of squares
+fn fit_metrics(rss: f64, tss: f64, n: usize, k: usize) -> FitMetrics {
+    if n == 0 {
+        return FitMetrics {
+            r_squared: 0.0,
+            adjusted_r_squared: 0.0,
+            aic: f64::NAN,
+            bic: f64::NAN,
+            n_observations: 0,
+        };
+    }
+    let (nf, kf) = (n as f64, k as f64);
+    let r_squared = if tss > 0.0 { 1.0 - rss / tss } else { 0.0 };
+    let adjusted_r_squared = if n > k { 1.0 - (1.0 - r_squared) * (nf - 1.0) / (nf - kf) } else { f64::NAN };
+    let log_likelihood_term = nf * (rss / nf).max(f64::MIN_POSITIVE).ln();
+    FitMetrics {
+        r_squared,
+        adjusted_r_squared,
+        aic: log_likelihood_term + 2.0 * kf,
+        bic: log_likelihood_term + kf * nf.ln(),
+        n_observations: n as u32,
+    }
+}
+
+/// Durbin-Watson statistic of regression residuals
+///
+/// `sum (e_t - e_{t-1})^2 / sum e_t^2`, between 0 and 4. Values near 2 mean
+/// no first-order autocorrelation; well below 2 (roughly under 1.5) means
+/// positively correlated residuals, i.e. a plain trend line is missing
+/// structure a time-series model would capture. Residuals must be in time
+/// order. Returns NaN for fewer than two residuals or all-zero residuals.
+///
+/// # Arguments
+/// * `residuals` - Regression residuals in time order
+#[napi]
+pub fn durbin_watson(residuals: Vec<f64>) -> f64 {
+    let sum_squares: f64 = residuals.iter().map(|e| e * e).sum();
+    if residuals.len() < 2 || sum_squares == 0.0 {
+        return f64::NAN;
+    }
+    let differences: f64 = residuals.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+    differences / sum_squares
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct RegularizationOptions {
+    /// Candidate penalties for cross-validation (default: 30 log-spaced values)
+    pub lambdas: Option<Vec<f64>>,
+    /// Cross-validation folds (default 5)
+    pub n_folds: Option<u32>,
+    /// Fold shuffle seed (default 42)
+    pub seed: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RegularizedRegressionResult {
+    pub intercept: f64,
+    /// One coefficient per predictor column, on the original scale
+    pub coefficients: Vec<f64>,
+    /// Penalty used: the given one, or the cross-validated choice
+    pub lambda: f64,
+    pub r_squared: f64,
+    /// Penalties tried by cross-validation (empty when `lambda` was given)
+    pub cv_lambdas: Vec<f64>,
+    /// Cross-validated mean squared error of each tried penalty
+    pub cv_errors: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Penalty {
+    Ridge,
+    Lasso,
+}
+
+/// Ridge (L2-penalized) multiple regression
+///
+/// Minimizes `RSS / (2n) + lambda / 2 * |b|^2` over standardized predictors,
+/// which keeps coefficients stable when drivers are collinear. Coefficients
+/// are reported on the original scale and the intercept is not penalized.
+///
+/// # Arguments
+/// * `x` - Predictor matrix, one row per observation
+/// * `y` - Response value for each row
+/// * `lambda` - Penalty strength; when omitted it is chosen by k-fold cross-validation
+/// * `options` - Candidate penalties, fold count and seed for cross-validation
+#[napi]
+pub fn ridge_regression(
+    x: Vec<Vec<f64>>,
+    y: Vec<f64>,
+    lambda: Option<f64>,
+    options: Option<RegularizationOptions>,
+) -> RegularizedRegressionResult {
+    regularized_regression(&x, &y, lambda, options.unwrap_or_default(), Penalty::Ridge)
+}
+
+/// Lasso (L1-penalized) multiple regression
+///
+/// Minimizes `RSS / (2n) + lambda * |b|_1` over standardized predictors by
+/// coordinate descent. Unlike ridge it sets weak or redundant drivers exactly
+/// to zero, so it doubles as variable selection.
+///
+/// # Arguments
+/// * `x` - Predictor matrix, one row per observation
+/// * `y` - Response value for each row
+/// * `lambda` - Penalty strength; when omitted it is chosen by k-fold cross-validation
+/// * `options` - Candidate penalties, fold count and seed for cross-validation
+#[napi]
+pub fn lasso_regression(
+    x: Vec<Vec<f64>>,
+    y: Vec<f64>,
+    lambda: Option<f64>,
+    options: Option<RegularizationOptions>,
+) -> RegularizedRegressionResult {
+    regularized_regression(&x, &y, lambda, options.unwrap_or_default(), Penalty::Lasso)
+}
+
+fn regularized_regression(
+    x: &[Vec<f64>],
+    y: &[f64],
+    lambda: Option<f64>,
+    options: RegularizationOptions,
+    penalty: Penalty,
+) -> RegularizedRegressionResult {
+    let mut result = RegularizedRegressionResult {
+        intercept: 0.0,
+        coefficients: Vec::new(),
+        lambda: lambda.unwrap_or(0.0),
+        r_squared: 0.0,
+        cv_lambdas: Vec::new(),
+        cv_errors: Vec::new(),
+    };
+    let p = x.first().map_or(0, |row| row.len());
+    if x.len() != y.len() || x.len() < 2 || p == 0 || x.iter().any(|row| row.len() != p) {
+        return result;
+    }
+
+    let lambda = match lambda {
+        Some(lambda) => lambda.max(0.0),
+        None => {
+            let n_folds = options.n_folds.unwrap_or(5).max(2);
+            if x.len() < n_folds as usize {
+                return result;
+            }
+            let lambdas = options.lambdas.unwrap_or_else(|| default_lambdas(x, y, penalty));
+            let folds = k_fold_indices(x.len() as u32, n_folds, Some(true), options.seed);
+            let errors: Vec<f64> = lambdas
+                .par_iter()
+                .map(|&lambda| {
+                    let sse: f64 = folds
+                        .iter()
+                        .map(|fold| {
+                            let rows = |idx: &[u32]| -> (Vec<Vec<f64>>, Vec<f64>) {
+                                idx.iter().map(|&i| (x[i as usize].clone(), y[i as usize])).unzip()
+                            };
+                            let ((train_x, train_y), (test_x, test_y)) = (rows(&fold.train), rows(&fold.test));
+                            match fit_penalized(&train_x, &train_y, lambda.max(0.0), penalty) {
+                                Some((b0, b)) => test_x.iter().zip(&test_y).map(|(row, t)| (t - b0 - dot(row, &b)).powi(2)).sum(),
+                                None => f64::INFINITY,
+                            }
+                        })
+                        .sum();
+                    sse / x.len() as f64
+                })
+                .collect();
+            let best = (0..lambdas.len()).min_by(|&a, &b| errors[a].total_cmp(&errors[b]));
+            let Some(best) = best else {
+                return result;
+            };
+            let chosen = lambdas[best].max(0.0);
+            result.cv_lambdas = lambdas;
+            result.cv_errors = errors;
+            chosen
+        }
+    };
+
+    let Some((intercept, coefficients)) = fit_penalized(x, y, lambda, penalty) else {
+        return result;
+    };
+    let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+    let tss: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    let rss: f64 = x.iter().zip(y).map(|(row, t)| (t - intercept - dot(row, &coefficients)).powi(2)).sum();
+    RegularizedRegressionResult {
+        intercept,
+        coefficients,
+        lambda,
+        r_squared: if tss > 0.0 { 1.0 - rss / tss } else { 0.0 },
+        ..result
+    }
+}
+
+/// Default cross-validation grid: 30 log-spaced penalties
+///
+/// Lasso spans four decades below the smallest penalty that zeroes every
+/// coefficient; ridge spans 1e-4 to 1e3 on the standardized scale.
+fn default_lambdas(x: &[Vec<f64>], y: &[f64], penalty: Penalty) -> Vec<f64> {
+    let (low, high) = match penalty {
+        Penalty::Ridge => (1e-4, 1e3),
+        Penalty::Lasso => {
+            let Standardized { z, yc, .. } = standardize(x, y);
+            let n = y.len() as f64;
+            let max = xt_y(&z, &yc, None).iter().fold(0.0f64, |m, v| m.max(v.abs() / n));
+            if max <= 0.0 {
+                return vec![0.0];
+            }
+            (max * 1e-4, max)
+        }
+    };
+    let steps = 29.0;
+    (0..30).rev().map(|k| low * (high / low).powf(k as f64 / steps)).collect()
+}
+
+/// Standardized predictors and centered response, with the scaling used
+struct Standardized {
+    z: Vec<Vec<f64>>,
+    yc: Vec<f64>,
+    y_mean: f64,
+    means: Vec<f64>,
+    scales: Vec<f64>,
+}
+
+fn standardize(x: &[Vec<f64>], y: &[f64]) -> Standardized {
+    let (means, scales) = column_scaling(x);
+    let z: Vec<Vec<f64>> = x
+        .iter()
+        .map(|row| row.iter().zip(means.iter().zip(&scales)).map(|(v, (m, s))| (v - m) / s).collect())
+        .collect();
+    let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+    let yc: Vec<f64> = y.iter().map(|v| v - y_mean).collect();
+    Standardized { z, yc, y_mean, means, scales }
+}
+
+/// Penalized fit returning `(intercept, coefficients)` on the original scale
+fn fit_penalized(x: &[Vec<f64>], y: &[f64], lambda: f64, penalty: Penalty) -> Option<(f64, Vec<f64>)> {
+    if x.len() < 2 {
+        return None;
+    }
+    let n = x.len() as f64;
+    let Standardized { z, yc, y_mean, means, scales } = standardize(x, y);
+    let p = means.len();
+
+    let beta = match penalty {
+        Penalty::Ridge => {
+            let mut a = gram(&z, None);
+            for (i, row) in a.iter_mut().enumerate() {
+                for v in row.iter_mut() {
+                    *v /= n;
+                }
+                row[i] += lambda;
+            }
+            let b: Vec<f64> = xt_y(&z, &yc, None).iter().map(|v| v / n).collect();
+            solve(a, b)?
+        }
+        Penalty::Lasso => {
+            let columns: Vec<Vec<f64>> = (0..p).map(|j| z.iter().map(|row| row[j]).collect()).collect();
+            let mut beta = vec![0.0; p];
+            let mut residual = yc;
+            lasso_coordinate_descent(&columns, &mut residual, &mut beta, lambda);
+            beta
+        }
+    };
+
+    let coefficients: Vec<f64> = beta.iter().zip(&scales).map(|(b, s)| b / s).collect();
+    let intercept = y_mean - dot(&coefficients, &means);
+    Some((intercept, coefficients))
+}
+
+/// Coordinate descent for `RSS / (2n) + lambda * |b|_1` without intercept
+///
+/// Starts from `beta` (a warm start along a penalty path) and updates it in
+/// place; `residual` must hold the target minus `columns * beta` on entry
+/// and is kept in step.
+pub(crate) fn lasso_coordinate_descent(columns: &[Vec<f64>], residual: &mut [f64], beta: &mut [f64], lambda: f64) {
+    let n = residual.len() as f64;
+    let norms: Vec<f64> = columns.iter().map(|c| dot(c, c) / n).collect();
+    for _ in 0..1000 {
+        let mut max_change = 0.0f64;
+        for j in 0..columns.len() {
+            if norms[j] <= 0.0 {
+                continue;
+            }
+            let rho = dot(&columns[j], residual) / n + norms[j] * beta[j];
+            let updated = rho.signum() * (rho.abs() - lambda).max(0.0) / norms[j];
+            let change = updated - beta[j];
+            if change != 0.0 {
+                for (r, v) in residual.iter_mut().zip(&columns[j]) {
+                    *r -= change * v;
+                }
+                beta[j] = updated;
+                max_change = max_change.max(change.abs());
+            }
+        }
+        if max_change < 1e-10 {
+            break;
+        }
+    }
+}
+
+/// Theil-Sen robust line fit: the median of all pairwise slopes
+///
+/// Up to about 29% of the points can be arbitrary outliers without dragging
+/// the line, so a single spike day cannot flip the trend the way it can with
+/// `linear_regression`. The exact median slope is found without listing all
+/// O(n^2) pairs: the slope is bisected using O(n log n) merge-sort pair
+/// counts until few candidate pairs remain, and only those are ranked. The
+/// intercept is the median of `y - slope * x`. Pairs with equal `x` are
+/// skipped; non-finite points are dropped.
+///
+/// # Arguments
+/// * `x` - Independent variable
+/// * `y` - Dependent variable
+#[napi]
+pub fn theil_sen(x: Vec<f64>, y: Vec<f64>) -> LinearRegressionResult {
+    let mut points: Vec<(f64, f64)> = x
+        .iter()
+        .zip(&y)
+        .filter(|(a, b)| a.is_finite() && b.is_finite())
+        .map(|(a, b)| (*a, *b))
+        .collect();
+    // Equal-x runs sorted by descending y always count as "slope <= s"; see `pairs_at_most`
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)));
+
+    let n = points.len() as u64;
+    let tied: u64 = points
+        .chunk_by(|a, b| a.0 == b.0)
+        .map(|run| run.len() as u64 * (run.len() as u64 - 1) / 2)
+        .sum();
+    let pairs = n * n.saturating_sub(1) / 2 - tied;
+    if pairs == 0 {
+        return LinearRegressionResult {
+            slope: 0.0,
+            intercept: median(points.iter().map(|p| p.1).collect()),
+            r_squared: 0.0,
+        };
+    }
+
+    let slope = if pairs % 2 == 1 {
+        kth_slope(&points, tied, pairs, pairs.div_ceil(2))
+    } else {
+        0.5 * (kth_slope(&points, tied, pairs, pairs / 2) + kth_slope(&points, tied, pairs, pairs / 2 + 1))
+    };
+    let intercept = median(points.iter().map(|(px, py)| py - slope * px).collect());
+
+    let y_mean = points.iter().map(|p| p.1).sum::<f64>() / n as f64;
+    let ss_tot: f64 = points.iter().map(|p| (p.1 - y_mean).powi(2)).sum();
+    let ss_res: f64 = points.iter().map(|(px, py)| (py - (slope * px + intercept)).powi(2)).sum();
+
+    LinearRegressionResult {
+        slope,
+        intercept,
+        r_squared: if ss_tot != 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 },
+    }
+}
+
+/// The `k`-th smallest (1-based) pairwise slope among `pairs` distinct-x pairs
+///
+/// `points` must be sorted as in `theil_sen`; `tied` is the number of equal-x pairs.
+fn kth_slope(points: &[(f64, f64)], tied: u64, pairs: u64, k: u64) -> f64 {
+    // Extreme slopes always occur between neighbouring x values
+    let runs: Vec<&[(f64, f64)]> = points.chunk_by(|a, b| a.0 == b.0).collect();
+    let (mut min_slope, mut max_slope) = (f64::INFINITY, f64::NEG_INFINITY);
+    for pair in runs.windows(2) {
+        let (left, right) = (pair[0], pair[1]);
+        let dx = right[0].0 - left[0].0;
+        // Runs are sorted by descending y
+        min_slope = min_slope.min((right[right.len() - 1].1 - left[0].1) / dx);
+        max_slope = max_slope.max((right[0].1 - left[left.len() - 1].1) / dx);
+    }
+
+    // Invariant: `count_lo` < k slopes are <= lo and `count_hi` >= k are <= hi
+    let (mut lo, mut hi) = (min_slope - (max_slope - min_slope) - 1.0, max_slope);
+    let (mut count_lo, mut count_hi) = (0, pairs);
+    while count_hi - count_lo > points.len() as u64 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            return hi;
+        }
+        let count = pairs_at_most(points, mid) - tied;
+        if count >= k {
+            (hi, count_hi) = (mid, count);
+        } else {
+            (lo, count_lo) = (mid, count);
+        }
+    }
+
+    let mut candidates = slopes_between(points, lo, hi);
+    if candidates.is_empty() {
+        return hi;
+    }
+    let index = ((k - count_lo - 1) as usize).min(candidates.len() - 1);
+    *candidates.select_nth_unstable_by(index, |a, b| a.total_cmp(b)).1
+}
+
+/// Number of pairs `i < j` (in sorted order) with `y_j - s x_j <= y_i - s x_i`,
+/// i.e. distinct-x pairs with slope <= `s` plus every equal-x pair
+fn pairs_at_most(points: &[(f64, f64)], s: f64) -> u64 {
+    let mut values: Vec<f64> = points.iter().map(|(x, y)| y - s * x).collect();
+    let mut buffer = vec![0.0; values.len()];
+    count_descents(&mut values, &mut buffer)
+}
+
+/// Merge-sort `values` ascending, counting pairs `i < j` with `values[j] <= values[i]`
+fn count_descents(values: &mut [f64], buffer: &mut [f64]) -> u64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let mid = n / 2;
+    let mut count = count_descents(&mut values[..mid], &mut buffer[..mid]) + count_descents(&mut values[mid..], &mut buffer[mid..]);
+    let (mut i, mut j) = (0, mid);
+    for slot in buffer.iter_mut() {
+        if j >= n || (i < mid && values[i] < values[j]) {
+            *slot = values[i];
+            i += 1;
+        } else {
+            // Every remaining left value is >= values[j]
+            count += (mid - i) as u64;
+            *slot = values[j];
+            j += 1;
+        }
+    }
+    values.copy_from_slice(buffer);
+    count
+}
+
+/// Slopes of all distinct-x pairs with `lo < slope <= hi`
+///
+/// Such pairs are exactly those ordered one way by `y - lo x` and the other
+/// way by `y - hi x`, so they are the inversions between the two orderings.
+fn slopes_between(points: &[(f64, f64)], lo: f64, hi: f64) -> Vec<f64> {
+    // (u at lo, u at hi, point index)
+    let mut keyed: Vec<(f64, f64, usize)> = points.iter().enumerate().map(|(i, (x, y))| (y - lo * x, y - hi * x, i)).collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let mut buffer = keyed.clone();
+    let mut slopes = Vec::new();
+    collect_inversions(&mut keyed, &mut buffer, &mut |a, b| {
+        let ((xa, ya), (xb, yb)) = (points[a], points[b]);
+        if xa != xb {
+            slopes.push((yb - ya) / (xb - xa));
+        }
+    });
+    slopes
+}
+
+/// Merge-sort by `(u at hi, -(u at lo))`, reporting each inverted pair of point indices
+fn collect_inversions<F: FnMut(usize, usize)>(items: &mut [(f64, f64, usize)], buffer: &mut [(f64, f64, usize)], report: &mut F) {
+    let n = items.len();
+    if n < 2 {
+        return;
+    }
+    let mid = n / 2;
+    collect_inversions(&mut items[..mid], &mut buffer[..mid], report);
+    collect_inversions(&mut items[mid..], &mut buffer[mid..], report);
+    let before = |a: &(f64, f64, usize), b: &(f64, f64, usize)| a.1 < b.1 || (a.1 == b.1 && a.0 >= b.0);
+    let (mut i, mut j) = (0, mid);
+    for slot in buffer.iter_mut() {
+        if j >= n || (i < mid && before(&items[i], &items[j])) {
+            *slot = items[i];
+            i += 1;
+        } else {
+            for left in &items[i..mid] {
+                report(left.2, items[j].2);
+            }
+            *slot = items[j];
+            j += 1;
+        }
+    }
+    items.copy_from_slice(buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_metrics() {
+        // Quadratic data: adding x^2 must win on every criterion
+        let x: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let y: Vec<f64> = x.iter().enumerate().map(|(i, v)| 5.0 + 0.5 * v * v + if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        let linear = multiple_regression(x.iter().map(|v| vec![*v]).collect(), y.clone());
+        let quadratic = multiple_regression(x.iter().map(|v| vec![*v, v * v]).collect(), y.clone());
+        assert!(quadratic.aic < linear.aic && quadratic.bic < linear.bic);
+        assert!(quadratic.adjusted_r_squared > linear.adjusted_r_squared);
+        assert!(quadratic.adjusted_r_squared < quadratic.r_squared);
+
+        // Same numbers from the standalone helper
+        let fitted: Vec<f64> = x.iter().map(|v| quadratic.intercept + quadratic.coefficients[0] * v + quadratic.coefficients[1] * v * v).collect();
+        let metrics = regression_fit_metrics(y, fitted, 3);
+        assert!((metrics.aic - quadratic.aic).abs() < 1e-6);
+        assert!((metrics.bic - metrics.aic - 3.0 * (30f64.ln() - 2.0)).abs() < 1e-9);
+        assert_eq!(linear_regression_full(x.clone(), x.clone()).adjusted_r_squared, 1.0);
+    }
+
+    #[test]
+    fn test_durbin_watson() {
+        // Alternating residuals: strong negative autocorrelation
+        let alternating: Vec<f64> = (0..100).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        assert!((durbin_watson(alternating) - 4.0 * 99.0 / 100.0).abs() < 1e-12);
+        // Slowly drifting residuals: strong positive autocorrelation
+        let drifting: Vec<f64> = (0..100).map(|i| (i as f64 / 10.0).sin()).collect();
+        assert!(durbin_watson(drifting) < 0.1);
+        assert!(durbin_watson(vec![0.0, 0.0]).is_nan());
+    }
+
+    #[test]
+    fn test_ridge_and_lasso() {
+        let mut rng = crate::sampling::Rng::new(11);
+        // Two nearly identical drivers plus an irrelevant one
+        let x: Vec<Vec<f64>> = (0..80)
+            .map(|_| {
+                let a = rng.next_f64() * 10.0;
+                vec![a, a + rng.next_f64() * 0.01, rng.next_f64() * 10.0]
+            })
+            .collect();
+        let y: Vec<f64> = x.iter().map(|row| 1.0 + 2.0 * row[0] + (rng.next_f64() - 0.5)).collect();
+
+        // lambda = 0 reproduces ordinary least squares
+        let ols = multiple_regression(x.clone(), y.clone());
+        let plain = ridge_regression(x.clone(), y.clone(), Some(0.0), None);
+        assert!(plain.coefficients.iter().zip(&ols.coefficients).all(|(a, b)| (a - b).abs() < 1e-6));
+
+        // Ridge shares the effect between the collinear drivers
+        let ridge = ridge_regression(x.clone(), y.clone(), Some(0.1), None);
+        assert!((ridge.coefficients[0] + ridge.coefficients[1] - 2.0).abs() < 0.2);
+        assert!((ridge.coefficients[0] - ridge.coefficients[1]).abs() < 0.2);
+
+        let lasso = lasso_regression(x.clone(), y.clone(), Some(0.2), None);
+        assert_eq!(lasso.coefficients[2], 0.0);
+        assert!(lasso.coefficients[0] == 0.0 || lasso.coefficients[1] == 0.0);
+
+        let cv = lasso_regression(x, y, None, Some(RegularizationOptions { n_folds: Some(4), ..Default::default() }));
+        assert_eq!(cv.cv_lambdas.len(), 30);
+        assert!(cv.cv_lambdas.contains(&cv.lambda));
+        assert!(cv.r_squared > 0.95);
+    }
+
+    #[test]
+    fn test_theil_sen() {
+        // A single spike barely moves the robust fit
+        let x: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let mut y: Vec<f64> = x.iter().map(|v| 5.0 + 2.0 * v).collect();
+        y[29] = -500.0;
+        let fit = theil_sen(x.clone(), y.clone());
+        assert!((fit.slope - 2.0).abs() < 1e-9);
+        assert!((fit.intercept - 5.0).abs() < 1e-9);
+
+        // Exact median of pairwise slopes, including tied x values
+        let mut rng = crate::sampling::Rng::new(3);
+        let x: Vec<f64> = (0..300).map(|_| (rng.next_f64() * 50.0).floor()).collect();
+        let y: Vec<f64> = x.iter().map(|v| 0.5 * v + rng.next_f64() * 20.0).collect();
+        let mut slopes = Vec::new();
+        for i in 0..x.len() {
+            for j in (i + 1)..x.len() {
+                if x[i] != x[j] {
+                    slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+                }
+            }
+        }
+        assert!((theil_sen(x, y).slope - median(slopes)).abs() < 1e-12);
+
+        assert_eq!(theil_sen(vec![1.0, 1.0], vec![2.0, 4.0]).slope, 0.0);
+    }
+
+    #[test]
+    fn test_linear_regression_full() {
+        // y = 1 + 2 x plus residuals [1, -1, 0, 0, -1, 1], orthogonal to x
+        let x = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let y = vec![4.0, 4.0, 7.0, 9.0, 10.0, 14.0];
+        let fit = linear_regression_full(x.clone(), y);
+        assert!((fit.slope - 2.0).abs() < 1e-12 && (fit.intercept - 1.0).abs() < 1e-12);
+        // RSS = 4, df = 4, Sxx = 17.5
+        let sigma = 1.0;
+        assert!((fit.slope_std_error - sigma / 17.5f64.sqrt()).abs() < 1e-12);
+        assert!(fit.slope_p_value < 0.01);
+
+        let predictions = predict_with_interval(fit.clone(), vec![3.5, 10.0], None);
+        assert!((predictions[0].prediction - 8.0).abs() < 1e-12);
+        // t(0.975, 4) = 2.776; at x = mean the mean interval is sigma / sqrt(n) wide
+        assert!((predictions[0].mean_upper - predictions[0].prediction - 2.776445 * sigma / 6f64.sqrt()).abs() < 1e-5);
+        assert!(predictions[1].upper - predictions[1].lower > predictions[0].upper - predictions[0].lower);
+        assert!(predictions.iter().all(|p| p.lower < p.mean_lower && p.mean_upper < p.upper));
+
+        // No trend in pure alternation
+        let flat = linear_regression_full(x, vec![1.0, -1.0, 1.0, -1.0, 1.0, -1.0]);
+        assert!(flat.slope_p_value > 0.3);
+        assert!(predict_with_interval(linear_regression_full(vec![1.0, 2.0], vec![1.0, 2.0]), vec![3.0], None).is_empty());
+    }
+
+    #[test]
+    fn test_multiple_regression() {
+        // y = 2 + 3 a - 1.5 b + small deterministic noise
+        let x: Vec<Vec<f64>> = (0..40).map(|i| vec![i as f64, ((i * 7) % 11) as f64]).collect();
+        let y: Vec<f64> = x
+            .iter()
+            .enumerate()
+            .map(|(i, row)| 2.0 + 3.0 * row[0] - 1.5 * row[1] + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+        let result = multiple_regression(x, y);
+        assert!((result.intercept - 2.0).abs() < 0.1);
+        assert!((result.coefficients[0] - 3.0).abs() < 0.01);
+        assert!((result.coefficients[1] + 1.5).abs() < 0.02);
+        assert!(result.r_squared > 0.999);
+        assert!(result.p_values.iter().all(|p| *p < 1e-6));
+        assert_eq!(result.n_observations, 40);
+
+        // Perfectly collinear predictors cannot be separated
+        let collinear: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64, 2.0 * i as f64]).collect();
+        assert!(multiple_regression(collinear, (0..10).map(|i| i as f64).collect()).coefficients.is_empty());
+    }
+}
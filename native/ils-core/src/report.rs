@@ -0,0 +1,411 @@
+//! Report Export
+//!
+//! Serializes analysis results to CSV or JSON strings in Rust, so large
+//! reports are assembled without building intermediate objects on the JS heap:
+//! - Anomaly analysis summaries
+//! - Forecast batches
+//! - Template-based narrative summaries of forecasts
+
+use napi_derive::napi;
+use serde::Serialize;
+
+use crate::anomaly::AnomalyAnalysisSummary;
+use crate::forecasting::{identify_surges, ForecastResult};
+use crate::statistics::linear_regression;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct NarrativeTemplate {
+    /// Trend sentence; placeholders `{direction}`, `{steps}`, `{trend_per_step}`
+    pub trend: Option<String>,
+    /// Peak sentence; placeholders `{peak_value}`, `{peak_step}`
+    pub peak: Option<String>,
+    /// Comparison sentence; placeholders `{forecast_total}`, `{previous_total}`,
+    /// `{change_percent}`, `{comparison}`, `{steps}`
+    pub comparison: Option<String>,
+    /// Surge sentence; placeholder `{surge_periods}`
+    pub surges: Option<String>,
+    /// Words substituted for `{direction}` (defaults "increasing", "decreasing", "stable")
+    pub increasing: Option<String>,
+    pub decreasing: Option<String>,
+    pub stable: Option<String>,
+    /// Words substituted for `{comparison}` (defaults "above", "below")
+    pub above: Option<String>,
+    pub below: Option<String>,
+    /// Decimal places for numbers in the text (default 1)
+    pub decimals: Option<u32>,
+    /// Ratio to the forecast mean that counts as a surge (default 1.2)
+    pub surge_threshold: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ForecastNarrative {
+    /// All sentences joined with spaces
+    pub summary: String,
+    pub sentences: Vec<String>,
+    /// "increasing", "decreasing" or "stable" (not localized)
+    pub direction: String,
+    /// 1-based forecast step of the highest predicted value
+    pub peak_step: u32,
+    pub peak_value: f64,
+    pub forecast_total: f64,
+    /// Sum of the last `steps` history values (0 when history is too short)
+    pub previous_total: f64,
+    pub change_percent: f64,
+    /// Surge periods as 1-based step ranges, e.g. "3" or "5-7"
+    pub surge_periods: Vec<String>,
+}
+
+/// Export an anomaly analysis summary as a "csv" or "json" string
+///
+/// JSON mirrors the summary object (camelCase keys). CSV is a single long
+/// table with one row per anomaly, seasonal anomaly and trend change,
+/// distinguished by the `record_type` column; cells that do not apply to a
+/// record type are left empty. Unknown formats fall back to JSON.
+///
+/// # Arguments
+/// * `summary` - Result of `analyze_anomalies`
+/// * `format` - "csv" or "json"
+#[napi]
+pub fn export_report(summary: AnomalyAnalysisSummary, format: String) -> String {
+    if !format.eq_ignore_ascii_case("csv") {
+        return serde_json::to_string(&summary).unwrap_or_default();
+    }
+
+    let mut out = String::from(
+        "record_type,index,value,expected_value,deviation,severity,methods,old_trend,new_trend,change_percent,significant\n",
+    );
+    for a in &summary.anomalies {
+        let methods = a.methods.join(";");
+        push_row(
+            &mut out,
+            &[
+                "anomaly",
+                &a.index.to_string(),
+                &a.value.to_string(),
+                "",
+                &a.deviation_percent.to_string(),
+                &a.severity,
+                &methods,
+                "",
+                "",
+                "",
+                "",
+            ],
+        );
+    }
+    for s in &summary.seasonal_anomalies {
+        push_row(
+            &mut out,
+            &[
+                "seasonal_anomaly",
+                &s.index.to_string(),
+                &s.value.to_string(),
+                &s.expected_value.to_string(),
+                &s.deviation.to_string(),
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+            ],
+        );
+    }
+    for t in &summary.trend_changes {
+        push_row(
+            &mut out,
+            &[
+                "trend_change",
+                &t.index.to_string(),
+                "",
+                "",
+                "",
+                "",
+                "",
+                &t.old_trend.to_string(),
+                &t.new_trend.to_string(),
+                &t.change_percent.to_string(),
+                &t.significant.to_string(),
+            ],
+        );
+    }
+    out
+}
+
+/// Export a batch of forecasts as a "csv" or "json" string
+///
+/// Each row carries its 1-based horizon `step`. Unknown formats fall back to JSON.
+///
+/// # Arguments
+/// * `forecasts` - Result of `predict_next` (or any forecast batch)
+/// * `format` - "csv" or "json"
+#[napi]
+pub fn export_forecast_report(forecasts: Vec<ForecastResult>, format: String) -> String {
+    if !format.eq_ignore_ascii_case("csv") {
+        let records: Vec<ForecastRecord> = forecasts
+            .iter()
+            .enumerate()
+            .map(|(i, forecast)| ForecastRecord { step: i as u32 + 1, forecast })
+            .collect();
+        return serde_json::to_string(&records).unwrap_or_default();
+    }
+
+    let mut out = String::from("step,predicted_value,confidence,lower_bound,upper_bound,confidence_level,trend\n");
+    for (i, f) in forecasts.iter().enumerate() {
+        push_row(
+            &mut out,
+            &[
+                &(i + 1).to_string(),
+                &f.predicted_value.to_string(),
+                &f.confidence.to_string(),
+                &f.lower_bound.to_string(),
+                &f.upper_bound.to_string(),
+                &f.confidence_level.to_string(),
+                &f.trend,
+            ],
+        );
+    }
+    out
+}
+
+/// Generate a natural-language summary of a forecast
+///
+/// Describes the trend direction, the expected peak, the change versus the
+/// same number of periods at the end of `history`, and notable surges. Every
+/// sentence comes from a replaceable template so callers can localize or
+/// restyle the text; the comparison sentence is omitted when `history` is
+/// shorter than the forecast and the surge sentence when there are no surges.
+///
+/// # Arguments
+/// * `history` - Observed values preceding the forecast
+/// * `forecasts` - Forecast batch (e.g. from `predict_next`)
+/// * `template` - Sentence templates and wording overrides
+#[napi]
+pub fn forecast_narrative(
+    history: Vec<f64>,
+    forecasts: Vec<ForecastResult>,
+    template: Option<NarrativeTemplate>,
+) -> ForecastNarrative {
+    let template = template.unwrap_or_default();
+    let decimals = template.decimals.unwrap_or(1) as usize;
+    let number = |v: f64| format!("{:.*}", decimals, v);
+    let steps = forecasts.len();
+    let values: Vec<f64> = forecasts.iter().map(|f| f.predicted_value).collect();
+
+    if steps == 0 {
+        return ForecastNarrative {
+            summary: String::new(),
+            sentences: Vec::new(),
+            direction: "stable".to_string(),
+            peak_step: 0,
+            peak_value: 0.0,
+            forecast_total: 0.0,
+            previous_total: 0.0,
+            change_percent: 0.0,
+            surge_periods: Vec::new(),
+        };
+    }
+
+    // Direction: fitted change across the horizon relative to the forecast level
+    let forecast_total: f64 = values.iter().sum();
+    let level = (forecast_total / steps as f64).abs();
+    let slope = linear_regression((0..steps).map(|i| i as f64).collect(), values.clone()).slope;
+    let relative_change = if level > 0.0 { slope * steps as f64 / level } else { 0.0 };
+    let direction = if relative_change > 0.05 {
+        "increasing"
+    } else if relative_change < -0.05 {
+        "decreasing"
+    } else {
+        "stable"
+    };
+    let direction_word = match direction {
+        "increasing" => template.increasing.clone().unwrap_or_else(|| direction.to_string()),
+        "decreasing" => template.decreasing.clone().unwrap_or_else(|| direction.to_string()),
+        _ => template.stable.clone().unwrap_or_else(|| direction.to_string()),
+    };
+
+    let (peak_index, peak_value) = values
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0));
+
+    let mut sentences = vec![
+        template
+            .trend
+            .as_deref()
+            .unwrap_or("Volume is expected to be {direction} over the next {steps} periods ({trend_per_step} per period).")
+            .replace("{direction}", &direction_word)
+            .replace("{steps}", &steps.to_string())
+            .replace("{trend_per_step}", &format!("{:+.*}", decimals, slope)),
+        template
+            .peak
+            .as_deref()
+            .unwrap_or("The expected peak is {peak_value} in period {peak_step}.")
+            .replace("{peak_value}", &number(peak_value))
+            .replace("{peak_step}", &(peak_index + 1).to_string()),
+    ];
+
+    let (previous_total, change_percent) = if history.len() >= steps {
+        let previous_total: f64 = history[history.len() - steps..].iter().sum();
+        let change = if previous_total != 0.0 {
+            (forecast_total - previous_total) / previous_total.abs() * 100.0
+        } else {
+            0.0
+        };
+        let comparison = if change >= 0.0 {
+            template.above.clone().unwrap_or_else(|| "above".to_string())
+        } else {
+            template.below.clone().unwrap_or_else(|| "below".to_string())
+        };
+        sentences.push(
+            template
+                .comparison
+                .as_deref()
+                .unwrap_or("The forecast total of {forecast_total} is {change_percent}% {comparison} the previous {steps} periods ({previous_total}).")
+                .replace("{forecast_total}", &number(forecast_total))
+                .replace("{previous_total}", &number(previous_total))
+                .replace("{change_percent}", &number(change.abs()))
+                .replace("{comparison}", &comparison)
+                .replace("{steps}", &steps.to_string()),
+        );
+        (previous_total, change)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let labels: Vec<String> = (1..=steps).map(|s| s.to_string()).collect();
+    let surge_periods: Vec<String> = identify_surges(values, labels, template.surge_threshold.unwrap_or(1.2))
+        .into_iter()
+        .map(|s| if s.start_date == s.end_date { s.start_date } else { format!("{}-{}", s.start_date, s.end_date) })
+        .collect();
+    if !surge_periods.is_empty() {
+        sentences.push(
+            template
+                .surges
+                .as_deref()
+                .unwrap_or("Notable surges are expected in periods {surge_periods}.")
+                .replace("{surge_periods}", &surge_periods.join(", ")),
+        );
+    }
+
+    ForecastNarrative {
+        summary: sentences.join(" "),
+        sentences,
+        direction: direction.to_string(),
+        peak_step: peak_index as u32 + 1,
+        peak_value,
+        forecast_total,
+        previous_total,
+        change_percent,
+        surge_periods,
+    }
+}
+
+#[derive(Serialize)]
+struct ForecastRecord<'a> {
+    step: u32,
+    #[serde(flatten)]
+    forecast: &'a ForecastResult,
+}
+
+fn push_row(out: &mut String, fields: &[&str]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anomaly::analyze_anomalies;
+    use crate::forecasting::predict_next;
+
+    #[test]
+    fn test_export_anomaly_report() {
+        let mut data: Vec<f64> = (0..60).map(|i| 100.0 + (i % 7) as f64).collect();
+        data[30] = 400.0;
+        let summary = analyze_anomalies(data, 2.5, 7, 7);
+        let n_rows = summary.anomalies.len() + summary.seasonal_anomalies.len() + summary.trend_changes.len();
+
+        let csv = export_report(summary.clone(), "csv".to_string());
+        assert_eq!(csv.lines().count(), n_rows + 1);
+        assert!(csv.lines().all(|line| line.split(',').count() == 11));
+        assert!(csv.contains("anomaly,30,400,"));
+
+        let json: serde_json::Value = serde_json::from_str(&export_report(summary, "json".to_string())).unwrap();
+        assert!(json["totalAnomalies"].as_u64().unwrap() >= 1);
+        assert_eq!(json["anomalies"][0]["index"], 30);
+    }
+
+    #[test]
+    fn test_export_forecast_report() {
+        let forecasts = predict_next((0..28).map(|i| 50.0 + (i % 7) as f64).collect(), 3, 7);
+        let csv = export_forecast_report(forecasts.clone(), "CSV".to_string());
+        assert!(csv.starts_with("step,predicted_value"));
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.lines().nth(1).unwrap().contains(",0.95,"));
+
+        let json: serde_json::Value = serde_json::from_str(&export_forecast_report(forecasts, "json".to_string())).unwrap();
+        assert_eq!(json[2]["step"], 3);
+        assert!(json[0]["predictedValue"].is_number());
+    }
+
+    fn forecast(value: f64) -> ForecastResult {
+        ForecastResult {
+            predicted_value: value,
+            confidence: 0.9,
+            lower_bound: value - 5.0,
+            upper_bound: value + 5.0,
+            confidence_level: 0.95,
+            trend: "increasing".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_forecast_narrative() {
+        let history = vec![10.0; 10];
+        let forecasts: Vec<ForecastResult> = [10.0, 12.0, 14.0, 30.0, 16.0, 18.0].iter().map(|&v| forecast(v)).collect();
+
+        let narrative = forecast_narrative(history, forecasts.clone(), None);
+        assert_eq!(narrative.direction, "increasing");
+        assert_eq!(narrative.peak_step, 4);
+        assert!((narrative.change_percent - 66.6667).abs() < 0.01);
+        assert_eq!(narrative.surge_periods, vec!["4".to_string()]);
+        assert_eq!(narrative.sentences.len(), 4);
+        assert!(narrative.summary.contains("66.7% above the previous 6 periods"));
+
+        let template = NarrativeTemplate {
+            peak: Some("Pico de {peak_value} en el periodo {peak_step}.".to_string()),
+            increasing: Some("creciente".to_string()),
+            trend: Some("Tendencia {direction}.".to_string()),
+            decimals: Some(0),
+            ..Default::default()
+        };
+        let localized = forecast_narrative(Vec::new(), forecasts, Some(template));
+        assert_eq!(localized.sentences[0], "Tendencia creciente.");
+        assert_eq!(localized.sentences[1], "Pico de 30 en el periodo 4.");
+        // No comparison sentence without enough history
+        assert_eq!(localized.sentences.len(), 3);
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let mut out = String::new();
+        push_row(&mut out, &["a,b", "say \"hi\"", "plain"]);
+        assert_eq!(out, "\"a,b\",\"say \"\"hi\"\"\",plain\n");
+    }
+}
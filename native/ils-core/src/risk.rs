@@ -0,0 +1,519 @@
+//! Operational Risk Models
+//!
+//! Probability models for order-book and fulfilment risk:
+//! - Logistic regression (Newton/IRLS with L2 penalty)
+//! - Order cancellation probability scoring
+//! - Shipment delay risk from empirical transit-time distributions
+
+use std::collections::HashMap;
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::{column_scaling, dot, gram, solve, xt_y};
+use crate::statistics::{mean, quantile};
+
+/// Quantile grid resolution for stored transit-time distributions
+const TRANSIT_GRID_POINTS: usize = 21;
+
+/// Fit a logistic regression model
+///
+/// Features are standardized internally; the returned model stores the
+/// scaling so `predict_probability` accepts raw feature rows. Fails when
+/// `x` and `y` differ in length or the rows differ in width.
+///
+/// # Arguments
+/// * `x` - Feature rows (one row per observation)
+/// * `y` - Binary outcomes (0 or 1)
+/// * `l2_penalty` - Ridge penalty on standardized coefficients (default 1.0)
+/// * `max_iterations` - Newton iteration cap (default 50)
+#[napi]
+pub fn logistic_regression(
+    x: Vec<Vec<f64>>,
+    y: Vec<f64>,
+    l2_penalty: Option<f64>,
+    max_iterations: Option<u32>,
+) -> napi::Result<LogisticModel> {
+    let n_features = x.first().map_or(0, |row| row.len());
+    if x.len() != y.len() {
+        return Err(Error::new(Status::InvalidArg, format!("{} feature rows but {} outcomes", x.len(), y.len())));
+    }
+    if let Some(row) = x.iter().position(|row| row.len() != n_features) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("feature row {} has {} values, expected {}", row, x[row].len(), n_features),
+        ));
+    }
+    if x.is_empty() {
+        return Ok(LogisticModel {
+            coefficients: vec![0.0; n_features],
+            intercept: 0.0,
+            feature_means: vec![0.0; n_features],
+            feature_scales: vec![1.0; n_features],
+            iterations: 0,
+            log_likelihood: 0.0,
+        });
+    }
+
+    let lambda = l2_penalty.unwrap_or(1.0).max(0.0);
+    let max_iter = max_iterations.unwrap_or(50);
+    let (feature_means, feature_scales) = column_scaling(&x);
+
+    // Design matrix with leading intercept column
+    let design: Vec<Vec<f64>> = x
+        .iter()
+        .map(|row| {
+            std::iter::once(1.0)
+                .chain(row.iter().enumerate().map(|(j, v)| (v - feature_means[j]) / feature_scales[j]))
+                .collect()
+        })
+        .collect();
+
+    let p = n_features + 1;
+    let mut beta = vec![0.0; p];
+    let mut iterations = 0;
+
+    for _ in 0..max_iter {
+        iterations += 1;
+        let probs: Vec<f64> = design.iter().map(|row| sigmoid(dot(row, &beta))).collect();
+        let weights: Vec<f64> = probs.iter().map(|p| (p * (1.0 - p)).max(1e-10)).collect();
+        let residuals: Vec<f64> = y.iter().zip(&probs).map(|(t, p)| t - p).collect();
+
+        let mut gradient = xt_y(&design, &residuals, None);
+        let mut hessian = gram(&design, Some(&weights));
+
+        // Intercept is not penalized
+        for a in 1..p {
+            gradient[a] -= lambda * beta[a];
+            hessian[a][a] += lambda;
+        }
+
+        let step = match solve(hessian, gradient) {
+            Some(step) => step,
+            None => break,
+        };
+        beta.iter_mut().zip(&step).for_each(|(b, s)| *b += s);
+
+        if step.iter().map(|s| s.abs()).fold(0.0, f64::max) < 1e-8 {
+            break;
+        }
+    }
+
+    let log_likelihood = design
+        .iter()
+        .zip(&y)
+        .map(|(row, &target)| {
+            let prob = sigmoid(dot(row, &beta)).clamp(1e-15, 1.0 - 1e-15);
+            target * prob.ln() + (1.0 - target) * (1.0 - prob).ln()
+        })
+        .sum();
+
+    Ok(LogisticModel {
+        intercept: beta[0],
+        coefficients: beta[1..].to_vec(),
+        feature_means,
+        feature_scales,
+        iterations,
+        log_likelihood,
+    })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LogisticModel {
+    /// Coefficients on standardized features
+    pub coefficients: Vec<f64>,
+    pub intercept: f64,
+    pub feature_means: Vec<f64>,
+    pub feature_scales: Vec<f64>,
+    pub iterations: u32,
+    pub log_likelihood: f64,
+}
+
+/// Predict event probabilities for feature rows (parallel)
+///
+/// Fails when a row's width differs from the model's.
+#[napi]
+pub fn predict_probability(model: LogisticModel, x: Vec<Vec<f64>>) -> napi::Result<Vec<f64>> {
+    x.par_iter().map(|row| logistic_score(&model, row)).collect()
+}
+
+/// Fit a cancellation model from historical orders and their outcomes
+///
+/// Each order is converted to the feature vector
+/// `[ln(1 + age), status_changes, ln(1 + hours_in_status), ...customer_features]`;
+/// the log transforms let the hazard flatten out for long-open orders. Fails
+/// when orders and outcomes differ in number or orders carry different
+/// numbers of customer features.
+#[napi]
+pub fn fit_cancellation_model(
+    orders: Vec<OpenOrder>,
+    cancelled: Vec<bool>,
+    l2_penalty: Option<f64>,
+) -> napi::Result<LogisticModel> {
+    let x: Vec<Vec<f64>> = orders.iter().map(order_features).collect();
+    let y: Vec<f64> = cancelled.iter().map(|&c| if c { 1.0 } else { 0.0 }).collect();
+    logistic_regression(x, y, l2_penalty, None)
+}
+
+/// Score the open-order book for cancellation risk (parallel)
+///
+/// Fails when an order's customer features do not match the model.
+#[napi]
+pub fn score_cancellation_risk(model: LogisticModel, orders: Vec<OpenOrder>) -> napi::Result<Vec<CancellationRisk>> {
+    orders
+        .par_iter()
+        .enumerate()
+        .map(|(index, order)| {
+            let probability = logistic_score(&model, &order_features(order))?;
+            Ok(CancellationRisk {
+                index: index as u32,
+                probability,
+                risk_level: risk_level(probability),
+            })
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct OpenOrder {
+    pub age_hours: f64,
+    pub status_changes: u32,
+    pub hours_in_status: f64,
+    pub customer_features: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CancellationRisk {
+    pub index: u32,
+    pub probability: f64,
+    pub risk_level: String,
+}
+
+/// Learn transit-time distributions per lane (carrier + destination)
+///
+/// Lanes with fewer than `min_lane_samples` shipments (default 20) fall back
+/// to the carrier-wide distribution, and unknown carriers to the overall one.
+#[napi]
+pub fn fit_transit_model(history: Vec<ShipmentRecord>, min_lane_samples: Option<u32>) -> TransitTimeModel {
+    let mut lanes: HashMap<(String, String), Vec<f64>> = HashMap::new();
+    let mut carriers: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut overall = Vec::with_capacity(history.len());
+
+    for record in history.iter().filter(|r| r.transit_days.is_finite()) {
+        lanes
+            .entry((record.carrier.clone(), record.destination.clone()))
+            .or_default()
+            .push(record.transit_days);
+        carriers.entry(record.carrier.clone()).or_default().push(record.transit_days);
+        overall.push(record.transit_days);
+    }
+
+    let mut lanes: Vec<TransitDistribution> = lanes
+        .into_iter()
+        .map(|((carrier, destination), values)| transit_distribution(carrier, destination, values))
+        .collect();
+    lanes.sort_by(|a, b| (&a.carrier, &a.destination).cmp(&(&b.carrier, &b.destination)));
+
+    let mut carriers: Vec<TransitDistribution> = carriers
+        .into_iter()
+        .map(|(carrier, values)| transit_distribution(carrier, String::new(), values))
+        .collect();
+    carriers.sort_by(|a, b| a.carrier.cmp(&b.carrier));
+
+    TransitTimeModel {
+        lanes,
+        carriers,
+        overall: transit_distribution(String::new(), String::new(), overall),
+        min_lane_samples: min_lane_samples.unwrap_or(20),
+    }
+}
+
+/// Score shipments for late-delivery risk against their promised transit time
+#[napi]
+pub fn score_shipment_delay(model: TransitTimeModel, shipments: Vec<ShipmentQuery>) -> Vec<ShipmentDelayRisk> {
+    shipments
+        .par_iter()
+        .enumerate()
+        .map(|(index, shipment)| {
+            let (distribution, source) = resolve_distribution(&model, &shipment.carrier, &shipment.destination);
+            let late_probability = if distribution.count == 0 {
+                0.0
+            } else {
+                1.0 - grid_cdf(&distribution.quantiles, shipment.promised_days)
+            };
+
+            ShipmentDelayRisk {
+                index: index as u32,
+                late_probability,
+                expected_transit_days: distribution.mean,
+                p50_transit_days: grid_quantile(&distribution.quantiles, 0.5),
+                p90_transit_days: grid_quantile(&distribution.quantiles, 0.9),
+                p95_transit_days: grid_quantile(&distribution.quantiles, 0.95),
+                risk_level: risk_level(late_probability),
+                source: source.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Transit time (in days) met with probability `q` for a lane
+///
+/// Use with q = 0.9 or 0.95 to derive promised delivery dates.
+#[napi]
+pub fn transit_time_quantile(model: TransitTimeModel, carrier: String, destination: String, q: f64) -> f64 {
+    let (distribution, _) = resolve_distribution(&model, &carrier, &destination);
+    grid_quantile(&distribution.quantiles, q.clamp(0.0, 1.0))
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ShipmentRecord {
+    pub carrier: String,
+    pub destination: String,
+    pub transit_days: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ShipmentQuery {
+    pub carrier: String,
+    pub destination: String,
+    pub promised_days: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TransitDistribution {
+    pub carrier: String,
+    pub destination: String,
+    pub count: u32,
+    pub mean: f64,
+    /// Transit-time quantiles on an evenly spaced grid from 0 to 1
+    pub quantiles: Vec<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TransitTimeModel {
+    pub lanes: Vec<TransitDistribution>,
+    pub carriers: Vec<TransitDistribution>,
+    pub overall: TransitDistribution,
+    pub min_lane_samples: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ShipmentDelayRisk {
+    pub index: u32,
+    pub late_probability: f64,
+    pub expected_transit_days: f64,
+    pub p50_transit_days: f64,
+    pub p90_transit_days: f64,
+    pub p95_transit_days: f64,
+    pub risk_level: String,
+    pub source: String,
+}
+
+fn transit_distribution(carrier: String, destination: String, values: Vec<f64>) -> TransitDistribution {
+    let quantiles = if values.is_empty() {
+        vec![0.0; TRANSIT_GRID_POINTS]
+    } else {
+        (0..TRANSIT_GRID_POINTS)
+            .map(|i| quantile(values.clone(), i as f64 / (TRANSIT_GRID_POINTS - 1) as f64))
+            .collect()
+    };
+
+    TransitDistribution {
+        carrier,
+        destination,
+        count: values.len() as u32,
+        mean: mean(values),
+        quantiles,
+    }
+}
+
+fn resolve_distribution<'a>(
+    model: &'a TransitTimeModel,
+    carrier: &str,
+    destination: &str,
+) -> (&'a TransitDistribution, &'static str) {
+    if let Some(lane) = model
+        .lanes
+        .iter()
+        .find(|l| l.carrier == carrier && l.destination == destination && l.count >= model.min_lane_samples)
+    {
+        return (lane, "lane");
+    }
+    if let Some(carrier) = model.carriers.iter().find(|c| c.carrier == carrier) {
+        return (carrier, "carrier");
+    }
+    (&model.overall, "overall")
+}
+
+/// Interpolated quantile from an evenly spaced quantile grid
+fn grid_quantile(grid: &[f64], q: f64) -> f64 {
+    if grid.is_empty() {
+        return 0.0;
+    }
+    let position = q * (grid.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(grid.len() - 1);
+    let frac = position - lower as f64;
+    grid[lower] * (1.0 - frac) + grid[upper] * frac
+}
+
+/// Interpolated CDF value at `x` from an evenly spaced quantile grid
+fn grid_cdf(grid: &[f64], x: f64) -> f64 {
+    let last = grid.len().saturating_sub(1);
+    if grid.is_empty() || x < grid[0] {
+        return 0.0;
+    }
+    if x >= grid[last] {
+        return 1.0;
+    }
+
+    // Last grid point not exceeding x, so ties resolve to the upper probability
+    let i = grid.iter().rposition(|&g| g <= x).unwrap_or(0);
+    let (lo, hi) = (grid[i], grid[i + 1]);
+    let frac = if hi > lo { (x - lo) / (hi - lo) } else { 1.0 };
+    (i as f64 + frac) / last as f64
+}
+
+fn order_features(order: &OpenOrder) -> Vec<f64> {
+    let mut features = vec![
+        order.age_hours.max(0.0).ln_1p(),
+        order.status_changes as f64,
+        order.hours_in_status.max(0.0).ln_1p(),
+    ];
+    features.extend_from_slice(&order.customer_features);
+    features
+}
+
+fn logistic_score(model: &LogisticModel, row: &[f64]) -> napi::Result<f64> {
+    let width = model.coefficients.len();
+    if model.feature_means.len() != width || model.feature_scales.len() != width {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+                "model has {} coefficients but {} feature means and {} feature scales",
+                width,
+                model.feature_means.len(),
+                model.feature_scales.len()
+            ),
+        ));
+    }
+    if row.len() != width {
+        return Err(Error::new(Status::InvalidArg, format!("feature row has {} values, model expects {}", row.len(), width)));
+    }
+    let z = model.intercept
+        + row
+            .iter()
+            .zip(&model.coefficients)
+            .zip(model.feature_means.iter().zip(&model.feature_scales))
+            .map(|((v, c), (m, s))| c * (v - m) / s)
+            .sum::<f64>();
+    Ok(sigmoid(z))
+}
+
+fn risk_level(probability: f64) -> String {
+    if probability >= 0.6 {
+        "high".to_string()
+    } else if probability >= 0.3 {
+        "medium".to_string()
+    } else {
+        "low".to_string()
+    }
+}
+
+pub(crate) fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logistic_regression() {
+        let x: Vec<Vec<f64>> = (0..40).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = (0..40).map(|i| if i >= 20 { 1.0 } else { 0.0 }).collect();
+        let model = logistic_regression(x.clone(), y.clone(), Some(0.1), None).unwrap();
+        assert!(model.coefficients[0] > 0.0);
+
+        let probs = predict_probability(model.clone(), vec![vec![2.0], vec![38.0]]).unwrap();
+        assert!(probs[0] < 0.1);
+        assert!(probs[1] > 0.9);
+
+        // Shape mismatches are errors, not panics or silent zero models
+        assert!(predict_probability(model.clone(), vec![vec![2.0, 1.0]]).is_err());
+        let truncated = LogisticModel { feature_scales: Vec::new(), ..model };
+        assert!(predict_probability(truncated, vec![vec![2.0]]).is_err());
+        assert!(logistic_regression(x, y[1..].to_vec(), None, None).is_err());
+        assert!(logistic_regression(vec![vec![1.0], vec![1.0, 2.0]], vec![0.0, 1.0], None, None).is_err());
+    }
+
+    #[test]
+    fn test_cancellation_risk() {
+        let orders: Vec<OpenOrder> = (0..60)
+            .map(|i| OpenOrder {
+                age_hours: (i * 4) as f64,
+                status_changes: (i % 3) as u32,
+                hours_in_status: (i * 2) as f64,
+                customer_features: vec![(i % 2) as f64],
+            })
+            .collect();
+        let cancelled: Vec<bool> = (0..60).map(|i| i > 40).collect();
+
+        let model = fit_cancellation_model(orders.clone(), cancelled.clone(), None).unwrap();
+        let scores = score_cancellation_risk(model.clone(), orders.clone()).unwrap();
+        assert_eq!(scores.len(), 60);
+        assert!(scores[59].probability > scores[0].probability);
+        assert_eq!(scores[59].risk_level, "high");
+        assert_eq!(scores[0].risk_level, "low");
+
+        let mut ragged = orders.clone();
+        ragged[5].customer_features.push(1.0);
+        assert!(fit_cancellation_model(ragged.clone(), cancelled, None).is_err());
+        assert!(score_cancellation_risk(model, ragged).is_err());
+    }
+
+    #[test]
+    fn test_shipment_delay() {
+        let mut history = Vec::new();
+        for i in 0..40 {
+            history.push(ShipmentRecord {
+                carrier: "fast".to_string(),
+                destination: "north".to_string(),
+                transit_days: 1.0 + (i % 3) as f64,
+            });
+            history.push(ShipmentRecord {
+                carrier: "slow".to_string(),
+                destination: "north".to_string(),
+                transit_days: 4.0 + (i % 5) as f64,
+            });
+        }
+        let model = fit_transit_model(history, Some(10));
+
+        let risks = score_shipment_delay(
+            model.clone(),
+            vec![
+                ShipmentQuery { carrier: "fast".to_string(), destination: "north".to_string(), promised_days: 3.0 },
+                ShipmentQuery { carrier: "slow".to_string(), destination: "north".to_string(), promised_days: 3.0 },
+                ShipmentQuery { carrier: "slow".to_string(), destination: "south".to_string(), promised_days: 6.0 },
+            ],
+        );
+        assert_eq!(risks[0].source, "lane");
+        assert!(risks[0].late_probability < 0.05);
+        assert!(risks[1].late_probability > 0.95);
+        assert_eq!(risks[1].risk_level, "high");
+        assert_eq!(risks[2].source, "carrier");
+        assert!(risks[2].p90_transit_days >= risks[2].p50_transit_days);
+
+        let p95 = transit_time_quantile(model, "fast".to_string(), "north".to_string(), 0.95);
+        assert!((p95 - 3.0).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,134 @@
+//! Random Sampling
+//!
+//! Deterministic, seedable pseudo-random number generation shared by the
+//! stochastic algorithms in this crate, so results are reproducible across
+//! runs and platforms:
+//! - Sampling with and without replacement
+//! - Shuffling
+
+use napi_derive::napi;
+
+/// SplitMix64 generator: tiny, fast and statistically solid for sampling
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform index in [0, n)
+    pub(crate) fn gen_index(&mut self, n: usize) -> usize {
+        ((self.next_f64() * n as f64) as usize).min(n.saturating_sub(1))
+    }
+
+    /// Fisher-Yates shuffle in place
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Random sample of `n` distinct elements (all of them when `n` exceeds the length)
+///
+/// # Arguments
+/// * `data` - Values to sample from
+/// * `n` - Sample size
+/// * `seed` - Random seed (default 42)
+/// * `keep_order` - Return the sample in its original order instead of random order (default false)
+#[napi]
+pub fn sample_without_replacement(data: Vec<f64>, n: u32, seed: Option<u32>, keep_order: Option<bool>) -> Vec<f64> {
+    let n = (n as usize).min(data.len());
+    let mut rng = Rng::new(seed.unwrap_or(42) as u64);
+    // Partial Fisher-Yates: only the first n positions are drawn
+    let mut indices: Vec<usize> = (0..data.len()).collect();
+    for i in 0..n {
+        let j = i + rng.gen_index(indices.len() - i);
+        indices.swap(i, j);
+    }
+    indices.truncate(n);
+    if keep_order.unwrap_or(false) {
+        indices.sort_unstable();
+    }
+    indices.into_iter().map(|i| data[i]).collect()
+}
+
+/// Random sample of `n` elements drawn with replacement (e.g. for bootstrapping)
+///
+/// # Arguments
+/// * `data` - Values to sample from
+/// * `n` - Sample size
+/// * `seed` - Random seed (default 42)
+#[napi]
+pub fn sample_with_replacement(data: Vec<f64>, n: u32, seed: Option<u32>) -> Vec<f64> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    let mut rng = Rng::new(seed.unwrap_or(42) as u64);
+    (0..n).map(|_| data[rng.gen_index(data.len())]).collect()
+}
+
+/// Randomly permuted copy of `data`
+///
+/// # Arguments
+/// * `data` - Values to shuffle
+/// * `seed` - Random seed (default 42)
+#[napi]
+pub fn shuffle(mut data: Vec<f64>, seed: Option<u32>) -> Vec<f64> {
+    Rng::new(seed.unwrap_or(42) as u64).shuffle(&mut data);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_deterministic() {
+        let a: Vec<u64> = (0..5).scan(Rng::new(42), |r, _| Some(r.next_u64())).collect();
+        let b: Vec<u64> = (0..5).scan(Rng::new(42), |r, _| Some(r.next_u64())).collect();
+        assert_eq!(a, b);
+
+        let mut rng = Rng::new(7);
+        assert!((0..1000).map(|_| rng.next_f64()).all(|v| (0.0..1.0).contains(&v)));
+    }
+
+    #[test]
+    fn test_sampling_helpers() {
+        let data: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let sample = sample_without_replacement(data.clone(), 50, Some(9), None);
+        assert_eq!(sample, sample_without_replacement(data.clone(), 50, Some(9), None));
+        let mut distinct = sample.clone();
+        distinct.sort_by(|a, b| a.total_cmp(b));
+        distinct.dedup();
+        assert_eq!(distinct.len(), 50);
+        assert_eq!(sample_without_replacement(data.clone(), 50, Some(9), Some(true)), distinct);
+        assert_eq!(sample_without_replacement(vec![1.0, 2.0], 5, None, None).len(), 2);
+
+        let boot = sample_with_replacement(vec![1.0, 2.0, 3.0], 100, Some(1));
+        assert_eq!(boot.len(), 100);
+        assert!(boot.iter().all(|v| [1.0, 2.0, 3.0].contains(v)));
+
+        let mut shuffled = shuffle(data.clone(), Some(3));
+        assert_ne!(shuffled, data);
+        shuffled.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(shuffled, data);
+    }
+}
@@ -0,0 +1,457 @@
+//! Signal Analysis
+//!
+//! Regularity and complexity measures for sensor and machine telemetry:
+//! - Sample entropy
+//! - Approximate entropy
+//! - Permutation entropy
+//! - LOESS and Savitzky-Golay smoothing
+//! - FFT power spectrum
+
+use std::collections::HashMap;
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::linalg::invert;
+use crate::statistics::std_dev;
+
+/// Sample entropy (SampEn) of a series
+///
+/// Negative log of the conditional probability that templates matching for
+/// `m` points (within tolerance `r`, Chebyshev distance, self-matches
+/// excluded) also match for `m + 1` points. Higher values mean less regular
+/// signals. When no `m + 1` matches exist the finite upper bound
+/// `ln((N - m - 1)(N - m) / 2)` is returned.
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `m` - Template length (default 2)
+/// * `r` - Absolute match tolerance (default 0.2 × standard deviation)
+#[napi]
+pub fn sample_entropy(data: Vec<f64>, m: Option<u32>, r: Option<f64>) -> f64 {
+    let m = m.unwrap_or(2).max(1) as usize;
+    let n = data.len();
+    if n <= m + 1 {
+        return 0.0;
+    }
+    let r = r.unwrap_or_else(|| 0.2 * std_dev(data.clone()));
+
+    // Same N - m templates for both lengths so the counts are comparable
+    let templates = n - m;
+    let (b, a) = (0..templates)
+        .into_par_iter()
+        .map(|i| {
+            let mut counts = (0u64, 0u64);
+            for j in i + 1..templates {
+                if within(&data, i, j, m, r) {
+                    counts.0 += 1;
+                    if (data[i + m] - data[j + m]).abs() <= r {
+                        counts.1 += 1;
+                    }
+                }
+            }
+            counts
+        })
+        .reduce(|| (0, 0), |x, y| (x.0 + y.0, x.1 + y.1));
+
+    if a == 0 || b == 0 {
+        let pairs = (templates as f64 - 1.0) * templates as f64 / 2.0;
+        return pairs.max(1.0).ln();
+    }
+    -(a as f64 / b as f64).ln()
+}
+
+/// Approximate entropy (ApEn) of a series
+///
+/// `phi(m) - phi(m + 1)`, where `phi(k)` is the mean log fraction of
+/// length-`k` templates within tolerance `r` of each template (self-matches
+/// included). Biased toward regularity for short series; prefer
+/// `sample_entropy` when comparing series of different lengths.
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `m` - Template length (default 2)
+/// * `r` - Absolute match tolerance (default 0.2 × standard deviation)
+#[napi]
+pub fn approximate_entropy(data: Vec<f64>, m: Option<u32>, r: Option<f64>) -> f64 {
+    let m = m.unwrap_or(2).max(1) as usize;
+    if data.len() <= m + 1 {
+        return 0.0;
+    }
+    let r = r.unwrap_or_else(|| 0.2 * std_dev(data.clone()));
+    phi(&data, m, r) - phi(&data, m + 1, r)
+}
+
+/// Normalized permutation entropy (0 = fully predictable, 1 = random)
+///
+/// Shannon entropy of the ordinal patterns of `order` points spaced `delay`
+/// steps apart, divided by `ln(order!)`. Robust to noise and monotonic
+/// transformations of the signal.
+///
+/// # Arguments
+/// * `data` - Series to analyze
+/// * `order` - Pattern length (default 3, at most 8)
+/// * `delay` - Spacing between pattern points (default 1)
+#[napi]
+pub fn permutation_entropy(data: Vec<f64>, order: Option<u32>, delay: Option<u32>) -> f64 {
+    let order = order.unwrap_or(3).clamp(2, 8) as usize;
+    let delay = delay.unwrap_or(1).max(1) as usize;
+    let span = (order - 1) * delay;
+    if data.len() <= span {
+        return 0.0;
+    }
+
+    let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+    for start in 0..data.len() - span {
+        let mut pattern: Vec<usize> = (0..order).collect();
+        pattern.sort_by(|&a, &b| data[start + a * delay].total_cmp(&data[start + b * delay]));
+        *counts.entry(pattern).or_insert(0) += 1;
+    }
+
+    let total = (data.len() - span) as f64;
+    let entropy: f64 = counts
+        .values()
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.ln()
+        })
+        .sum();
+    let max_entropy: f64 = (2..=order).map(|k| (k as f64).ln()).sum();
+    entropy / max_entropy
+}
+
+/// LOESS smoothing (locally weighted linear regression)
+///
+/// Each point is fitted by a weighted linear regression on its
+/// `ceil(frac * n)` nearest neighbors in `x`, with tricube weights on the
+/// distance scaled by the farthest neighbor. Returns the smoothed value at
+/// every `x`; `x` need not be sorted or evenly spaced. Fewer than three
+/// points are returned unsmoothed. Fails when `x` and `y` differ in length.
+///
+/// # Arguments
+/// * `x` - Predictor values
+/// * `y` - Responses aligned with `x`
+/// * `frac` - Fraction of points in each local fit (default 0.3 when not in (0, 1])
+#[napi]
+pub fn loess(x: Vec<f64>, y: Vec<f64>, frac: f64) -> napi::Result<Vec<f64>> {
+    if x.len() != y.len() {
+        return Err(Error::new(Status::InvalidArg, format!("x has {} values but y has {}", x.len(), y.len())));
+    }
+    let n = x.len();
+    if n < 3 {
+        return Ok(y);
+    }
+    let frac = if frac > 0.0 && frac <= 1.0 { frac } else { 0.3 };
+    let k = ((frac * n as f64).ceil() as usize).clamp(3, n);
+
+    Ok((0..n)
+        .into_par_iter()
+        .map(|i| {
+            let mut distances: Vec<(f64, usize)> = (0..n).map(|j| ((x[j] - x[i]).abs(), j)).collect();
+            distances.select_nth_unstable_by(k - 1, |a, b| a.0.total_cmp(&b.0));
+            let neighbors = &distances[..k];
+            let max_distance = neighbors.iter().map(|d| d.0).fold(0.0, f64::max);
+
+            let (mut sw, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for &(d, j) in neighbors {
+                let w = if max_distance > 0.0 { (1.0 - (d / max_distance).powi(3)).powi(3) } else { 1.0 };
+                sw += w;
+                sx += w * x[j];
+                sy += w * y[j];
+                sxx += w * x[j] * x[j];
+                sxy += w * x[j] * y[j];
+            }
+            if sw <= 0.0 {
+                return y[i];
+            }
+            let (x_mean, y_mean) = (sx / sw, sy / sw);
+            let var = sxx / sw - x_mean * x_mean;
+            if var <= 1e-12 * (1.0 + x_mean * x_mean) {
+                return y_mean;
+            }
+            let slope = (sxy / sw - x_mean * y_mean) / var;
+            y_mean + slope * (x[i] - x_mean)
+        })
+        .collect())
+}
+
+/// Savitzky-Golay smoothing filter
+///
+/// Fits a polynomial of degree `poly_order` by least squares to each window
+/// of `window` points and takes its value at the window center, preserving
+/// peak heights and widths better than a moving average. Near the ends the
+/// first/last full window's polynomial is evaluated at the edge positions.
+///
+/// # Arguments
+/// * `data` - Evenly spaced series
+/// * `window` - Window length (made odd, at least `poly_order + 1`)
+/// * `poly_order` - Degree of the local polynomial
+#[napi]
+pub fn savitzky_golay(data: Vec<f64>, window: u32, poly_order: u32) -> Vec<f64> {
+    let order = poly_order as usize;
+    // Round even lengths up to the next odd one
+    let window = (window as usize).max(order + 1) | 1;
+    let n = data.len();
+    if n < window {
+        return data;
+    }
+    let half = window / 2;
+
+    // Smoothing weights for evaluating the fitted polynomial at each offset in the window
+    let design: Vec<Vec<f64>> = (0..window)
+        .map(|i| (0..=order).map(|p| (i as f64 - half as f64).powi(p as i32)).collect())
+        .collect();
+    let gram: Vec<Vec<f64>> = (0..=order)
+        .map(|a| (0..=order).map(|b| design.iter().map(|row| row[a] * row[b]).sum()).collect())
+        .collect();
+    let Some(gram_inv) = invert(&gram) else {
+        return data;
+    };
+    let weights_at = |offset: f64| -> Vec<f64> {
+        let powers: Vec<f64> = (0..=order).map(|p| offset.powi(p as i32)).collect();
+        let projected: Vec<f64> = (0..=order).map(|b| (0..=order).map(|a| powers[a] * gram_inv[a][b]).sum()).collect();
+        design.iter().map(|row| row.iter().zip(&projected).map(|(r, p)| r * p).sum()).collect()
+    };
+    let offsets: Vec<Vec<f64>> = (0..window).map(|i| weights_at(i as f64 - half as f64)).collect();
+
+    (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let start = i.saturating_sub(half).min(n - window);
+            let weights = &offsets[i - start];
+            data[start..start + window].iter().zip(weights).map(|(v, w)| v * w).sum()
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PowerSpectrum {
+    /// Frequencies from 0 to the Nyquist frequency, in cycles per unit of `sample_rate`
+    pub frequencies: Vec<f64>,
+    /// One-sided periodogram; sums to the total power `sum(x^2) / n` of the input
+    pub power: Vec<f64>,
+    /// Non-zero frequency with the highest power (0 when there is none)
+    pub dominant_frequency: f64,
+    /// `1 / dominant_frequency` (0 when there is no dominant frequency)
+    pub dominant_period: f64,
+}
+
+/// Power spectrum of a real series via FFT
+///
+/// Exact DFT of any length (radix-2 FFT, Bluestein's algorithm for other
+/// lengths). For periodicity detection in order arrivals use a sample rate
+/// of 1 and read `dominant_period` in samples; for telemetry pass the
+/// sampling frequency in Hz.
+///
+/// # Arguments
+/// * `data` - Evenly sampled series
+/// * `sample_rate` - Samples per unit time (default 1 when not positive)
+#[napi]
+pub fn power_spectrum(data: Vec<f64>, sample_rate: f64) -> PowerSpectrum {
+    let n = data.len();
+    let sample_rate = if sample_rate > 0.0 { sample_rate } else { 1.0 };
+    if n == 0 {
+        return PowerSpectrum {
+            frequencies: Vec::new(),
+            power: Vec::new(),
+            dominant_frequency: 0.0,
+            dominant_period: 0.0,
+        };
+    }
+
+    let spectrum = dft(data.iter().map(|&v| (v, 0.0)).collect());
+    let bins = n / 2 + 1;
+    let frequencies: Vec<f64> = (0..bins).map(|k| k as f64 * sample_rate / n as f64).collect();
+    let power: Vec<f64> = (0..bins)
+        .map(|k| {
+            let (re, im) = spectrum[k];
+            // Fold the negative frequencies onto the positive ones (DC and Nyquist have no mirror)
+            let mirrored = k != 0 && 2 * k != n;
+            (re * re + im * im) / (n * n) as f64 * if mirrored { 2.0 } else { 1.0 }
+        })
+        .collect();
+
+    let dominant = (1..bins).max_by(|&a, &b| power[a].total_cmp(&power[b])).filter(|&k| power[k] > 0.0);
+    let dominant_frequency = dominant.map_or(0.0, |k| frequencies[k]);
+    PowerSpectrum {
+        frequencies,
+        power,
+        dominant_frequency,
+        dominant_period: if dominant_frequency > 0.0 { 1.0 / dominant_frequency } else { 0.0 },
+    }
+}
+
+type Complex = (f64, f64);
+
+fn mul(a: Complex, b: Complex) -> Complex {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+/// Discrete Fourier transform of any length
+fn dft(mut x: Vec<Complex>) -> Vec<Complex> {
+    let n = x.len();
+    if n.is_power_of_two() {
+        fft_radix2(&mut x, false);
+        return x;
+    }
+
+    // Bluestein: express the DFT as a convolution with a chirp and do that with power-of-two FFTs
+    let chirp: Vec<Complex> = (0..n)
+        .map(|k| {
+            let angle = -std::f64::consts::PI * ((k * k) % (2 * n)) as f64 / n as f64;
+            (angle.cos(), angle.sin())
+        })
+        .collect();
+    let m = (2 * n - 1).next_power_of_two();
+    let mut a = vec![(0.0, 0.0); m];
+    let mut b = vec![(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = mul(x[k], chirp[k]);
+        let conj = (chirp[k].0, -chirp[k].1);
+        b[k] = conj;
+        if k > 0 {
+            b[m - k] = conj;
+        }
+    }
+    fft_radix2(&mut a, false);
+    fft_radix2(&mut b, false);
+    let mut conv: Vec<Complex> = a.iter().zip(&b).map(|(&p, &q)| mul(p, q)).collect();
+    fft_radix2(&mut conv, true);
+    (0..n).map(|k| mul(conv[k], chirp[k])).collect()
+}
+
+/// In-place iterative radix-2 FFT (length must be a power of two); the inverse is scaled by 1/n
+fn fft_radix2(x: &mut [Complex], inverse: bool) {
+    let n = x.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * std::f64::consts::PI / len as f64;
+        let step = (angle.cos(), angle.sin());
+        for chunk in x.chunks_mut(len) {
+            let mut w = (1.0, 0.0);
+            let (lower, upper) = chunk.split_at_mut(len / 2);
+            for (u, v) in lower.iter_mut().zip(upper.iter_mut()) {
+                let t = mul(*v, w);
+                *v = (u.0 - t.0, u.1 - t.1);
+                *u = (u.0 + t.0, u.1 + t.1);
+                w = mul(w, step);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f64;
+        x.iter_mut().for_each(|v| *v = (v.0 * scale, v.1 * scale));
+    }
+}
+
+/// Whether the length-`m` templates starting at `i` and `j` match within `r`
+fn within(data: &[f64], i: usize, j: usize, m: usize, r: f64) -> bool {
+    (0..m).all(|k| (data[i + k] - data[j + k]).abs() <= r)
+}
+
+fn phi(data: &[f64], m: usize, r: f64) -> f64 {
+    let templates = data.len() + 1 - m;
+    let total: f64 = (0..templates)
+        .into_par_iter()
+        .map(|i| {
+            let matches = (0..templates).filter(|&j| within(data, i, j, m, r)).count();
+            (matches as f64 / templates as f64).ln()
+        })
+        .sum();
+    total / templates as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampling::Rng;
+
+    fn signals() -> (Vec<f64>, Vec<f64>) {
+        let regular: Vec<f64> = (0..300).map(|t| (t as f64 * 0.5).sin()).collect();
+        let mut rng = Rng::new(21);
+        let noisy: Vec<f64> = (0..300).map(|_| rng.next_f64() * 2.0 - 1.0).collect();
+        (regular, noisy)
+    }
+
+    #[test]
+    fn test_sample_and_approximate_entropy() {
+        let (regular, noisy) = signals();
+        let regular_sampen = sample_entropy(regular.clone(), None, None);
+        let noisy_sampen = sample_entropy(noisy.clone(), None, None);
+        assert!(regular_sampen < 0.5);
+        assert!(noisy_sampen > regular_sampen + 1.0);
+
+        assert!(approximate_entropy(noisy, None, None) > approximate_entropy(regular, None, None));
+        assert_eq!(sample_entropy(vec![1.0, 2.0], None, None), 0.0);
+    }
+
+    #[test]
+    fn test_loess() {
+        let x: Vec<f64> = (0..100).map(|i| i as f64 / 10.0).collect();
+        let mut rng = Rng::new(3);
+        let y: Vec<f64> = x.iter().map(|&v| v.sin() + (rng.next_f64() - 0.5) * 0.5).collect();
+        assert!(loess(x[1..].to_vec(), y.clone(), 0.2).is_err());
+        let smooth = loess(x.clone(), y.clone(), 0.2).unwrap();
+        let error = |fit: &[f64]| fit.iter().zip(&x).map(|(f, v)| (f - v.sin()).powi(2)).sum::<f64>();
+        assert!(error(&smooth) < error(&y) / 3.0);
+
+        // A straight line is reproduced exactly
+        let line: Vec<f64> = x.iter().map(|v| 2.0 * v + 1.0).collect();
+        assert!(loess(x, line.clone(), 0.3).unwrap().iter().zip(&line).all(|(a, b)| (a - b).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_savitzky_golay() {
+        // Quadratics pass through a quadratic filter unchanged, including the edges
+        let quadratic: Vec<f64> = (0..20).map(|i| 0.5 * (i * i) as f64 - 3.0 * i as f64).collect();
+        let filtered = savitzky_golay(quadratic.clone(), 7, 2);
+        assert!(filtered.iter().zip(&quadratic).all(|(a, b)| (a - b).abs() < 1e-8));
+
+        // Order 0 is a centered moving average
+        let data = vec![1.0, 5.0, 3.0, 8.0, 2.0, 6.0, 4.0];
+        assert!((savitzky_golay(data, 3, 0)[2] - 16.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_power_spectrum() {
+        // 7-sample cycle in 98 points (not a power of two) plus an offset
+        let data: Vec<f64> = (0..98).map(|t| 5.0 + (2.0 * std::f64::consts::PI * t as f64 / 7.0).sin()).collect();
+        let spectrum = power_spectrum(data.clone(), 1.0);
+        assert_eq!(spectrum.frequencies.len(), 50);
+        assert!((spectrum.dominant_period - 7.0).abs() < 1e-9);
+        // Parseval: the one-sided power adds up to the mean square
+        let mean_square = data.iter().map(|v| v * v).sum::<f64>() / 98.0;
+        assert!((spectrum.power.iter().sum::<f64>() - mean_square).abs() < 1e-9);
+
+        // 50 Hz tone sampled at 1 kHz, power-of-two length
+        let tone: Vec<f64> = (0..1024).map(|t| (2.0 * std::f64::consts::PI * 50.0 * t as f64 / 1000.0).cos()).collect();
+        assert!((power_spectrum(tone, 1000.0).dominant_frequency - 50.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_permutation_entropy() {
+        let (regular, noisy) = signals();
+        let monotonic: Vec<f64> = (0..100).map(|t| t as f64).collect();
+        assert_eq!(permutation_entropy(monotonic, None, None), 0.0);
+        assert!(permutation_entropy(noisy, Some(4), None) > 0.95);
+        assert!(permutation_entropy(regular, Some(4), None) < 0.6);
+    }
+}
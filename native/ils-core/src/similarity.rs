@@ -0,0 +1,958 @@
+//! Series Similarity
+//!
+//! Shape-based comparison and grouping of time series:
+//! - Dynamic time warping (DTW) distance with a Sakoe-Chiba band
+//! - Z-normalized Euclidean distance
+//! - K-medoids / k-means clustering over a parallel distance matrix
+//! - DTW barycenter averaging (DBA)
+//! - Matrix profile and motif discovery
+//! - SAX symbolic representation
+//! - Similar-window search over long histories
+
+use napi::{Error, Status};
+use napi_derive::napi;
+use rayon::prelude::*;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+use crate::sampling::Rng;
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SeriesClusteringOptions {
+    /// "dtw" (default) or "euclidean"; other values are rejected
+    pub metric: Option<String>,
+    /// "kmedoids" (default) or "kmeans"
+    pub method: Option<String>,
+    /// DTW warping window in steps (default unconstrained)
+    pub window: Option<u32>,
+    /// Z-normalize each series so clusters reflect shape rather than level (default true)
+    pub normalize: Option<bool>,
+    /// Maximum assignment/update rounds (default 50)
+    pub max_iterations: Option<u32>,
+    pub seed: Option<u32>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SeriesClusteringResult {
+    /// Cluster index of each series
+    pub labels: Vec<u32>,
+    /// Index of the most central member series of each cluster
+    pub medoids: Vec<u32>,
+    /// Cluster centers (the medoid series for k-medoids, the mean or DBA series for k-means)
+    pub centroids: Vec<Vec<f64>>,
+    pub cluster_sizes: Vec<u32>,
+    /// Sum of distances from each series to its cluster center
+    pub inertia: f64,
+    pub iterations: u32,
+}
+
+/// Dynamic time warping distance between two series
+///
+/// # Arguments
+/// * `a`, `b` - Series to compare (may differ in length)
+/// * `window` - Sakoe-Chiba band half-width in steps (default unconstrained)
+#[napi]
+pub fn dtw_distance(a: Vec<f64>, b: Vec<f64>, window: Option<u32>) -> f64 {
+    dtw(&a, &b, window.map(|w| w as usize))
+}
+
+/// DTW barycenter average (DBA) of a set of series
+///
+/// Iteratively refines an average curve so that it minimizes the summed DTW
+/// distance to the members, preserving shared shape features that a
+/// point-wise mean would smear out when members are shifted in time.
+///
+/// # Arguments
+/// * `series` - Series to average (may differ in length)
+/// * `max_iterations` - Refinement rounds (default 20)
+/// * `window` - DTW warping window in steps (default unconstrained)
+#[napi]
+pub fn dtw_barycenter(series: Vec<Vec<f64>>, max_iterations: Option<u32>, window: Option<u32>) -> Vec<f64> {
+    let members: Vec<&[f64]> = series.iter().map(|s| s.as_slice()).filter(|s| !s.is_empty()).collect();
+    if members.is_empty() {
+        return Vec::new();
+    }
+
+    // Start from the medoid: the member closest (in total DTW) to all others
+    let window = window.map(|w| w as usize);
+    let initial = (0..members.len())
+        .into_par_iter()
+        .map(|i| (i, members.iter().map(|m| dtw(members[i], m, window)).sum::<f64>()))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i);
+
+    dba(&members, members[initial], window, max_iterations.unwrap_or(20).max(1) as usize)
+}
+
+/// Cluster a set of series by shape
+///
+/// K-medoids works with any metric and returns real member series as
+/// prototypes. K-means averages members point-wise under the Euclidean
+/// metric and by DTW barycenter averaging under DTW. Fails for an unknown
+/// metric or `k` of 0.
+///
+/// # Arguments
+/// * `series` - Series to cluster
+/// * `k` - Number of clusters (capped at the number of series)
+/// * `options` - Metric, method and iteration settings
+#[napi]
+pub fn cluster_series(series: Vec<Vec<f64>>, k: u32, options: Option<SeriesClusteringOptions>) -> napi::Result<SeriesClusteringResult> {
+    let options = options.unwrap_or_default();
+    let metric = SeriesMetric::parse(options.metric.as_deref(), options.window)?;
+    if k == 0 {
+        return Err(Error::new(Status::InvalidArg, "k must be at least 1".to_string()));
+    }
+    let n = series.len();
+    let k = (k as usize).min(n.max(1));
+    if n == 0 {
+        return Ok(SeriesClusteringResult {
+            labels: Vec::new(),
+            medoids: Vec::new(),
+            centroids: Vec::new(),
+            cluster_sizes: Vec::new(),
+            inertia: 0.0,
+            iterations: 0,
+        });
+    }
+
+    let data: Vec<Vec<f64>> = if options.normalize.unwrap_or(true) {
+        series.iter().map(|s| z_normalize(s)).collect()
+    } else {
+        series
+    };
+    let max_iterations = options.max_iterations.unwrap_or(50).max(1);
+    let mut rng = Rng::new(options.seed.unwrap_or(42) as u64);
+
+    let distances = distance_matrix(&data, metric);
+    let mut medoids = plus_plus_init(&distances, k, &mut rng);
+
+    if options.method.as_deref() == Some("kmeans") {
+        let mut centroids: Vec<Vec<f64>> = medoids.iter().map(|&m| data[m].clone()).collect();
+        let mut labels = vec![usize::MAX; n];
+        let mut iterations = 0;
+        for _ in 0..max_iterations {
+            iterations += 1;
+            let next: Vec<usize> = data.par_iter().map(|s| nearest_center(s, &centroids, metric).0).collect();
+            let changed = next != labels;
+            labels = next;
+            for (c, centroid) in centroids.iter_mut().enumerate() {
+                let members: Vec<&[f64]> =
+                    labels.iter().zip(&data).filter(|(&l, _)| l == c).map(|(_, s)| s.as_slice()).collect();
+                if !members.is_empty() {
+                    *centroid = match metric {
+                        SeriesMetric::Dtw(window) => dba(&members, centroid, window, 10),
+                        SeriesMetric::Euclidean => pointwise_mean(&members),
+                    };
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let inertia = data
+            .iter()
+            .zip(&labels)
+            .map(|(s, &l)| metric.distance(s, &centroids[l]))
+            .sum();
+        medoids = (0..k)
+            .map(|c| {
+                (0..n)
+                    .filter(|&i| labels[i] == c)
+                    .min_by(|&a, &b| {
+                        metric.distance(&data[a], &centroids[c]).total_cmp(&metric.distance(&data[b], &centroids[c]))
+                    })
+                    .unwrap_or(0)
+            })
+            .collect();
+        return Ok(clustering_result(labels, medoids, centroids, inertia, iterations));
+    }
+
+    let mut labels = assign_to_medoids(&distances, &medoids);
+    let mut iterations = 0;
+    for _ in 0..max_iterations {
+        iterations += 1;
+        let updated: Vec<usize> = (0..k)
+            .map(|c| {
+                let members: Vec<usize> = (0..n).filter(|&i| labels[i] == c).collect();
+                members
+                    .iter()
+                    .copied()
+                    .min_by(|&a, &b| {
+                        let cost = |m: usize| members.iter().map(|&i| distances[m][i]).sum::<f64>();
+                        cost(a).total_cmp(&cost(b))
+                    })
+                    .unwrap_or(medoids[c])
+            })
+            .collect();
+        if updated == medoids {
+            break;
+        }
+        medoids = updated;
+        labels = assign_to_medoids(&distances, &medoids);
+    }
+
+    let inertia = labels.iter().enumerate().map(|(i, &l)| distances[i][medoids[l]]).sum();
+    let centroids = medoids.iter().map(|&m| data[m].clone()).collect();
+    Ok(clustering_result(labels, medoids, centroids, inertia, iterations))
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct MatrixProfile {
+    /// Z-normalized distance from each subsequence to its nearest non-trivial match
+    pub profile: Vec<f64>,
+    /// Start index of that nearest match
+    pub profile_index: Vec<u32>,
+    pub subsequence_len: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Motif {
+    /// Start index of the motif's defining subsequence
+    pub index: u32,
+    /// Start index of its nearest match
+    pub neighbor_index: u32,
+    /// Z-normalized distance between the pair
+    pub distance: f64,
+    /// Start indices of all non-overlapping occurrences, in time order
+    pub occurrences: Vec<u32>,
+}
+
+/// Matrix profile of a series (self-join, z-normalized Euclidean distance)
+///
+/// Matches within `subsequence_len / 2` steps of a subsequence are excluded
+/// as trivial. Diagonals are processed in parallel.
+///
+/// # Arguments
+/// * `data` - Series to profile
+/// * `subsequence_len` - Window length in steps
+#[napi]
+pub fn matrix_profile(data: Vec<f64>, subsequence_len: u32) -> MatrixProfile {
+    let m = subsequence_len as usize;
+    let (profile, index) = self_join(&data, m);
+    MatrixProfile {
+        profile,
+        profile_index: index.into_iter().map(|i| i as u32).collect(),
+        subsequence_len,
+    }
+}
+
+/// Top-k recurring patterns (motifs) in a series
+///
+/// Each motif starts from the closest remaining pair in the matrix profile;
+/// its occurrences are all non-overlapping subsequences within `radius` times
+/// the pair distance. Occurrences of earlier motifs are excluded from later ones.
+///
+/// # Arguments
+/// * `data` - Series to search
+/// * `subsequence_len` - Pattern length in steps
+/// * `k` - Maximum number of motifs to return
+/// * `radius` - Occurrence threshold as a multiple of the pair distance (default 2)
+#[napi]
+pub fn find_motifs(data: Vec<f64>, subsequence_len: u32, k: u32, radius: Option<f64>) -> Vec<Motif> {
+    let m = subsequence_len as usize;
+    let (profile, index) = self_join(&data, m);
+    let exclusion = (m / 2).max(1);
+    let radius = radius.unwrap_or(2.0).max(1.0);
+    let mut available = vec![true; profile.len()];
+    let mut motifs = Vec::new();
+
+    while motifs.len() < k as usize {
+        let best = (0..profile.len())
+            .filter(|&i| available[i] && available[index[i]] && profile[i].is_finite())
+            .min_by(|&a, &b| profile[a].total_cmp(&profile[b]));
+        let Some(i) = best else { break };
+        let pair_distance = profile[i];
+
+        // Greedily accept the closest matches first, skipping overlaps
+        let distances = distance_profile(&data, &data[i..i + m]);
+        let threshold = (pair_distance * radius).max(1e-9);
+        let mut candidates: Vec<usize> = (0..distances.len())
+            .filter(|&j| available[j] && distances[j] <= threshold)
+            .collect();
+        candidates.sort_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+        let mut occurrences: Vec<usize> = Vec::new();
+        for j in candidates {
+            if occurrences.iter().all(|&o| o.abs_diff(j) >= m) {
+                occurrences.push(j);
+            }
+        }
+        for &j in &[i, index[i]] {
+            if !occurrences.contains(&j) && occurrences.iter().all(|&o| o.abs_diff(j) >= m) {
+                occurrences.push(j);
+            }
+        }
+        occurrences.sort_unstable();
+
+        for &o in &occurrences {
+            let lo = o.saturating_sub(exclusion);
+            let hi = (o + exclusion + 1).min(available.len());
+            available[lo..hi].iter_mut().for_each(|a| *a = false);
+        }
+        motifs.push(Motif {
+            index: i as u32,
+            neighbor_index: index[i] as u32,
+            distance: pair_distance,
+            occurrences: occurrences.into_iter().map(|o| o as u32).collect(),
+        });
+    }
+    motifs
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SaxWord {
+    /// Symbols as letters, 'a' being the lowest band
+    pub word: String,
+    /// Symbol index of each segment (0-based)
+    pub symbols: Vec<u32>,
+    /// Piecewise aggregate approximation of the z-normalized series
+    pub paa: Vec<f64>,
+    /// Length of the encoded series, needed for `sax_distance`
+    pub series_len: u32,
+}
+
+/// Symbolic Aggregate approXimation (SAX) of a series
+///
+/// The series is z-normalized, averaged over `segments` equal-width frames
+/// and each frame mapped to one of `alphabet` equiprobable Gaussian bands.
+///
+/// # Arguments
+/// * `data` - Series to encode
+/// * `segments` - Word length
+/// * `alphabet` - Alphabet size (2-26)
+#[napi]
+pub fn sax_encode(data: Vec<f64>, segments: u32, alphabet: u32) -> SaxWord {
+    let n = data.len();
+    let segments = (segments as usize).clamp(1, n.max(1));
+    let breakpoints = sax_breakpoints(alphabet);
+
+    let normalized = z_normalize(&data);
+    // Fractional frame boundaries so every point contributes to exactly `segments` frames in total
+    let paa: Vec<f64> = if n == 0 {
+        Vec::new()
+    } else {
+        (0..segments)
+            .map(|s| {
+                let start = s as f64 * n as f64 / segments as f64;
+                let end = (s + 1) as f64 * n as f64 / segments as f64;
+                let mut total = 0.0;
+                for (i, v) in normalized.iter().enumerate() {
+                    let overlap = ((i + 1) as f64).min(end) - (i as f64).max(start);
+                    if overlap > 0.0 {
+                        total += v * overlap;
+                    }
+                }
+                total / (end - start)
+            })
+            .collect()
+    };
+
+    let symbols: Vec<u32> = paa
+        .iter()
+        .map(|&v| breakpoints.iter().filter(|&&b| v >= b).count() as u32)
+        .collect();
+    let word = symbols.iter().map(|&s| (b'a' + s as u8) as char).collect();
+
+    SaxWord {
+        word,
+        symbols,
+        paa,
+        series_len: n as u32,
+    }
+}
+
+/// Lower bound on the z-normalized Euclidean distance between two SAX words (MINDIST)
+///
+/// Fails when `alphabet` is outside 2-26 or a word uses a letter beyond it.
+///
+/// # Arguments
+/// * `a`, `b` - Words of equal length produced with the same alphabet
+/// * `series_len` - Length of the original series
+/// * `alphabet` - Alphabet size used for encoding
+#[napi]
+pub fn sax_distance(a: String, b: String, series_len: u32, alphabet: u32) -> napi::Result<f64> {
+    if !(2..=26).contains(&alphabet) {
+        return Err(Error::new(Status::InvalidArg, format!("alphabet size {} is outside 2-26", alphabet)));
+    }
+    let last = b'a' + alphabet as u8 - 1;
+    if let Some(c) = a.bytes().chain(b.bytes()).find(|c| !(b'a'..=last).contains(c)) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("symbol {:?} is not in the alphabet a-{}", c as char, last as char),
+        ));
+    }
+    let breakpoints = sax_breakpoints(alphabet);
+    let w = a.len().min(b.len());
+    if w == 0 {
+        return Ok(0.0);
+    }
+    let cell = |x: u8, y: u8| -> f64 {
+        let (lo, hi) = (x.min(y) as usize, x.max(y) as usize);
+        if hi - lo <= 1 {
+            0.0
+        } else {
+            breakpoints[hi - 1] - breakpoints[lo]
+        }
+    };
+    let total: f64 = a
+        .bytes()
+        .zip(b.bytes())
+        .map(|(x, y)| cell(x - b'a', y - b'a').powi(2))
+        .sum();
+    Ok((series_len as f64 / w as f64).sqrt() * total.sqrt())
+}
+
+/// Standard normal quantiles splitting the line into `alphabet` equiprobable bands
+fn sax_breakpoints(alphabet: u32) -> Vec<f64> {
+    let alphabet = alphabet.clamp(2, 26);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    (1..alphabet).map(|i| normal.inverse_cdf(i as f64 / alphabet as f64)).collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SimilarWindow {
+    /// Start index of the matching window in `history`
+    pub start_index: u32,
+    pub distance: f64,
+    /// `1 / (1 + rms)`, where `rms` is the distance per point; 1 = identical
+    pub similarity: f64,
+    /// Values of the matching window
+    pub values: Vec<f64>,
+}
+
+/// Most similar historical windows to a query pattern
+///
+/// Slides the query over the whole history and returns the `top_k` closest
+/// non-overlapping windows, best first. Fails for an unknown `metric`.
+///
+/// # Arguments
+/// * `history` - Long series to search
+/// * `query_window` - Pattern to look for (e.g. the last few weeks of demand)
+/// * `top_k` - Number of windows to return
+/// * `metric` - "znorm" (shape only, default), "euclidean" (shape and level) or "dtw"
+#[napi]
+pub fn find_similar_windows(
+    history: Vec<f64>,
+    query_window: Vec<f64>,
+    top_k: u32,
+    metric: Option<String>,
+) -> napi::Result<Vec<SimilarWindow>> {
+    if let Some(other) = metric.as_deref().filter(|m| !["znorm", "euclidean", "dtw"].contains(m)) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("unknown metric \"{}\"; expected \"znorm\", \"euclidean\" or \"dtw\"", other),
+        ));
+    }
+    let m = query_window.len();
+    if m == 0 || history.len() < m {
+        return Ok(Vec::new());
+    }
+
+    let distances: Vec<f64> = match metric.as_deref() {
+        Some("euclidean") => (0..=history.len() - m)
+            .into_par_iter()
+            .map(|j| euclidean(&query_window, &history[j..j + m]))
+            .collect(),
+        Some("dtw") => {
+            let query = z_normalize(&query_window);
+            let window = Some((m / 10).max(1));
+            (0..=history.len() - m)
+                .into_par_iter()
+                .map(|j| dtw(&query, &z_normalize(&history[j..j + m]), window))
+                .collect()
+        }
+        _ => distance_profile(&history, &query_window),
+    };
+
+    let mut order: Vec<usize> = (0..distances.len()).filter(|&j| distances[j].is_finite()).collect();
+    order.sort_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+
+    let mut selected: Vec<usize> = Vec::new();
+    for j in order {
+        if selected.len() >= top_k as usize {
+            break;
+        }
+        if selected.iter().all(|&s| s.abs_diff(j) >= m) {
+            selected.push(j);
+        }
+    }
+
+    Ok(selected
+        .into_iter()
+        .map(|j| SimilarWindow {
+            start_index: j as u32,
+            distance: distances[j],
+            similarity: 1.0 / (1.0 + distances[j] / (m as f64).sqrt()),
+            values: history[j..j + m].to_vec(),
+        })
+        .collect())
+}
+
+/// Distance used to compare two series
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SeriesMetric {
+    Dtw(Option<usize>),
+    Euclidean,
+}
+
+impl SeriesMetric {
+    /// Fails for anything but "dtw", "euclidean" or no metric
+    pub(crate) fn parse(name: Option<&str>, window: Option<u32>) -> napi::Result<Self> {
+        match name {
+            None | Some("dtw") => Ok(SeriesMetric::Dtw(window.map(|w| w as usize))),
+            Some("euclidean") => Ok(SeriesMetric::Euclidean),
+            Some(other) => Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown metric \"{}\"; expected \"dtw\" or \"euclidean\"", other),
+            )),
+        }
+    }
+
+    pub(crate) fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            SeriesMetric::Dtw(window) => dtw(a, b, *window),
+            SeriesMetric::Euclidean => euclidean(a, b),
+        }
+    }
+}
+
+/// Refine `initial` toward the DTW barycenter of `members`
+fn dba(members: &[&[f64]], initial: &[f64], window: Option<usize>, iterations: usize) -> Vec<f64> {
+    let mut average = initial.to_vec();
+    let mut cost = f64::INFINITY;
+    for _ in 0..iterations {
+        let paths: Vec<Vec<(usize, usize)>> = members.par_iter().map(|m| dtw_path(&average, m, window)).collect();
+
+        let mut sums = vec![0.0; average.len()];
+        let mut counts = vec![0usize; average.len()];
+        for (path, member) in paths.iter().zip(members) {
+            for &(i, j) in path {
+                sums[i] += member[j];
+                counts[i] += 1;
+            }
+        }
+        let updated: Vec<f64> = sums
+            .iter()
+            .zip(&counts)
+            .zip(&average)
+            .map(|((s, &c), &old)| if c > 0 { s / c as f64 } else { old })
+            .collect();
+
+        let new_cost: f64 = members.par_iter().map(|m| dtw(&updated, m, window).powi(2)).sum();
+        average = updated;
+        if new_cost >= cost - 1e-9 {
+            break;
+        }
+        cost = new_cost;
+    }
+    average
+}
+
+/// Optimal DTW alignment between `a` and `b` as `(index_a, index_b)` pairs
+fn dtw_path(a: &[f64], b: &[f64], window: Option<usize>) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+    let w = window.map_or(n.max(m), |w| w.max(n.abs_diff(m)));
+
+    let mut cost = vec![vec![f64::INFINITY; m + 1]; n + 1];
+    cost[0][0] = 0.0;
+    for i in 1..=n {
+        let lo = i.saturating_sub(w).max(1);
+        let hi = (i + w).min(m);
+        for j in lo..=hi {
+            cost[i][j] = (a[i - 1] - b[j - 1]).powi(2) + cost[i - 1][j - 1].min(cost[i - 1][j]).min(cost[i][j - 1]);
+        }
+    }
+
+    let (mut i, mut j) = (n, m);
+    let mut path = vec![(n - 1, m - 1)];
+    while i > 1 || j > 1 {
+        let diagonal = if i > 1 && j > 1 { cost[i - 1][j - 1] } else { f64::INFINITY };
+        let up = if i > 1 { cost[i - 1][j] } else { f64::INFINITY };
+        let left = if j > 1 { cost[i][j - 1] } else { f64::INFINITY };
+        if diagonal <= up && diagonal <= left {
+            i -= 1;
+            j -= 1;
+        } else if up <= left {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+        path.push((i - 1, j - 1));
+    }
+    path.reverse();
+    path
+}
+
+/// DTW distance (square root of the minimal cumulative squared cost)
+pub(crate) fn dtw(a: &[f64], b: &[f64], window: Option<usize>) -> f64 {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return if n == m { 0.0 } else { f64::INFINITY };
+    }
+    // The band must at least cover the length difference to reach the corner
+    let w = window.map_or(n.max(m), |w| w.max(n.abs_diff(m)));
+
+    let mut prev = vec![f64::INFINITY; m + 1];
+    let mut curr = vec![f64::INFINITY; m + 1];
+    prev[0] = 0.0;
+    for i in 1..=n {
+        curr.fill(f64::INFINITY);
+        let lo = i.saturating_sub(w).max(1);
+        let hi = (i + w).min(m);
+        for j in lo..=hi {
+            let cost = (a[i - 1] - b[j - 1]).powi(2);
+            curr[j] = cost + prev[j - 1].min(prev[j]).min(curr[j - 1]);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m].sqrt()
+}
+
+/// Euclidean distance over the common prefix of two series
+pub(crate) fn euclidean(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Rescale to zero mean and unit variance (constant series map to zeros)
+pub(crate) fn z_normalize(data: &[f64]) -> Vec<f64> {
+    let n = data.len().max(1) as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let sd = (data.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if sd > 1e-12 {
+        data.iter().map(|v| (v - mean) / sd).collect()
+    } else {
+        vec![0.0; data.len()]
+    }
+}
+
+/// Rolling mean and standard deviation of every length-`m` window
+fn rolling_stats(data: &[f64], m: usize) -> (Vec<f64>, Vec<f64>) {
+    let l = data.len() + 1 - m;
+    let mut sum: f64 = data[..m].iter().sum();
+    let mut sum_sq: f64 = data[..m].iter().map(|v| v * v).sum();
+    let mut means = Vec::with_capacity(l);
+    let mut stds = Vec::with_capacity(l);
+    for i in 0..l {
+        if i > 0 {
+            sum += data[i + m - 1] - data[i - 1];
+            sum_sq += data[i + m - 1].powi(2) - data[i - 1].powi(2);
+        }
+        let mean = sum / m as f64;
+        means.push(mean);
+        stds.push((sum_sq / m as f64 - mean * mean).max(0.0).sqrt());
+    }
+    (means, stds)
+}
+
+/// Z-normalized distance from a sliding dot product
+fn znorm_distance(qt: f64, m: usize, mean_a: f64, std_a: f64, mean_b: f64, std_b: f64) -> f64 {
+    let flat_a = std_a < 1e-8;
+    let flat_b = std_b < 1e-8;
+    let correlation = match (flat_a, flat_b) {
+        (true, true) => 1.0,
+        (true, false) | (false, true) => 0.0,
+        _ => ((qt - m as f64 * mean_a * mean_b) / (m as f64 * std_a * std_b)).clamp(-1.0, 1.0),
+    };
+    (2.0 * m as f64 * (1.0 - correlation)).max(0.0).sqrt()
+}
+
+/// Self-join matrix profile, accumulating one diagonal at a time (SCRIMP order)
+fn self_join(data: &[f64], m: usize) -> (Vec<f64>, Vec<usize>) {
+    if m < 2 || data.len() < 2 * m {
+        return (Vec::new(), Vec::new());
+    }
+    let l = data.len() + 1 - m;
+    let exclusion = (m / 2).max(1);
+    let (means, stds) = rolling_stats(data, m);
+
+    let identity = || (vec![f64::INFINITY; l], vec![0usize; l]);
+    (exclusion + 1..l)
+        .into_par_iter()
+        .fold(identity, |(mut profile, mut index), diagonal| {
+            let mut qt: f64 = (0..m).map(|t| data[t] * data[diagonal + t]).sum();
+            for i in 0..l - diagonal {
+                let j = i + diagonal;
+                if i > 0 {
+                    qt += data[i + m - 1] * data[j + m - 1] - data[i - 1] * data[j - 1];
+                }
+                let d = znorm_distance(qt, m, means[i], stds[i], means[j], stds[j]);
+                if d < profile[i] {
+                    profile[i] = d;
+                    index[i] = j;
+                }
+                if d < profile[j] {
+                    profile[j] = d;
+                    index[j] = i;
+                }
+            }
+            (profile, index)
+        })
+        .reduce(identity, |(mut pa, mut ia), (pb, ib)| {
+            for t in 0..l {
+                if pb[t] < pa[t] {
+                    pa[t] = pb[t];
+                    ia[t] = ib[t];
+                }
+            }
+            (pa, ia)
+        })
+}
+
+/// Z-normalized distance from `query` to every same-length window of `data`
+fn distance_profile(data: &[f64], query: &[f64]) -> Vec<f64> {
+    let m = query.len();
+    if m == 0 || data.len() < m {
+        return Vec::new();
+    }
+    let (means, stds) = rolling_stats(data, m);
+    let (q_mean, q_std) = {
+        let (mu, sd) = rolling_stats(query, m);
+        (mu[0], sd[0])
+    };
+    (0..means.len())
+        .into_par_iter()
+        .map(|j| {
+            let qt: f64 = query.iter().zip(&data[j..j + m]).map(|(a, b)| a * b).sum();
+            znorm_distance(qt, m, q_mean, q_std, means[j], stds[j])
+        })
+        .collect()
+}
+
+/// Symmetric pairwise distance matrix, computed in parallel
+fn distance_matrix(data: &[Vec<f64>], metric: SeriesMetric) -> Vec<Vec<f64>> {
+    let n = data.len();
+    let upper: Vec<Vec<f64>> = (0..n)
+        .into_par_iter()
+        .map(|i| (i + 1..n).map(|j| metric.distance(&data[i], &data[j])).collect())
+        .collect();
+
+    let mut matrix = vec![vec![0.0; n]; n];
+    for (i, row) in upper.iter().enumerate() {
+        for (offset, &d) in row.iter().enumerate() {
+            let j = i + 1 + offset;
+            matrix[i][j] = d;
+            matrix[j][i] = d;
+        }
+    }
+    matrix
+}
+
+/// K-means++ style seeding: spread initial centers by squared distance
+fn plus_plus_init(distances: &[Vec<f64>], k: usize, rng: &mut Rng) -> Vec<usize> {
+    let n = distances.len();
+    let mut centers = vec![rng.gen_index(n)];
+    while centers.len() < k {
+        let weights: Vec<f64> = (0..n)
+            .map(|i| centers.iter().map(|&c| distances[i][c]).fold(f64::INFINITY, f64::min).powi(2))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let next = if total > 0.0 && total.is_finite() {
+            let mut target = rng.next_f64() * total;
+            weights
+                .iter()
+                .position(|&w| {
+                    target -= w;
+                    target <= 0.0
+                })
+                .unwrap_or(n - 1)
+        } else {
+            (0..n).find(|i| !centers.contains(i)).unwrap_or(0)
+        };
+        centers.push(next);
+    }
+    centers
+}
+
+fn assign_to_medoids(distances: &[Vec<f64>], medoids: &[usize]) -> Vec<usize> {
+    distances
+        .iter()
+        .map(|row| {
+            medoids
+                .iter()
+                .enumerate()
+                .min_by(|a, b| row[*a.1].total_cmp(&row[*b.1]))
+                .map_or(0, |(c, _)| c)
+        })
+        .collect()
+}
+
+fn nearest_center(series: &[f64], centers: &[Vec<f64>], metric: SeriesMetric) -> (usize, f64) {
+    centers
+        .iter()
+        .enumerate()
+        .map(|(c, center)| (c, metric.distance(series, center)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+/// Point-wise mean of series, over the length of the longest member
+fn pointwise_mean(members: &[&[f64]]) -> Vec<f64> {
+    let len = members.iter().map(|s| s.len()).max().unwrap_or(0);
+    (0..len)
+        .map(|t| {
+            let values: Vec<f64> = members.iter().filter_map(|s| s.get(t).copied()).collect();
+            values.iter().sum::<f64>() / values.len().max(1) as f64
+        })
+        .collect()
+}
+
+fn clustering_result(
+    labels: Vec<usize>,
+    medoids: Vec<usize>,
+    centroids: Vec<Vec<f64>>,
+    inertia: f64,
+    iterations: u32,
+) -> SeriesClusteringResult {
+    let mut cluster_sizes = vec![0u32; medoids.len()];
+    for &l in &labels {
+        cluster_sizes[l] += 1;
+    }
+    SeriesClusteringResult {
+        labels: labels.into_iter().map(|l| l as u32).collect(),
+        medoids: medoids.into_iter().map(|m| m as u32).collect(),
+        centroids,
+        cluster_sizes,
+        inertia,
+        iterations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dtw_distance() {
+        let a = vec![0.0, 1.0, 2.0, 1.0, 0.0];
+        let shifted = vec![0.0, 0.0, 1.0, 2.0, 1.0, 0.0];
+        assert_eq!(dtw_distance(a.clone(), shifted.clone(), None), 0.0);
+        assert!(euclidean(&a, &shifted) > 1.0);
+        assert_eq!(dtw_distance(a.clone(), a, Some(0)), 0.0);
+    }
+
+    fn patterns() -> Vec<Vec<f64>> {
+        let mut rng = Rng::new(5);
+        (0..20)
+            .map(|i| {
+                let phase = (i % 3) as f64;
+                (0..24)
+                    .map(|t| {
+                        let base = if i < 10 { (t as f64 / 2.0 + phase * 0.2).sin() } else { t as f64 / 6.0 };
+                        100.0 + 10.0 * base + rng.next_f64()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cluster_series_kmedoids() {
+        assert!(cluster_series(patterns(), 0, None).is_err());
+        let result = cluster_series(patterns(), 2, None).unwrap();
+        assert_eq!(result.cluster_sizes.iter().sum::<u32>(), 20);
+        assert!(result.labels[..10].iter().all(|&l| l == result.labels[0]));
+        assert!(result.labels[10..].iter().all(|&l| l != result.labels[0]));
+        assert_eq!(result.labels[result.medoids[0] as usize], 0);
+    }
+
+    #[test]
+    fn test_cluster_series_kmeans_euclidean() {
+        let options = SeriesClusteringOptions {
+            metric: Some("euclidean".to_string()),
+            method: Some("kmeans".to_string()),
+            ..Default::default()
+        };
+        let unknown = SeriesClusteringOptions { metric: Some("manhattan".to_string()), ..options.clone() };
+        assert!(cluster_series(patterns(), 2, Some(unknown)).is_err());
+        let result = cluster_series(patterns(), 2, Some(options)).unwrap();
+        assert!(result.labels[..10].iter().all(|&l| l == result.labels[0]));
+        assert!(result.labels[10..].iter().all(|&l| l != result.labels[0]));
+        assert_eq!(result.centroids[0].len(), 24);
+    }
+
+    #[test]
+    fn test_dtw_barycenter_preserves_peak() {
+        // Same bump at different offsets: the point-wise mean flattens it, DBA keeps its height
+        let series: Vec<Vec<f64>> = [4, 6, 8]
+            .iter()
+            .map(|&offset| (0..20).map(|t| if (offset..offset + 3).contains(&t) { 10.0 } else { 0.0 }).collect())
+            .collect();
+        let members: Vec<&[f64]> = series.iter().map(|s| s.as_slice()).collect();
+        let mean_peak = pointwise_mean(&members).iter().cloned().fold(0.0, f64::max);
+
+        let average = dtw_barycenter(series.clone(), None, None);
+        assert_eq!(average.len(), 20);
+        assert!(average.iter().cloned().fold(0.0, f64::max) > 9.0);
+        assert!(mean_peak < 7.0);
+
+        let total = |c: &[f64]| series.iter().map(|s| dtw(c, s, None)).sum::<f64>();
+        assert!(total(&average) <= total(&pointwise_mean(&members)));
+    }
+
+    #[test]
+    fn test_sax_encode() {
+        let data: Vec<f64> = (0..16).map(|i| i as f64).collect();
+        let sax = sax_encode(data.clone(), 4, 4);
+        assert_eq!(sax.word, "abcd");
+        assert_eq!(sax.paa.len(), 4);
+
+        let reversed = sax_encode(data.into_iter().rev().collect(), 4, 4);
+        assert_eq!(reversed.word, "dcba");
+        assert_eq!(sax_distance(sax.word.clone(), sax.word.clone(), 16, 4).unwrap(), 0.0);
+        assert!(sax_distance(sax.word.clone(), reversed.word, 16, 4).unwrap() > 0.0);
+        assert!(sax_distance(sax.word.clone(), "aaez".to_string(), 16, 4).is_err());
+        assert!(sax_distance(sax.word.clone(), "AAAA".to_string(), 16, 4).is_err());
+        assert!(sax_distance(sax.word, "abcd".to_string(), 16, 40).is_err());
+    }
+
+    #[test]
+    fn test_find_similar_windows() {
+        let history: Vec<f64> = (0..200).map(|t| 50.0 + 10.0 * (t as f64 * 0.3).sin() + (t % 7) as f64).collect();
+        let query = history[100..120].to_vec();
+
+        assert!(find_similar_windows(history.clone(), query.clone(), 3, Some("cosine".to_string())).is_err());
+        let matches = find_similar_windows(history.clone(), query.clone(), 3, None).unwrap();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].start_index, 100);
+        assert!((matches[0].similarity - 1.0).abs() < 1e-6);
+        assert!(matches.windows(2).all(|w| w[0].distance <= w[1].distance));
+        assert!(matches[1].start_index.abs_diff(100) >= 20);
+
+        let raised: Vec<f64> = query.iter().map(|v| v + 100.0).collect();
+        let by_shape = find_similar_windows(history.clone(), raised.clone(), 1, None).unwrap();
+        let by_level = find_similar_windows(history, raised, 1, Some("euclidean".to_string())).unwrap();
+        assert_eq!(by_shape[0].start_index, 100);
+        assert!(by_level[0].similarity < 0.2);
+    }
+
+    #[test]
+    fn test_matrix_profile_and_motifs() {
+        let mut rng = Rng::new(9);
+        let mut data: Vec<f64> = (0..300).map(|_| rng.next_f64()).collect();
+        let pattern = [0.0, 3.0, 6.0, 9.0, 6.0, 3.0, 0.0, -3.0];
+        for &start in &[40, 150, 260] {
+            for (t, v) in pattern.iter().enumerate() {
+                data[start + t] += v;
+            }
+        }
+
+        let mp = matrix_profile(data.clone(), 8);
+        assert_eq!(mp.profile.len(), 293);
+        assert!(mp.profile[40] < 1.0);
+        assert!([150, 260].contains(&mp.profile_index[40]));
+
+        let motifs = find_motifs(data, 8, 2, None);
+        let occurrences = &motifs[0].occurrences;
+        assert_eq!(occurrences.len(), 3);
+        assert!(occurrences.iter().zip([40, 150, 260]).all(|(&o, p)| o.abs_diff(p) <= 2));
+        assert!(motifs.len() <= 2);
+    }
+}
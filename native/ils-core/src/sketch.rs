@@ -0,0 +1,298 @@
+//! Streaming Sketches
+//!
+//! Bounded-memory summaries of unbounded event streams:
+//! - Approximate quantiles with a merging t-digest
+//! - Uniform reservoir sampling
+
+use napi_derive::napi;
+
+use crate::sampling::Rng;
+
+/// Buffered points per unit of compression before a merge pass
+const BUFFER_FACTOR: f64 = 5.0;
+
+/// Approximate quantile sketch (merging t-digest)
+///
+/// Tracks quantiles such as p95 turnaround time over millions of events in
+/// a few kilobytes. Accuracy is best in the tails, where the error is a small
+/// fraction of a rank; sketches built on separate shards can be merged.
+#[napi]
+pub struct QuantileSketch {
+    compression: f64,
+    /// (mean, weight), sorted by mean
+    centroids: Vec<(f64, f64)>,
+    /// Unmerged (value, weight) pairs
+    buffer: Vec<(f64, f64)>,
+    total_weight: f64,
+    min: f64,
+    max: f64,
+}
+
+#[napi]
+impl QuantileSketch {
+    /// # Arguments
+    /// * `compression` - Size/accuracy trade-off; roughly the number of centroids kept (default 100)
+    #[napi(constructor)]
+    pub fn new(compression: Option<f64>) -> Self {
+        QuantileSketch {
+            compression: compression.filter(|c| *c >= 10.0).unwrap_or(100.0),
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            total_weight: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Add one observation (NaN and infinities are ignored)
+    #[napi]
+    pub fn push(&mut self, value: f64) {
+        self.add(value, 1.0);
+    }
+
+    /// Add many observations in one call
+    #[napi]
+    pub fn push_many(&mut self, values: Vec<f64>) {
+        for value in values {
+            self.add(value, 1.0);
+        }
+    }
+
+    /// Fold another sketch's observations into this one
+    ///
+    /// `other` may be this very sketch (JS can pass the same object), so its
+    /// points are copied out before any are added.
+    #[napi]
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        let points: Vec<(f64, f64)> = other.centroids.iter().chain(&other.buffer).copied().collect();
+        let (min, max) = (other.min, other.max);
+        for (mean, weight) in points {
+            self.add(mean, weight);
+        }
+        self.min = self.min.min(min);
+        self.max = self.max.max(max);
+    }
+
+    /// Approximate value at quantile `q` in [0, 1]; NaN when empty
+    #[napi]
+    pub fn quantile(&mut self, q: f64) -> f64 {
+        self.flush();
+        if self.centroids.is_empty() || !(0.0..=1.0).contains(&q) {
+            return f64::NAN;
+        }
+        if self.centroids.len() == 1 {
+            return self.centroids[0].0;
+        }
+
+        // Each centroid's mean sits at the middle of its rank range
+        let target = q * self.total_weight;
+        let (first, last) = (self.centroids[0], self.centroids[self.centroids.len() - 1]);
+        if target <= first.1 / 2.0 {
+            return self.min + (first.0 - self.min) * (target / (first.1 / 2.0)).min(1.0);
+        }
+        if target >= self.total_weight - last.1 / 2.0 {
+            let remaining = (self.total_weight - target) / (last.1 / 2.0);
+            return self.max - (self.max - last.0) * remaining.min(1.0);
+        }
+        let mut cumulative = first.1 / 2.0;
+        for pair in self.centroids.windows(2) {
+            let ((left_mean, left_weight), (right_mean, right_weight)) = (pair[0], pair[1]);
+            let step = (left_weight + right_weight) / 2.0;
+            if target <= cumulative + step {
+                return left_mean + (right_mean - left_mean) * (target - cumulative) / step;
+            }
+            cumulative += step;
+        }
+        last.0
+    }
+
+    /// Number of observations seen
+    #[napi]
+    pub fn count(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Number of centroids currently kept (memory footprint)
+    #[napi]
+    pub fn centroid_count(&mut self) -> u32 {
+        self.flush();
+        self.centroids.len() as u32
+    }
+}
+
+impl QuantileSketch {
+    fn add(&mut self, value: f64, weight: f64) {
+        if !value.is_finite() || weight <= 0.0 {
+            return;
+        }
+        self.buffer.push((value, weight));
+        self.total_weight += weight;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        if self.buffer.len() as f64 >= BUFFER_FACTOR * self.compression {
+            self.flush();
+        }
+    }
+
+    /// Merge buffered points into the centroids under the k1 scale function
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut points = std::mem::take(&mut self.centroids);
+        points.append(&mut self.buffer);
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let delta = self.compression;
+        let scale = |q: f64| delta / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin();
+        let inverse = |k: f64| ((k * 2.0 * std::f64::consts::PI / delta).min(std::f64::consts::FRAC_PI_2).sin() + 1.0) / 2.0;
+
+        let mut merged = Vec::with_capacity(points.len().min(2 * delta as usize));
+        let mut current = points[0];
+        let mut weight_before = 0.0;
+        let mut limit = inverse(scale(0.0) + 1.0) * self.total_weight;
+        for &(mean, weight) in &points[1..] {
+            if weight_before + current.1 + weight <= limit {
+                current.0 += (mean - current.0) * weight / (current.1 + weight);
+                current.1 += weight;
+            } else {
+                weight_before += current.1;
+                merged.push(current);
+                limit = inverse(scale(weight_before / self.total_weight) + 1.0) * self.total_weight;
+                current = (mean, weight);
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+}
+
+/// Fixed-size uniform random sample of a stream (reservoir sampling)
+///
+/// After any number of pushes every value seen so far is in the sample with
+/// equal probability, so the sample can stand in for the full stream when
+/// seeding `describe` or anomaly baselines.
+#[napi]
+pub struct ReservoirSampler {
+    capacity: usize,
+    reservoir: Vec<f64>,
+    seen: u64,
+    rng: Rng,
+}
+
+#[napi]
+impl ReservoirSampler {
+    /// # Arguments
+    /// * `capacity` - Sample size to maintain
+    /// * `seed` - Random seed (default 42)
+    #[napi(constructor)]
+    pub fn new(capacity: u32, seed: Option<u32>) -> Self {
+        ReservoirSampler {
+            capacity: capacity as usize,
+            reservoir: Vec::with_capacity(capacity as usize),
+            seen: 0,
+            rng: Rng::new(seed.unwrap_or(42) as u64),
+        }
+    }
+
+    /// Offer one value to the sample
+    #[napi]
+    pub fn push(&mut self, value: f64) {
+        self.seen += 1;
+        if self.reservoir.len() < self.capacity {
+            self.reservoir.push(value);
+        } else {
+            let slot = self.rng.gen_index(self.seen as usize);
+            if slot < self.capacity {
+                self.reservoir[slot] = value;
+            }
+        }
+    }
+
+    /// Offer many values in one call
+    #[napi]
+    pub fn push_many(&mut self, values: Vec<f64>) {
+        for value in values {
+            self.push(value);
+        }
+    }
+
+    /// Current sample (up to `capacity` values, in no particular order)
+    #[napi]
+    pub fn sample(&self) -> Vec<f64> {
+        self.reservoir.clone()
+    }
+
+    /// Number of values offered so far
+    #[napi]
+    pub fn count(&self) -> f64 {
+        self.seen as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile_sketch_accuracy() {
+        let mut sketch = QuantileSketch::new(None);
+        let mut rng = crate::sampling::Rng::new(5);
+        let mut values: Vec<f64> = (0..200_000).map(|_| -(1.0 - rng.next_f64()).ln() * 30.0).collect();
+        sketch.push_many(values.clone());
+        sketch.push(f64::NAN);
+        assert_eq!(sketch.count(), 200_000.0);
+        assert!(sketch.centroid_count() < 300);
+
+        values.sort_by(|a, b| a.total_cmp(b));
+        // Error measured in rank: where the estimate falls in the exact distribution
+        for (q, tolerance) in [(0.001, 0.0005), (0.01, 0.001), (0.5, 0.002), (0.95, 0.001), (0.99, 0.0005), (0.999, 0.0002)] {
+            let estimate = sketch.quantile(q);
+            let rank = values.partition_point(|&v| v < estimate) as f64 / values.len() as f64;
+            assert!((rank - q).abs() < tolerance, "q={} rank={}", q, rank);
+        }
+        assert_eq!(sketch.quantile(0.0), values[0]);
+        assert_eq!(sketch.quantile(1.0), values[values.len() - 1]);
+    }
+
+    #[test]
+    fn test_reservoir_sampler() {
+        let mut sampler = ReservoirSampler::new(100, Some(1));
+        sampler.push_many((0..50).map(|i| i as f64).collect());
+        assert_eq!(sampler.sample().len(), 50);
+
+        sampler.push_many((50..100_000).map(|i| i as f64).collect());
+        let sample = sampler.sample();
+        assert_eq!((sample.len(), sampler.count()), (100, 100_000.0));
+        // A uniform sample of 0..100000 has mean near 50000
+        let mean = sample.iter().sum::<f64>() / 100.0;
+        assert!((mean - 50_000.0).abs() < 10_000.0);
+        assert!(sample.iter().filter(|&&v| v < 100.0).count() < 5);
+    }
+
+    #[test]
+    fn test_quantile_sketch_merge() {
+        let (mut a, mut b) = (QuantileSketch::new(Some(200.0)), QuantileSketch::new(Some(200.0)));
+        a.push_many((0..50_000).map(|i| i as f64).collect());
+        b.push_many((50_000..100_000).map(|i| i as f64).collect());
+        a.merge(&b);
+        assert_eq!(a.count(), 100_000.0);
+        assert!((a.quantile(0.95) - 95_000.0).abs() < 100.0);
+        assert!((a.quantile(0.5) - 50_000.0).abs() < 300.0);
+        assert!(QuantileSketch::new(None).quantile(0.5).is_nan());
+    }
+
+    #[test]
+    fn test_quantile_sketch_merge_into_itself() {
+        let mut sketch = QuantileSketch::new(Some(50.0));
+        sketch.push_many((0..1_000).map(|i| i as f64).collect());
+        sketch.push_many(vec![f64::INFINITY, f64::NEG_INFINITY, f64::NAN]);
+        assert_eq!(sketch.count(), 1_000.0);
+        // JS passing the same object as `other` aliases it with `self`
+        let other = unsafe { &*(&sketch as *const QuantileSketch) };
+        sketch.merge(other);
+        assert_eq!(sketch.count(), 2_000.0);
+        assert_eq!((sketch.quantile(0.0), sketch.quantile(1.0)), (0.0, 999.0));
+        assert!((sketch.quantile(0.5) - 500.0).abs() < 20.0);
+    }
+}
@@ -0,0 +1,306 @@
+//! Staffing Planning
+//!
+//! Turns forecast output into staffing decisions:
+//! - Multi-day staffing plans with headcount smoothing
+//! - Expected overtime / under-coverage cost of a staffing level
+
+use napi_derive::napi;
+
+use crate::forecasting::{calculate_staffing_needs, interval_z, ForecastResult, ORDERS_PER_TECH_DAY};
+
+/// Integration grid size across the forecast distribution
+const INTEGRATION_POINTS: usize = 401;
+
+/// Convert a horizon of daily forecasts into a day-by-day staffing plan
+///
+/// Daily requirements come from `calculate_staffing_needs`. Headcount is then
+/// smoothed so it never changes by more than `max_daily_change` between days:
+/// increases are ramped in ahead of peaks and decreases are released
+/// gradually, so the plan never drops below the raw requirement.
+#[napi]
+pub fn plan_staffing(forecasts: Vec<ForecastResult>, config: StaffingPlanConfig) -> StaffingPlan {
+    let max_change = config.max_daily_change.unwrap_or(1);
+    let use_upper = config.use_upper_bound.unwrap_or(false);
+
+    let volumes: Vec<f64> = forecasts
+        .iter()
+        .map(|f| if use_upper { f.upper_bound } else { f.predicted_value }.max(0.0))
+        .collect();
+
+    let raw: Vec<_> = volumes
+        .iter()
+        .map(|&v| calculate_staffing_needs(v, config.complexity_score, config.historical_efficiency))
+        .collect();
+
+    let lab_techs = smooth_headcount(&raw.iter().map(|r| r.lab_techs).collect::<Vec<_>>(), max_change);
+    let engineers = smooth_headcount(&raw.iter().map(|r| r.engineers).collect::<Vec<_>>(), max_change);
+
+    let days: Vec<StaffingPlanDay> = (0..forecasts.len())
+        .map(|i| StaffingPlanDay {
+            day: i as u32,
+            order_volume: volumes[i],
+            required_lab_techs: raw[i].lab_techs,
+            required_engineers: raw[i].engineers,
+            lab_techs: lab_techs[i],
+            engineers: engineers[i],
+        })
+        .collect();
+
+    let total_lab_tech_days = lab_techs.iter().sum();
+    let total_engineer_days = engineers.iter().sum();
+    let peak_lab_techs = lab_techs.iter().copied().max().unwrap_or(0);
+    let peak_engineers = engineers.iter().copied().max().unwrap_or(0);
+
+    let reasoning = format!(
+        "Planned {} days from {} forecasts with headcount changes limited to {} per day; peak of {} lab techs and {} engineers, {} lab tech days and {} engineer days in total.",
+        days.len(),
+        if use_upper { "upper-bound" } else { "expected" },
+        max_change,
+        peak_lab_techs,
+        peak_engineers,
+        total_lab_tech_days,
+        total_engineer_days
+    );
+
+    StaffingPlan {
+        days,
+        total_lab_tech_days,
+        total_engineer_days,
+        peak_lab_techs,
+        peak_engineers,
+        reasoning,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StaffingPlanConfig {
+    pub complexity_score: f64,
+    pub historical_efficiency: f64,
+    /// Maximum headcount change between consecutive days (default 1)
+    pub max_daily_change: Option<u32>,
+    /// Staff to the forecast upper bound instead of the point forecast
+    pub use_upper_bound: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StaffingPlanDay {
+    pub day: u32,
+    pub order_volume: f64,
+    pub required_lab_techs: u32,
+    pub required_engineers: u32,
+    pub lab_techs: u32,
+    pub engineers: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StaffingPlan {
+    pub days: Vec<StaffingPlanDay>,
+    pub total_lab_tech_days: u32,
+    pub total_engineer_days: u32,
+    pub peak_lab_techs: u32,
+    pub peak_engineers: u32,
+    pub reasoning: String,
+}
+
+/// Estimate expected overtime, unmet demand and their cost for a staffing level
+///
+/// Demand is modelled as normal around the forecast, with spread derived from
+/// the interval (`upper_bound - predicted_value = z sigma`, z = 1.96 at the
+/// usual 95% `confidence_level`; levels outside (0, 1) are read as 95%),
+/// truncated at zero. Expectations are integrated numerically over that distribution:
+/// demand above regular capacity is absorbed by overtime up to the per-tech
+/// overtime limit, and the remainder goes unmet.
+#[napi]
+pub fn estimate_coverage_cost(forecast: ForecastResult, lab_techs: u32, config: CoverageCostConfig) -> CoverageCostResult {
+    let orders_per_hour = ORDERS_PER_TECH_DAY * config.historical_efficiency / config.shift_hours.unwrap_or(8.0);
+    let regular_capacity = lab_techs as f64 * ORDERS_PER_TECH_DAY * config.historical_efficiency;
+    let overtime_capacity = lab_techs as f64 * config.max_overtime_hours_per_tech.unwrap_or(2.0) * orders_per_hour;
+
+    let mu = forecast.predicted_value;
+    let sigma = ((forecast.upper_bound - forecast.predicted_value) / interval_z(forecast.confidence_level)).max(0.0);
+
+    let (mut overtime_orders, mut unmet_orders, mut p_overtime, mut p_unmet) = (0.0, 0.0, 0.0, 0.0);
+
+    if sigma == 0.0 {
+        let demand = mu.max(0.0);
+        overtime_orders = (demand - regular_capacity).clamp(0.0, overtime_capacity);
+        unmet_orders = (demand - regular_capacity - overtime_capacity).max(0.0);
+        p_overtime = if demand > regular_capacity { 1.0 } else { 0.0 };
+        p_unmet = if unmet_orders > 0.0 { 1.0 } else { 0.0 };
+    } else {
+        // Trapezoidal integration over mu +/- 6 sigma; negative demand is treated as zero
+        let lo = mu - 6.0 * sigma;
+        let step = 12.0 * sigma / (INTEGRATION_POINTS - 1) as f64;
+        for i in 0..INTEGRATION_POINTS {
+            let x = lo + i as f64 * step;
+            let z = (x - mu) / sigma;
+            let weight = if i == 0 || i == INTEGRATION_POINTS - 1 { 0.5 } else { 1.0 };
+            let density = weight * step * (-0.5 * z * z).exp() / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+
+            let demand = x.max(0.0);
+            let excess = demand - regular_capacity;
+            if excess > 0.0 {
+                overtime_orders += density * excess.min(overtime_capacity);
+                p_overtime += density;
+                if excess > overtime_capacity {
+                    unmet_orders += density * (excess - overtime_capacity);
+                    p_unmet += density;
+                }
+            }
+        }
+    }
+
+    let expected_overtime_hours = if orders_per_hour > 0.0 { overtime_orders / orders_per_hour } else { 0.0 };
+    let overtime_cost = expected_overtime_hours * config.overtime_cost_per_hour;
+    let unmet_cost = unmet_orders * config.unmet_order_cost;
+    let regular_cost = lab_techs as f64 * config.regular_cost_per_tech_day.unwrap_or(0.0);
+
+    CoverageCostResult {
+        lab_techs,
+        expected_overtime_hours,
+        expected_unmet_orders: unmet_orders,
+        overtime_probability: p_overtime.min(1.0),
+        unmet_probability: p_unmet.min(1.0),
+        overtime_cost,
+        unmet_cost,
+        regular_cost,
+        total_cost: regular_cost + overtime_cost + unmet_cost,
+    }
+}
+
+/// Evaluate coverage cost for every staffing level in `[min_lab_techs, max_lab_techs]`
+///
+/// The result is ordered by headcount; pick the minimum `total_cost` entry
+/// for a cost-optimal staffing decision.
+#[napi]
+pub fn staffing_cost_curve(
+    forecast: ForecastResult,
+    min_lab_techs: u32,
+    max_lab_techs: u32,
+    config: CoverageCostConfig,
+) -> Vec<CoverageCostResult> {
+    (min_lab_techs..=max_lab_techs)
+        .map(|techs| estimate_coverage_cost(forecast.clone(), techs, config.clone()))
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CoverageCostConfig {
+    pub historical_efficiency: f64,
+    pub overtime_cost_per_hour: f64,
+    pub unmet_order_cost: f64,
+    /// Fully loaded cost of one tech for a regular day (default 0)
+    pub regular_cost_per_tech_day: Option<f64>,
+    /// Regular shift length in hours (default 8)
+    pub shift_hours: Option<f64>,
+    /// Overtime limit per tech per day in hours (default 2)
+    pub max_overtime_hours_per_tech: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CoverageCostResult {
+    pub lab_techs: u32,
+    pub expected_overtime_hours: f64,
+    pub expected_unmet_orders: f64,
+    pub overtime_probability: f64,
+    pub unmet_probability: f64,
+    pub overtime_cost: f64,
+    pub unmet_cost: f64,
+    pub regular_cost: f64,
+    pub total_cost: f64,
+}
+
+/// Limit day-over-day headcount changes without going below requirements
+fn smooth_headcount(required: &[u32], max_change: u32) -> Vec<u32> {
+    let mut plan = required.to_vec();
+
+    // Backward pass: ramp up ahead of peaks
+    for i in (0..plan.len().saturating_sub(1)).rev() {
+        plan[i] = plan[i].max(plan[i + 1].saturating_sub(max_change));
+    }
+
+    // Forward pass: release staff gradually after peaks
+    for i in 1..plan.len() {
+        plan[i] = plan[i].max(plan[i - 1].saturating_sub(max_change));
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast(value: f64) -> ForecastResult {
+        ForecastResult {
+            predicted_value: value,
+            confidence: 0.9,
+            lower_bound: value * 0.8,
+            upper_bound: value * 1.2,
+            confidence_level: 0.95,
+            trend: "stable".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_smooth_headcount() {
+        let plan = smooth_headcount(&[2, 2, 8, 2, 2, 2], 2);
+        assert_eq!(plan, vec![4, 6, 8, 6, 4, 2]);
+    }
+
+    #[test]
+    fn test_plan_staffing() {
+        let forecasts: Vec<ForecastResult> = [100.0, 100.0, 400.0, 100.0, 100.0].iter().map(|&v| forecast(v)).collect();
+        let config = StaffingPlanConfig {
+            complexity_score: 0.3,
+            historical_efficiency: 0.85,
+            max_daily_change: Some(3),
+            use_upper_bound: None,
+        };
+        let plan = plan_staffing(forecasts, config);
+
+        assert_eq!(plan.days.len(), 5);
+        for pair in plan.days.windows(2) {
+            assert!((pair[0].lab_techs as i64 - pair[1].lab_techs as i64).abs() <= 3);
+        }
+        assert!(plan.days.iter().all(|d| d.lab_techs >= d.required_lab_techs));
+        assert_eq!(plan.peak_lab_techs, plan.days[2].required_lab_techs);
+    }
+
+    #[test]
+    fn test_estimate_coverage_cost() {
+        let config = CoverageCostConfig {
+            historical_efficiency: 1.0,
+            overtime_cost_per_hour: 40.0,
+            unmet_order_cost: 25.0,
+            regular_cost_per_tech_day: Some(200.0),
+            shift_hours: None,
+            max_overtime_hours_per_tech: None,
+        };
+
+        // 10 techs cover 150 orders; a 150-order forecast overruns half the time
+        let at_capacity = estimate_coverage_cost(forecast(150.0), 10, config.clone());
+        assert!((at_capacity.overtime_probability - 0.5).abs() < 0.01);
+        assert!(at_capacity.expected_overtime_hours > 0.0);
+
+        let overstaffed = estimate_coverage_cost(forecast(150.0), 20, config.clone());
+        assert!(overstaffed.expected_overtime_hours < 1e-6);
+        assert!(overstaffed.expected_unmet_orders < 1e-6);
+
+        // A level given as a percentage (or NaN) is read as 95% instead of aborting
+        for level in [95.0, f64::NAN] {
+            let odd = estimate_coverage_cost(ForecastResult { confidence_level: level, ..forecast(150.0) }, 12, config.clone());
+            let usual = estimate_coverage_cost(forecast(150.0), 12, config.clone());
+            assert_eq!(odd.expected_overtime_hours, usual.expected_overtime_hours);
+        }
+
+        let curve = staffing_cost_curve(forecast(150.0), 5, 20, config);
+        assert_eq!(curve.len(), 16);
+        assert!(curve[0].expected_unmet_orders > curve[15].expected_unmet_orders);
+    }
+}
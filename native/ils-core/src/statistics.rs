@@ -3,8 +3,17 @@
 //! High-performance implementations of common statistical operations
 //! using SIMD optimizations and parallel processing where beneficial.
 
+use std::collections::HashMap;
+
+use napi::{Error, Status};
 use napi_derive::napi;
 use rayon::prelude::*;
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+use crate::econometrics::{adf_statistic, interpolate_p_value, AdfTerms};
+
+/// Arrays at least this long are sorted in parallel
+const PARALLEL_SORT_THRESHOLD: usize = 100_000;
 
 /// Calculate the mean (average) of a dataset
 #[napi]
@@ -12,7 +21,22 @@ pub fn mean(data: Vec<f64>) -> f64 {
     if data.is_empty() {
         return 0.0;
     }
-    data.iter().sum::<f64>() / data.len() as f64
+    stable_sum(data.iter().copied()) / data.len() as f64
+}
+
+/// Sum with Neumaier (improved Kahan) compensation
+///
+/// Carries the rounding error of every addition, so long series of
+/// large-magnitude values (e.g. millions of readings around 1e9) sum to
+/// within one rounding of the exact result instead of drifting.
+pub(crate) fn stable_sum<I: IntoIterator<Item = f64>>(values: I) -> f64 {
+    let (mut sum, mut compensation) = (0.0f64, 0.0f64);
+    for value in values {
+        let total = sum + value;
+        compensation += if sum.abs() >= value.abs() { (sum - total) + value } else { (value - total) + sum };
+        sum = total;
+    }
+    sum + compensation
 }
 
 /// Calculate the median of a dataset
@@ -21,10 +45,10 @@ pub fn median(data: Vec<f64>) -> f64 {
     if data.is_empty() {
         return 0.0;
     }
-    let mut sorted = data.clone();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mut sorted = data;
+    sort_values(&mut sorted);
     let mid = sorted.len() / 2;
-    if sorted.len() % 2 == 0 {
+    if sorted.len().is_multiple_of(2) {
         (sorted[mid - 1] + sorted[mid]) / 2.0
     } else {
         sorted[mid]
@@ -34,33 +58,169 @@ pub fn median(data: Vec<f64>) -> f64 {
 /// Calculate the standard deviation of a dataset
 #[napi]
 pub fn std_dev(data: Vec<f64>) -> f64 {
-    if data.len() < 2 {
-        return 0.0;
-    }
-    let m = mean(data.clone());
-    let variance: f64 = data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (data.len() - 1) as f64;
-    variance.sqrt()
+    variance(data).sqrt()
 }
 
 /// Calculate variance of a dataset
+///
+/// Corrected two-pass algorithm with compensated sums: the second term
+/// removes the residual error left in the mean.
 #[napi]
 pub fn variance(data: Vec<f64>) -> f64 {
     if data.len() < 2 {
         return 0.0;
     }
+    let n = data.len() as f64;
     let m = mean(data.clone());
-    data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (data.len() - 1) as f64
+    let deviations = stable_sum(data.iter().map(|x| x - m));
+    let squares = stable_sum(data.iter().map(|x| (x - m).powi(2)));
+    let v = (squares - deviations * deviations / n) / (n - 1.0);
+    // Rounding can leave a tiny negative value; NaN from NaN or inf data passes through
+    if v < 0.0 { 0.0 } else { v }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct MissingValueOptions {
+    /// How NaN values are handled: "ignore" (default) drops them, "propagate"
+    /// makes the result NaN, "error" throws; other values are rejected
+    pub missing: Option<String>,
+}
+
+/// Mean with an explicit missing-value policy
+#[napi]
+pub fn mean_with_options(data: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing(data, missing_policy(&options))?.map_or(f64::NAN, mean))
+}
+
+/// Median with an explicit missing-value policy
+#[napi]
+pub fn median_with_options(data: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing(data, missing_policy(&options))?.map_or(f64::NAN, median))
+}
+
+/// Standard deviation with an explicit missing-value policy
+#[napi]
+pub fn std_dev_with_options(data: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing(data, missing_policy(&options))?.map_or(f64::NAN, std_dev))
+}
+
+/// Variance with an explicit missing-value policy
+#[napi]
+pub fn variance_with_options(data: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing(data, missing_policy(&options))?.map_or(f64::NAN, variance))
+}
+
+fn missing_policy(options: &Option<MissingValueOptions>) -> Option<&str> {
+    options.as_ref().and_then(|o| o.missing.as_deref())
+}
+
+/// Apply a missing-value policy: `Some(clean data)`, `None` to return NaN, or an error
+fn drop_missing(data: Vec<f64>, policy: Option<&str>) -> napi::Result<Option<Vec<f64>>> {
+    check_policy(policy)?;
+    let missing = data.iter().filter(|x| x.is_nan()).count();
+    if missing == 0 {
+        return Ok(Some(data));
+    }
+    match policy {
+        Some("error") => Err(missing_error(missing)),
+        Some("propagate") => Ok(None),
+        _ => Ok(Some(data.into_iter().filter(|x| !x.is_nan()).collect())),
+    }
+}
+
+/// Paired version of `drop_missing`: "ignore" drops every pair with a NaN on either side
+fn drop_missing_pairs(x: Vec<f64>, y: Vec<f64>, policy: Option<&str>) -> napi::Result<Option<(Vec<f64>, Vec<f64>)>> {
+    check_policy(policy)?;
+    let missing = x.iter().chain(&y).filter(|v| v.is_nan()).count();
+    if missing == 0 {
+        return Ok(Some((x, y)));
+    }
+    match policy {
+        Some("error") => Err(missing_error(missing)),
+        Some("propagate") => Ok(None),
+        // Unequal lengths have no pairs to drop; the statistic handles the mismatch
+        _ if x.len() != y.len() => Ok(Some((x, y))),
+        _ => Ok(Some(x.into_iter().zip(y).filter(|(a, b)| !a.is_nan() && !b.is_nan()).unzip())),
+    }
+}
+
+/// Fails for anything but "ignore", "propagate", "error" or no policy
+fn check_policy(policy: Option<&str>) -> napi::Result<()> {
+    match policy {
+        None | Some("ignore" | "propagate" | "error") => Ok(()),
+        Some(other) => Err(Error::new(
+            Status::InvalidArg,
+            format!("unknown missing-value policy \"{}\"; expected \"ignore\", \"propagate\" or \"error\"", other),
+        )),
+    }
+}
+
+fn missing_error(missing: usize) -> Error {
+    Error::new(Status::InvalidArg, format!("input contains {} NaN value(s) and the missing-value policy is \"error\"", missing))
+}
+
+/// Calculate the weighted mean of a dataset
+///
+/// Returns 0 when the lengths differ, a weight is negative, or the weights sum to zero.
+#[napi]
+pub fn weighted_mean(data: Vec<f64>, weights: Vec<f64>) -> f64 {
+    if !valid_weights(&data, &weights) {
+        return 0.0;
+    }
+    data.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>() / weights.iter().sum::<f64>()
+}
+
+/// Calculate the weighted standard deviation of a dataset
+///
+/// Treats the weights as reliability weights and applies the matching
+/// small-sample correction `V1 - V2 / V1` (`V1 = sum(w)`, `V2 = sum(w^2)`), so
+/// equal weights give the same result as `std_dev`.
+#[napi]
+pub fn weighted_std_dev(data: Vec<f64>, weights: Vec<f64>) -> f64 {
+    if data.len() < 2 || !valid_weights(&data, &weights) {
+        return 0.0;
+    }
+    let v1: f64 = weights.iter().sum();
+    let v2: f64 = weights.iter().map(|w| w * w).sum();
+    let denominator = v1 - v2 / v1;
+    if denominator <= 0.0 {
+        return 0.0;
+    }
+    let m = weighted_mean(data.clone(), weights.clone());
+    let sum_sq: f64 = data.iter().zip(&weights).map(|(x, w)| w * (x - m).powi(2)).sum();
+    (sum_sq / denominator).sqrt()
+}
+
+fn valid_weights(data: &[f64], weights: &[f64]) -> bool {
+    !data.is_empty()
+        && data.len() == weights.len()
+        && weights.iter().all(|&w| w >= 0.0)
+        && weights.iter().sum::<f64>() > 0.0
 }
 
 /// Calculate quantile (percentile) of a dataset
 #[napi]
 pub fn quantile(data: Vec<f64>, q: f64) -> f64 {
-    if data.is_empty() || q < 0.0 || q > 1.0 {
+    if data.is_empty() || !(0.0..=1.0).contains(&q) {
         return 0.0;
     }
     let mut sorted: Vec<f64> = data.into_iter().filter(|x| !x.is_nan()).collect();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sort_values(&mut sorted);
+    sorted_quantile(&sorted, q)
+}
+
+/// Sort ascending by `total_cmp`, in parallel for large arrays
+pub(crate) fn sort_values(values: &mut [f64]) {
+    if values.len() >= PARALLEL_SORT_THRESHOLD {
+        values.par_sort_unstable_by(|a, b| a.total_cmp(b));
+    } else {
+        values.sort_unstable_by(|a, b| a.total_cmp(b));
+    }
+}
 
+/// Linearly interpolated quantile of already sorted, NaN-free data (0 when empty)
+pub(crate) fn sorted_quantile(sorted: &[f64], q: f64) -> f64 {
     if sorted.is_empty() {
         return 0.0;
     }
@@ -78,12 +238,143 @@ pub fn quantile(data: Vec<f64>, q: f64) -> f64 {
     }
 }
 
+/// Percentile rank (0-100) of `value` within `data`
+///
+/// # Arguments
+/// * `data` - Reference distribution (NaN values are ignored)
+/// * `value` - Value to locate
+/// * `kind` - "mean" (default) counts ties as half below, "weak" counts values
+///   <= `value`, "strict" counts values < `value`
+#[napi]
+pub fn percentile_rank(data: Vec<f64>, value: f64, kind: Option<String>) -> f64 {
+    percentile_ranks(data, vec![value], kind)[0]
+}
+
+/// Percentile ranks (0-100) of several values against the same `data`
+///
+/// Sorts `data` once and binary-searches each value, so many lookups cost
+/// one round trip. NaN values (or an empty reference) give NaN.
+///
+/// # Arguments
+/// * `data` - Reference distribution (NaN values are ignored)
+/// * `values` - Values to locate
+/// * `kind` - "mean" (default), "weak" or "strict"; see `percentile_rank`
+#[napi]
+pub fn percentile_ranks(data: Vec<f64>, values: Vec<f64>, kind: Option<String>) -> Vec<f64> {
+    let mut sorted: Vec<f64> = data.into_iter().filter(|x| !x.is_nan()).collect();
+    sort_values(&mut sorted);
+    let n = sorted.len() as f64;
+    values
+        .par_iter()
+        .map(|&value| {
+            if sorted.is_empty() || value.is_nan() {
+                return f64::NAN;
+            }
+            let below = sorted.partition_point(|&x| x < value) as f64;
+            let at_or_below = sorted.partition_point(|&x| x <= value) as f64;
+            let count = match kind.as_deref() {
+                Some("weak") => at_or_below,
+                Some("strict") => below,
+                _ => 0.5 * (below + at_or_below),
+            };
+            100.0 * count / n
+        })
+        .collect()
+}
+
+/// Calculate the weighted quantile of a dataset
+///
+/// Places each sorted value at the share of the total weight before it,
+/// rescaled so the smallest value sits at 0 and the largest at 1, and
+/// interpolates linearly between them. Only relative weights matter, so
+/// normalized weights give the same result as counts, and equal weights
+/// reproduce `quantile`. Returns 0 for
+/// invalid weights (see `weighted_mean`) or `q` outside [0, 1]; NaN values
+/// are ignored.
+///
+/// # Arguments
+/// * `data` - Sample values
+/// * `weights` - Non-negative weight of each value
+/// * `q` - Quantile in [0, 1]
+#[napi]
+pub fn weighted_quantile(data: Vec<f64>, weights: Vec<f64>, q: f64) -> f64 {
+    if !valid_weights(&data, &weights) || !(0.0..=1.0).contains(&q) {
+        return 0.0;
+    }
+    let mut pairs: Vec<(f64, f64)> = data.into_iter().zip(weights).filter(|(x, w)| !x.is_nan() && *w > 0.0).collect();
+    if pairs.is_empty() {
+        return 0.0;
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let cumulative: Vec<f64> = pairs
+        .iter()
+        .scan(0.0, |total, (_, w)| {
+            *total += w;
+            Some(*total)
+        })
+        .collect();
+
+    // Normalized weight before each value; with equal weights these are `quantile`'s i / (n - 1)
+    let span = cumulative[cumulative.len() - 1] - pairs[pairs.len() - 1].1;
+    if span <= 0.0 {
+        return pairs[0].0;
+    }
+    let positions: Vec<f64> = cumulative.iter().zip(&pairs).map(|(c, (_, w))| (c - w) / span).collect();
+    let upper = positions.partition_point(|&p| p < q).clamp(1, pairs.len() - 1);
+    let (p0, p1) = (positions[upper - 1], positions[upper]);
+    let (x0, x1) = (pairs[upper - 1].0, pairs[upper].0);
+    x0 + (q - p0) / (p1 - p0) * (x1 - x0)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EmpiricalCdf {
+    /// Distinct values in ascending order
+    pub values: Vec<f64>,
+    /// Share of the data at or below each value; the last entry is 1
+    pub probabilities: Vec<f64>,
+}
+
+/// Empirical cumulative distribution function of a dataset
+///
+/// Gives the step points of the ECDF for plotting; NaN values are ignored.
+#[napi]
+pub fn ecdf(data: Vec<f64>) -> EmpiricalCdf {
+    let mut sorted: Vec<f64> = data.into_iter().filter(|x| !x.is_nan()).collect();
+    sort_values(&mut sorted);
+    let n = sorted.len() as f64;
+    let mut values = Vec::new();
+    let mut probabilities = Vec::new();
+    for (i, &v) in sorted.iter().enumerate() {
+        if values.last() == Some(&v) {
+            *probabilities.last_mut().unwrap() = (i + 1) as f64 / n;
+        } else {
+            values.push(v);
+            probabilities.push((i + 1) as f64 / n);
+        }
+    }
+    EmpiricalCdf { values, probabilities }
+}
+
+/// Evaluate the empirical CDF of `data` at each of `points`
+///
+/// Returns the share of `data` at or below each point, e.g. for KS-style
+/// comparisons of two samples on a common grid.
+///
+/// # Arguments
+/// * `data` - Sample defining the ECDF (NaN values are ignored)
+/// * `points` - Where to evaluate it
+#[napi]
+pub fn ecdf_evaluate(data: Vec<f64>, points: Vec<f64>) -> Vec<f64> {
+    percentile_ranks(data, points, Some("weak".to_string())).into_iter().map(|p| p / 100.0).collect()
+}
+
 /// Calculate Interquartile Range (IQR)
 #[napi]
 pub fn iqr(data: Vec<f64>) -> f64 {
-    let q1 = quantile(data.clone(), 0.25);
-    let q3 = quantile(data, 0.75);
-    q3 - q1
+    let mut sorted: Vec<f64> = data.into_iter().filter(|x| !x.is_nan()).collect();
+    sort_values(&mut sorted);
+    sorted_quantile(&sorted, 0.75) - sorted_quantile(&sorted, 0.25)
 }
 
 /// Calculate z-scores for all values in a dataset
@@ -114,6 +405,31 @@ pub fn z_scores_parallel(data: Vec<f64>) -> Vec<f64> {
     data.par_iter().map(|x| (x - m) / sd).collect()
 }
 
+/// Calculate z-scores after removing a seasonal pattern
+///
+/// Subtracts each seasonal position's median offset from the overall median
+/// before standardizing, so values are scored against their own position in
+/// the cycle (a typical Monday peak scores near zero). Falls back to plain
+/// z-scores without two full cycles of data.
+///
+/// # Arguments
+/// * `data` - Series to standardize
+/// * `period` - Length of the seasonal cycle (e.g. 7 for daily data)
+#[napi]
+pub fn seasonal_z_scores(data: Vec<f64>, period: u32) -> Vec<f64> {
+    let period = period as usize;
+    if period < 2 || data.len() < period * 2 {
+        return z_scores(data);
+    }
+
+    let overall = median(data.clone());
+    let offsets: Vec<f64> = (0..period)
+        .map(|p| median(data.iter().skip(p).step_by(period).copied().collect()) - overall)
+        .collect();
+    let adjusted: Vec<f64> = data.iter().enumerate().map(|(i, x)| x - offsets[i % period]).collect();
+    z_scores(adjusted)
+}
+
 /// Calculate moving average
 #[napi]
 pub fn moving_average(data: Vec<f64>, window_size: u32) -> Vec<f64> {
@@ -134,6 +450,52 @@ pub fn moving_average(data: Vec<f64>, window_size: u32) -> Vec<f64> {
     result
 }
 
+/// Calculate the sample standard deviation over a sliding window
+///
+/// Like `moving_average`, each output covers `window_size` consecutive
+/// values; with `step` > 1 only every `step`-th window is computed.
+///
+/// # Arguments
+/// * `data` - Series
+/// * `window_size` - Values per window
+/// * `step` - Distance between window starts (default 1)
+#[napi]
+pub fn rolling_std(data: Vec<f64>, window_size: u32, step: Option<u32>) -> Vec<f64> {
+    rolling(&data, window_size, step, |w| std_dev(w.to_vec()))
+}
+
+/// Calculate the minimum over a sliding window (see `rolling_std` for arguments)
+#[napi]
+pub fn rolling_min(data: Vec<f64>, window_size: u32, step: Option<u32>) -> Vec<f64> {
+    rolling(&data, window_size, step, |w| w.iter().copied().fold(f64::INFINITY, f64::min))
+}
+
+/// Calculate the maximum over a sliding window (see `rolling_std` for arguments)
+#[napi]
+pub fn rolling_max(data: Vec<f64>, window_size: u32, step: Option<u32>) -> Vec<f64> {
+    rolling(&data, window_size, step, |w| w.iter().copied().fold(f64::NEG_INFINITY, f64::max))
+}
+
+/// Calculate the median over a sliding window (see `rolling_std` for arguments)
+#[napi]
+pub fn rolling_median(data: Vec<f64>, window_size: u32, step: Option<u32>) -> Vec<f64> {
+    rolling(&data, window_size, step, |w| median(w.to_vec()))
+}
+
+/// Apply `stat` to every `step`-th window of `window_size` values, in parallel
+fn rolling<F>(data: &[f64], window_size: u32, step: Option<u32>, stat: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64 + Sync,
+{
+    let window = window_size as usize;
+    let step = step.unwrap_or(1).max(1) as usize;
+    if data.len() < window || window == 0 {
+        return vec![];
+    }
+    let starts: Vec<usize> = (0..=data.len() - window).step_by(step).collect();
+    starts.par_iter().map(|&s| stat(&data[s..s + window])).collect()
+}
+
 /// Calculate exponential moving average
 #[napi]
 pub fn exponential_moving_average(data: Vec<f64>, alpha: f64) -> Vec<f64> {
@@ -163,31 +525,25 @@ pub fn linear_regression(x: Vec<f64>, y: Vec<f64>) -> LinearRegressionResult {
         };
     }
 
-    let n = x.len() as f64;
-    let sum_x: f64 = x.iter().sum();
-    let sum_y: f64 = y.iter().sum();
-    let sum_xy: f64 = x.iter().zip(y.iter()).map(|(a, b)| a * b).sum();
-    let sum_x2: f64 = x.iter().map(|a| a * a).sum();
-    let _sum_y2: f64 = y.iter().map(|a| a * a).sum();
-
-    let denominator = n * sum_x2 - sum_x * sum_x;
-    if denominator == 0.0 {
+    // Centered, compensated sums stay accurate for large offsets such as timestamps
+    let mean_x = mean(x.clone());
+    let mean_y = mean(y.clone());
+    let sxx = stable_sum(x.iter().map(|a| (a - mean_x).powi(2)));
+    let sxy = stable_sum(x.iter().zip(&y).map(|(a, b)| (a - mean_x) * (b - mean_y)));
+    if sxx == 0.0 {
         return LinearRegressionResult {
             slope: 0.0,
-            intercept: mean(y),
+            intercept: mean_y,
             r_squared: 0.0,
         };
     }
 
-    let slope = (n * sum_xy - sum_x * sum_y) / denominator;
-    let intercept = (sum_y - slope * sum_x) / n;
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
 
     // Calculate R-squared
-    let y_mean = sum_y / n;
-    let ss_tot: f64 = y.iter().map(|yi| (yi - y_mean).powi(2)).sum();
-    let ss_res: f64 = x.iter().zip(y.iter())
-        .map(|(xi, yi)| (yi - (slope * xi + intercept)).powi(2))
-        .sum();
+    let ss_tot = stable_sum(y.iter().map(|yi| (yi - mean_y).powi(2)));
+    let ss_res = stable_sum(x.iter().zip(y.iter()).map(|(xi, yi)| (yi - (slope * xi + intercept)).powi(2)));
 
     let r_squared = if ss_tot != 0.0 { 1.0 - (ss_res / ss_tot) } else { 0.0 };
 
@@ -198,6 +554,50 @@ pub fn linear_regression(x: Vec<f64>, y: Vec<f64>) -> LinearRegressionResult {
     }
 }
 
+/// Weighted least-squares line fit
+///
+/// Minimizes `sum w (y - a - b x)^2`, so heavily weighted points (e.g. recent
+/// observations) dominate the slope. `r_squared` is the weighted coefficient
+/// of determination. Returns a zero fit when the lengths differ, a weight is
+/// negative, or the weights sum to zero.
+///
+/// # Arguments
+/// * `x` - Independent variable
+/// * `y` - Dependent variable
+/// * `weights` - Non-negative weight of each point
+#[napi]
+pub fn weighted_linear_regression(x: Vec<f64>, y: Vec<f64>, weights: Vec<f64>) -> LinearRegressionResult {
+    if x.len() != y.len() || !valid_weights(&x, &weights) {
+        return LinearRegressionResult {
+            slope: 0.0,
+            intercept: 0.0,
+            r_squared: 0.0,
+        };
+    }
+    let total = stable_sum(weights.iter().copied());
+    let weighted_mean = |v: &[f64]| stable_sum(v.iter().zip(&weights).map(|(a, w)| a * w)) / total;
+    let (mean_x, mean_y) = (weighted_mean(&x), weighted_mean(&y));
+    let sxx = stable_sum(x.iter().zip(&weights).map(|(a, w)| w * (a - mean_x).powi(2)));
+    let sxy = stable_sum(x.iter().zip(&y).zip(&weights).map(|((a, b), w)| w * (a - mean_x) * (b - mean_y)));
+    if sxx == 0.0 {
+        return LinearRegressionResult {
+            slope: 0.0,
+            intercept: mean_y,
+            r_squared: 0.0,
+        };
+    }
+
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let ss_tot = stable_sum(y.iter().zip(&weights).map(|(b, w)| w * (b - mean_y).powi(2)));
+    let ss_res = stable_sum(x.iter().zip(&y).zip(&weights).map(|((a, b), w)| w * (b - slope * a - intercept).powi(2)));
+    LinearRegressionResult {
+        slope,
+        intercept,
+        r_squared: if ss_tot != 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 },
+    }
+}
+
 #[napi(object)]
 #[derive(Debug, Clone)]
 pub struct LinearRegressionResult {
@@ -245,9 +645,362 @@ pub fn covariance(x: Vec<f64>, y: Vec<f64>) -> f64 {
         .sum::<f64>() / (x.len() - 1) as f64
 }
 
+/// Correlation with an explicit missing-value policy ("ignore" drops incomplete pairs)
+#[napi]
+pub fn correlation_with_options(x: Vec<f64>, y: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing_pairs(x, y, missing_policy(&options))?.map_or(f64::NAN, |(x, y)| correlation(x, y)))
+}
+
+/// Covariance with an explicit missing-value policy ("ignore" drops incomplete pairs)
+#[napi]
+pub fn covariance_with_options(x: Vec<f64>, y: Vec<f64>, options: Option<MissingValueOptions>) -> napi::Result<f64> {
+    Ok(drop_missing_pairs(x, y, missing_policy(&options))?.map_or(f64::NAN, |(x, y)| covariance(x, y)))
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BinnedStatistic {
+    /// Statistic of each bin (NaN for empty bins, except "count" and "sum" which give 0)
+    pub statistic: Vec<f64>,
+    /// Number of values in each bin
+    pub counts: Vec<u32>,
+    pub edges: Vec<f64>,
+}
+
+/// Statistic of `values` within bins of `x`
+///
+/// Bin `i` covers `[edges[i], edges[i + 1])`, with the last bin also
+/// including its right edge; points outside the edges or with NaN are
+/// dropped. Points are assigned to bins in parallel, so curves such as
+/// load vs turnaround time can be built from millions of orders in one call.
+/// Returns no bins when `edges` has fewer than two values, is not sorted, or
+/// the inputs differ in length.
+///
+/// # Arguments
+/// * `x` - Binning variable (e.g. daily load)
+/// * `values` - Values to aggregate (e.g. turnaround time)
+/// * `edges` - Ascending bin edges
+/// * `statistic` - "mean" (default), "median", "count" or "sum"
+#[napi]
+pub fn binned_statistic(x: Vec<f64>, values: Vec<f64>, edges: Vec<f64>, statistic: Option<String>) -> BinnedStatistic {
+    if x.len() != values.len() || edges.len() < 2 || !edges.windows(2).all(|w| w[0] < w[1]) {
+        return BinnedStatistic {
+            statistic: Vec::new(),
+            counts: Vec::new(),
+            edges,
+        };
+    }
+    let bins = edges.len() - 1;
+    let last = edges[bins];
+    let bin_of = |v: f64| -> Option<usize> {
+        if v == last {
+            return Some(bins - 1);
+        }
+        let i = edges.partition_point(|&e| e <= v);
+        (1..=bins).contains(&i).then(|| i - 1)
+    };
+
+    let statistic = statistic.unwrap_or_default();
+    let assigned: Vec<(usize, f64)> = x
+        .par_iter()
+        .zip(values.par_iter())
+        .filter(|(_, v)| !v.is_nan())
+        .filter_map(|(&xi, &v)| bin_of(xi).map(|b| (b, v)))
+        .collect();
+
+    let mut counts = vec![0u32; bins];
+    let mut sums = vec![0.0; bins];
+    let mut members: Vec<Vec<f64>> = if statistic == "median" { vec![Vec::new(); bins] } else { Vec::new() };
+    for &(b, v) in &assigned {
+        counts[b] += 1;
+        sums[b] += v;
+        if let Some(bucket) = members.get_mut(b) {
+            bucket.push(v);
+        }
+    }
+
+    let result = (0..bins)
+        .map(|b| match statistic.as_str() {
+            "count" => counts[b] as f64,
+            "sum" => sums[b],
+            _ if counts[b] == 0 => f64::NAN,
+            "median" => median(std::mem::take(&mut members[b])),
+            _ => sums[b] / counts[b] as f64,
+        })
+        .collect();
+    BinnedStatistic {
+        statistic: result,
+        counts,
+        edges,
+    }
+}
+
+/// Sample covariance matrix of several equal-length series
+///
+/// Entry `[i][j]` equals `covariance(series[i], series[j])`; pairs are computed
+/// in parallel. Returns an empty matrix when the series differ in length or
+/// have fewer than two observations.
+///
+/// # Arguments
+/// * `series` - One vector per variable, all observed at the same points
+#[napi]
+pub fn covariance_matrix(series: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let k = series.len();
+    let n = series.first().map_or(0, |s| s.len());
+    if n < 2 || series.iter().any(|s| s.len() != n) {
+        return Vec::new();
+    }
+
+    let centered: Vec<Vec<f64>> = series
+        .par_iter()
+        .map(|s| {
+            let m = s.iter().sum::<f64>() / n as f64;
+            s.iter().map(|v| v - m).collect()
+        })
+        .collect();
+    let upper: Vec<(usize, usize, f64)> = (0..k)
+        .flat_map(|i| (i..k).map(move |j| (i, j)))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(i, j)| {
+            let sum: f64 = centered[i].iter().zip(&centered[j]).map(|(a, b)| a * b).sum();
+            (i, j, sum / (n - 1) as f64)
+        })
+        .collect();
+
+    let mut matrix = vec![vec![0.0; k]; k];
+    for (i, j, c) in upper {
+        matrix[i][j] = c;
+        matrix[j][i] = c;
+    }
+    matrix
+}
+
+/// Most frequent value of a dataset
+///
+/// Values are compared exactly, so this is meant for discrete data; ties go
+/// to the smallest value. Use `detect_modes` for continuous data.
+#[napi]
+pub fn mode(data: Vec<f64>) -> f64 {
+    let mut sorted: Vec<f64> = data.into_iter().filter(|v| !v.is_nan()).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+        .chunk_by(|a, b| a == b)
+        .fold((0.0, 0), |best, run| if run.len() > best.1 { (run[0], run.len()) } else { best })
+        .0
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DensityMode {
+    /// Location of the density peak
+    pub location: f64,
+    /// Estimated density at the peak
+    pub density: f64,
+    /// Share of the distribution between the valleys around the peak
+    pub weight: f64,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ModeDetection {
+    /// Peaks from highest to lowest density
+    pub modes: Vec<DensityMode>,
+    /// Kernel bandwidth used
+    pub bandwidth: f64,
+    pub is_multimodal: bool,
+}
+
+/// Find the modes of a distribution with a Gaussian kernel density estimate
+///
+/// The density is evaluated on a 512-point grid; local maxima below 10% of
+/// the highest peak are treated as noise. A smaller bandwidth reveals more
+/// (and noisier) modes.
+///
+/// # Arguments
+/// * `data` - Sample values
+/// * `bandwidth` - Kernel standard deviation; 0 or less uses Silverman's rule of thumb
+#[napi]
+pub fn detect_modes(data: Vec<f64>, bandwidth: f64) -> ModeDetection {
+    let values: Vec<f64> = data.into_iter().filter(|v| v.is_finite()).collect();
+    let bandwidth = if bandwidth > 0.0 {
+        bandwidth
+    } else {
+        let spread = std_dev(values.clone()).min(iqr(values.clone()) / 1.34);
+        let spread = if spread > 0.0 { spread } else { std_dev(values.clone()) };
+        0.9 * spread * (values.len() as f64).powf(-0.2)
+    };
+    if values.len() < 2 || bandwidth <= 0.0 {
+        let modes = values
+            .first()
+            .map(|_| DensityMode { location: mode(values.clone()), density: f64::INFINITY, weight: 1.0 })
+            .into_iter()
+            .collect();
+        return ModeDetection { modes, bandwidth: 0.0, is_multimodal: false };
+    }
+
+    const GRID: usize = 512;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min) - 3.0 * bandwidth;
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max) + 3.0 * bandwidth;
+    let step = (max - min) / (GRID - 1) as f64;
+    let norm = 1.0 / (values.len() as f64 * bandwidth * (2.0 * std::f64::consts::PI).sqrt());
+    let grid: Vec<f64> = (0..GRID).map(|i| min + i as f64 * step).collect();
+    let density: Vec<f64> = grid
+        .par_iter()
+        .map(|&g| values.iter().map(|&v| (-0.5 * ((g - v) / bandwidth).powi(2)).exp()).sum::<f64>() * norm)
+        .collect();
+
+    let peaks: Vec<usize> = (1..GRID - 1).filter(|&i| density[i] > density[i - 1] && density[i] >= density[i + 1]).collect();
+    let highest = peaks.iter().map(|&i| density[i]).fold(0.0, f64::max);
+    let peaks: Vec<usize> = peaks.into_iter().filter(|&i| density[i] >= 0.1 * highest).collect();
+
+    // Each peak owns the mass between the lowest points separating it from its neighbors
+    let valley = |from: usize, to: usize| (from..=to).min_by(|&a, &b| density[a].total_cmp(&density[b])).unwrap_or(from);
+    let bounds: Vec<usize> = std::iter::once(0)
+        .chain(peaks.windows(2).map(|w| valley(w[0], w[1])))
+        .chain(std::iter::once(GRID - 1))
+        .collect();
+    let mass = |from: usize, to: usize| (from..to).map(|i| (density[i] + density[i + 1]) * step / 2.0).sum::<f64>();
+    let total = mass(0, GRID - 1);
+
+    let mut modes: Vec<DensityMode> = peaks
+        .iter()
+        .enumerate()
+        .map(|(p, &i)| DensityMode {
+            location: grid[i],
+            density: density[i],
+            weight: if total > 0.0 { mass(bounds[p], bounds[p + 1]) / total } else { 0.0 },
+        })
+        .collect();
+    modes.sort_by(|a, b| b.density.total_cmp(&a.density));
+
+    ModeDetection {
+        is_multimodal: modes.len() > 1,
+        modes,
+        bandwidth,
+    }
+}
+
+/// Gini coefficient of a non-negative dataset
+///
+/// 0 when every value is equal (e.g. work spread evenly across
+/// technicians), approaching 1 when one member holds everything. Returns 0
+/// for empty input or a zero total, and NaN for any negative or NaN value.
+#[napi]
+pub fn gini(data: Vec<f64>) -> f64 {
+    if data.iter().any(|x| x.is_nan() || *x < 0.0) {
+        return f64::NAN;
+    }
+    let mut sorted = data;
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    let total: f64 = sorted.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    // sum_i (2i - n - 1) x_(i) / (n * total), with 1-based ranks i
+    let weighted: f64 = sorted.iter().enumerate().map(|(i, x)| (2.0 * (i + 1) as f64 - n - 1.0) * x).sum();
+    weighted / (n * total)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LorenzCurve {
+    /// Cumulative share of members, from 0 to 1
+    pub population_share: Vec<f64>,
+    /// Cumulative share of the total held by the smallest members, from 0 to 1
+    pub value_share: Vec<f64>,
+}
+
+/// Lorenz curve of a non-negative dataset
+///
+/// Points start at (0, 0) and end at (1, 1); the further the curve sags below
+/// the diagonal, the more concentrated the values. Empty for invalid input
+/// (see `gini`).
+#[napi]
+pub fn lorenz_curve(data: Vec<f64>) -> LorenzCurve {
+    let total: f64 = data.iter().sum();
+    if data.iter().any(|x| x.is_nan() || *x < 0.0) || total <= 0.0 {
+        return LorenzCurve {
+            population_share: Vec::new(),
+            value_share: Vec::new(),
+        };
+    }
+    let mut sorted = data;
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    let mut population_share = vec![0.0];
+    let mut value_share = vec![0.0];
+    let mut running = 0.0;
+    for (i, x) in sorted.iter().enumerate() {
+        running += x;
+        population_share.push((i + 1) as f64 / n);
+        value_share.push(running / total);
+    }
+    LorenzCurve {
+        population_share,
+        value_share,
+    }
+}
+
 /// Descriptive statistics for a dataset
+///
+/// Moments and extremes come from a single pass and every quantile from a
+/// single sort, so this stays cheap on multi-million-element arrays.
 #[napi]
 pub fn describe(data: Vec<f64>) -> DescriptiveStats {
+    summarize(data, &[])
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct DescribeOptions {
+    /// Extra percentiles to report, in 0-100 (e.g. `[90, 95, 99]`)
+    pub percentiles: Option<Vec<f64>>,
+    /// How NaN values are handled: "ignore" (default), "propagate" (every
+    /// statistic except `count` becomes NaN) or "error"; other values are rejected
+    pub missing: Option<String>,
+}
+
+/// Descriptive statistics for a dataset, with extra percentiles
+///
+/// Fails for a percentile outside 0-100. Empty data reports every requested
+/// percentile as NaN.
+///
+/// # Arguments
+/// * `data` - Dataset to summarize
+/// * `options` - Additional percentiles and the missing-value policy
+#[napi]
+pub fn describe_with_options(data: Vec<f64>, options: Option<DescribeOptions>) -> napi::Result<DescriptiveStats> {
+    let options = options.unwrap_or_default();
+    let percentiles = options.percentiles.unwrap_or_default();
+    if let Some(p) = percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+        return Err(Error::new(Status::InvalidArg, format!("percentile {} is outside 0-100", p)));
+    }
+    let count = data.len();
+    Ok(match drop_missing(data, options.missing.as_deref())? {
+        Some(clean) => summarize(clean, &percentiles),
+        None => DescriptiveStats {
+            count: count as u32,
+            mean: f64::NAN,
+            std_dev: f64::NAN,
+            min: f64::NAN,
+            q1: f64::NAN,
+            median: f64::NAN,
+            q3: f64::NAN,
+            max: f64::NAN,
+            iqr: f64::NAN,
+            skewness: f64::NAN,
+            kurtosis: f64::NAN,
+            range: f64::NAN,
+            coefficient_of_variation: f64::NAN,
+            standard_error: f64::NAN,
+            percentiles: percentiles.iter().map(|&percentile| PercentileValue { percentile, value: f64::NAN }).collect(),
+        },
+    })
+}
+
+fn summarize(data: Vec<f64>, percentiles: &[f64]) -> DescriptiveStats {
     if data.is_empty() {
         return DescriptiveStats {
             count: 0,
@@ -261,33 +1014,51 @@ pub fn describe(data: Vec<f64>) -> DescriptiveStats {
             iqr: 0.0,
             skewness: 0.0,
             kurtosis: 0.0,
+            range: 0.0,
+            coefficient_of_variation: 0.0,
+            standard_error: 0.0,
+            percentiles: percentiles.iter().map(|&percentile| PercentileValue { percentile, value: f64::NAN }).collect(),
         };
     }
 
+    // One pass for the extremes and central moments (Pebay's online update)
+    let mut moments = Moments::default();
+    for &x in &data {
+        moments.push(x);
+    }
     let count = data.len();
-    let m = mean(data.clone());
-    let sd = std_dev(data.clone());
-    let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
-    let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-    let q1 = quantile(data.clone(), 0.25);
-    let med = median(data.clone());
-    let q3 = quantile(data.clone(), 0.75);
-
-    // Calculate skewness and kurtosis
     let n = count as f64;
+    let Moments { mean: m, m2, m3, m4, min, max, .. } = moments;
+    let sd = if count >= 2 { (m2 / (n - 1.0)).sqrt() } else { 0.0 };
+
     let skewness = if sd != 0.0 {
-        data.iter().map(|x| ((x - m) / sd).powi(3)).sum::<f64>() * n / ((n - 1.0) * (n - 2.0))
+        m3 / sd.powi(3) * n / ((n - 1.0) * (n - 2.0))
     } else {
         0.0
     };
 
     let kurtosis = if sd != 0.0 && n > 3.0 {
-        let excess = data.iter().map(|x| ((x - m) / sd).powi(4)).sum::<f64>() / n;
+        let excess = m4 / sd.powi(4) / n;
         excess - 3.0  // Excess kurtosis (normal = 0)
     } else {
         0.0
     };
 
+    // One sort serves every quantile
+    let mut sorted = data;
+    sorted.retain(|x| !x.is_nan());
+    sort_values(&mut sorted);
+    let q1 = sorted_quantile(&sorted, 0.25);
+    let med = sorted_quantile(&sorted, 0.5);
+    let q3 = sorted_quantile(&sorted, 0.75);
+    let percentiles = percentiles
+        .iter()
+        .map(|&percentile| PercentileValue {
+            percentile,
+            value: sorted_quantile(&sorted, percentile / 100.0),
+        })
+        .collect();
+
     DescriptiveStats {
         count: count as u32,
         mean: m,
@@ -300,9 +1071,106 @@ pub fn describe(data: Vec<f64>) -> DescriptiveStats {
         iqr: q3 - q1,
         skewness,
         kurtosis,
+        range: max - min,
+        coefficient_of_variation: if m != 0.0 { sd / m.abs() } else { 0.0 },
+        standard_error: sd / n.sqrt(),
+        percentiles,
+    }
+}
+
+/// Running count, mean, extremes and central moment sums `sum (x - mean)^k`
+struct Moments {
+    count: f64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for Moments {
+    fn default() -> Self {
+        Moments {
+            count: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        let previous = self.count;
+        self.count += 1.0;
+        let n = self.count;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let term = delta * delta_n * previous;
+        self.mean += delta_n;
+        self.m4 += term * delta_n * delta_n * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n * delta_n * self.m2 - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
     }
 }
 
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GroupStats {
+    pub key: String,
+    pub stats: DescriptiveStats,
+}
+
+/// Descriptive statistics per group in one call
+///
+/// Values are split by their group key and each group is summarized in
+/// parallel. Groups are returned in order of first appearance. Fails when
+/// `values` and `group_keys` differ in length.
+///
+/// # Arguments
+/// * `values` - Values to summarize
+/// * `group_keys` - Group key of each value (e.g. lab site)
+/// * `options` - Extra percentiles and missing-value policy, applied to every group
+#[napi]
+pub fn grouped_describe(values: Vec<f64>, group_keys: Vec<String>, options: Option<DescribeOptions>) -> napi::Result<Vec<GroupStats>> {
+    if values.len() != group_keys.len() {
+        return Err(Error::new(Status::InvalidArg, format!("{} values but {} group keys", values.len(), group_keys.len())));
+    }
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut groups: Vec<(&str, Vec<f64>)> = Vec::new();
+    for (value, key) in values.iter().zip(&group_keys) {
+        let slot = *index.entry(key.as_str()).or_insert_with(|| {
+            groups.push((key.as_str(), Vec::new()));
+            groups.len() - 1
+        });
+        groups[slot].1.push(*value);
+    }
+
+    groups
+        .into_par_iter()
+        .map(|(key, data)| {
+            Ok(GroupStats {
+                key: key.to_string(),
+                stats: describe_with_options(data, options.clone())?,
+            })
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PercentileValue {
+    /// Requested percentile (0-100)
+    pub percentile: f64,
+    pub value: f64,
+}
+
 #[napi(object)]
 #[derive(Debug, Clone)]
 pub struct DescriptiveStats {
@@ -317,6 +1185,168 @@ pub struct DescriptiveStats {
     pub iqr: f64,
     pub skewness: f64,
     pub kurtosis: f64,
+    /// max - min
+    pub range: f64,
+    /// std_dev / |mean| (0 when the mean is 0)
+    pub coefficient_of_variation: f64,
+    /// Standard error of the mean, std_dev / sqrt(count)
+    pub standard_error: f64,
+    /// Values at the requested extra percentiles (empty for `describe`)
+    pub percentiles: Vec<PercentileValue>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StationarityTestResult {
+    pub test_statistic: f64,
+    /// Approximate p-value of the test's null hypothesis
+    pub p_value: f64,
+    /// Critical values at the 1%, 5% and 10% levels
+    pub critical_values: Vec<f64>,
+    /// Conclusion at the 5% level
+    pub is_stationary: bool,
+    /// Augmentation lags (ADF, chosen by AIC) or Bartlett bandwidth (KPSS)
+    pub lags: u32,
+    /// Deterministic terms used: "constant" or "trend"
+    pub regression: String,
+}
+
+/// Augmented Dickey-Fuller unit-root test
+///
+/// The null hypothesis is a unit root (non-stationary, needs differencing);
+/// a small p-value means the series is stationary around a constant, or
+/// around a linear trend with `regression = "trend"`. Critical values follow
+/// MacKinnon (2010). Needs at least 10 finite values; otherwise the
+/// statistic and p-value are NaN.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `max_lag` - Largest augmentation lag considered (default `12 * (n / 100)^(1/4)`)
+/// * `regression` - "constant" (default) or "trend"
+#[napi]
+pub fn adf_test(data: Vec<f64>, max_lag: Option<u32>, regression: Option<String>) -> StationarityTestResult {
+    let trend = regression.as_deref() == Some("trend");
+    let n = data.len();
+    if n < 10 || data.iter().any(|v| !v.is_finite()) {
+        return invalid_stationarity_test(trend);
+    }
+
+    let default_lag = (12.0 * (n as f64 / 100.0).powf(0.25)) as u32;
+    let terms = if trend { AdfTerms::ConstantTrend } else { AdfTerms::Constant };
+    let (test_statistic, lags) = adf_statistic(&data, max_lag.unwrap_or(default_lag) as usize, terms);
+
+    // MacKinnon (2010) response surfaces, N = 1
+    let surfaces: [[f64; 4]; 3] = if trend {
+        [[-3.95877, -9.0531, -28.428, -134.155], [-3.41049, -4.3904, -9.036, -45.374], [-3.12705, -2.5856, -3.925, -22.380]]
+    } else {
+        [[-3.43035, -6.5393, -16.786, -79.433], [-2.86154, -2.8903, -4.234, -40.040], [-2.56677, -1.5384, -2.809, 0.0]]
+    };
+    let t = n as f64;
+    let critical_values: Vec<f64> = surfaces.iter().map(|c| c[0] + c[1] / t + c[2] / (t * t) + c[3] / (t * t * t)).collect();
+
+    StationarityTestResult {
+        test_statistic,
+        p_value: interpolate_p_value(test_statistic, &critical_values),
+        is_stationary: test_statistic < critical_values[1],
+        critical_values,
+        lags: lags as u32,
+        regression: if trend { "trend" } else { "constant" }.to_string(),
+    }
+}
+
+/// KPSS stationarity test
+///
+/// The complement of `adf_test`: the null hypothesis is stationarity (around
+/// a constant, or around a linear trend with `regression = "trend"`), so a
+/// small p-value means the series needs differencing or detrending. Running
+/// both tests separates clear cases from ambiguous ones. Critical values are
+/// from Kwiatkowski et al. (1992); the p-value is interpolated from that
+/// table and therefore limited to [0.01, 0.10]. Needs at least 10 finite
+/// values; otherwise the statistic and p-value are NaN.
+///
+/// # Arguments
+/// * `data` - Time series
+/// * `lags` - Bartlett-kernel bandwidth for the long-run variance (default `12 * (n / 100)^(1/4)`)
+/// * `regression` - "constant" (default) or "trend"
+#[napi]
+pub fn kpss_test(data: Vec<f64>, lags: Option<u32>, regression: Option<String>) -> StationarityTestResult {
+    let trend = regression.as_deref() == Some("trend");
+    let n = data.len();
+    if n < 10 || data.iter().any(|v| !v.is_finite()) {
+        return invalid_stationarity_test(trend);
+    }
+
+    let residuals: Vec<f64> = if trend {
+        let fit = linear_regression((0..n).map(|i| i as f64).collect(), data.clone());
+        data.iter().enumerate().map(|(i, v)| v - fit.intercept - fit.slope * i as f64).collect()
+    } else {
+        let level = mean(data.clone());
+        data.iter().map(|v| v - level).collect()
+    };
+
+    let lags = (lags.unwrap_or((12.0 * (n as f64 / 100.0).powf(0.25)) as u32) as usize).min(n - 1);
+    let mut long_run_variance: f64 = residuals.iter().map(|e| e * e).sum();
+    for lag in 1..=lags {
+        let weight = 1.0 - lag as f64 / (lags as f64 + 1.0);
+        let autocovariance: f64 = residuals[lag..].iter().zip(&residuals).map(|(a, b)| a * b).sum();
+        long_run_variance += 2.0 * weight * autocovariance;
+    }
+    long_run_variance /= n as f64;
+
+    let mut partial_sum = 0.0;
+    let eta: f64 = residuals
+        .iter()
+        .map(|e| {
+            partial_sum += e;
+            partial_sum * partial_sum
+        })
+        .sum::<f64>()
+        / (n * n) as f64;
+    let test_statistic = if long_run_variance > 0.0 { eta / long_run_variance } else { 0.0 };
+
+    // Kwiatkowski et al. (1992), Table 1: levels 10%, 5%, 2.5%, 1%
+    let levels = [0.10, 0.05, 0.025, 0.01];
+    let table = if trend { [0.119, 0.146, 0.176, 0.216] } else { [0.347, 0.463, 0.574, 0.739] };
+    let p_value = if test_statistic <= table[0] {
+        levels[0]
+    } else if test_statistic >= table[3] {
+        levels[3]
+    } else {
+        let i = table.iter().rposition(|&c| c <= test_statistic).unwrap_or(0);
+        let fraction = (test_statistic - table[i]) / (table[i + 1] - table[i]);
+        levels[i] + fraction * (levels[i + 1] - levels[i])
+    };
+
+    StationarityTestResult {
+        test_statistic,
+        p_value,
+        critical_values: vec![table[3], table[1], table[0]],
+        is_stationary: test_statistic < table[1],
+        lags: lags as u32,
+        regression: if trend { "trend" } else { "constant" }.to_string(),
+    }
+}
+
+fn invalid_stationarity_test(trend: bool) -> StationarityTestResult {
+    StationarityTestResult {
+        test_statistic: f64::NAN,
+        p_value: f64::NAN,
+        critical_values: Vec::new(),
+        is_stationary: false,
+        lags: 0,
+        regression: if trend { "trend" } else { "constant" }.to_string(),
+    }
+}
+
+/// Two-sided p-value of a Student's t statistic with `df` degrees of freedom
+pub(crate) fn t_p_value(t: f64, df: f64) -> f64 {
+    if !t.is_finite() {
+        return if t.is_nan() { 1.0 } else { 0.0 };
+    }
+    match StudentsT::new(0.0, 1.0, df.max(1e-9)) {
+        Ok(dist) => (2.0 * (1.0 - dist.cdf(t.abs()))).clamp(0.0, 1.0),
+        Err(_) => 1.0,
+    }
 }
 
 #[cfg(test)]
@@ -340,6 +1370,311 @@ mod tests {
         assert!((sd - 2.138).abs() < 0.01);
     }
 
+    #[test]
+    fn test_weighted_quantile() {
+        let data = vec![3.0, 1.0, 2.0, 5.0];
+        let weights = vec![1.0, 1.0, 2.0, 3.0];
+        // Sorted values 1, 2, 3, 5 sit at 0, 1/4, 3/4 and 1
+        assert_eq!(weighted_quantile(data.clone(), weights.clone(), 0.5), 2.5);
+        assert_eq!(weighted_quantile(data.clone(), weights.clone(), 0.875), 4.0);
+        // Scaling every weight, e.g. to sum to 1, changes nothing
+        let normalized: Vec<f64> = weights.iter().map(|w| w / 7.0).collect();
+        for q in [0.0, 0.1, 0.25, 0.5, 0.6, 0.9, 1.0] {
+            let counts = weighted_quantile(data.clone(), weights.clone(), q);
+            assert!((weighted_quantile(data.clone(), normalized.clone(), q) - counts).abs() < 1e-12);
+            assert!((weighted_quantile(data.clone(), vec![0.25; 4], q) - quantile(data.clone(), q)).abs() < 1e-12);
+        }
+        assert_eq!(weighted_quantile(vec![4.0], vec![0.3], 0.7), 4.0);
+        assert_eq!(weighted_quantile(data, vec![1.0, -1.0, 1.0, 1.0], 0.5), 0.0);
+    }
+
+    #[test]
+    fn test_ecdf() {
+        let data = vec![3.0, 1.0, 2.0, 2.0, f64::NAN];
+        let cdf = ecdf(data.clone());
+        assert_eq!(cdf.values, vec![1.0, 2.0, 3.0]);
+        assert_eq!(cdf.probabilities, vec![0.25, 0.75, 1.0]);
+        assert_eq!(ecdf_evaluate(data, vec![0.0, 2.0, 2.5, 9.0]), vec![0.0, 0.75, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_gini_and_lorenz_curve() {
+        assert_eq!(gini(vec![5.0, 5.0, 5.0, 5.0]), 0.0);
+        assert!((gini(vec![0.0, 0.0, 0.0, 10.0]) - 0.75).abs() < 1e-12);
+        assert!((gini(vec![1.0, 2.0, 3.0, 4.0]) - 0.25).abs() < 1e-12);
+        assert!(gini(vec![1.0, -1.0]).is_nan());
+        assert!(gini(vec![1.0, f64::NAN]).is_nan());
+
+        let curve = lorenz_curve(vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(curve.population_share, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(curve.value_share, vec![0.0, 0.1, 0.3, 0.6, 1.0]);
+        assert!(lorenz_curve(vec![]).value_share.is_empty());
+    }
+
+    #[test]
+    fn test_describe_matches_multi_pass() {
+        let mut rng = crate::sampling::Rng::new(8);
+        let data: Vec<f64> = (0..10_001).map(|_| 1e6 + (-(1.0 - rng.next_f64()).ln()) * 250.0).collect();
+        let stats = describe(data.clone());
+
+        let m = mean(data.clone());
+        let sd = std_dev(data.clone());
+        let n = data.len() as f64;
+        let skewness = data.iter().map(|x| ((x - m) / sd).powi(3)).sum::<f64>() * n / ((n - 1.0) * (n - 2.0));
+        let kurtosis = data.iter().map(|x| ((x - m) / sd).powi(4)).sum::<f64>() / n - 3.0;
+        assert!((stats.mean - m).abs() < 1e-6);
+        assert!((stats.std_dev - sd).abs() < 1e-9 * sd);
+        // Exponential noise: skewness near 2, excess kurtosis near 6
+        assert!((stats.skewness - skewness).abs() < 1e-8 && (stats.skewness - 2.0).abs() < 0.3);
+        assert!((stats.kurtosis - kurtosis).abs() < 1e-7);
+        assert_eq!(stats.median, median(data.clone()));
+        assert_eq!((stats.q1, stats.q3), (quantile(data.clone(), 0.25), quantile(data, 0.75)));
+    }
+
+    #[test]
+    fn test_describe_extended_fields() {
+        let data: Vec<f64> = (1..=100).map(|i| i as f64).collect();
+        let stats = describe(data.clone());
+        assert_eq!(stats.range, 99.0);
+        assert!((stats.coefficient_of_variation - stats.std_dev / 50.5).abs() < 1e-12);
+        assert!((stats.standard_error - stats.std_dev / 10.0).abs() < 1e-12);
+        assert!(stats.percentiles.is_empty());
+
+        let options = DescribeOptions {
+            percentiles: Some(vec![90.0, 99.0]),
+            ..Default::default()
+        };
+        let stats = describe_with_options(data.clone(), Some(options.clone())).unwrap();
+        assert_eq!(stats.percentiles.len(), 2);
+        assert!((stats.percentiles[0].value - 90.1).abs() < 1e-9);
+        assert_eq!(stats.percentiles[1].percentile, 99.0);
+        let empty = describe_with_options(vec![], Some(options)).unwrap();
+        assert_eq!(empty.percentiles.len(), 2);
+        assert!(empty.percentiles.iter().all(|p| p.value.is_nan()));
+        let out_of_range = DescribeOptions { percentiles: Some(vec![150.0]), ..Default::default() };
+        assert!(describe_with_options(data, Some(out_of_range)).is_err());
+    }
+
+    #[test]
+    fn test_missing_value_policies() {
+        let data = vec![1.0, f64::NAN, 3.0];
+        let policy = |p: &str| Some(MissingValueOptions { missing: Some(p.to_string()) });
+        assert_eq!(mean_with_options(data.clone(), None).unwrap(), 2.0);
+        assert!(mean_with_options(data.clone(), policy("propagate")).unwrap().is_nan());
+        assert!(std_dev_with_options(data.clone(), policy("error")).is_err());
+        assert_eq!(median_with_options(vec![3.0, 1.0, 2.0], policy("error")).unwrap(), 2.0);
+        assert!(mean_with_options(vec![1.0, 2.0], policy("Error")).is_err());
+        assert!(mean_with_options(data.clone(), policy("raise")).is_err());
+
+        let x = vec![1.0, 2.0, f64::NAN, 4.0];
+        let y = vec![2.0, 4.0, 100.0, 8.0];
+        assert!((correlation_with_options(x.clone(), y.clone(), None).unwrap() - 1.0).abs() < 1e-12);
+        assert!(covariance_with_options(x.clone(), y, policy("propagate")).unwrap().is_nan());
+        assert!(correlation_with_options(x.clone(), x.clone(), policy("drop")).is_err());
+        // The policy applies even when the lengths differ
+        assert!(correlation_with_options(x.clone(), vec![1.0], policy("error")).is_err());
+        assert!(covariance_with_options(x.clone(), vec![1.0], policy("propagate")).unwrap().is_nan());
+
+        let stats = describe_with_options(data.clone(), None).unwrap();
+        assert_eq!((stats.count, stats.max), (2, 3.0));
+        let propagated = describe_with_options(data.clone(), Some(DescribeOptions {
+            missing: Some("propagate".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(propagated.count, 3);
+        assert!(propagated.mean.is_nan() && propagated.min.is_nan());
+    }
+
+    #[test]
+    fn test_grouped_describe() {
+        let values = vec![1.0, 10.0, 2.0, 20.0, 3.0, f64::NAN];
+        let keys: Vec<String> = ["a", "b", "a", "b", "a", "b"].iter().map(|k| k.to_string()).collect();
+        assert!(grouped_describe(values[1..].to_vec(), keys.clone(), None).is_err());
+        let groups = grouped_describe(values.clone(), keys.clone(), None).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].key.as_str(), groups[0].stats.count, groups[0].stats.mean), ("a", 3, 2.0));
+        assert_eq!((groups[1].key.as_str(), groups[1].stats.count, groups[1].stats.mean), ("b", 2, 15.0));
+
+        let strict = DescribeOptions { missing: Some("error".to_string()), ..Default::default() };
+        assert!(grouped_describe(values, keys, Some(strict)).is_err());
+    }
+
+    #[test]
+    fn test_percentile_ranks() {
+        let data = vec![1.0, 2.0, 3.0, 3.0, 4.0, f64::NAN];
+        assert_eq!(percentile_rank(data.clone(), 3.0, None), 60.0);
+        assert_eq!(percentile_rank(data.clone(), 3.0, Some("weak".to_string())), 80.0);
+        assert_eq!(percentile_rank(data.clone(), 3.0, Some("strict".to_string())), 40.0);
+        let ranks = percentile_ranks(data, vec![0.0, 10.0, 2.5], None);
+        assert_eq!(ranks, vec![0.0, 100.0, 40.0]);
+        assert!(percentile_rank(vec![], 1.0, None).is_nan());
+    }
+
+    #[test]
+    fn test_binned_statistic() {
+        let x = vec![0.5, 1.5, 1.7, 2.0, 3.0, -1.0, 2.5];
+        let values = vec![10.0, 20.0, 40.0, 5.0, 7.0, 99.0, f64::NAN];
+        let edges = vec![0.0, 1.0, 2.0, 3.0];
+        let means = binned_statistic(x.clone(), values.clone(), edges.clone(), None);
+        assert_eq!(means.counts, vec![1, 2, 2]);
+        assert_eq!(means.statistic, vec![10.0, 30.0, 6.0]);
+        let sums = binned_statistic(x.clone(), values.clone(), edges.clone(), Some("sum".to_string()));
+        assert_eq!(sums.statistic, vec![10.0, 60.0, 12.0]);
+        let medians = binned_statistic(x.clone(), values.clone(), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0], Some("median".to_string()));
+        assert_eq!(medians.statistic[1], 30.0);
+        assert_eq!(medians.statistic[3], 7.0);
+        assert!(medians.statistic[4].is_nan());
+        assert!(binned_statistic(x, values, vec![1.0, 0.0], None).statistic.is_empty());
+    }
+
+    #[test]
+    fn test_covariance_matrix() {
+        let a = vec![1.0, 2.0, 3.0, 4.0];
+        let b = vec![2.0, 4.0, 6.0, 9.0];
+        let c = vec![4.0, 3.0, 2.0, 1.0];
+        let matrix = covariance_matrix(vec![a.clone(), b.clone(), c.clone()]);
+        assert!((matrix[0][1] - covariance(a.clone(), b.clone())).abs() < 1e-12);
+        assert_eq!(matrix[1][0], matrix[0][1]);
+        assert!((matrix[0][0] - variance(a.clone())).abs() < 1e-12);
+        assert!((matrix[2][0] + variance(c)).abs() < 1e-12);
+        assert!(covariance_matrix(vec![a, vec![1.0]]).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_mean_and_std_dev() {
+        let data = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert!((weighted_mean(data.clone(), vec![1.0; 8]) - mean(data.clone())).abs() < 1e-12);
+        assert!((weighted_std_dev(data.clone(), vec![2.5; 8]) - std_dev(data.clone())).abs() < 1e-12);
+
+        assert!((weighted_mean(vec![10.0, 20.0], vec![1.0, 3.0]) - 17.5).abs() < 1e-12);
+        // Integer weights act like repeated observations in the mean
+        assert!((weighted_mean(vec![1.0, 5.0], vec![3.0, 1.0]) - mean(vec![1.0, 1.0, 1.0, 5.0])).abs() < 1e-12);
+        assert_eq!(weighted_mean(data.clone(), vec![1.0, 2.0]), 0.0);
+        assert_eq!(weighted_std_dev(data, vec![-1.0; 8]), 0.0);
+    }
+
+    #[test]
+    fn test_mode_and_detect_modes() {
+        assert_eq!(mode(vec![3.0, 1.0, 3.0, 2.0, 1.0, 3.0]), 3.0);
+        assert_eq!(mode(vec![2.0, 1.0, 2.0, 1.0]), 1.0);
+        assert_eq!(mode(vec![]), 0.0);
+
+        // Rush orders around 4h, standard orders around 24h (twice as many)
+        let mut turnaround: Vec<f64> = (0..100).map(|i| 4.0 + (i % 10) as f64 * 0.2).collect();
+        turnaround.extend((0..200).map(|i| 24.0 + (i % 20) as f64 * 0.3));
+        let result = detect_modes(turnaround, 0.0);
+        assert!(result.is_multimodal);
+        assert_eq!(result.modes.len(), 2);
+        assert!((result.modes[0].location - 27.0).abs() < 2.0);
+        assert!((result.modes[1].location - 5.0).abs() < 2.0);
+        assert!((result.modes[0].weight - 2.0 / 3.0).abs() < 0.05);
+
+        let unimodal = detect_modes((0..200).map(|i| ((i * 37) % 100) as f64 / 10.0).map(|v: f64| v * v / 10.0).collect(), 3.0);
+        assert!(!unimodal.is_multimodal);
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        let data = vec![4.0, 1.0, 7.0, 3.0, 9.0, 2.0, 8.0];
+        assert_eq!(rolling_min(data.clone(), 3, None), vec![1.0, 1.0, 3.0, 2.0, 2.0]);
+        assert_eq!(rolling_max(data.clone(), 3, None), vec![7.0, 7.0, 9.0, 9.0, 9.0]);
+        assert_eq!(rolling_median(data.clone(), 3, None), vec![4.0, 3.0, 7.0, 3.0, 8.0]);
+        assert_eq!(rolling_median(data.clone(), 3, Some(2)), vec![4.0, 7.0, 8.0]);
+        assert!((rolling_std(data.clone(), 7, None)[0] - std_dev(data.clone())).abs() < 1e-12);
+        assert_eq!(rolling_std(data.clone(), 3, Some(3)).len(), 2);
+        assert!(rolling_max(data, 8, None).is_empty());
+    }
+
+    #[test]
+    fn test_seasonal_z_scores() {
+        // Weekly pattern with a big regular Monday peak and one real outlier on a Thursday
+        let mut data: Vec<f64> = (0..56).map(|i| if i % 7 == 0 { 200.0 } else { 100.0 + (i % 3) as f64 }).collect();
+        data[31] = 140.0;
+
+        let plain = z_scores(data.clone());
+        let seasonal = seasonal_z_scores(data, 7);
+        assert!(plain[0].abs() > plain[31].abs());
+        assert!(seasonal[0].abs() < 1.0);
+        assert!(seasonal[31] > 3.0);
+        assert_eq!(seasonal_z_scores(vec![1.0, 2.0, 3.0], 7), z_scores(vec![1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn test_parallel_sort_quantiles() {
+        // Above the threshold the parallel sort must give the same order statistics
+        let n = PARALLEL_SORT_THRESHOLD + 1;
+        let data: Vec<f64> = (0..n).map(|i| ((i * 7919) % n) as f64).collect();
+        assert_eq!(median(data.clone()), (n / 2) as f64);
+        assert_eq!(quantile(data.clone(), 0.25), (n - 1) as f64 * 0.25);
+        assert_eq!(iqr(data), (n - 1) as f64 * 0.5);
+        assert_eq!(iqr(vec![f64::NAN, 1.0, 2.0, 3.0, 4.0, 5.0]), 2.0);
+    }
+
+    #[test]
+    fn test_stable_summation() {
+        // 1e16 + 1 + ... + 1 - 1e16: naive summation loses every 1
+        let mut values = vec![1e16];
+        values.extend(std::iter::repeat_n(1.0, 1000));
+        values.push(-1e16);
+        assert_eq!(stable_sum(values.iter().copied()), 1000.0);
+
+        // Large offset with a small spread: variance must not drift
+        let readings: Vec<f64> = (0..1_000_000).map(|i| 1e9 + (i % 10) as f64 * 0.1).collect();
+        assert!((mean(readings.clone()) - (1e9 + 0.45)).abs() < 1e-6);
+        assert!((variance(readings.clone()) - 0.0825 * 1_000_000.0 / 999_999.0).abs() < 1e-7);
+        assert_eq!(variance(vec![2.0; 5]), 0.0);
+        assert!(variance(vec![1.0, f64::NAN, 3.0]).is_nan());
+        assert!(std_dev(vec![1.0, f64::INFINITY]).is_nan());
+
+        // Epoch-second timestamps as x
+        let x: Vec<f64> = (0..1000).map(|i| 1.7e9 + 86_400.0 * i as f64).collect();
+        let y: Vec<f64> = (0..1000).map(|i| 5.0 + 0.25 * i as f64).collect();
+        let fit = linear_regression(x, y);
+        assert!((fit.slope - 0.25 / 86_400.0).abs() < 1e-15 && (fit.r_squared - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_linear_regression() {
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = vec![1.0, 3.0, 5.0, 7.0, 9.0];
+        let equal = weighted_linear_regression(x.clone(), y.clone(), vec![2.0; 5]);
+        assert!((equal.slope - 2.0).abs() < 1e-12 && (equal.intercept - 1.0).abs() < 1e-12);
+
+        // Regime change: only the last three points carry weight
+        let shifted = vec![1.0, 3.0, 5.0, 4.0, 3.0];
+        let recent = weighted_linear_regression(x.clone(), shifted, vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert!((recent.slope + 1.0).abs() < 1e-12);
+        assert_eq!(weighted_linear_regression(x, y, vec![1.0; 4]).slope, 0.0);
+    }
+
+    #[test]
+    fn test_stationarity_tests() {
+        let mut rng = crate::sampling::Rng::new(11);
+        let noise: Vec<f64> = (0..300).map(|_| rng.next_f64() - 0.5).collect();
+        let walk: Vec<f64> = noise.iter().scan(0.0, |level, e| {
+            *level += e;
+            Some(*level)
+        }).collect();
+
+        let adf_noise = adf_test(noise.clone(), None, None);
+        assert!(adf_noise.is_stationary && adf_noise.p_value < 0.01);
+        assert!((adf_noise.critical_values[1] + 2.8717).abs() < 1e-3);
+        assert!(!adf_test(walk.clone(), None, None).is_stationary);
+
+        let kpss_noise = kpss_test(noise.clone(), None, None);
+        assert!(kpss_noise.is_stationary && kpss_noise.p_value > 0.05);
+        let kpss_walk = kpss_test(walk, None, None);
+        assert!(!kpss_walk.is_stationary && kpss_walk.p_value < 0.05);
+
+        // A deterministic trend is stationary once the trend is allowed for
+        let trended: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 0.05 * i as f64 + e).collect();
+        assert!(!kpss_test(trended.clone(), None, None).is_stationary);
+        assert!(kpss_test(trended.clone(), None, Some("trend".to_string())).is_stationary);
+        assert!(adf_test(trended, None, Some("trend".to_string())).is_stationary);
+        assert!(adf_test(vec![1.0; 5], None, None).p_value.is_nan());
+    }
+
     #[test]
     fn test_linear_regression() {
         let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
@@ -0,0 +1,196 @@
+//! Temporal Hierarchy Forecasting
+//!
+//! Daily, weekly and monthly forecasts that are guaranteed to agree with each
+//! other (every weekly/monthly value is the sum of its daily values):
+//! - Bottom-up: forecast daily and aggregate
+//! - MAPA-style: forecast at every aggregation level, disaggregate to daily
+//!   and combine, then aggregate the reconciled dailies
+
+use napi::{Error, Status};
+use napi_derive::napi;
+
+use crate::forecasting::{predict_next, ForecastResult};
+
+const WEEK_LENGTH: usize = 7;
+const DEFAULT_MONTH_LENGTH: usize = 28;
+
+/// Produce mutually consistent daily, weekly and monthly forecasts
+///
+/// Fails for a `method` other than "bottom_up" or "mapa".
+///
+/// # Arguments
+/// * `data` - Daily history
+/// * `horizon_days` - Number of future days to forecast
+/// * `season_length` - Daily seasonal cycle (e.g., 7 for weekly)
+/// * `method` - "bottom_up" or "mapa" (default)
+/// * `month_length` - Days per monthly bucket (default 28, i.e. four weeks)
+#[napi]
+pub fn predict_temporal_hierarchy(
+    data: Vec<f64>,
+    horizon_days: u32,
+    season_length: u32,
+    method: Option<String>,
+    month_length: Option<u32>,
+) -> napi::Result<TemporalForecast> {
+    let method = match method.as_deref() {
+        None | Some("mapa") => "mapa",
+        Some("bottom_up") => "bottom_up",
+        Some(other) => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("unknown method \"{}\"; expected \"bottom_up\" or \"mapa\"", other),
+            ))
+        }
+    };
+    let horizon = horizon_days as usize;
+    let month_len = month_length.map(|m| m as usize).filter(|&m| m > 0).unwrap_or(DEFAULT_MONTH_LENGTH);
+
+    let base = predict_next(data.clone(), horizon_days, season_length);
+    if base.is_empty() || horizon == 0 {
+        return Ok(TemporalForecast {
+            daily: vec![],
+            weekly: vec![],
+            monthly: vec![],
+            method: method.to_string(),
+        });
+    }
+
+    let base_values: Vec<f64> = base.iter().map(|f| f.predicted_value).collect();
+    let margins: Vec<f64> = base.iter().map(|f| f.upper_bound - f.predicted_value).collect();
+
+    let daily_values = match method {
+        "bottom_up" => base_values,
+        _ => {
+            let mut levels = vec![base_values.clone()];
+            for level in [WEEK_LENGTH, month_len] {
+                if let Some(disaggregated) = forecast_level(&data, &base_values, level, season_length as usize) {
+                    levels.push(disaggregated);
+                }
+            }
+            (0..horizon)
+                .map(|t| levels.iter().map(|l| l[t]).sum::<f64>() / levels.len() as f64)
+                .collect()
+        }
+    };
+
+    Ok(TemporalForecast {
+        daily: aggregate_buckets(&daily_values, &margins, 1),
+        weekly: aggregate_buckets(&daily_values, &margins, WEEK_LENGTH),
+        monthly: aggregate_buckets(&daily_values, &margins, month_len),
+        method: method.to_string(),
+    })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TemporalForecast {
+    pub daily: Vec<AggregatedForecast>,
+    pub weekly: Vec<AggregatedForecast>,
+    pub monthly: Vec<AggregatedForecast>,
+    pub method: String,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct AggregatedForecast {
+    pub start_step: u32,
+    pub length: u32,
+    pub predicted_value: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+/// Forecast history aggregated into `level`-day buckets and spread the
+/// bucket totals back over the horizon using the daily base forecast shape
+fn forecast_level(data: &[f64], base: &[f64], level: usize, season_length: usize) -> Option<Vec<f64>> {
+    // Align buckets to the end of the history so the last bucket is complete
+    let offset = data.len() % level;
+    let aggregated: Vec<f64> = data[offset..].chunks_exact(level).map(|c| c.iter().sum()).collect();
+    if aggregated.len() < 2 {
+        return None;
+    }
+
+    let steps = base.len().div_ceil(level);
+    let level_season = if season_length > level && season_length.is_multiple_of(level) {
+        (season_length / level) as u32
+    } else {
+        1
+    };
+    let forecasts: Vec<ForecastResult> = predict_next(aggregated, steps as u32, level_season);
+
+    let mut disaggregated = vec![0.0; base.len()];
+    for (bucket, forecast) in forecasts.iter().enumerate() {
+        let start = bucket * level;
+        let end = (start + level).min(base.len());
+        let base_total: f64 = base[start..end].iter().sum();
+        // A trailing partial bucket only receives its share of the total
+        let bucket_total = forecast.predicted_value * (end - start) as f64 / level as f64;
+
+        for t in start..end {
+            disaggregated[t] = if base_total > 0.0 {
+                bucket_total * base[t] / base_total
+            } else {
+                bucket_total / (end - start) as f64
+            };
+        }
+    }
+
+    Some(disaggregated)
+}
+
+/// Sum daily values into consecutive buckets, combining interval margins
+/// under an independence assumption
+fn aggregate_buckets(values: &[f64], margins: &[f64], length: usize) -> Vec<AggregatedForecast> {
+    values
+        .chunks(length)
+        .zip(margins.chunks(length))
+        .enumerate()
+        .map(|(i, (v, m))| {
+            let total: f64 = v.iter().sum();
+            let margin = m.iter().map(|x| x * x).sum::<f64>().sqrt();
+            AggregatedForecast {
+                start_step: (i * length) as u32,
+                length: v.len() as u32,
+                predicted_value: total,
+                lower_bound: (total - margin).max(0.0),
+                upper_bound: total + margin,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekly_data() -> Vec<f64> {
+        (0..84).map(|i| 100.0 + (i as f64 * 0.3) + ((i % 7) as f64 * 4.0)).collect()
+    }
+
+    #[test]
+    fn test_weekly_equals_sum_of_dailies() {
+        for method in ["bottom_up", "mapa"] {
+            let result = predict_temporal_hierarchy(weekly_data(), 28, 7, Some(method.to_string()), None).unwrap();
+            assert_eq!(result.daily.len(), 28);
+            assert_eq!(result.weekly.len(), 4);
+            assert_eq!(result.monthly.len(), 1);
+
+            for (w, week) in result.weekly.iter().enumerate() {
+                let daily_sum: f64 = result.daily[w * 7..(w + 1) * 7].iter().map(|d| d.predicted_value).sum();
+                assert!((week.predicted_value - daily_sum).abs() < 1e-6);
+            }
+            let weekly_sum: f64 = result.weekly.iter().map(|w| w.predicted_value).sum();
+            assert!((result.monthly[0].predicted_value - weekly_sum).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_partial_buckets() {
+        let result = predict_temporal_hierarchy(weekly_data(), 10, 7, None, Some(30)).unwrap();
+        assert_eq!(result.weekly.len(), 2);
+        assert_eq!(result.weekly[1].length, 3);
+        assert_eq!(result.monthly[0].length, 10);
+        assert_eq!(result.method, "mapa");
+        assert!(predict_temporal_hierarchy(weekly_data(), 10, 7, Some("top_down".to_string()), None).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+//! Data Transforms
+//!
+//! Reshape skewed or outlier-prone data before modelling:
+//! - Box-Cox and Yeo-Johnson power transforms with maximum-likelihood lambda
+//! - Inverse transforms back to the original scale
+//! - Winsorizing and clipping of extreme values
+
+use napi::{Error, Status};
+use napi_derive::napi;
+
+use crate::statistics::quantile;
+
+/// Lambda search interval for maximum-likelihood estimation
+const LAMBDA_RANGE: (f64, f64) = (-5.0, 5.0);
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PowerTransformResult {
+    /// Transformed values
+    pub values: Vec<f64>,
+    /// Lambda used (given or estimated)
+    pub lambda: f64,
+    /// Profile log-likelihood of the normal model at `lambda`
+    pub log_likelihood: f64,
+}
+
+/// Box-Cox power transform
+///
+/// `(x^lambda - 1) / lambda`, or `ln x` when lambda is 0. With no lambda
+/// given, the one that makes the result most nearly normal (maximum
+/// likelihood) is chosen, so right-skewed volumes become symmetric enough
+/// for z-score thresholds and Gaussian forecast intervals. Requires strictly
+/// positive data; otherwise returns no values and a NaN lambda.
+///
+/// # Arguments
+/// * `data` - Positive values to transform
+/// * `lambda` - Fixed lambda (default: maximum-likelihood estimate)
+#[napi]
+pub fn box_cox(data: Vec<f64>, lambda: Option<f64>) -> PowerTransformResult {
+    if data.is_empty() || data.iter().any(|x| !(x.is_finite() && *x > 0.0)) {
+        return PowerTransformResult {
+            values: Vec::new(),
+            lambda: f64::NAN,
+            log_likelihood: f64::NAN,
+        };
+    }
+    let log_sum: f64 = data.iter().map(|x| x.ln()).sum();
+    let likelihood = |l: f64| {
+        let values: Vec<f64> = data.iter().map(|&x| box_cox_value(x, l)).collect();
+        normal_profile_likelihood(&values) + (l - 1.0) * log_sum
+    };
+    let lambda = lambda.unwrap_or_else(|| maximize(likelihood));
+    PowerTransformResult {
+        values: data.iter().map(|&x| box_cox_value(x, lambda)).collect(),
+        lambda,
+        log_likelihood: likelihood(lambda),
+    }
+}
+
+/// Inverse of `box_cox` for a given lambda
+///
+/// Values outside the transform's range (possible for forecasts) give NaN.
+#[napi]
+pub fn inverse_box_cox(values: Vec<f64>, lambda: f64) -> Vec<f64> {
+    values
+        .iter()
+        .map(|&y| if lambda.abs() < 1e-12 { y.exp() } else { (lambda * y + 1.0).powf(1.0 / lambda) })
+        .collect()
+}
+
+/// Yeo-Johnson power transform
+///
+/// Extends Box-Cox to zero and negative values. With no lambda given, the
+/// maximum-likelihood lambda is estimated as for `box_cox`.
+///
+/// # Arguments
+/// * `data` - Finite values to transform
+/// * `lambda` - Fixed lambda (default: maximum-likelihood estimate)
+#[napi]
+pub fn yeo_johnson(data: Vec<f64>, lambda: Option<f64>) -> PowerTransformResult {
+    if data.is_empty() || data.iter().any(|x| !x.is_finite()) {
+        return PowerTransformResult {
+            values: Vec::new(),
+            lambda: f64::NAN,
+            log_likelihood: f64::NAN,
+        };
+    }
+    let log_sum: f64 = data.iter().map(|x| x.signum() * x.abs().ln_1p()).sum();
+    let likelihood = |l: f64| {
+        let values: Vec<f64> = data.iter().map(|&x| yeo_johnson_value(x, l)).collect();
+        normal_profile_likelihood(&values) + (l - 1.0) * log_sum
+    };
+    let lambda = lambda.unwrap_or_else(|| maximize(likelihood));
+    PowerTransformResult {
+        values: data.iter().map(|&x| yeo_johnson_value(x, lambda)).collect(),
+        lambda,
+        log_likelihood: likelihood(lambda),
+    }
+}
+
+/// Inverse of `yeo_johnson` for a given lambda
+#[napi]
+pub fn inverse_yeo_johnson(values: Vec<f64>, lambda: f64) -> Vec<f64> {
+    values
+        .iter()
+        .map(|&y| {
+            if y >= 0.0 {
+                if lambda.abs() < 1e-12 { y.exp_m1() } else { (lambda * y + 1.0).powf(1.0 / lambda) - 1.0 }
+            } else if (lambda - 2.0).abs() < 1e-12 {
+                -(-y).exp_m1()
+            } else {
+                1.0 - (1.0 - (2.0 - lambda) * y).powf(1.0 / (2.0 - lambda))
+            }
+        })
+        .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ClippedValues {
+    /// Copy of the data with out-of-range values replaced by the nearest bound
+    pub values: Vec<f64>,
+    /// Indices of the values that were changed
+    pub modified_indices: Vec<u32>,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Winsorize: pull values beyond the given quantiles back to those quantiles
+///
+/// Tames spikes before smoothing (e.g. `holt_winters`) while keeping the
+/// series length and timing intact. NaN values are left as they are.
+/// Fails when `lower_q` is above `upper_q`.
+///
+/// # Arguments
+/// * `data` - Values to clean
+/// * `lower_q` - Lower quantile bound in [0, 1] (e.g. 0.01)
+/// * `upper_q` - Upper quantile bound in [0, 1] (e.g. 0.99)
+#[napi]
+pub fn winsorize(data: Vec<f64>, lower_q: f64, upper_q: f64) -> napi::Result<ClippedValues> {
+    if lower_q > upper_q {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("lower quantile {} is above upper quantile {}", lower_q, upper_q),
+        ));
+    }
+    let lower = quantile(data.clone(), lower_q.clamp(0.0, 1.0));
+    let upper = quantile(data.clone(), upper_q.clamp(0.0, 1.0));
+    clip(data, lower, upper)
+}
+
+/// Clip values to `[min, max]`, reporting which points were changed
+///
+/// NaN values are left as they are. Fails when `min` is above `max`.
+#[napi]
+pub fn clip(data: Vec<f64>, min: f64, max: f64) -> napi::Result<ClippedValues> {
+    if min > max {
+        return Err(Error::new(Status::InvalidArg, format!("min {} is above max {}", min, max)));
+    }
+    let mut modified_indices = Vec::new();
+    let values = data
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let clipped = if v < min { min } else if v > max { max } else { v };
+            if clipped != v && !v.is_nan() {
+                modified_indices.push(i as u32);
+            }
+            clipped
+        })
+        .collect();
+    Ok(ClippedValues {
+        values,
+        modified_indices,
+        lower: min,
+        upper: max,
+    })
+}
+
+fn box_cox_value(x: f64, lambda: f64) -> f64 {
+    if lambda.abs() < 1e-12 { x.ln() } else { (x.powf(lambda) - 1.0) / lambda }
+}
+
+fn yeo_johnson_value(x: f64, lambda: f64) -> f64 {
+    if x >= 0.0 {
+        if lambda.abs() < 1e-12 { x.ln_1p() } else { ((x + 1.0).powf(lambda) - 1.0) / lambda }
+    } else if (lambda - 2.0).abs() < 1e-12 {
+        -(-x).ln_1p()
+    } else {
+        -((1.0 - x).powf(2.0 - lambda) - 1.0) / (2.0 - lambda)
+    }
+}
+
+/// `-(n/2) ln(variance)`: the normal log-likelihood up to constants, maximized over mean and variance
+fn normal_profile_likelihood(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    if variance > 0.0 { -0.5 * n * variance.ln() } else { f64::NEG_INFINITY }
+}
+
+/// Golden-section search for the maximizing lambda within `LAMBDA_RANGE`
+fn maximize<F: Fn(f64) -> f64>(f: F) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = LAMBDA_RANGE;
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+    while b - a > 1e-8 {
+        if fc >= fd {
+            b = d;
+            (d, fd) = (c, fc);
+            c = b - ratio * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            (c, fc) = (d, fd);
+            d = a + ratio * (b - a);
+            fd = f(d);
+        }
+    }
+    (a + b) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_cox() {
+        // Log-normal data: the log (lambda 0) makes it symmetric
+        let data: Vec<f64> = (-50..=50).map(|i| (i as f64 / 20.0).exp()).collect();
+        let result = box_cox(data.clone(), None);
+        assert!(result.lambda.abs() < 0.05);
+        let restored = inverse_box_cox(result.values, result.lambda);
+        assert!(restored.iter().zip(&data).all(|(a, b)| (a - b).abs() < 1e-9 * b));
+
+        let fixed = box_cox(vec![1.0, 4.0, 9.0], Some(0.5));
+        assert_eq!(fixed.values, vec![0.0, 2.0, 4.0]);
+        assert!(box_cox(vec![1.0, 0.0], None).lambda.is_nan());
+    }
+
+    #[test]
+    fn test_winsorize_and_clip() {
+        let mut data: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        data[50] = 10_000.0;
+        data[7] = f64::NAN;
+        let result = winsorize(data.clone(), 0.05, 0.95).unwrap();
+        assert_eq!(result.modified_indices.iter().filter(|&&i| i == 50).count(), 1);
+        assert_eq!(result.values[50], result.upper);
+        assert!(result.values[7].is_nan());
+        assert!(result.values.iter().filter(|v| !v.is_nan()).all(|&v| v >= result.lower && v <= result.upper));
+
+        let clipped = clip(vec![-5.0, 1.0, 5.0], 0.0, 2.0).unwrap();
+        assert_eq!(clipped.values, vec![0.0, 1.0, 2.0]);
+        assert_eq!(clipped.modified_indices, vec![0, 2]);
+
+        assert!(clip(vec![1.0, 2.0, 3.0], 3.0, 1.0).is_err());
+        assert!(winsorize(data, 0.95, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_yeo_johnson() {
+        let data: Vec<f64> = (0..200).map(|i| ((i as f64 / 40.0).exp() - 3.0) * 10.0).collect();
+        let result = yeo_johnson(data.clone(), None);
+        assert!(result.lambda < 1.0);
+        let skew = |v: &[f64]| {
+            let n = v.len() as f64;
+            let m = v.iter().sum::<f64>() / n;
+            let sd = (v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / n).sqrt();
+            v.iter().map(|x| ((x - m) / sd).powi(3)).sum::<f64>() / n
+        };
+        assert!(skew(&result.values).abs() < skew(&data).abs());
+        for lambda in [result.lambda, 0.0, 2.0, 3.0] {
+            let restored = inverse_yeo_johnson(yeo_johnson(data.clone(), Some(lambda)).values, lambda);
+            assert!(restored.iter().zip(&data).all(|(a, b)| (a - b).abs() < 1e-6 * (1.0 + b.abs())));
+        }
+    }
+}
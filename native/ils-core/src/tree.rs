@@ -0,0 +1,286 @@
+//! Decision Trees
+//!
+//! CART trees shared by the tree-ensemble models. Splits are chosen by
+//! exhaustive search over sorted feature values, maximizing the reduction
+//! in squared error (regression) or Gini impurity (classification).
+
+use rayon::prelude::*;
+
+use crate::sampling::Rng;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Criterion {
+    SquaredError,
+    /// Gini impurity over `n_classes` labels encoded as 0..n_classes
+    Gini(usize),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TreeParams {
+    pub max_depth: usize,
+    pub min_samples_leaf: usize,
+    /// Number of features considered per split (`None` = all)
+    pub max_features: Option<usize>,
+    pub criterion: Criterion,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    /// Mean target (regression) or class probabilities (classification)
+    Leaf {
+        value: Vec<f64>,
+    },
+    Split {
+        feature: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// Leaf value reached by `row`
+    pub(crate) fn predict_leaf(&self, row: &[f64]) -> &[f64] {
+        let mut index = 0;
+        loop {
+            match &self.nodes[index] {
+                Node::Leaf { value } => return value,
+                Node::Split { feature, threshold, left, right } => {
+                    index = if row.get(*feature).copied().unwrap_or(0.0) <= *threshold { *left } else { *right };
+                }
+            }
+        }
+    }
+
+    /// Regression prediction for `row`
+    pub(crate) fn predict(&self, row: &[f64]) -> f64 {
+        self.predict_leaf(row).first().copied().unwrap_or(0.0)
+    }
+
+    /// Fit a tree on the rows listed in `indices`
+    ///
+    /// Split gains (impurity reduction) are added to `importances`.
+    pub(crate) fn fit(
+        x: &[Vec<f64>],
+        y: &[f64],
+        indices: &[usize],
+        params: &TreeParams,
+        rng: &mut Rng,
+        importances: &mut [f64],
+    ) -> Tree {
+        let mut tree = Tree { nodes: Vec::new() };
+        tree.grow(x, y, indices.to_vec(), 0, params, rng, importances);
+        tree
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn grow(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[f64],
+        indices: Vec<usize>,
+        depth: usize,
+        params: &TreeParams,
+        rng: &mut Rng,
+        importances: &mut [f64],
+    ) -> usize {
+        let node_index = self.nodes.len();
+        let value = leaf_value(y, &indices, params.criterion);
+        let pure = value.iter().filter(|&&v| v > 0.0).count() <= 1 && matches!(params.criterion, Criterion::Gini(_));
+        self.nodes.push(Node::Leaf { value });
+
+        if pure || depth >= params.max_depth || indices.len() < 2 * params.min_samples_leaf.max(1) {
+            return node_index;
+        }
+
+        let n_features = x.first().map_or(0, |row| row.len());
+        let mut candidates: Vec<usize> = (0..n_features).collect();
+        if let Some(k) = params.max_features.filter(|&k| k < n_features) {
+            rng.shuffle(&mut candidates);
+            candidates.truncate(k.max(1));
+        }
+
+        let min_leaf = params.min_samples_leaf.max(1);
+        let best = candidates
+            .par_iter()
+            .filter_map(|&feature| best_split(x, y, &indices, feature, min_leaf, params.criterion))
+            .max_by(|a, b| a.gain.total_cmp(&b.gain));
+
+        let split = match best {
+            Some(split) if split.gain > 1e-12 => split,
+            _ => return node_index,
+        };
+
+        importances[split.feature] += split.gain;
+
+        let (left_rows, right_rows): (Vec<usize>, Vec<usize>) =
+            indices.into_iter().partition(|&i| x[i][split.feature] <= split.threshold);
+
+        let left = self.grow(x, y, left_rows, depth + 1, params, rng, importances);
+        let right = self.grow(x, y, right_rows, depth + 1, params, rng, importances);
+        self.nodes[node_index] = Node::Split {
+            feature: split.feature,
+            threshold: split.threshold,
+            left,
+            right,
+        };
+        node_index
+    }
+}
+
+/// Scale raw importances so they sum to 1
+pub(crate) fn normalize_importances(raw: &[f64]) -> Vec<f64> {
+    let total: f64 = raw.iter().sum();
+    if total > 0.0 {
+        raw.iter().map(|v| v / total).collect()
+    } else {
+        vec![0.0; raw.len()]
+    }
+}
+
+struct Split {
+    feature: usize,
+    threshold: f64,
+    gain: f64,
+}
+
+fn leaf_value(y: &[f64], indices: &[usize], criterion: Criterion) -> Vec<f64> {
+    let n = indices.len().max(1) as f64;
+    match criterion {
+        Criterion::SquaredError => vec![indices.iter().map(|&i| y[i]).sum::<f64>() / n],
+        Criterion::Gini(n_classes) => {
+            let mut counts = vec![0.0; n_classes];
+            for &i in indices {
+                counts[class_of(y[i], n_classes)] += 1.0;
+            }
+            counts.iter().map(|c| c / n).collect()
+        }
+    }
+}
+
+fn class_of(label: f64, n_classes: usize) -> usize {
+    (label.max(0.0) as usize).min(n_classes.saturating_sub(1))
+}
+
+/// Sum of squared class counts divided by the total (n minus n * Gini)
+fn gini_score(counts: &[f64], n: f64) -> f64 {
+    counts.iter().map(|c| c * c).sum::<f64>() / n
+}
+
+fn best_split(
+    x: &[Vec<f64>],
+    y: &[f64],
+    indices: &[usize],
+    feature: usize,
+    min_leaf: usize,
+    criterion: Criterion,
+) -> Option<Split> {
+    let mut pairs: Vec<(f64, f64)> = indices.iter().map(|&i| (x[i][feature], y[i])).collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let n = pairs.len();
+    let n_classes = match criterion {
+        Criterion::Gini(k) => k,
+        Criterion::SquaredError => 0,
+    };
+
+    // Running left-side statistics: target sum (regression) or class counts
+    let total: f64 = pairs.iter().map(|p| p.1).sum();
+    let mut total_counts = vec![0.0; n_classes];
+    for p in &pairs {
+        if n_classes > 0 {
+            total_counts[class_of(p.1, n_classes)] += 1.0;
+        }
+    }
+    let baseline = match criterion {
+        Criterion::SquaredError => total * total / n as f64,
+        Criterion::Gini(_) => gini_score(&total_counts, n as f64),
+    };
+
+    let mut left_sum = 0.0;
+    let mut left_counts = vec![0.0; n_classes];
+    let mut best: Option<Split> = None;
+
+    for i in 0..n - 1 {
+        left_sum += pairs[i].1;
+        if n_classes > 0 {
+            left_counts[class_of(pairs[i].1, n_classes)] += 1.0;
+        }
+
+        let n_left = i + 1;
+        let n_right = n - n_left;
+        if n_left < min_leaf || n_right < min_leaf || pairs[i].0 == pairs[i + 1].0 {
+            continue;
+        }
+
+        let gain = match criterion {
+            Criterion::SquaredError => {
+                let right_sum = total - left_sum;
+                left_sum * left_sum / n_left as f64 + right_sum * right_sum / n_right as f64 - baseline
+            }
+            Criterion::Gini(_) => {
+                let right_counts: Vec<f64> = total_counts.iter().zip(&left_counts).map(|(t, l)| t - l).collect();
+                gini_score(&left_counts, n_left as f64) + gini_score(&right_counts, n_right as f64) - baseline
+            }
+        };
+
+        if best.as_ref().is_none_or(|b| gain > b.gain) {
+            best = Some(Split {
+                feature,
+                threshold: (pairs[i].0 + pairs[i + 1].0) / 2.0,
+                gain,
+            });
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_fits_step_function() {
+        let x: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64, 0.0]).collect();
+        let y: Vec<f64> = (0..20).map(|i| if i < 10 { 1.0 } else { 5.0 }).collect();
+        let indices: Vec<usize> = (0..20).collect();
+        let params = TreeParams {
+            max_depth: 3,
+            min_samples_leaf: 1,
+            max_features: None,
+            criterion: Criterion::SquaredError,
+        };
+        let mut importances = vec![0.0; 2];
+
+        let tree = Tree::fit(&x, &y, &indices, &params, &mut Rng::new(1), &mut importances);
+        assert!((tree.predict(&[3.0, 0.0]) - 1.0).abs() < 1e-10);
+        assert!((tree.predict(&[15.0, 0.0]) - 5.0).abs() < 1e-10);
+        assert!(importances[0] > 0.0);
+        assert_eq!(importances[1], 0.0);
+    }
+
+    #[test]
+    fn test_tree_classification() {
+        let x: Vec<Vec<f64>> = (0..30).map(|i| vec![0.0, i as f64]).collect();
+        let y: Vec<f64> = (0..30).map(|i| (i / 10) as f64).collect();
+        let indices: Vec<usize> = (0..30).collect();
+        let params = TreeParams {
+            max_depth: 5,
+            min_samples_leaf: 1,
+            max_features: None,
+            criterion: Criterion::Gini(3),
+        };
+        let mut importances = vec![0.0; 2];
+
+        let tree = Tree::fit(&x, &y, &indices, &params, &mut Rng::new(1), &mut importances);
+        assert_eq!(tree.predict_leaf(&[0.0, 5.0]), &[1.0, 0.0, 0.0]);
+        assert_eq!(tree.predict_leaf(&[0.0, 25.0]), &[0.0, 0.0, 1.0]);
+        assert!(importances[1] > 0.0);
+    }
+}
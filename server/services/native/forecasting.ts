@@ -127,6 +127,7 @@ function fallbackPredictNext(
       confidence,
       lowerBound: Math.max(0, Math.round(predictedValue - confidenceMargin)),
       upperBound: Math.round(predictedValue + confidenceMargin),
+      confidenceLevel: 0.95,
       trend: trendDirection,
     });
   }
@@ -156,6 +157,7 @@ function fallbackPredictSimple(data: number[], steps: number): ForecastResult[]
       confidence,
       lowerBound: Math.max(0, Math.round(predictedValue - confidenceMargin)),
       upperBound: Math.round(predictedValue + confidenceMargin),
+      confidenceLevel: 0.95,
       trend: trendDirection,
     });
   }
@@ -266,6 +268,7 @@ function fallbackIdentifySurges(
         endDate: dates[i],
         peakValue: predictedValues[i],
         severity: determineSeverity(ratio),
+        direction: "surge",
       };
     } else if (isHighVolume && currentSurge) {
       currentSurge.endDate = dates[i];
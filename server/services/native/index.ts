@@ -33,6 +33,15 @@ export interface DescriptiveStats {
   iqr: number;
   skewness: number;
   kurtosis: number;
+  range: number;
+  coefficientOfVariation: number;
+  standardError: number;
+  percentiles: PercentileValue[];
+}
+
+export interface PercentileValue {
+  percentile: number;
+  value: number;
 }
 
 export interface ForecastResult {
@@ -40,6 +49,7 @@ export interface ForecastResult {
   confidence: number;
   lowerBound: number;
   upperBound: number;
+  confidenceLevel: number;
   trend: "increasing" | "decreasing" | "stable";
 }
 
@@ -61,6 +71,7 @@ export interface SurgePeriod {
   endDate: string;
   peakValue: number;
   severity: "low" | "medium" | "high";
+  direction: "surge" | "dip";
 }
 
 export interface AnomalyResult {
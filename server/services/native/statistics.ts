@@ -153,6 +153,7 @@ function fallbackDescribe(data: number[]): DescriptiveStats {
     return {
       count: 0, mean: 0, stdDev: 0, min: 0, q1: 0,
       median: 0, q3: 0, max: 0, iqr: 0, skewness: 0, kurtosis: 0,
+      range: 0, coefficientOfVariation: 0, standardError: 0, percentiles: [],
     };
   }
 
@@ -184,6 +185,10 @@ function fallbackDescribe(data: number[]): DescriptiveStats {
     iqr: fallbackIqr(data),
     skewness,
     kurtosis,
+    range: sorted[n - 1] - sorted[0],
+    coefficientOfVariation: m !== 0 ? sd / Math.abs(m) : 0,
+    standardError: sd / Math.sqrt(n),
+    percentiles: [],
   };
 }
 